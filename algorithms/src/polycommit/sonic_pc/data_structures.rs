@@ -15,7 +15,7 @@
 use super::{LabeledPolynomial, PolynomialInfo};
 use crate::{crypto_hash::sha256::sha256, fft::EvaluationDomain, polycommit::kzg10};
 use snarkvm_curves::PairingEngine;
-use snarkvm_fields::{ConstraintFieldError, Field, PrimeField, ToConstraintField};
+use snarkvm_fields::{ConstraintFieldError, Field, PrimeField, ToConstraintField, Zero};
 use snarkvm_utilities::{error, serialize::*, FromBytes, ToBytes};
 
 use hashbrown::HashMap;
@@ -679,3 +679,39 @@ impl<E: PairingEngine> ToBytes for BatchLCProof<E> {
         CanonicalSerialize::serialize_compressed(self, &mut writer).map_err(|_| error("could not serialize struct"))
     }
 }
+
+/// The accumulated KZG opening checks of one or more batch proofs, which hold if and only if
+/// `e(Σ comms[d], [β^{-d}]h) · e(-adjusted_witness, h) · e(-witness, [β]h) = 1`.
+///
+/// Accumulators for the same universal verifier can be combined with random scalars,
+/// so that the opening checks of many proofs are verified with a single multi-pairing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairingAccumulator<E: PairingEngine> {
+    /// The combined commitments, by degree bound.
+    pub(crate) combined_comms: BTreeMap<Option<usize>, E::G1Projective>,
+    /// The combined evaluation proofs.
+    pub(crate) combined_witness: E::G1Projective,
+    /// The combined evaluation proofs, adjusted by the evaluation points and values.
+    pub(crate) combined_adjusted_witness: E::G1Projective,
+}
+
+impl<E: PairingEngine> Default for PairingAccumulator<E> {
+    fn default() -> Self {
+        Self {
+            combined_comms: BTreeMap::new(),
+            combined_witness: E::G1Projective::zero(),
+            combined_adjusted_witness: E::G1Projective::zero(),
+        }
+    }
+}
+
+impl<E: PairingEngine> PairingAccumulator<E> {
+    /// Adds the given accumulator, scaled by the given randomizer, to this accumulator.
+    pub fn combine(&mut self, other: &Self, randomizer: E::Fr) {
+        for (degree_bound, comm) in &other.combined_comms {
+            *self.combined_comms.entry(*degree_bound).or_insert_with(E::G1Projective::zero) += *comm * randomizer;
+        }
+        self.combined_witness += other.combined_witness * randomizer;
+        self.combined_adjusted_witness += other.combined_adjusted_witness * randomizer;
+    }
+}
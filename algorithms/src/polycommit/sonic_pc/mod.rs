@@ -348,12 +348,29 @@ impl<E: PairingEngine, S: AlgebraicSponge<E::Fq, 2>> SonicKZG10<E, S> {
         proof: &BatchProof<E>,
         fs_rng: &mut S,
     ) -> Result<bool>
+    where
+        Commitment<E>: 'a,
+    {
+        let accumulator = Self::batch_accumulate(vk, commitments, query_set, values, proof, fs_rng)?;
+        Self::check_accumulator(vk, accumulator)
+    }
+
+    /// Accumulates the opening checks of the given batch proof, without performing the pairing check.
+    /// The opening checks hold if and only if `SonicKZG10::check_accumulator` holds for the accumulator.
+    pub fn batch_accumulate<'a>(
+        vk: &UniversalVerifier<E>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Commitment<E>>>,
+        query_set: &QuerySet<E::Fr>,
+        values: &Evaluations<E::Fr>,
+        proof: &BatchProof<E>,
+        fs_rng: &mut S,
+    ) -> Result<PairingAccumulator<E>>
     where
         Commitment<E>: 'a,
     {
         let commitments: BTreeMap<_, _> = commitments.into_iter().map(|c| (c.label().to_owned(), c)).collect();
-        let batch_check_time = start_timer!(|| format!(
-            "Checking {} commitments at query set of size {}",
+        let batch_accumulate_time = start_timer!(|| format!(
+            "Accumulating {} commitments at query set of size {}",
             commitments.len(),
             query_set.len(),
         ));
@@ -404,9 +421,14 @@ impl<E: PairingEngine, S: AlgebraicSponge<E::Fq, 2>> SonicKZG10<E, S> {
             randomizer = fs_rng.squeeze_short_nonnative_field_element::<E::Fr>();
         }
 
-        let result = Self::check_elems(vk, combined_comms, combined_witness, combined_adjusted_witness);
-        end_timer!(batch_check_time);
-        result.map_err(Into::into)
+        end_timer!(batch_accumulate_time);
+        Ok(PairingAccumulator { combined_comms, combined_witness, combined_adjusted_witness })
+    }
+
+    /// Returns `true` if the opening checks in the given accumulator hold.
+    pub fn check_accumulator(vk: &UniversalVerifier<E>, accumulator: PairingAccumulator<E>) -> Result<bool> {
+        let PairingAccumulator { combined_comms, combined_witness, combined_adjusted_witness } = accumulator;
+        Self::check_elems(vk, combined_comms, combined_witness, combined_adjusted_witness)
     }
 
     pub fn open_combinations<'a>(
@@ -482,6 +504,25 @@ impl<E: PairingEngine, S: AlgebraicSponge<E::Fq, 2>> SonicKZG10<E, S> {
         proof: &BatchLCProof<E>,
         fs_rng: &mut S,
     ) -> Result<bool>
+    where
+        Commitment<E>: 'a,
+    {
+        let accumulator =
+            Self::accumulate_combinations(vk, linear_combinations, commitments, query_set, evaluations, proof, fs_rng)?;
+        Self::check_accumulator(vk, accumulator)
+    }
+
+    /// Accumulates the opening checks of the given linear combinations, without performing the pairing check.
+    /// The opening checks hold if and only if `SonicKZG10::check_accumulator` holds for the accumulator.
+    pub fn accumulate_combinations<'a>(
+        vk: &UniversalVerifier<E>,
+        linear_combinations: impl IntoIterator<Item = &'a LinearCombination<E::Fr>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Commitment<E>>>,
+        query_set: &QuerySet<E::Fr>,
+        evaluations: &Evaluations<E::Fr>,
+        proof: &BatchLCProof<E>,
+        fs_rng: &mut S,
+    ) -> Result<PairingAccumulator<E>>
     where
         Commitment<E>: 'a,
     {
@@ -539,7 +580,7 @@ impl<E: PairingEngine, S: AlgebraicSponge<E::Fq, 2>> SonicKZG10<E, S> {
             .collect::<Vec<_>>();
         end_timer!(combined_comms_norm_time);
 
-        Self::batch_check(vk, &lc_commitments, query_set, &evaluations, proof, fs_rng)
+        Self::batch_accumulate(vk, &lc_commitments, query_set, &evaluations, proof, fs_rng)
     }
 }
 
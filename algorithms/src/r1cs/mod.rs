@@ -30,6 +30,9 @@ pub use linear_combination::*;
 mod namespace;
 pub use namespace::*;
 
+mod r1cs_circuit;
+pub use r1cs_circuit::*;
+
 #[cfg(feature = "test")]
 mod optional_vec;
 #[cfg(feature = "test")]
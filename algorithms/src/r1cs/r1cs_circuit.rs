@@ -0,0 +1,160 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::r1cs::{errors::SynthesisError, ConstraintSynthesizer, ConstraintSystem, LinearCombination};
+use snarkvm_fields::Field;
+
+use anyhow::{ensure, Result};
+
+/// A sparse matrix, where each row is a list of `(coefficient, column)` pairs.
+pub type R1CSMatrix<F> = Vec<Vec<(F, usize)>>;
+
+/// A rank-1 constraint system given by its constraint matrices `(A, B, C)`, and optionally its witness,
+/// such that `(A * z) ∘ (B * z) = C * z` for the full assignment `z`.
+///
+/// The columns `0..num_public` correspond to the public variables, where column 0 is the constant `1`,
+/// and the columns `num_public..num_public + num_private` correspond to the private variables.
+/// This allows circuits built outside of snarkVM to be indexed, proven, and verified with Varuna.
+/// A circuit without a witness can be indexed, but not proven.
+#[derive(Clone, Debug)]
+pub struct R1CSCircuit<F: Field> {
+    /// The number of public variables, including the constant `1`.
+    num_public: usize,
+    /// The number of private variables.
+    num_private: usize,
+    /// The `A` matrix.
+    a: R1CSMatrix<F>,
+    /// The `B` matrix.
+    b: R1CSMatrix<F>,
+    /// The `C` matrix.
+    c: R1CSMatrix<F>,
+    /// The full assignment, starting with the constant `1`, if any.
+    witness: Option<Vec<F>>,
+}
+
+impl<F: Field> R1CSCircuit<F> {
+    /// Initializes a new circuit from the given number of public variables (including the constant `1`),
+    /// the number of private variables, and the constraint matrices.
+    pub fn new(
+        num_public: usize,
+        num_private: usize,
+        a: R1CSMatrix<F>,
+        b: R1CSMatrix<F>,
+        c: R1CSMatrix<F>,
+    ) -> Result<Self> {
+        ensure!(num_public > 0, "The circuit must have the constant '1' as its first public variable");
+        ensure!(a.len() == b.len() && b.len() == c.len(), "The constraint matrices must have the same number of rows");
+        // Ensure every column is a variable of the circuit.
+        let num_variables = num_public + num_private;
+        for matrix in [&a, &b, &c] {
+            ensure!(
+                matrix.iter().flatten().all(|(_, column)| *column < num_variables),
+                "The constraint matrices must only reference the {num_variables} variables of the circuit"
+            );
+        }
+        Ok(Self { num_public, num_private, a, b, c, witness: None })
+    }
+
+    /// Sets the full assignment of the circuit, which starts with the constant `1`,
+    /// followed by the public variables, and then the private variables.
+    pub fn with_witness(mut self, witness: Vec<F>) -> Result<Self> {
+        ensure!(
+            witness.len() == self.num_variables(),
+            "The witness has {} values, but the circuit has {} variables",
+            witness.len(),
+            self.num_variables()
+        );
+        ensure!(witness[0] == F::one(), "The first value of the witness must be one");
+        self.witness = Some(witness);
+        Ok(self)
+    }
+
+    /// Returns the number of public variables, including the constant `1`.
+    pub const fn num_public(&self) -> usize {
+        self.num_public
+    }
+
+    /// Returns the number of private variables.
+    pub const fn num_private(&self) -> usize {
+        self.num_private
+    }
+
+    /// Returns the number of public and private variables.
+    pub const fn num_variables(&self) -> usize {
+        self.num_public + self.num_private
+    }
+
+    /// Returns the number of constraints.
+    pub fn num_constraints(&self) -> usize {
+        self.a.len()
+    }
+
+    /// Returns the public inputs of the circuit, starting with the constant `1`, if the witness is set.
+    /// These are the inputs to verify a proof for the circuit.
+    pub fn public_inputs(&self) -> Option<&[F]> {
+        self.witness.as_ref().map(|witness| &witness[..self.num_public])
+    }
+
+    /// Returns `true` if the witness is set and satisfies all of the constraints.
+    pub fn is_satisfied(&self) -> bool {
+        let Some(witness) = &self.witness else {
+            return false;
+        };
+        // Evaluates a row of a matrix on the witness.
+        let evaluate = |row: &[(F, usize)]| {
+            row.iter().fold(F::zero(), |sum, (coefficient, column)| sum + *coefficient * witness[*column])
+        };
+        self.a.iter().zip(&self.b).zip(&self.c).all(|((a, b), c)| evaluate(a) * evaluate(b) == evaluate(c))
+    }
+
+    /// Returns the value of the variable in the given column, if the witness is set.
+    fn value(&self, column: usize) -> Result<F, SynthesisError> {
+        self.witness.as_ref().map(|witness| witness[column]).ok_or(SynthesisError::AssignmentMissing)
+    }
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for R1CSCircuit<F> {
+    /// Synthesizes the constraint matrices into a `snarkvm_algorithms::r1cs`-compliant constraint system.
+    fn generate_constraints<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Result<(), SynthesisError> {
+        // Allocate the variables, where the constant `1` is the first public variable of the constraint system.
+        let mut variables = Vec::with_capacity(self.num_variables());
+        variables.push(CS::one());
+        for column in 1..self.num_public {
+            variables.push(cs.alloc_input(|| format!("Public {column}"), || self.value(column))?);
+        }
+        for column in self.num_public..self.num_variables() {
+            variables.push(cs.alloc(|| format!("Private {column}"), || self.value(column))?);
+        }
+
+        // Converts a row of a matrix into a linear combination.
+        let to_linear_combination = |row: &[(F, usize)]| {
+            let mut linear_combination = LinearCombination::zero();
+            for (coefficient, column) in row {
+                linear_combination += (*coefficient, variables[*column]);
+            }
+            linear_combination
+        };
+
+        // Enforce the constraints.
+        for (i, ((a, b), c)) in self.a.iter().zip(&self.b).zip(&self.c).enumerate() {
+            cs.enforce(
+                || format!("Constraint {i}"),
+                |lc| lc + to_linear_combination(a),
+                |lc| lc + to_linear_combination(b),
+                |lc| lc + to_linear_combination(c),
+            );
+        }
+        Ok(())
+    }
+}
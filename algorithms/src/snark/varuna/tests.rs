@@ -15,6 +15,7 @@
 #[cfg(any(test, feature = "test"))]
 mod varuna {
     use crate::{
+        r1cs::R1CSCircuit,
         snark::varuna::{
             mode::SNARKMode,
             test_circuit::TestCircuit,
@@ -297,6 +298,92 @@ mod varuna {
         SonicPCTest::test_bincode(num_constraints, num_variables);
         SonicPCPoswTest::test_bincode(num_constraints, num_variables);
     }
+
+    #[test]
+    fn prove_and_verify_with_accumulators() {
+        let rng = &mut TestRng::default();
+
+        let max_degree = AHPForR1CS::<Fr, VarunaHidingMode>::max_degree(100, 25, 300).unwrap();
+        let universal_srs = VarunaSonicInst::universal_setup(max_degree).unwrap();
+        let universal_prover = &universal_srs.to_universal_prover().unwrap();
+        let universal_verifier = &universal_srs.to_universal_verifier().unwrap();
+        let fs_parameters = FS::sample_parameters();
+
+        // Prove a few circuits of different sizes.
+        let mut instances = Vec::new();
+        for i in 0..3 {
+            let (circuit, public_inputs) = TestCircuit::gen_rand(1 + i, 25 + i, 25, rng);
+            let (index_pk, index_vk) = VarunaSonicInst::circuit_setup(&universal_srs, &circuit).unwrap();
+            let proof = VarunaSonicInst::prove(universal_prover, &fs_parameters, &index_pk, &circuit, rng).unwrap();
+            instances.push((index_vk, public_inputs, proof));
+        }
+
+        // Accumulate the proofs, and ensure they verify in a single check.
+        let accumulate = |index_vk, public_inputs: &Vec<Fr>, proof| {
+            let inputs = [public_inputs.clone()];
+            let vks_to_inputs = BTreeMap::from([(index_vk, &inputs[..])]);
+            VarunaSonicInst::accumulate_batch(universal_verifier, &fs_parameters, &vks_to_inputs, proof)
+                .unwrap()
+                .unwrap()
+        };
+        let mut accumulators = instances
+            .iter()
+            .map(|(index_vk, public_inputs, proof)| accumulate(index_vk, public_inputs, proof))
+            .collect::<Vec<_>>();
+        assert!(VarunaSonicInst::check_accumulators(universal_verifier, &accumulators, rng).unwrap());
+
+        // Ensure the accumulators do not verify if one of the proofs is invalid.
+        let (index_vk, public_inputs, proof) = &instances[1];
+        let mut fake_inputs = public_inputs.clone();
+        fake_inputs[public_inputs.len() - 1] = Fr::rand(rng);
+        accumulators[1] = accumulate(index_vk, &fake_inputs, proof);
+        assert!(!VarunaSonicInst::check_accumulators(universal_verifier, &accumulators, rng).unwrap());
+        // Ensure the invalid proof is found by checking each accumulator individually.
+        for (i, accumulator) in accumulators.iter().enumerate() {
+            let is_valid =
+                VarunaSonicInst::check_accumulators(universal_verifier, &[accumulator.clone()], rng).unwrap();
+            assert_eq!(is_valid, i != 1);
+        }
+    }
+
+    #[test]
+    fn prove_and_verify_with_r1cs_circuit() {
+        let rng = &mut TestRng::default();
+
+        let max_degree = AHPForR1CS::<Fr, VarunaHidingMode>::max_degree(100, 25, 300).unwrap();
+        let universal_srs = VarunaSonicInst::universal_setup(max_degree).unwrap();
+        let universal_prover = &universal_srs.to_universal_prover().unwrap();
+        let universal_verifier = &universal_srs.to_universal_verifier().unwrap();
+        let fs_parameters = FS::sample_parameters();
+
+        // Construct the circuit for `x^3 + x + 5 = out`, with the variables `[1, out, x, x^2, x^3]`.
+        let field = |value: u64| Fr::from(value);
+        let a = vec![vec![(field(1), 2)], vec![(field(1), 3)], vec![(field(1), 4), (field(1), 2), (field(5), 0)]];
+        let b = vec![vec![(field(1), 2)], vec![(field(1), 2)], vec![(field(1), 0)]];
+        let c = vec![vec![(field(1), 3)], vec![(field(1), 4)], vec![(field(1), 1)]];
+        let circuit = R1CSCircuit::new(2, 3, a, b, c).unwrap();
+        assert!(!circuit.is_satisfied());
+
+        // Index the circuit, without a witness.
+        let (index_pk, index_vk) = VarunaSonicInst::circuit_setup(&universal_srs, &circuit).unwrap();
+        assert!(VarunaSonicInst::prove(universal_prover, &fs_parameters, &index_pk, &circuit, rng).is_err());
+
+        // Ensure an unsatisfying witness is detected.
+        let invalid_circuit = circuit.clone().with_witness([1, 36, 3, 9, 27].map(field).to_vec()).unwrap();
+        assert!(!invalid_circuit.is_satisfied());
+
+        // Prove and verify the circuit, with the witness for `x = 3`.
+        let circuit = circuit.with_witness([1, 35, 3, 9, 27].map(field).to_vec()).unwrap();
+        assert!(circuit.is_satisfied());
+        let public_inputs = circuit.public_inputs().unwrap();
+        let proof = VarunaSonicInst::prove(universal_prover, &fs_parameters, &index_pk, &circuit, rng).unwrap();
+        assert!(VarunaSonicInst::verify(universal_verifier, &fs_parameters, &index_vk, public_inputs, &proof).unwrap());
+        // Ensure the proof does not verify for another output.
+        let invalid_inputs = [field(1), field(36)];
+        assert!(
+            !VarunaSonicInst::verify(universal_verifier, &fs_parameters, &index_vk, invalid_inputs, &proof).unwrap()
+        );
+    }
 }
 
 #[cfg(any(test, feature = "test"))]
@@ -20,6 +20,7 @@ use crate::{
         CommitterUnionKey,
         Evaluations,
         LabeledCommitment,
+        PairingAccumulator,
         QuerySet,
         Randomness,
         SonicKZG10,
@@ -44,7 +45,7 @@ use crate::{
 use rand::RngCore;
 use snarkvm_curves::PairingEngine;
 use snarkvm_fields::{One, PrimeField, ToConstraintField, Zero};
-use snarkvm_utilities::{to_bytes_le, ToBytes};
+use snarkvm_utilities::{to_bytes_le, ToBytes, Uniform};
 
 use anyhow::{anyhow, bail, ensure, Result};
 use core::marker::PhantomData;
@@ -628,6 +629,46 @@ where
         keys_to_inputs: &BTreeMap<&Self::VerifyingKey, &[B]>,
         proof: &Self::Proof,
     ) -> Result<bool> {
+        let verifier_time = start_timer!(|| "Varuna::Verify");
+        let Some(accumulator) = Self::accumulate_batch(universal_verifier, fs_parameters, keys_to_inputs, proof)?
+        else {
+            return Ok(false);
+        };
+
+        let pc_time = start_timer!(|| "Checking the accumulated linear combinations with PC");
+        let evaluations_are_correct = SonicKZG10::<E, FS>::check_accumulator(universal_verifier, accumulator)?;
+        end_timer!(pc_time);
+
+        if !evaluations_are_correct {
+            #[cfg(debug_assertions)]
+            eprintln!("SonicKZG10::Check failed for the accumulated linear combinations");
+        }
+
+        end_timer!(verifier_time, || format!(
+            " SonicKZG10::Check for AHP Verifier linear equations: {evaluations_are_correct}"
+        ));
+        Ok(evaluations_are_correct)
+    }
+}
+
+impl<E: PairingEngine, FS, SM> VarunaSNARK<E, FS, SM>
+where
+    E::Fr: PrimeField,
+    E::Fq: PrimeField,
+    FS: AlgebraicSponge<E::Fq, 2>,
+    SM: SNARKMode,
+{
+    /// Runs the verifier on the given proof, up to (and excluding) the final pairing check of the polynomial
+    /// commitment openings, which is returned as an accumulator. The proof is valid if and only if
+    /// `VarunaSNARK::check_accumulators` holds for the accumulator.
+    ///
+    /// Returns `None` if the proof has an incorrect hiding mode.
+    pub fn accumulate_batch<B: Borrow<[E::Fr]>>(
+        universal_verifier: &UniversalVerifier<E>,
+        fs_parameters: &FS::Parameters,
+        keys_to_inputs: &BTreeMap<&CircuitVerifyingKey<E>, &[B]>,
+        proof: &Proof<E>,
+    ) -> Result<Option<PairingAccumulator<E>>> {
         if keys_to_inputs.is_empty() {
             bail!(SNARKError::EmptyBatch);
         }
@@ -723,10 +764,10 @@ where
                 "Found `mask_poly` in the first round when not expected, or proof has incorrect hiding mode ({})",
                 proof.pc_proof.is_hiding()
             );
-            return Ok(false);
+            return Ok(None);
         }
 
-        let verifier_time = start_timer!(|| format!("Varuna::Verify with batch sizes: {:?}", batch_sizes));
+        let accumulate_time = start_timer!(|| format!("Varuna::Accumulate with batch sizes: {:?}", batch_sizes));
 
         let first_round_info = AHPForR1CS::<E::Fr, SM>::first_round_polynomial_info(batch_sizes.iter());
 
@@ -896,8 +937,8 @@ where
         )?;
         end_timer!(lc_time);
 
-        let pc_time = start_timer!(|| "Checking linear combinations with PC");
-        let evaluations_are_correct = SonicKZG10::<E, FS>::check_combinations(
+        let pc_time = start_timer!(|| "Accumulating linear combinations with PC");
+        let accumulator = SonicKZG10::<E, FS>::accumulate_combinations(
             universal_verifier,
             lc_s.values(),
             &commitments,
@@ -908,15 +949,32 @@ where
         )?;
         end_timer!(pc_time);
 
-        if !evaluations_are_correct {
-            #[cfg(debug_assertions)]
-            eprintln!("SonicKZG10::Check failed using final challenge: {:?}", verifier_state.gamma);
-        }
+        end_timer!(accumulate_time);
+        Ok(Some(accumulator))
+    }
 
-        end_timer!(verifier_time, || format!(
-            " SonicKZG10::Check for AHP Verifier linear equations: {}",
-            evaluations_are_correct & proof_has_correct_zk_mode
-        ));
-        Ok(evaluations_are_correct & proof_has_correct_zk_mode)
+    /// Returns `true` if the opening checks in all of the given accumulators hold.
+    /// The accumulators are combined with random scalars, and checked with a single multi-pairing.
+    pub fn check_accumulators<R: Rng + CryptoRng>(
+        universal_verifier: &UniversalVerifier<E>,
+        accumulators: &[PairingAccumulator<E>],
+        rng: &mut R,
+    ) -> Result<bool> {
+        let check_time = start_timer!(|| format!("Varuna::CheckAccumulators for {} proofs", accumulators.len()));
+        // Combine the accumulators with random scalars.
+        let mut combined = PairingAccumulator::default();
+        for accumulator in accumulators {
+            let randomizer = loop {
+                let randomizer = E::Fr::rand(rng);
+                if !randomizer.is_zero() {
+                    break randomizer;
+                }
+            };
+            combined.combine(accumulator, randomizer);
+        }
+        // Check the combined accumulator.
+        let result = SonicKZG10::<E, FS>::check_accumulator(universal_verifier, combined);
+        end_timer!(check_time);
+        result
     }
 }
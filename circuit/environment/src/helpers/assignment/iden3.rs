@@ -0,0 +1,362 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm_utilities::{error, BigInteger, FromBytes, ToBytes};
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{Read, Result as IoResult, Write},
+};
+
+/// The magic bytes of an iden3 `.r1cs` file.
+const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+/// The version of the iden3 `.r1cs` format.
+const R1CS_VERSION: u32 = 1;
+/// The section type of the header in an iden3 `.r1cs` file.
+const R1CS_HEADER_SECTION: u32 = 1;
+/// The section type of the constraints in an iden3 `.r1cs` file.
+const R1CS_CONSTRAINTS_SECTION: u32 = 2;
+/// The section type of the wire-to-label map in an iden3 `.r1cs` file.
+const R1CS_WIRE_TO_LABEL_SECTION: u32 = 3;
+
+/// The magic bytes of an iden3 `.wtns` file.
+const WTNS_MAGIC: &[u8; 4] = b"wtns";
+/// The version of the iden3 `.wtns` format.
+const WTNS_VERSION: u32 = 2;
+/// The section type of the header in an iden3 `.wtns` file.
+const WTNS_HEADER_SECTION: u32 = 1;
+/// The section type of the witness values in an iden3 `.wtns` file.
+const WTNS_VALUES_SECTION: u32 = 2;
+
+/// The iden3 (circom) `.r1cs` and `.wtns` binary formats.
+///
+/// Wire 0 is the constant `1`, followed by the public variables, and then the private variables.
+/// The public variables are written as public inputs, and the private variables as internal wires.
+/// The constant term of a linear combination is written as a coefficient of wire 0.
+impl<F: PrimeField> Assignment<F> {
+    /// Writes the constraints of the assignment in the iden3 `.r1cs` format.
+    pub fn write_r1cs<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the header section.
+        let mut header = Vec::new();
+        Self::write_field_header(&mut header)?;
+        u32::try_from(self.num_wires())
+            .map_err(|_| error("Too many wires for the r1cs format"))?
+            .write_le(&mut header)?;
+        0u32.write_le(&mut header)?; // The number of public outputs.
+        u32::try_from(self.public.len().saturating_sub(1))
+            .map_err(|_| error("Too many public inputs for the r1cs format"))?
+            .write_le(&mut header)?;
+        0u32.write_le(&mut header)?; // The number of private inputs.
+        (self.num_wires() as u64).write_le(&mut header)?;
+        u32::try_from(self.constraints.len())
+            .map_err(|_| error("Too many constraints for the r1cs format"))?
+            .write_le(&mut header)?;
+
+        // Write the constraints section.
+        let mut constraints = Vec::new();
+        for (a, b, c) in self.constraints.iter() {
+            for lc in [a, b, c] {
+                let terms = self.to_wire_terms(lc)?;
+                (terms.len() as u32).write_le(&mut constraints)?;
+                for (wire, coefficient) in terms {
+                    wire.write_le(&mut constraints)?;
+                    coefficient.to_bigint().write_le(&mut constraints)?;
+                }
+            }
+        }
+
+        // Write the wire-to-label section, where each wire is its own label.
+        let mut labels = Vec::new();
+        for wire in 0..self.num_wires() as u64 {
+            wire.write_le(&mut labels)?;
+        }
+
+        // Write the file.
+        writer.write_all(R1CS_MAGIC)?;
+        R1CS_VERSION.write_le(&mut writer)?;
+        3u32.write_le(&mut writer)?;
+        write_section(&mut writer, R1CS_HEADER_SECTION, &header)?;
+        write_section(&mut writer, R1CS_CONSTRAINTS_SECTION, &constraints)?;
+        write_section(&mut writer, R1CS_WIRE_TO_LABEL_SECTION, &labels)
+    }
+
+    /// Writes the values of the assignment in the iden3 `.wtns` format.
+    pub fn write_wtns<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the header section.
+        let mut header = Vec::new();
+        Self::write_field_header(&mut header)?;
+        u32::try_from(self.num_wires())
+            .map_err(|_| error("Too many wires for the wtns format"))?
+            .write_le(&mut header)?;
+
+        // Write the values section.
+        let mut values = Vec::new();
+        for (_, value) in self.public.iter().chain(self.private.iter()) {
+            value.to_bigint().write_le(&mut values)?;
+        }
+
+        // Write the file.
+        writer.write_all(WTNS_MAGIC)?;
+        WTNS_VERSION.write_le(&mut writer)?;
+        2u32.write_le(&mut writer)?;
+        write_section(&mut writer, WTNS_HEADER_SECTION, &header)?;
+        write_section(&mut writer, WTNS_VALUES_SECTION, &values)
+    }
+
+    /// Reads an assignment from its constraints in the iden3 `.r1cs` format, and its values in the iden3 `.wtns` format.
+    ///
+    /// The public outputs and public inputs of the `.r1cs` file become the public variables,
+    /// and all other wires become private variables. The coefficients of wire 0 become constant terms.
+    pub fn read_r1cs_and_wtns<R1: Read, R2: Read>(r1cs: R1, wtns: R2) -> IoResult<Self> {
+        // Read the sections of the files.
+        let mut r1cs_sections = read_sections(r1cs, R1CS_MAGIC, R1CS_VERSION)?;
+        let mut wtns_sections = read_sections(wtns, WTNS_MAGIC, WTNS_VERSION)?;
+
+        // Read the header of the `.r1cs` file.
+        let Some(header) = r1cs_sections.remove(&R1CS_HEADER_SECTION) else {
+            return Err(error("Missing the header section in the r1cs file"));
+        };
+        let mut header = &header[..];
+        Self::read_field_header(&mut header)?;
+        let num_wires = u32::read_le(&mut header)? as usize;
+        let num_public_outputs = u32::read_le(&mut header)? as usize;
+        let num_public_inputs = u32::read_le(&mut header)? as usize;
+        let _num_private_inputs = u32::read_le(&mut header)?;
+        let _num_labels = u64::read_le(&mut header)?;
+        let num_constraints = u32::read_le(&mut header)? as usize;
+        // Compute the number of public variables, including the constant `1`.
+        let num_public = 1 + num_public_outputs + num_public_inputs;
+        if num_wires < num_public {
+            return Err(error("The r1cs file has fewer wires than public variables"));
+        }
+
+        // Read the constraints of the `.r1cs` file.
+        let Some(constraints) = r1cs_sections.remove(&R1CS_CONSTRAINTS_SECTION) else {
+            return Err(error("Missing the constraints section in the r1cs file"));
+        };
+        let mut reader = &constraints[..];
+        let mut read_lc = || -> IoResult<AssignmentLC<F>> {
+            let num_terms = u32::read_le(&mut reader)?;
+            let mut constant = F::zero();
+            let mut terms = Vec::new();
+            for _ in 0..num_terms {
+                let wire = u32::read_le(&mut reader)? as usize;
+                let coefficient = read_field::<F, _>(&mut reader)?;
+                match wire {
+                    0 => constant += coefficient,
+                    wire if wire < num_public => terms.push((AssignmentVariable::Public(wire as Index), coefficient)),
+                    wire if wire < num_wires => {
+                        terms.push((AssignmentVariable::Private((wire - num_public) as Index), coefficient))
+                    }
+                    _ => return Err(error("Invalid wire in the r1cs file")),
+                }
+            }
+            Ok(AssignmentLC { constant, terms })
+        };
+        let constraints =
+            (0..num_constraints).map(|_| Ok((read_lc()?, read_lc()?, read_lc()?))).collect::<IoResult<Vec<_>>>()?;
+
+        // Read the header of the `.wtns` file.
+        let Some(header) = wtns_sections.remove(&WTNS_HEADER_SECTION) else {
+            return Err(error("Missing the header section in the wtns file"));
+        };
+        let mut header = &header[..];
+        Self::read_field_header(&mut header)?;
+        if u32::read_le(&mut header)? as usize != num_wires {
+            return Err(error("The number of values in the wtns file does not match the r1cs file"));
+        }
+
+        // Read the values of the `.wtns` file.
+        let Some(values) = wtns_sections.remove(&WTNS_VALUES_SECTION) else {
+            return Err(error("Missing the values section in the wtns file"));
+        };
+        let mut reader = &values[..];
+        let values = (0..num_wires).map(|_| read_field::<F, _>(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Ensure wire 0 is the constant `1`.
+        if values.first() != Some(&F::one()) {
+            return Err(error("The first value in the wtns file must be one"));
+        }
+
+        // Construct the assignment.
+        let (public, private) = values.split_at(num_public);
+        Ok(Self {
+            public: public.iter().enumerate().map(|(index, value)| (index as Index, *value)).collect(),
+            private: private.iter().enumerate().map(|(index, value)| (index as Index, *value)).collect(),
+            constraints: constraints.into(),
+            num_variables: num_wires as u64,
+        })
+    }
+
+    /// Returns the number of wires, which are the public and private variables.
+    fn num_wires(&self) -> usize {
+        self.public.len() + self.private.len()
+    }
+
+    /// Returns the terms of the given linear combination as `(wire, coefficient)` pairs, in ascending order of wire.
+    fn to_wire_terms(&self, lc: &AssignmentLC<F>) -> IoResult<BTreeMap<u32, F>> {
+        let mut terms = BTreeMap::new();
+        let mut add_term = |wire: usize, coefficient: F| {
+            *terms.entry(wire as u32).or_insert_with(F::zero) += coefficient;
+        };
+        add_term(0, lc.constant);
+        for (variable, coefficient) in lc.terms.iter() {
+            match variable {
+                AssignmentVariable::Constant(_) => return Err(error("Constants cannot be terms of a constraint")),
+                AssignmentVariable::Public(index) => add_term(*index as usize, *coefficient),
+                AssignmentVariable::Private(index) => add_term(self.public.len() + *index as usize, *coefficient),
+            }
+        }
+        terms.retain(|_, coefficient| !coefficient.is_zero());
+        Ok(terms)
+    }
+
+    /// Writes the size of a field element in bytes, followed by the modulus.
+    fn write_field_header<W: Write>(mut writer: W) -> IoResult<()> {
+        (field_size_in_bytes::<F>() as u32).write_le(&mut writer)?;
+        F::modulus().write_le(&mut writer)
+    }
+
+    /// Reads the size of a field element in bytes, followed by the modulus, and ensures they match the field.
+    fn read_field_header<R: Read>(mut reader: R) -> IoResult<()> {
+        if u32::read_le(&mut reader)? as usize != field_size_in_bytes::<F>() {
+            return Err(error("The field element size does not match the field"));
+        }
+        if F::BigInteger::read_le(&mut reader)? != F::modulus() {
+            return Err(error("The modulus does not match the field"));
+        }
+        Ok(())
+    }
+}
+
+/// Writes a section with the given type and contents.
+fn write_section<W: Write>(mut writer: W, section_type: u32, contents: &[u8]) -> IoResult<()> {
+    section_type.write_le(&mut writer)?;
+    (contents.len() as u64).write_le(&mut writer)?;
+    writer.write_all(contents)
+}
+
+/// Reads the sections of a file with the given magic bytes and version, by section type.
+fn read_sections<R: Read>(mut reader: R, magic: &[u8; 4], version: u32) -> IoResult<HashMap<u32, Vec<u8>>> {
+    // Ensure the magic bytes and version match.
+    if <[u8; 4]>::read_le(&mut reader)? != *magic {
+        return Err(error("Invalid magic bytes"));
+    }
+    if u32::read_le(&mut reader)? != version {
+        return Err(error("Unsupported version"));
+    }
+    // Read the sections.
+    let num_sections = u32::read_le(&mut reader)?;
+    let mut sections = HashMap::new();
+    for _ in 0..num_sections {
+        let section_type = u32::read_le(&mut reader)?;
+        let size = u64::read_le(&mut reader)?;
+        let mut contents = Vec::new();
+        (&mut reader).take(size).read_to_end(&mut contents)?;
+        if contents.len() as u64 != size {
+            return Err(error("Unexpected end of a section"));
+        }
+        if sections.insert(section_type, contents).is_some() {
+            return Err(error("Duplicate section"));
+        }
+    }
+    Ok(sections)
+}
+
+/// Returns the size of a field element in bytes.
+fn field_size_in_bytes<F: PrimeField>() -> usize {
+    F::BigInteger::NUM_LIMBS * 8
+}
+
+/// Reads a field element in its canonical little-endian representation.
+fn read_field<F: PrimeField, R: Read>(reader: R) -> IoResult<F> {
+    F::from_bigint(F::BigInteger::read_le(reader)?).ok_or_else(|| error("Invalid field element"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_algorithms::r1cs::{ConstraintSynthesizer, ConstraintSystem, TestConstraintSystem};
+    use snarkvm_circuit::prelude::*;
+
+    type CurrentField = <Circuit as Environment>::BaseField;
+
+    /// Returns the assignment of a circuit that computes `(a + 1) * b`.
+    fn sample_assignment() -> Assignment<CurrentField> {
+        let one = snarkvm_console_types::Field::<<Circuit as Environment>::Network>::one();
+        let a = Field::<Circuit>::new(Mode::Public, one + one);
+        let b = Field::<Circuit>::new(Mode::Private, one + one + one);
+        let _output = (a + Field::new(Mode::Constant, one)) * b;
+        assert!(Circuit::is_satisfied());
+        Circuit::eject_assignment_and_reset()
+    }
+
+    #[test]
+    fn test_r1cs_and_wtns() {
+        let assignment = sample_assignment();
+
+        // Export the assignment.
+        let mut r1cs = Vec::new();
+        assignment.write_r1cs(&mut r1cs).unwrap();
+        let mut wtns = Vec::new();
+        assignment.write_wtns(&mut wtns).unwrap();
+        assert_eq!(&r1cs[..4], R1CS_MAGIC);
+        assert_eq!(&wtns[..4], WTNS_MAGIC);
+
+        // Import the assignment.
+        let candidate = Assignment::<CurrentField>::read_r1cs_and_wtns(&r1cs[..], &wtns[..]).unwrap();
+        assert_eq!(assignment.num_public(), candidate.num_public());
+        assert_eq!(assignment.num_private(), candidate.num_private());
+        assert_eq!(assignment.num_constraints(), candidate.num_constraints());
+        assert_eq!(assignment.public_inputs(), candidate.public_inputs());
+        assert_eq!(assignment.private_inputs(), candidate.private_inputs());
+
+        // Ensure the imported assignment is satisfied.
+        let mut cs = TestConstraintSystem::new();
+        candidate.generate_constraints(&mut cs).unwrap();
+        assert_eq!(assignment.num_constraints(), cs.num_constraints() as u64);
+        assert!(cs.is_satisfied());
+        assert!(candidate.to_r1cs_circuit().unwrap().is_satisfied());
+
+        // Ensure the imported assignment is exported to the same bytes.
+        let mut candidate_r1cs = Vec::new();
+        candidate.write_r1cs(&mut candidate_r1cs).unwrap();
+        let mut candidate_wtns = Vec::new();
+        candidate.write_wtns(&mut candidate_wtns).unwrap();
+        assert_eq!(r1cs, candidate_r1cs);
+        assert_eq!(wtns, candidate_wtns);
+    }
+
+    #[test]
+    fn test_r1cs_and_wtns_invalid() {
+        let assignment = sample_assignment();
+        let mut r1cs = Vec::new();
+        assignment.write_r1cs(&mut r1cs).unwrap();
+        let mut wtns = Vec::new();
+        assignment.write_wtns(&mut wtns).unwrap();
+
+        // Ensure the files cannot be swapped.
+        assert!(Assignment::<CurrentField>::read_r1cs_and_wtns(&wtns[..], &r1cs[..]).is_err());
+        // Ensure a truncated file is rejected.
+        assert!(Assignment::<CurrentField>::read_r1cs_and_wtns(&r1cs[..r1cs.len() - 1], &wtns[..]).is_err());
+        // Ensure an unsatisfying witness is detected.
+        let last_value = wtns.len() - field_size_in_bytes::<CurrentField>();
+        wtns[last_value] ^= 1;
+        let candidate = Assignment::<CurrentField>::read_r1cs_and_wtns(&r1cs[..], &wtns[..]).unwrap();
+        let mut cs = TestConstraintSystem::new();
+        candidate.generate_constraints(&mut cs).unwrap();
+        assert!(!cs.is_satisfied());
+        assert!(!candidate.to_r1cs_circuit().unwrap().is_satisfied());
+    }
+}
@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::Index;
+mod iden3;
+
+use crate::{
+    prelude::{bail, Error, Result},
+    Index,
+};
 use snarkvm_fields::PrimeField;
 
 use indexmap::IndexMap;
@@ -157,6 +162,40 @@ impl<F: PrimeField> Assignment<F> {
             .map(|(a, b, c)| (a.num_nonzeros(), b.num_nonzeros(), c.num_nonzeros()))
             .fold((0, 0, 0), |(a, b, c), (x, y, z)| (a.saturating_add(x), b.saturating_add(y), c.saturating_add(z)))
     }
+
+    /// Returns the assignment as a generic circuit of constraint matrices and a witness,
+    /// which can be indexed, proven, and verified with Varuna.
+    pub fn to_r1cs_circuit(&self) -> Result<snarkvm_algorithms::r1cs::R1CSCircuit<F>> {
+        // Returns the column of the given variable.
+        let num_public = self.public.len();
+        let to_column = |variable: &AssignmentVariable<F>| match variable {
+            AssignmentVariable::Constant(_) => bail!("Constants cannot be terms of a constraint"),
+            AssignmentVariable::Public(index) => Ok(*index as usize),
+            AssignmentVariable::Private(index) => Ok(num_public + *index as usize),
+        };
+        // Converts a linear combination into a row of a matrix, where the constant term is in column 0.
+        let to_row = |lc: &AssignmentLC<F>| {
+            let mut row = Vec::with_capacity(lc.terms.len() + 1);
+            if !lc.constant.is_zero() {
+                row.push((lc.constant, 0));
+            }
+            for (variable, coefficient) in lc.terms.iter() {
+                row.push((*coefficient, to_column(variable)?));
+            }
+            Ok::<_, Error>(row)
+        };
+
+        // Construct the constraint matrices.
+        let (mut a, mut b, mut c) = (Vec::new(), Vec::new(), Vec::new());
+        for (a_lc, b_lc, c_lc) in self.constraints.iter() {
+            a.push(to_row(a_lc)?);
+            b.push(to_row(b_lc)?);
+            c.push(to_row(c_lc)?);
+        }
+        // Construct the circuit.
+        let witness = self.public.iter().chain(self.private.iter()).map(|(_, value)| *value).collect();
+        snarkvm_algorithms::r1cs::R1CSCircuit::new(num_public, self.private.len(), a, b, c)?.with_witness(witness)
+    }
 }
 
 impl<F: PrimeField> snarkvm_algorithms::r1cs::ConstraintSynthesizer<F> for Assignment<F> {
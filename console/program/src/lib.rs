@@ -50,3 +50,6 @@ pub use response::*;
 
 pub mod state_path;
 pub use state_path::*;
+
+mod upgrade_policy;
+pub use upgrade_policy::*;
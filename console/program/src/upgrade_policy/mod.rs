@@ -0,0 +1,123 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod bytes;
+mod parse;
+mod serialize;
+
+use snarkvm_console_account::Address;
+use snarkvm_console_network::Network;
+use snarkvm_console_types::prelude::*;
+
+/// The upgrade policy declared by a deployment, which governs how the *next* edition of the program may be deployed.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UpgradePolicy<N: Network> {
+    /// The program may not be upgraded.
+    Immutable,
+    /// The program may be upgraded by a deployment that is owned by the given address.
+    Admin(Address<N>),
+    /// The program may be upgraded to the program with the given checksum.
+    Checksum(Field<N>),
+}
+
+impl<N: Network> UpgradePolicy<N> {
+    /// Returns `true` if the upgrade policy does not permit any upgrades.
+    pub const fn is_immutable(&self) -> bool {
+        matches!(self, Self::Immutable)
+    }
+
+    /// Returns the admin address, if the upgrade policy is `Admin`.
+    pub const fn admin(&self) -> Option<&Address<N>> {
+        match self {
+            Self::Admin(address) => Some(address),
+            _ => None,
+        }
+    }
+
+    /// Returns the locked program checksum, if the upgrade policy is `Checksum`.
+    pub const fn checksum(&self) -> Option<&Field<N>> {
+        match self {
+            Self::Checksum(checksum) => Some(checksum),
+            _ => None,
+        }
+    }
+
+    /// Ensures the next edition of the program, with the given owner and program checksum, is permitted by this policy.
+    pub fn check_upgrade(&self, owner: &Address<N>, checksum: &Field<N>) -> Result<()> {
+        match self {
+            Self::Immutable => bail!("The program is immutable and cannot be upgraded"),
+            Self::Admin(admin) => {
+                ensure!(admin == owner, "The upgrade must be owned by the admin '{admin}' (found '{owner}')")
+            }
+            Self::Checksum(expected) => {
+                ensure!(expected == checksum, "The upgrade must have the checksum '{expected}' (found '{checksum}')")
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> Default for UpgradePolicy<N> {
+    /// Returns the default upgrade policy, which is `Immutable`.
+    fn default() -> Self {
+        Self::Immutable
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use super::*;
+    use snarkvm_console_account::PrivateKey;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    pub(crate) fn sample_upgrade_policies() -> Vec<UpgradePolicy<CurrentNetwork>> {
+        // Initialize the RNG.
+        let rng = &mut TestRng::default();
+
+        // Sample an admin address and a checksum.
+        let admin = Address::try_from(PrivateKey::new(rng).unwrap()).unwrap();
+        let checksum: Field<CurrentNetwork> = rng.gen();
+
+        vec![UpgradePolicy::Immutable, UpgradePolicy::Admin(admin), UpgradePolicy::Checksum(checksum)]
+    }
+
+    #[test]
+    fn test_check_upgrade() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample the owners and checksums.
+        let admin = Address::<CurrentNetwork>::try_from(PrivateKey::new(rng)?)?;
+        let other = Address::<CurrentNetwork>::try_from(PrivateKey::new(rng)?)?;
+        let checksum: Field<CurrentNetwork> = rng.gen();
+        let other_checksum: Field<CurrentNetwork> = rng.gen();
+
+        // An immutable program cannot be upgraded.
+        assert!(UpgradePolicy::Immutable.check_upgrade(&admin, &checksum).is_err());
+
+        // An admin program can only be upgraded by the admin.
+        let policy = UpgradePolicy::Admin(admin);
+        assert!(policy.check_upgrade(&admin, &checksum).is_ok());
+        assert!(policy.check_upgrade(&admin, &other_checksum).is_ok());
+        assert!(policy.check_upgrade(&other, &checksum).is_err());
+
+        // A checksum-locked program can only be upgraded to the locked checksum.
+        let policy = UpgradePolicy::Checksum(checksum);
+        assert!(policy.check_upgrade(&admin, &checksum).is_ok());
+        assert!(policy.check_upgrade(&other, &checksum).is_ok());
+        assert!(policy.check_upgrade(&admin, &other_checksum).is_err());
+        Ok(())
+    }
+}
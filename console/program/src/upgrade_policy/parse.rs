@@ -0,0 +1,85 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> Parser for UpgradePolicy<N> {
+    /// Parses a string into an upgrade policy of the form `immutable`, `admin({address})`, or `checksum({field})`.
+    #[inline]
+    fn parse(string: &str) -> ParserResult<Self> {
+        alt((
+            map(tag("immutable"), |_| Self::Immutable),
+            map(pair(tag("admin("), terminated(Address::parse, tag(")"))), |(_, address)| Self::Admin(address)),
+            map(pair(tag("checksum("), terminated(Field::parse, tag(")"))), |(_, checksum)| Self::Checksum(checksum)),
+        ))(string)
+    }
+}
+
+impl<N: Network> FromStr for UpgradePolicy<N> {
+    type Err = Error;
+
+    /// Parses a string into an upgrade policy.
+    #[inline]
+    fn from_str(string: &str) -> Result<Self> {
+        match Self::parse(string) {
+            Ok((remainder, object)) => {
+                // Ensure the remainder is empty.
+                ensure!(remainder.is_empty(), "Failed to parse string. Found invalid character in: \"{remainder}\"");
+                // Return the object.
+                Ok(object)
+            }
+            Err(error) => bail!("Failed to parse string. {error}"),
+        }
+    }
+}
+
+impl<N: Network> Debug for UpgradePolicy<N> {
+    /// Prints the upgrade policy as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<N: Network> Display for UpgradePolicy<N> {
+    /// Prints the upgrade policy as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Immutable => write!(f, "immutable"),
+            Self::Admin(address) => write!(f, "admin({address})"),
+            Self::Checksum(checksum) => write!(f, "checksum({checksum})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_parse_display() -> Result<()> {
+        for expected in test_helpers::sample_upgrade_policies() {
+            let candidate = UpgradePolicy::<CurrentNetwork>::from_str(&expected.to_string())?;
+            assert_eq!(expected, candidate);
+        }
+
+        assert_eq!(UpgradePolicy::<CurrentNetwork>::Immutable, UpgradePolicy::from_str("immutable")?);
+        assert!(UpgradePolicy::<CurrentNetwork>::from_str("admin()").is_err());
+        assert!(UpgradePolicy::<CurrentNetwork>::from_str("checksum(1u8)").is_err());
+        assert!(UpgradePolicy::<CurrentNetwork>::from_str("immutable ").is_err());
+        Ok(())
+    }
+}
@@ -20,7 +20,7 @@ impl<N: Network> FromBytes for Deployment<N> {
         // Read the version.
         let version = u8::read_le(&mut reader)?;
         // Ensure the version is valid.
-        if version != 1 {
+        if version != 1 && version != 2 {
             return Err(error("Invalid deployment version"));
         }
 
@@ -28,6 +28,11 @@ impl<N: Network> FromBytes for Deployment<N> {
         let edition = u16::read_le(&mut reader)?;
         // Read the program.
         let program = Program::read_le(&mut reader)?;
+        // Read the upgrade policy.
+        let upgrade_policy = match version {
+            1 => UpgradePolicy::Immutable,
+            _ => UpgradePolicy::read_le(&mut reader)?,
+        };
 
         // Read the number of entries in the bundle.
         let num_entries = u16::read_le(&mut reader)?;
@@ -44,8 +49,14 @@ impl<N: Network> FromBytes for Deployment<N> {
             verifying_keys.push((identifier, (verifying_key, certificate)));
         }
 
-        // Return the deployment.
-        Self::new(edition, program, verifying_keys).map_err(|err| error(format!("{err}")))
+        // Construct the deployment.
+        let deployment = Self::new_with_upgrade_policy(edition, program, verifying_keys, upgrade_policy)
+            .map_err(|err| error(format!("{err}")))?;
+        // Ensure the version matches the deployment format, so that the encoding is unique.
+        match (version == 1) == deployment.is_legacy() {
+            true => Ok(deployment),
+            false => Err(error(format!("Invalid deployment version {version} for edition {edition}"))),
+        }
     }
 }
 
@@ -53,11 +64,18 @@ impl<N: Network> ToBytes for Deployment<N> {
     /// Writes the deployment to a buffer.
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
         // Write the version.
-        1u8.write_le(&mut writer)?;
+        match self.is_legacy() {
+            true => 1u8.write_le(&mut writer)?,
+            false => 2u8.write_le(&mut writer)?,
+        }
         // Write the edition.
         self.edition.write_le(&mut writer)?;
         // Write the program.
         self.program.write_le(&mut writer)?;
+        // Write the upgrade policy.
+        if !self.is_legacy() {
+            self.upgrade_policy.write_le(&mut writer)?;
+        }
         // Write the number of entries in the bundle.
         (u16::try_from(self.verifying_keys.len()).map_err(|e| error(e.to_string()))?).write_le(&mut writer)?;
         // Write each entry.
@@ -76,6 +94,12 @@ impl<N: Network> ToBytes for Deployment<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use console::{
+        account::{Address, PrivateKey},
+        network::MainnetV0,
+    };
+
+    type CurrentNetwork = MainnetV0;
 
     #[test]
     fn test_bytes() -> Result<()> {
@@ -89,4 +113,31 @@ mod tests {
         assert_eq!(expected, Deployment::read_le(&expected_bytes[..])?);
         Ok(())
     }
+
+    #[test]
+    fn test_bytes_with_upgrade_policy() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Construct a new deployment with an admin upgrade policy.
+        let deployment = test_helpers::sample_deployment(rng);
+        let admin = Address::try_from(PrivateKey::new(rng)?)?;
+        let expected = Deployment::new_with_upgrade_policy(
+            deployment.edition(),
+            deployment.program().clone(),
+            deployment.verifying_keys().clone(),
+            UpgradePolicy::Admin(admin),
+        )?;
+        assert!(!expected.is_legacy());
+
+        // Check the byte representation.
+        let expected_bytes = expected.to_bytes_le()?;
+        assert_eq!(2u8, expected_bytes[0]);
+        assert_eq!(expected, Deployment::read_le(&expected_bytes[..])?);
+
+        // Ensure the legacy version cannot be used for a non-legacy deployment.
+        let mut malformed_bytes = expected_bytes;
+        malformed_bytes[0] = 1u8;
+        assert!(Deployment::<CurrentNetwork>::read_le(&malformed_bytes[..]).is_err());
+        Ok(())
+    }
 }
@@ -21,7 +21,7 @@ mod string;
 use crate::Transaction;
 use console::{
     network::prelude::*,
-    program::{Identifier, ProgramID},
+    program::{Identifier, ProgramID, UpgradePolicy},
     types::Field,
 };
 use synthesizer_program::Program;
@@ -35,17 +35,29 @@ pub struct Deployment<N: Network> {
     program: Program<N>,
     /// The mapping of function names to their verifying key and certificate.
     verifying_keys: Vec<(Identifier<N>, (VerifyingKey<N>, Certificate<N>))>,
+    /// The upgrade policy for the next edition of the program.
+    upgrade_policy: UpgradePolicy<N>,
 }
 
 impl<N: Network> Deployment<N> {
-    /// Initializes a new deployment.
+    /// Initializes a new deployment, with an immutable upgrade policy.
     pub fn new(
         edition: u16,
         program: Program<N>,
         verifying_keys: Vec<(Identifier<N>, (VerifyingKey<N>, Certificate<N>))>,
+    ) -> Result<Self> {
+        Self::new_with_upgrade_policy(edition, program, verifying_keys, UpgradePolicy::Immutable)
+    }
+
+    /// Initializes a new deployment, with the given upgrade policy.
+    pub fn new_with_upgrade_policy(
+        edition: u16,
+        program: Program<N>,
+        verifying_keys: Vec<(Identifier<N>, (VerifyingKey<N>, Certificate<N>))>,
+        upgrade_policy: UpgradePolicy<N>,
     ) -> Result<Self> {
         // Construct the deployment.
-        let deployment = Self { edition, program, verifying_keys };
+        let deployment = Self { edition, program, verifying_keys, upgrade_policy };
         // Ensure the deployment is ordered.
         deployment.check_is_ordered()?;
         // Return the deployment.
@@ -56,10 +68,11 @@ impl<N: Network> Deployment<N> {
     pub fn check_is_ordered(&self) -> Result<()> {
         let program_id = self.program.id();
 
-        // Ensure the edition matches.
+        // Ensure the edition is not below the network edition.
+        // Note: Upgrades of an existing program are deployed with subsequent editions.
         ensure!(
-            self.edition == N::EDITION,
-            "Deployed the wrong edition (expected '{}', found '{}').",
+            self.edition >= N::EDITION,
+            "Deployed the wrong edition (expected at least '{}', found '{}').",
             N::EDITION,
             self.edition
         );
@@ -124,6 +137,24 @@ impl<N: Network> Deployment<N> {
         &self.verifying_keys
     }
 
+    /// Returns the upgrade policy for the next edition of the program.
+    pub const fn upgrade_policy(&self) -> &UpgradePolicy<N> {
+        &self.upgrade_policy
+    }
+
+    /// Returns `true` if the deployment is an upgrade of an existing program.
+    pub fn is_upgrade(&self) -> bool {
+        self.edition > N::EDITION
+    }
+
+    /// Returns `true` if the deployment uses the original (version 1) format, which applies to
+    /// the initial edition of a program with an immutable upgrade policy.
+    ///
+    /// Note: All other deployments commit to their edition and upgrade policy in the deployment ID.
+    pub fn is_legacy(&self) -> bool {
+        self.edition == N::EDITION && self.upgrade_policy.is_immutable()
+    }
+
     /// Returns the sum of the variable counts for all functions in this deployment.
     pub fn num_combined_variables(&self) -> Result<u64> {
         // Initialize the accumulator.
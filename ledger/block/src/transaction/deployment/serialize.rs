@@ -19,10 +19,14 @@ impl<N: Network> Serialize for Deployment<N> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match serializer.is_human_readable() {
             true => {
-                let mut deployment = serializer.serialize_struct("Deployment", 3)?;
+                let len = if self.is_legacy() { 3 } else { 4 };
+                let mut deployment = serializer.serialize_struct("Deployment", len)?;
                 deployment.serialize_field("edition", &self.edition)?;
                 deployment.serialize_field("program", &self.program)?;
                 deployment.serialize_field("verifying_keys", &self.verifying_keys)?;
+                if !self.is_legacy() {
+                    deployment.serialize_field("upgrade_policy", &self.upgrade_policy)?;
+                }
                 deployment.end()
             }
             false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
@@ -38,14 +42,22 @@ impl<'de, N: Network> Deserialize<'de> for Deployment<N> {
                 // Parse the deployment from a string into a value.
                 let mut deployment = serde_json::Value::deserialize(deserializer)?;
 
+                // Retrieve the upgrade policy, which is omitted for legacy deployments.
+                let upgrade_policy = match deployment.get("upgrade_policy") {
+                    Some(_) => DeserializeExt::take_from_value::<D>(&mut deployment, "upgrade_policy")?,
+                    None => UpgradePolicy::Immutable,
+                };
+
                 // Recover the deployment.
-                let deployment = Self::new(
+                let deployment = Self::new_with_upgrade_policy(
                     // Retrieve the edition.
                     DeserializeExt::take_from_value::<D>(&mut deployment, "edition")?,
                     // Retrieve the program.
                     DeserializeExt::take_from_value::<D>(&mut deployment, "program")?,
                     // Retrieve the verifying keys.
                     DeserializeExt::take_from_value::<D>(&mut deployment, "verifying_keys")?,
+                    // Use the upgrade policy.
+                    upgrade_policy,
                 )
                 .map_err(de::Error::custom)?;
 
@@ -109,12 +109,19 @@ impl<N: Network> Transaction<N> {
         let program = deployment.program();
         // Prepare the leaves.
         let leaves = program.functions().values().enumerate().map(|(index, function)| {
+            // Compute the leaf input, which additionally commits to the edition and upgrade policy,
+            // if the deployment is not in the legacy format.
+            let input = match deployment.is_legacy() {
+                true => to_bits_le![program.id(), function.to_bytes_le()?],
+                false => to_bits_le![
+                    program.id(),
+                    deployment.edition(),
+                    deployment.upgrade_policy().to_bytes_le()?,
+                    function.to_bytes_le()?
+                ],
+            };
             // Construct the transaction leaf.
-            Ok(TransactionLeaf::new_deployment(
-                u16::try_from(index)?,
-                N::hash_bhp1024(&to_bits_le![program.id(), function.to_bytes_le()?])?,
-            )
-            .to_bits_le())
+            Ok(TransactionLeaf::new_deployment(u16::try_from(index)?, N::hash_bhp1024(&input)?).to_bits_le())
         });
         // If the fee is present, add it to the leaves.
         let leaves = match fee {
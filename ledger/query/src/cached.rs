@@ -0,0 +1,196 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::QueryTrait;
+use console::{network::prelude::*, program::StatePath, types::Field};
+
+use indexmap::IndexMap;
+use std::sync::RwLock;
+
+/// A query that caches the state root and state paths fetched from the wrapped query.
+///
+/// The cached state paths are for the cached state root, until the cache is cleared.
+pub struct CachedQuery<N: Network, Q: QueryTrait<N>> {
+    /// The wrapped query.
+    query: Q,
+    /// The cached state root.
+    state_root: RwLock<Option<N::StateRoot>>,
+    /// The cached state paths.
+    state_paths: RwLock<IndexMap<Field<N>, StatePath<N>>>,
+}
+
+impl<N: Network, Q: QueryTrait<N>> CachedQuery<N, Q> {
+    /// Initializes a new cache for the given query.
+    pub fn new(query: Q) -> Self {
+        Self { query, state_root: Default::default(), state_paths: Default::default() }
+    }
+
+    /// Returns the wrapped query.
+    pub const fn query(&self) -> &Q {
+        &self.query
+    }
+
+    /// Clears the cached state root and state paths, so that they are fetched again from the wrapped query.
+    pub fn clear(&self) {
+        *self.state_root.write().unwrap_or_else(|e| e.into_inner()) = None;
+        self.state_paths.write().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Returns the cached state root, if it exists.
+    fn cached_state_root(&self) -> Option<N::StateRoot> {
+        *self.state_root.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the cached state path for the given commitment, if it exists.
+    fn cached_state_path(&self, commitment: &Field<N>) -> Option<StatePath<N>> {
+        self.state_paths.read().unwrap_or_else(|e| e.into_inner()).get(commitment).cloned()
+    }
+
+    /// Caches the given state root.
+    fn cache_state_root(&self, state_root: N::StateRoot) -> N::StateRoot {
+        *self.state_root.write().unwrap_or_else(|e| e.into_inner()) = Some(state_root);
+        state_root
+    }
+
+    /// Caches the given state path, if it is for the cached state root, or if there is no cached state root.
+    fn cache_state_path(&self, commitment: Field<N>, state_path: StatePath<N>) -> StatePath<N> {
+        let mut state_root = self.state_root.write().unwrap_or_else(|e| e.into_inner());
+        match *state_root {
+            Some(state_root) if state_root != state_path.global_state_root() => (),
+            _ => {
+                *state_root = Some(state_path.global_state_root());
+                self.state_paths.write().unwrap_or_else(|e| e.into_inner()).insert(commitment, state_path.clone());
+            }
+        }
+        state_path
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait(?Send))]
+impl<N: Network, Q: QueryTrait<N>> QueryTrait<N> for CachedQuery<N, Q> {
+    /// Returns the current state root.
+    fn current_state_root(&self) -> Result<N::StateRoot> {
+        match self.cached_state_root() {
+            Some(state_root) => Ok(state_root),
+            None => Ok(self.cache_state_root(self.query.current_state_root()?)),
+        }
+    }
+
+    /// Returns the current state root.
+    #[cfg(feature = "async")]
+    async fn current_state_root_async(&self) -> Result<N::StateRoot> {
+        match self.cached_state_root() {
+            Some(state_root) => Ok(state_root),
+            None => Ok(self.cache_state_root(self.query.current_state_root_async().await?)),
+        }
+    }
+
+    /// Returns a state path for the given `commitment`.
+    fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        match self.cached_state_path(commitment) {
+            Some(state_path) => Ok(state_path),
+            None => Ok(self.cache_state_path(*commitment, self.query.get_state_path_for_commitment(commitment)?)),
+        }
+    }
+
+    /// Returns a state path for the given `commitment`.
+    #[cfg(feature = "async")]
+    async fn get_state_path_for_commitment_async(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        match self.cached_state_path(commitment) {
+            Some(state_path) => Ok(state_path),
+            None => {
+                let state_path = self.query.get_state_path_for_commitment_async(commitment).await?;
+                Ok(self.cache_state_path(*commitment, state_path))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SnapshotQuery;
+    use console::{network::MainnetV0, program::test_helpers::sample_global_state_path};
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    type CurrentNetwork = MainnetV0;
+
+    /// A query that counts the number of calls to the wrapped query.
+    struct CountingQuery {
+        query: SnapshotQuery<CurrentNetwork>,
+        count: AtomicUsize,
+    }
+
+    #[cfg_attr(feature = "async", async_trait(?Send))]
+    impl QueryTrait<CurrentNetwork> for CountingQuery {
+        fn current_state_root(&self) -> Result<<CurrentNetwork as Network>::StateRoot> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.query.current_state_root()
+        }
+
+        #[cfg(feature = "async")]
+        async fn current_state_root_async(&self) -> Result<<CurrentNetwork as Network>::StateRoot> {
+            self.current_state_root()
+        }
+
+        fn get_state_path_for_commitment(
+            &self,
+            commitment: &Field<CurrentNetwork>,
+        ) -> Result<StatePath<CurrentNetwork>> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.query.get_state_path_for_commitment(commitment)
+        }
+
+        #[cfg(feature = "async")]
+        async fn get_state_path_for_commitment_async(
+            &self,
+            commitment: &Field<CurrentNetwork>,
+        ) -> Result<StatePath<CurrentNetwork>> {
+            self.get_state_path_for_commitment(commitment)
+        }
+    }
+
+    #[test]
+    fn test_cached_query() {
+        let rng = &mut TestRng::default();
+
+        // Sample a snapshot with one state path.
+        let commitment = Field::<CurrentNetwork>::rand(rng);
+        let state_path = sample_global_state_path(Some(commitment), rng).unwrap();
+        let state_root = state_path.global_state_root();
+        let snapshot =
+            SnapshotQuery::new(state_root, [(commitment, state_path.clone())].into_iter().collect()).unwrap();
+
+        // Wrap the snapshot in a cache.
+        let query = CachedQuery::new(CountingQuery { query: snapshot, count: AtomicUsize::new(0) });
+
+        // Ensure the wrapped query is only called once for each value.
+        for _ in 0..3 {
+            assert_eq!(query.current_state_root().unwrap(), state_root);
+            assert_eq!(query.get_state_path_for_commitment(&commitment).unwrap(), state_path);
+        }
+        assert_eq!(query.query().count.load(Ordering::SeqCst), 2);
+
+        // Ensure errors are not cached.
+        assert!(query.get_state_path_for_commitment(&Field::rand(rng)).is_err());
+        assert!(query.get_state_path_for_commitment(&Field::rand(rng)).is_err());
+        assert_eq!(query.query().count.load(Ordering::SeqCst), 4);
+
+        // Ensure the cache can be cleared.
+        query.clear();
+        assert_eq!(query.current_state_root().unwrap(), state_root);
+        assert_eq!(query.query().count.load(Ordering::SeqCst), 5);
+    }
+}
@@ -23,5 +23,11 @@ mod query;
 #[cfg(feature = "query")]
 pub use query::*;
 
+mod cached;
+pub use cached::*;
+
+mod snapshot;
+pub use snapshot::*;
+
 mod traits;
 pub use traits::*;
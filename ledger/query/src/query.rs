@@ -21,12 +21,16 @@ use console::{
 use ledger_store::{BlockStorage, BlockStore};
 use synthesizer_program::Program;
 
+use std::sync::Arc;
+
 #[derive(Clone)]
 pub enum Query<N: Network, B: BlockStorage<N>> {
     /// The block store from the VM.
     VM(BlockStore<N, B>),
     /// The base URL of the node.
     REST(String),
+    /// A custom query backend, such as a `SnapshotQuery` or a `CachedQuery`.
+    Custom(Arc<dyn QueryTrait<N> + Send + Sync>),
 }
 
 impl<N: Network, B: BlockStorage<N>> From<BlockStore<N, B>> for Query<N, B> {
@@ -59,24 +63,20 @@ impl<N: Network, B: BlockStorage<N>> From<&str> for Query<N, B> {
     }
 }
 
+impl<N: Network, B: BlockStorage<N>> From<Box<dyn QueryTrait<N> + Send + Sync>> for Query<N, B> {
+    fn from(query: Box<dyn QueryTrait<N> + Send + Sync>) -> Self {
+        Self::Custom(Arc::from(query))
+    }
+}
+
 #[cfg_attr(feature = "async", async_trait(?Send))]
 impl<N: Network, B: BlockStorage<N>> QueryTrait<N> for Query<N, B> {
     /// Returns the current state root.
     fn current_state_root(&self) -> Result<N::StateRoot> {
         match self {
             Self::VM(block_store) => Ok(block_store.current_state_root()),
-            Self::REST(url) => match N::ID {
-                console::network::MainnetV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/mainnet/latest/stateRoot"))?.into_json()?)
-                }
-                console::network::TestnetV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/testnet/latest/stateRoot"))?.into_json()?)
-                }
-                console::network::CanaryV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/canary/latest/stateRoot"))?.into_json()?)
-                }
-                _ => bail!("Unsupported network ID in inclusion query"),
-            },
+            Self::REST(url) => Ok(Self::get_request(&Self::rest_url(url, "latest/stateRoot")?)?.into_json()?),
+            Self::Custom(query) => query.current_state_root(),
         }
     }
 
@@ -85,18 +85,10 @@ impl<N: Network, B: BlockStorage<N>> QueryTrait<N> for Query<N, B> {
     async fn current_state_root_async(&self) -> Result<N::StateRoot> {
         match self {
             Self::VM(block_store) => Ok(block_store.current_state_root()),
-            Self::REST(url) => match N::ID {
-                console::network::MainnetV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/mainnet/latest/stateRoot")).await?.json().await?)
-                }
-                console::network::TestnetV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/testnet/latest/stateRoot")).await?.json().await?)
-                }
-                console::network::CanaryV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/canary/latest/stateRoot")).await?.json().await?)
-                }
-                _ => bail!("Unsupported network ID in inclusion query"),
-            },
+            Self::REST(url) => {
+                Ok(Self::get_request_async(&Self::rest_url(url, "latest/stateRoot")?).await?.json().await?)
+            }
+            Self::Custom(query) => query.current_state_root_async().await,
         }
     }
 
@@ -104,18 +96,10 @@ impl<N: Network, B: BlockStorage<N>> QueryTrait<N> for Query<N, B> {
     fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
         match self {
             Self::VM(block_store) => block_store.get_state_path_for_commitment(commitment),
-            Self::REST(url) => match N::ID {
-                console::network::MainnetV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/mainnet/statePath/{commitment}"))?.into_json()?)
-                }
-                console::network::TestnetV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/testnet/statePath/{commitment}"))?.into_json()?)
-                }
-                console::network::CanaryV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/canary/statePath/{commitment}"))?.into_json()?)
-                }
-                _ => bail!("Unsupported network ID in inclusion query"),
-            },
+            Self::REST(url) => {
+                Ok(Self::get_request(&Self::rest_url(url, &format!("statePath/{commitment}"))?)?.into_json()?)
+            }
+            Self::Custom(query) => query.get_state_path_for_commitment(commitment),
         }
     }
 
@@ -124,18 +108,11 @@ impl<N: Network, B: BlockStorage<N>> QueryTrait<N> for Query<N, B> {
     async fn get_state_path_for_commitment_async(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
         match self {
             Self::VM(block_store) => block_store.get_state_path_for_commitment(commitment),
-            Self::REST(url) => match N::ID {
-                console::network::MainnetV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/mainnet/statePath/{commitment}")).await?.json().await?)
-                }
-                console::network::TestnetV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/testnet/statePath/{commitment}")).await?.json().await?)
-                }
-                console::network::CanaryV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/canary/statePath/{commitment}")).await?.json().await?)
-                }
-                _ => bail!("Unsupported network ID in inclusion query"),
-            },
+            Self::REST(url) => Ok(Self::get_request_async(&Self::rest_url(url, &format!("statePath/{commitment}"))?)
+                .await?
+                .json()
+                .await?),
+            Self::Custom(query) => query.get_state_path_for_commitment_async(commitment).await,
         }
     }
 }
@@ -147,18 +124,10 @@ impl<N: Network, B: BlockStorage<N>> Query<N, B> {
             Self::VM(block_store) => {
                 block_store.get_program(program_id)?.ok_or_else(|| anyhow!("Program {program_id} not found in storage"))
             }
-            Self::REST(url) => match N::ID {
-                console::network::MainnetV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/mainnet/program/{program_id}"))?.into_json()?)
-                }
-                console::network::TestnetV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/testnet/program/{program_id}"))?.into_json()?)
-                }
-                console::network::CanaryV0::ID => {
-                    Ok(Self::get_request(&format!("{url}/canary/program/{program_id}"))?.into_json()?)
-                }
-                _ => bail!("Unsupported network ID in inclusion query"),
-            },
+            Self::REST(url) => {
+                Ok(Self::get_request(&Self::rest_url(url, &format!("program/{program_id}"))?)?.into_json()?)
+            }
+            Self::Custom(..) => bail!("Program queries are unsupported by custom query backends"),
         }
     }
 
@@ -169,21 +138,56 @@ impl<N: Network, B: BlockStorage<N>> Query<N, B> {
             Self::VM(block_store) => {
                 block_store.get_program(program_id)?.ok_or_else(|| anyhow!("Program {program_id} not found in storage"))
             }
-            Self::REST(url) => match N::ID {
-                console::network::MainnetV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/mainnet/program/{program_id}")).await?.json().await?)
-                }
-                console::network::TestnetV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/testnet/program/{program_id}")).await?.json().await?)
-                }
-                console::network::CanaryV0::ID => {
-                    Ok(Self::get_request_async(&format!("{url}/canary/program/{program_id}")).await?.json().await?)
-                }
-                _ => bail!("Unsupported network ID in inclusion query"),
-            },
+            Self::REST(url) => Ok(Self::get_request_async(&Self::rest_url(url, &format!("program/{program_id}"))?)
+                .await?
+                .json()
+                .await?),
+            Self::Custom(..) => bail!("Program queries are unsupported by custom query backends"),
         }
     }
 
+    /// Returns the latest edition of the program for the given program ID.
+    pub fn get_program_edition(&self, program_id: &ProgramID<N>) -> Result<u16> {
+        match self {
+            Self::VM(block_store) => block_store
+                .transaction_store()
+                .deployment_store()
+                .get_edition(program_id)?
+                .ok_or_else(|| anyhow!("Program {program_id} not found in storage")),
+            Self::REST(url) => {
+                Ok(Self::get_request(&Self::rest_url(url, &format!("program/{program_id}/latest_edition"))?)?
+                    .into_json()?)
+            }
+            Self::Custom(..) => bail!("Program queries are unsupported by custom query backends"),
+        }
+    }
+
+    /// Returns the program for the given program ID, at the given edition.
+    pub fn get_program_for_edition(&self, program_id: &ProgramID<N>, edition: u16) -> Result<Program<N>> {
+        match self {
+            Self::VM(block_store) => block_store
+                .transaction_store()
+                .deployment_store()
+                .get_program_for_edition(program_id, edition)?
+                .ok_or_else(|| anyhow!("Program {program_id} (edition {edition}) not found in storage")),
+            Self::REST(url) => {
+                Ok(Self::get_request(&Self::rest_url(url, &format!("program/{program_id}/{edition}"))?)?.into_json()?)
+            }
+            Self::Custom(..) => bail!("Program queries are unsupported by custom query backends"),
+        }
+    }
+
+    /// Returns the URL of the given REST endpoint path, for the network.
+    pub fn rest_url(url: &str, path: &str) -> Result<String> {
+        let network = match N::ID {
+            console::network::MainnetV0::ID => "mainnet",
+            console::network::TestnetV0::ID => "testnet",
+            console::network::CanaryV0::ID => "canary",
+            _ => bail!("Unsupported network ID in inclusion query"),
+        };
+        Ok(format!("{url}/{network}/{path}"))
+    }
+
     /// Performs a GET request to the given URL.
     fn get_request(url: &str) -> Result<ureq::Response> {
         let response = ureq::get(url).call()?;
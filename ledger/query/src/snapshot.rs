@@ -0,0 +1,183 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::QueryTrait;
+use console::{network::prelude::*, program::StatePath, types::Field};
+
+use indexmap::IndexMap;
+use std::path::Path;
+
+/// A query backed by a snapshot of the ledger state, consisting of a state root and the state paths for a set of
+/// commitments. A snapshot is exported from a ledger, and allows offline provers to build inclusion proofs without a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotQuery<N: Network> {
+    /// The state root of the snapshot.
+    state_root: N::StateRoot,
+    /// The state paths, for the commitments in the snapshot.
+    state_paths: IndexMap<Field<N>, StatePath<N>>,
+}
+
+impl<N: Network> SnapshotQuery<N> {
+    /// Initializes a new snapshot from the given state root and state paths.
+    pub fn new(state_root: N::StateRoot, state_paths: IndexMap<Field<N>, StatePath<N>>) -> Result<Self> {
+        // Ensure the state paths are for the state root.
+        for (commitment, state_path) in &state_paths {
+            ensure!(
+                state_path.global_state_root() == state_root,
+                "The state path for commitment '{commitment}' is not for the state root '{state_root}'"
+            );
+        }
+        Ok(Self { state_root, state_paths })
+    }
+
+    /// Exports a snapshot of the current state in the given block store, for the given commitments.
+    #[cfg(feature = "query")]
+    pub fn from_block_store<B: ledger_store::BlockStorage<N>>(
+        block_store: &ledger_store::BlockStore<N, B>,
+        commitments: impl IntoIterator<Item = Field<N>>,
+    ) -> Result<Self> {
+        // Retrieve the state paths.
+        let state_paths = commitments
+            .into_iter()
+            .map(|commitment| Ok((commitment, block_store.get_state_path_for_commitment(&commitment)?)))
+            .collect::<Result<IndexMap<_, _>>>()?;
+        Self::new(block_store.current_state_root(), state_paths)
+    }
+
+    /// Loads the snapshot from the file at the given path.
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::from_bytes_le(&std::fs::read(path)?)
+    }
+
+    /// Saves the snapshot to a file at the given path.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::write(path, self.to_bytes_le()?)?)
+    }
+
+    /// Returns the state root of the snapshot.
+    pub const fn state_root(&self) -> N::StateRoot {
+        self.state_root
+    }
+
+    /// Returns the state paths, for the commitments in the snapshot.
+    pub const fn state_paths(&self) -> &IndexMap<Field<N>, StatePath<N>> {
+        &self.state_paths
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait(?Send))]
+impl<N: Network> QueryTrait<N> for SnapshotQuery<N> {
+    /// Returns the current state root.
+    fn current_state_root(&self) -> Result<N::StateRoot> {
+        Ok(self.state_root)
+    }
+
+    /// Returns the current state root.
+    #[cfg(feature = "async")]
+    async fn current_state_root_async(&self) -> Result<N::StateRoot> {
+        self.current_state_root()
+    }
+
+    /// Returns a state path for the given `commitment`.
+    fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        self.state_paths
+            .get(commitment)
+            .cloned()
+            .ok_or_else(|| anyhow!("Commitment '{commitment}' is not in the snapshot"))
+    }
+
+    /// Returns a state path for the given `commitment`.
+    #[cfg(feature = "async")]
+    async fn get_state_path_for_commitment_async(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        self.get_state_path_for_commitment(commitment)
+    }
+}
+
+impl<N: Network> FromBytes for SnapshotQuery<N> {
+    /// Reads the snapshot from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid snapshot version"));
+        }
+
+        // Read the state root.
+        let state_root = N::StateRoot::read_le(&mut reader)?;
+        // Read the state paths.
+        let num_state_paths = u32::read_le(&mut reader)?;
+        let mut state_paths = IndexMap::with_capacity(num_state_paths as usize);
+        for _ in 0..num_state_paths {
+            let commitment = Field::read_le(&mut reader)?;
+            let state_path = StatePath::read_le(&mut reader)?;
+            state_paths.insert(commitment, state_path);
+        }
+
+        Self::new(state_root, state_paths).map_err(error)
+    }
+}
+
+impl<N: Network> ToBytes for SnapshotQuery<N> {
+    /// Writes the snapshot to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+
+        // Write the state root.
+        self.state_root.write_le(&mut writer)?;
+        // Write the state paths.
+        u32::try_from(self.state_paths.len()).map_err(error)?.write_le(&mut writer)?;
+        for (commitment, state_path) in &self.state_paths {
+            commitment.write_le(&mut writer)?;
+            state_path.write_le(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::{network::MainnetV0, program::test_helpers::sample_global_state_path};
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_snapshot_query() {
+        let rng = &mut TestRng::default();
+
+        // Sample a state path.
+        let commitment = Field::<CurrentNetwork>::rand(rng);
+        let state_path = sample_global_state_path(Some(commitment), rng).unwrap();
+        let state_root = state_path.global_state_root();
+
+        // Construct the snapshot.
+        let snapshot =
+            SnapshotQuery::new(state_root, [(commitment, state_path.clone())].into_iter().collect()).unwrap();
+        assert_eq!(snapshot.current_state_root().unwrap(), state_root);
+        assert_eq!(snapshot.get_state_path_for_commitment(&commitment).unwrap(), state_path);
+        assert!(snapshot.get_state_path_for_commitment(&Field::rand(rng)).is_err());
+
+        // Ensure the snapshot round-trips through a file.
+        let path = std::env::temp_dir().join(format!("snapshot-{}.bin", u64::rand(rng)));
+        snapshot.save(&path).unwrap();
+        assert_eq!(SnapshotQuery::load(&path).unwrap(), snapshot);
+        std::fs::remove_file(path).unwrap();
+
+        // Ensure a state path for a different state root is rejected.
+        let other_state_path = sample_global_state_path(Some(commitment), rng).unwrap();
+        assert!(SnapshotQuery::new(state_root, [(commitment, other_state_path)].into_iter().collect()).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::program::Argument;
+use ledger_store::to_key_id;
+use synthesizer::{program::FinalizeOperation, vm::proving_rewards};
+
+/// Returns the key ID that the given finalize operation writes to, if any.
+fn to_written_key_id<N: Network>(operation: &FinalizeOperation<N>) -> Option<&Field<N>> {
+    match operation {
+        FinalizeOperation::InsertKeyValue(_, key_id, _)
+        | FinalizeOperation::UpdateKeyValue(_, key_id, _)
+        | FinalizeOperation::RemoveKeyValue(_, key_id) => Some(key_id),
+        _ => None,
+    }
+}
+
+/// Returns the arguments of the future output of the given transition, if any.
+fn to_future_arguments<N: Network>(transition: &Transition<N>) -> Option<&[Argument<N>]> {
+    transition.outputs().iter().find_map(|output| match output {
+        Output::Future(_, Some(future)) => Some(future.arguments()),
+        _ => None,
+    })
+}
+
+/// Returns the address in the given argument.
+fn to_address<N: Network>(argument: Option<&Argument<N>>) -> Result<Address<N>> {
+    match argument {
+        Some(Argument::Plaintext(Plaintext::Literal(Literal::Address(address), _))) => Ok(*address),
+        _ => bail!("Invalid address argument in a 'credits.aleo' future"),
+    }
+}
+
+/// Returns the amount in the given argument.
+fn to_amount<N: Network>(argument: Option<&Argument<N>>) -> Result<u64> {
+    match argument {
+        Some(Argument::Plaintext(Plaintext::Literal(Literal::U64(amount), _))) => Ok(**amount),
+        _ => bail!("Invalid amount argument in a 'credits.aleo' future"),
+    }
+}
+
+/// Returns the public balance in the given `account` value, or zero if there is no value.
+fn to_balance<N: Network>(value: Option<Value<N>>) -> Result<u64> {
+    match value {
+        Some(Value::Plaintext(Plaintext::Literal(Literal::U64(balance), _))) => Ok(*balance),
+        None => Ok(0),
+        _ => bail!("Invalid public balance in finalize storage"),
+    }
+}
+
+/// Returns the microcredits in the given `bonded` or `unbonding` value, or zero if there is no value.
+fn to_microcredits<N: Network>(value: Option<Value<N>>, microcredits: &Identifier<N>) -> Result<u64> {
+    match value {
+        Some(Value::Plaintext(Plaintext::Struct(state, _))) => match state.get(microcredits) {
+            Some(Plaintext::Literal(Literal::U64(amount), _)) => Ok(**amount),
+            _ => bail!("Invalid microcredits in finalize storage"),
+        },
+        None => Ok(0),
+        _ => bail!("Invalid bond or unbond state in finalize storage"),
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Returns the `credits.aleo` accounting of the given address over the given range of block heights,
+    /// which consists of its public balance changes, the public fees it paid, the puzzle and staking rewards
+    /// it received, and its bonding, unbonding and delegation events.
+    ///
+    /// The blocks that affect the address are found from their finalize operations and ratifications,
+    /// while the balances before and after each block are read from the finalize history.
+    ///
+    /// Note: The finalize history must cover the given range, and the blocks in the range must not be pruned.
+    /// Fees paid with private records are not included.
+    pub fn get_accounting_report(&self, address: &Address<N>, heights: Range<u32>) -> Result<AccountingReport<N>> {
+        let timer = timer!("Ledger::get_accounting_report");
+
+        // Ensure the range of block heights is valid.
+        ensure!(heights.start < heights.end, "The range of block heights is empty");
+        ensure!(heights.end <= self.latest_height() + 1, "The range of block heights exceeds the latest block");
+        // Ensure the finalize history covers the range.
+        let Some(start_height) = self.vm.finalize_store().history_start_height()? else {
+            bail!("The finalize history is not enabled")
+        };
+        ensure!(
+            heights.start >= start_height,
+            "The finalize history does not cover block {}, as it starts at block {start_height}",
+            heights.start
+        );
+
+        // Construct the `credits.aleo` identifiers.
+        let credits = ProgramID::from_str("credits.aleo")?;
+        let account = Identifier::from_str("account")?;
+        let bonded = Identifier::from_str("bonded")?;
+        let unbonding = Identifier::from_str("unbonding")?;
+        let delegated = Identifier::from_str("delegated")?;
+        let microcredits = Identifier::from_str("microcredits")?;
+
+        // Compute the key IDs of the address in the `credits.aleo` mappings.
+        let key = Plaintext::from(Literal::Address(*address));
+        let account_key_id = to_key_id(&credits, &account, &key)?;
+        let key_ids = [
+            account_key_id,
+            to_key_id(&credits, &bonded, &key)?,
+            to_key_id(&credits, &unbonding, &key)?,
+            to_key_id(&credits, &delegated, &key)?,
+        ];
+
+        // Returns the value of the given mapping for the address, after the block at the given height.
+        let value_after = |mapping: Identifier<N>, height: u32| {
+            self.vm.finalize_store().get_value_confirmed_at(credits, mapping, &key, height)
+        };
+        // Returns the value of the given mapping for the address, before the block at the given height.
+        let value_before = |mapping: Identifier<N>, height: u32| match height.checked_sub(1) {
+            Some(previous_height) => value_after(mapping, previous_height),
+            None => Ok(None),
+        };
+
+        let mut events = Vec::new();
+        for height in heights.clone() {
+            let block = self.get_block(height)?;
+
+            // Collect the events of the transactions that write to the entries of the address.
+            let mut is_account_written = false;
+            let (mut bonded_amount, mut unbonded_amount) = (0u64, 0u64);
+            for confirmed in block.transactions().iter() {
+                let written_key_ids = confirmed.finalize_operations().iter().filter_map(to_written_key_id);
+                let written_key_ids = written_key_ids.filter(|key_id| key_ids.contains(*key_id)).collect::<Vec<_>>();
+                if written_key_ids.is_empty() {
+                    continue;
+                }
+                is_account_written |= written_key_ids.contains(&&account_key_id);
+
+                for transition in confirmed.transaction().transitions() {
+                    if *transition.program_id() != credits {
+                        continue;
+                    }
+                    let Some(arguments) = to_future_arguments(transition) else {
+                        continue;
+                    };
+                    let transition_id = *transition.id();
+                    let function_name = transition.function_name().to_string();
+                    match function_name.as_str() {
+                        "fee_public" => {
+                            if to_address(arguments.first())? == *address {
+                                events.push(AccountingEvent::FeePaid(
+                                    height,
+                                    confirmed.id(),
+                                    to_amount(arguments.get(1))?,
+                                ));
+                            }
+                        }
+                        "bond_public" | "bond_validator" => {
+                            // Retrieve the staker, the validator, and the amount.
+                            let (staker, validator, amount) = match function_name.as_str() {
+                                "bond_public" => (
+                                    to_address(arguments.first())?,
+                                    to_address(arguments.get(1))?,
+                                    to_amount(arguments.get(3))?,
+                                ),
+                                _ => {
+                                    let validator = to_address(arguments.first())?;
+                                    (validator, validator, to_amount(arguments.get(2))?)
+                                }
+                            };
+                            if staker == *address {
+                                bonded_amount = bonded_amount.saturating_add(amount);
+                            }
+                            if staker == *address || validator == *address {
+                                events.push(AccountingEvent::Bonded(height, transition_id, staker, validator, amount));
+                            }
+                        }
+                        "unbond_public" => {
+                            let caller = to_address(arguments.first())?;
+                            let staker = to_address(arguments.get(1))?;
+                            let amount = to_amount(arguments.get(2))?;
+                            if staker == *address {
+                                unbonded_amount = unbonded_amount.saturating_add(amount);
+                            }
+                            if staker == *address || caller == *address {
+                                events.push(AccountingEvent::Unbonded(height, transition_id, staker, amount));
+                            }
+                        }
+                        "claim_unbond_public" => {
+                            if to_address(arguments.first())? == *address {
+                                // The entire unbonding balance is claimed.
+                                let amount = to_microcredits(value_before(unbonding, height)?, &microcredits)?;
+                                events.push(AccountingEvent::UnbondClaimed(height, transition_id, amount));
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            // Determine whether the ratifications write to the public balance of the address.
+            // Note: If the ratified finalize operations were not recorded, the public balance is checked.
+            let is_account_ratified = match self.vm.block_store().get_ratified_finalize_operations(&block.hash())? {
+                Some(operations) => {
+                    operations.iter().filter_map(to_written_key_id).any(|key_id| *key_id == account_key_id)
+                }
+                None => true,
+            };
+            for ratify in block.ratifications().iter() {
+                match ratify {
+                    Ratify::PuzzleReward(puzzle_reward) if is_account_ratified && *puzzle_reward > 0 => {
+                        // Compute the proving rewards of the block.
+                        let Some(solutions) = block.solutions().deref() else {
+                            continue;
+                        };
+                        let proof_targets = solutions
+                            .values()
+                            .map(|solution| Ok((solution.address(), self.puzzle().get_proof_target(solution)?)))
+                            .collect::<Result<Vec<_>>>()?;
+                        if let Some(amount) = proving_rewards(proof_targets, *puzzle_reward).get(address) {
+                            events.push(AccountingEvent::PuzzleReward(height, *amount));
+                        }
+                    }
+                    Ratify::BlockReward(block_reward) if *block_reward > 0 => {
+                        // The staking reward is the bonded balance after the block, less the bonded balance
+                        // after the transactions of the block, as the rewards are ratified after the transactions.
+                        let bonded_after = to_microcredits(value_after(bonded, height)?, &microcredits)?;
+                        if bonded_after > 0 {
+                            let bonded_before = to_microcredits(value_before(bonded, height)?, &microcredits)?;
+                            let bonded_after_transactions =
+                                bonded_before.saturating_add(bonded_amount).saturating_sub(unbonded_amount);
+                            let amount = bonded_after.saturating_sub(bonded_after_transactions);
+                            if amount > 0 {
+                                events.push(AccountingEvent::StakingReward(height, amount));
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            // Record the change in the public balance of the address.
+            if is_account_written || is_account_ratified {
+                let balance_before = to_balance(value_before(account, height)?)?;
+                let balance_after = to_balance(value_after(account, height)?)?;
+                if balance_before != balance_after {
+                    events.push(AccountingEvent::PublicBalanceChanged(height, balance_before, balance_after));
+                }
+            }
+        }
+
+        finish!(timer);
+        Ok(AccountingReport::new(*address, heights, events))
+    }
+}
@@ -43,6 +43,34 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         self.vm.transaction_store().find_transaction_id_from_transition_id(transition_id)
     }
 
+    /// Returns the (`block height`, `transition ID`) pairs of the transitions that call the given function,
+    /// in the order they were added to the ledger, skipping the first `offset` pairs and returning at most `limit`.
+    ///
+    /// Note: The transition index must be enabled, and only covers the blocks added after it was enabled.
+    pub fn find_transitions_from_function(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransitionID)>> {
+        self.vm.block_store().find_transitions_from_function(program_id, function_name, offset, limit)
+    }
+
+    /// Returns the (`block height`, `transition ID`) pairs of the transitions that have the given address
+    /// as a public argument, in the order they were added to the ledger, skipping the first `offset` pairs
+    /// and returning at most `limit`.
+    ///
+    /// Note: The transition index must be enabled, and only covers the blocks added after it was enabled.
+    pub fn find_transitions_from_address(
+        &self,
+        address: &Address<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransitionID)>> {
+        self.vm.block_store().find_transitions_from_address(address, offset, limit)
+    }
+
     /// Returns the transition ID that contains the given `input ID` or `output ID`.
     pub fn find_transition_id(&self, id: &Field<N>) -> Result<N::TransitionID> {
         self.vm.transition_store().find_transition_id(id)
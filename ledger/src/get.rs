@@ -47,6 +47,7 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
     }
 
     /// Returns a state path for the given commitment.
+    /// Returns an error if the block containing the commitment has been pruned.
     pub fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
         self.vm.block_store().get_state_path_for_commitment(commitment)
     }
@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{network::prelude::*, types::Address};
+
+use core::ops::Range;
+
+/// An event in the `credits.aleo` accounting of an address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccountingEvent<N: Network> {
+    /// The public balance changed in a block, as (`height`, `balance before`, `balance after`).
+    PublicBalanceChanged(u32, u64, u64),
+    /// A public fee was paid, as (`height`, `transaction ID`, `amount`).
+    FeePaid(u32, N::TransactionID, u64),
+    /// A puzzle reward was received, as (`height`, `amount`).
+    PuzzleReward(u32, u64),
+    /// A staking reward was received, as (`height`, `amount`).
+    StakingReward(u32, u64),
+    /// Microcredits were bonded, as (`height`, `transition ID`, `staker`, `validator`, `amount`).
+    /// The address is either the staker, or the validator that the microcredits are delegated to.
+    Bonded(u32, N::TransitionID, Address<N>, Address<N>, u64),
+    /// Microcredits were unbonded, as (`height`, `transition ID`, `staker`, `requested amount`).
+    /// The address is either the staker, or the validator that unbonded the staker.
+    Unbonded(u32, N::TransitionID, Address<N>, u64),
+    /// Unbonded microcredits were claimed, as (`height`, `transition ID`, `amount`).
+    UnbondClaimed(u32, N::TransitionID, u64),
+}
+
+impl<N: Network> AccountingEvent<N> {
+    /// Returns the height of the block that the event belongs to.
+    pub const fn height(&self) -> u32 {
+        match self {
+            Self::PublicBalanceChanged(height, ..)
+            | Self::FeePaid(height, ..)
+            | Self::PuzzleReward(height, ..)
+            | Self::StakingReward(height, ..)
+            | Self::Bonded(height, ..)
+            | Self::Unbonded(height, ..)
+            | Self::UnbondClaimed(height, ..) => *height,
+        }
+    }
+}
+
+/// The `credits.aleo` accounting of an address, over a range of blocks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountingReport<N: Network> {
+    /// The address.
+    address: Address<N>,
+    /// The range of block heights.
+    heights: Range<u32>,
+    /// The events, in the order they occurred.
+    events: Vec<AccountingEvent<N>>,
+}
+
+impl<N: Network> AccountingReport<N> {
+    /// Initializes a new accounting report.
+    pub fn new(address: Address<N>, heights: Range<u32>, events: Vec<AccountingEvent<N>>) -> Self {
+        Self { address, heights, events }
+    }
+
+    /// Returns the address.
+    pub const fn address(&self) -> &Address<N> {
+        &self.address
+    }
+
+    /// Returns the range of block heights.
+    pub fn heights(&self) -> Range<u32> {
+        self.heights.clone()
+    }
+
+    /// Returns the events, in the order they occurred.
+    pub fn events(&self) -> &[AccountingEvent<N>] {
+        &self.events
+    }
+
+    /// Returns the total amount of public fees paid.
+    pub fn total_fees_paid(&self) -> u64 {
+        self.sum(|event| match event {
+            AccountingEvent::FeePaid(_, _, amount) => Some(*amount),
+            _ => None,
+        })
+    }
+
+    /// Returns the total amount of puzzle rewards received.
+    pub fn total_puzzle_rewards(&self) -> u64 {
+        self.sum(|event| match event {
+            AccountingEvent::PuzzleReward(_, amount) => Some(*amount),
+            _ => None,
+        })
+    }
+
+    /// Returns the total amount of staking rewards received.
+    pub fn total_staking_rewards(&self) -> u64 {
+        self.sum(|event| match event {
+            AccountingEvent::StakingReward(_, amount) => Some(*amount),
+            _ => None,
+        })
+    }
+
+    /// Returns the sum of the amounts selected from the events.
+    fn sum(&self, select: impl Fn(&AccountingEvent<N>) -> Option<u64>) -> u64 {
+        self.events.iter().filter_map(select).fold(0u64, |total, amount| total.saturating_add(amount))
+    }
+}
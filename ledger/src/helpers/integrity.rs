@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::network::prelude::*;
+use ledger_store::IntegrityIssue;
+
+/// The result of a ledger integrity check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// The latest block height, at the time of the check.
+    latest_height: u32,
+    /// The inconsistencies that were found.
+    issues: Vec<IntegrityIssue>,
+    /// The block height that the ledger was rolled back to, if it was repaired.
+    repaired_height: Option<u32>,
+}
+
+impl IntegrityReport {
+    /// Initializes a new integrity report.
+    pub fn new(latest_height: u32, issues: Vec<IntegrityIssue>) -> Self {
+        Self { latest_height, issues, repaired_height: None }
+    }
+
+    /// Returns the latest block height, at the time of the check.
+    pub const fn latest_height(&self) -> u32 {
+        self.latest_height
+    }
+
+    /// Returns the inconsistencies that were found.
+    pub fn issues(&self) -> &[IntegrityIssue] {
+        &self.issues
+    }
+
+    /// Returns `true` if no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the block height that the ledger was rolled back to, if it was repaired.
+    pub const fn repaired_height(&self) -> Option<u32> {
+        self.repaired_height
+    }
+
+    /// Returns the height of the last block that precedes every inconsistency attributed to a block.
+    /// Returns `None` if an inconsistency is attributed to the genesis block.
+    ///
+    /// Note: Inconsistencies that are not attributed to a block are not taken into account,
+    /// as they cannot be repaired by rolling back the ledger.
+    pub fn last_consistent_height(&self) -> Option<u32> {
+        match self.issues.iter().filter_map(|issue| issue.height).min() {
+            Some(height) => height.checked_sub(1),
+            None => Some(self.latest_height),
+        }
+    }
+
+    /// Records that the ledger was rolled back to the given block height.
+    pub(crate) fn set_repaired_height(&mut self, height: u32) {
+        self.repaired_height = Some(height);
+    }
+}
+
+impl Display for IntegrityReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.issues.len() {
+            0 => write!(f, "The ledger is consistent up to block {}", self.latest_height)?,
+            1 => write!(f, "Found 1 inconsistency in the ledger up to block {}", self.latest_height)?,
+            n => write!(f, "Found {n} inconsistencies in the ledger up to block {}", self.latest_height)?,
+        }
+        for issue in &self.issues {
+            write!(f, "\n  - {issue}")?;
+        }
+        if let Some(height) = self.repaired_height {
+            write!(f, "\nRolled back the ledger to block {height}")?;
+        }
+        Ok(())
+    }
+}
@@ -12,8 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod accounting;
+pub use accounting::*;
+
 mod bft;
 pub use bft::*;
 
+mod integrity;
+pub use integrity::*;
+
+mod pool;
+pub use pool::*;
+
 mod supply;
 pub use supply::*;
+
+mod scanner;
+pub use scanner::*;
+
+mod snapshot;
+pub use snapshot::*;
@@ -0,0 +1,255 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Ledger;
+use console::{network::prelude::*, types::Field};
+use ledger_block::{Transaction, Transactions};
+use ledger_store::ConsensusStorage;
+
+use anyhow::Result;
+use core::cmp::Ordering;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// The default maximum number of transactions in the pool.
+const DEFAULT_CAPACITY: usize = 10_000;
+/// The default number of blocks after which the global state root of a transaction is considered too old.
+const DEFAULT_MAX_ANCHOR_AGE: u32 = 100;
+
+/// A transaction in the pool, along with the attributes used to prioritize it.
+struct PooledTransaction<N: Network> {
+    /// The transaction.
+    transaction: Transaction<N>,
+    /// The priority fee of the transaction, in microcredits.
+    priority_fee: u64,
+    /// The size of the transaction, in bytes.
+    size_in_bytes: u64,
+    /// The height of the block of the global state root of the transaction.
+    anchor_height: u32,
+    /// The order in which the transaction was added to the pool.
+    sequence: u64,
+}
+
+impl<N: Network> PooledTransaction<N> {
+    /// Orders the transactions by descending priority fee per byte, then by the order they were added to the pool.
+    fn cmp_priority(&self, other: &Self) -> Ordering {
+        let fee_per_byte = (self.priority_fee as u128).saturating_mul(other.size_in_bytes as u128);
+        let other_fee_per_byte = (other.priority_fee as u128).saturating_mul(self.size_in_bytes as u128);
+        other_fee_per_byte.cmp(&fee_per_byte).then(self.sequence.cmp(&other.sequence))
+    }
+}
+
+/// Returns the global state root that the given transaction was created against.
+fn to_global_state_root<N: Network>(transaction: &Transaction<N>) -> N::StateRoot {
+    match transaction {
+        Transaction::Deploy(_, _, _, fee) | Transaction::Fee(_, fee) => fee.global_state_root(),
+        Transaction::Execute(_, execution, _) => execution.global_state_root(),
+    }
+}
+
+/// A pool of unconfirmed transactions, to be included in the next blocks.
+///
+/// Transactions are verified against the ledger when they are added, and the pool never holds
+/// two transactions with the same ID or that spend the same serial number. Transactions are proposed
+/// in descending order of priority fee per byte. Once a block is added to the ledger, `TransactionPool::refresh`
+/// evicts the transactions that were confirmed, that conflict with the ledger, or whose global state root is too old.
+pub struct TransactionPool<N: Network> {
+    /// The transactions, by transaction ID.
+    transactions: IndexMap<N::TransactionID, PooledTransaction<N>>,
+    /// The IDs of the transactions, by the serial numbers they spend.
+    serial_numbers: HashMap<Field<N>, N::TransactionID>,
+    /// The maximum number of transactions in the pool.
+    capacity: usize,
+    /// The number of blocks after which the global state root of a transaction is considered too old.
+    max_anchor_age: u32,
+    /// The order of the next transaction to be added to the pool.
+    next_sequence: u64,
+}
+
+impl<N: Network> Default for TransactionPool<N> {
+    /// Initializes a new, empty transaction pool.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Network> TransactionPool<N> {
+    /// Initializes a new, empty transaction pool.
+    pub fn new() -> Self {
+        Self {
+            transactions: Default::default(),
+            serial_numbers: Default::default(),
+            capacity: DEFAULT_CAPACITY,
+            max_anchor_age: DEFAULT_MAX_ANCHOR_AGE,
+            next_sequence: 0,
+        }
+    }
+
+    /// Sets the maximum number of transactions in the pool.
+    pub fn with_capacity(mut self, capacity: usize) -> Result<Self> {
+        ensure!(capacity > 0, "The capacity of the transaction pool must be greater than zero");
+        self.capacity = capacity;
+        Ok(self)
+    }
+
+    /// Sets the number of blocks after which the global state root of a transaction is considered too old.
+    pub fn with_max_anchor_age(mut self, max_anchor_age: u32) -> Self {
+        self.max_anchor_age = max_anchor_age;
+        self
+    }
+
+    /// Returns the number of transactions in the pool.
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns `true` if the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Returns `true` if the pool contains the given transaction ID.
+    pub fn contains_transaction(&self, transaction_id: &N::TransactionID) -> bool {
+        self.transactions.contains_key(transaction_id)
+    }
+
+    /// Returns the transaction for the given transaction ID, if it is in the pool.
+    pub fn get_transaction(&self, transaction_id: &N::TransactionID) -> Option<&Transaction<N>> {
+        self.transactions.get(transaction_id).map(|pooled| &pooled.transaction)
+    }
+
+    /// Verifies the given transaction against the ledger, and adds it to the pool.
+    ///
+    /// If the pool is full, the transaction with the lowest priority fee per byte is evicted,
+    /// provided that the given transaction has a higher priority fee per byte.
+    pub fn add_transaction<C: ConsensusStorage<N>, R: CryptoRng + Rng>(
+        &mut self,
+        ledger: &Ledger<N, C>,
+        transaction: Transaction<N>,
+        rng: &mut R,
+    ) -> Result<()> {
+        let transaction_id = transaction.id();
+
+        // Ensure the transaction is not in the pool or in the ledger.
+        ensure!(!self.contains_transaction(&transaction_id), "Transaction '{transaction_id}' is already in the pool");
+        ensure!(
+            !ledger.contains_transaction_id(&transaction_id)?,
+            "Transaction '{transaction_id}' already exists in the ledger"
+        );
+        // Ensure the transaction does not spend a serial number that is spent by a transaction in the pool.
+        if let Some(serial_number) = transaction.serial_numbers().find(|s| self.serial_numbers.contains_key(s)) {
+            bail!("Transaction '{transaction_id}' spends serial number '{serial_number}', which is already in the pool")
+        }
+        // Ensure the global state root of the transaction is recent enough.
+        let global_state_root = to_global_state_root(&transaction);
+        let Some(anchor_height) = ledger.find_block_height_from_state_root(global_state_root)? else {
+            bail!("Transaction '{transaction_id}' has an unknown global state root '{global_state_root}'")
+        };
+        ensure!(
+            !self.is_anchor_expired(anchor_height, ledger.latest_height()),
+            "Transaction '{transaction_id}' has a global state root from block {anchor_height}, which is too old"
+        );
+
+        // Construct the pooled transaction.
+        let pooled = PooledTransaction {
+            priority_fee: *transaction.priority_fee_amount()?,
+            size_in_bytes: transaction.to_bytes_le()?.len() as u64,
+            anchor_height,
+            sequence: self.next_sequence,
+            transaction,
+        };
+
+        // If the pool is full, determine the transaction to evict.
+        let evicted_id = match self.transactions.len() >= self.capacity {
+            true => match self.transactions.values().max_by(|a, b| a.cmp_priority(b)) {
+                Some(lowest) if pooled.cmp_priority(lowest) == Ordering::Less => Some(lowest.transaction.id()),
+                _ => bail!("The transaction pool is full, and transaction '{transaction_id}' has a lower priority"),
+            },
+            false => None,
+        };
+
+        // Verify the transaction against the ledger.
+        ledger.check_transaction_basic(&pooled.transaction, None, rng)?;
+
+        // Evict the transaction, if the pool is full.
+        if let Some(evicted_id) = evicted_id {
+            self.remove_transaction(&evicted_id);
+        }
+        // Add the transaction to the pool.
+        for serial_number in pooled.transaction.serial_numbers() {
+            self.serial_numbers.insert(*serial_number, transaction_id);
+        }
+        self.transactions.insert(transaction_id, pooled);
+        self.next_sequence += 1;
+        Ok(())
+    }
+
+    /// Removes the transaction for the given transaction ID from the pool, and returns it.
+    pub fn remove_transaction(&mut self, transaction_id: &N::TransactionID) -> Option<Transaction<N>> {
+        let pooled = self.transactions.shift_remove(transaction_id)?;
+        for serial_number in pooled.transaction.serial_numbers() {
+            self.serial_numbers.remove(serial_number);
+        }
+        Some(pooled.transaction)
+    }
+
+    /// Returns up to `limit` transactions for the next block, in descending order of priority fee per byte.
+    pub fn select_transactions(&self, limit: usize) -> Vec<Transaction<N>> {
+        let mut transactions = self.transactions.values().collect::<Vec<_>>();
+        transactions.sort_unstable_by(|a, b| a.cmp_priority(b));
+        transactions
+            .into_iter()
+            .take(limit.min(Transactions::<N>::MAX_TRANSACTIONS))
+            .map(|pooled| pooled.transaction.clone())
+            .collect()
+    }
+
+    /// Evicts the transactions that are in the ledger, that spend a serial number that is spent in the ledger,
+    /// or whose global state root is too old. Returns the IDs of the evicted transactions.
+    ///
+    /// This method should be called after each block is added to the ledger.
+    pub fn refresh<C: ConsensusStorage<N>>(&mut self, ledger: &Ledger<N, C>) -> Result<Vec<N::TransactionID>> {
+        let latest_height = ledger.latest_height();
+
+        // Determine the transactions to evict.
+        let mut evicted_ids = Vec::new();
+        for (transaction_id, pooled) in &self.transactions {
+            // Evict the transaction if its global state root is too old, or if it is in the ledger.
+            if self.is_anchor_expired(pooled.anchor_height, latest_height)
+                || ledger.contains_transaction_id(transaction_id)?
+            {
+                evicted_ids.push(*transaction_id);
+                continue;
+            }
+            // Evict the transaction if it spends a serial number that is spent in the ledger.
+            for serial_number in pooled.transaction.serial_numbers() {
+                if ledger.contains_serial_number(serial_number)? {
+                    evicted_ids.push(*transaction_id);
+                    break;
+                }
+            }
+        }
+
+        // Evict the transactions.
+        for transaction_id in &evicted_ids {
+            self.remove_transaction(transaction_id);
+        }
+        Ok(evicted_ids)
+    }
+
+    /// Returns `true` if a global state root from the given block is too old, as of the given latest block.
+    fn is_anchor_expired(&self, anchor_height: u32, latest_height: u32) -> bool {
+        latest_height.saturating_sub(anchor_height) > self.max_anchor_age
+    }
+}
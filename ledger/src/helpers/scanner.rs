@@ -0,0 +1,333 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Ledger;
+use console::{
+    account::{GraphKey, ViewKey},
+    network::prelude::*,
+    program::{Identifier, Plaintext, ProgramID, Record, ValueType},
+    types::Field,
+};
+use ledger_block::Output;
+use ledger_store::ConsensusStorage;
+
+use anyhow::Result;
+use std::{collections::VecDeque, path::Path};
+
+#[cfg(not(feature = "serial"))]
+use rayon::prelude::*;
+
+/// The default number of blocks that are scanned in one batch.
+const DEFAULT_BATCH_SIZE: u32 = 100;
+
+/// A record that was found by the record scanner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScannedRecord<N: Network> {
+    /// The height of the block containing the record.
+    height: u32,
+    /// The ID of the transition that created the record.
+    transition_id: N::TransitionID,
+    /// The commitment of the record.
+    commitment: Field<N>,
+    /// The decrypted record.
+    record: Record<N, Plaintext<N>>,
+    /// Whether the record is spent, as of the time it was scanned.
+    is_spent: bool,
+}
+
+impl<N: Network> ScannedRecord<N> {
+    /// Returns the height of the block containing the record.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the ID of the transition that created the record.
+    pub const fn transition_id(&self) -> &N::TransitionID {
+        &self.transition_id
+    }
+
+    /// Returns the commitment of the record.
+    pub const fn commitment(&self) -> &Field<N> {
+        &self.commitment
+    }
+
+    /// Returns the decrypted record.
+    pub const fn record(&self) -> &Record<N, Plaintext<N>> {
+        &self.record
+    }
+
+    /// Returns `true` if the record was spent, as of the time it was scanned.
+    pub const fn is_spent(&self) -> bool {
+        self.is_spent
+    }
+}
+
+/// The position of a record scanner in the ledger, which can be persisted to resume a scan.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ScanCursor<N: Network> {
+    /// The height of the next block to scan.
+    next_height: u32,
+    /// The hash of the last scanned block, if any.
+    last_block_hash: Option<N::BlockHash>,
+}
+
+impl<N: Network> ScanCursor<N> {
+    /// Initializes a new cursor that starts scanning at the given block height.
+    pub const fn new(start_height: u32) -> Self {
+        Self { next_height: start_height, last_block_hash: None }
+    }
+
+    /// Loads the cursor from a file at the given path.
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::from_bytes_le(&std::fs::read(path)?)
+    }
+
+    /// Saves the cursor to a file at the given path.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::write(path, self.to_bytes_le()?)?)
+    }
+
+    /// Returns the height of the next block to scan.
+    pub const fn next_height(&self) -> u32 {
+        self.next_height
+    }
+
+    /// Returns the hash of the last scanned block, if any.
+    pub const fn last_block_hash(&self) -> Option<N::BlockHash> {
+        self.last_block_hash
+    }
+}
+
+impl<N: Network> FromBytes for ScanCursor<N> {
+    /// Reads the cursor from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid scan cursor version"));
+        }
+
+        // Read the next height.
+        let next_height = u32::read_le(&mut reader)?;
+        // Read the last block hash.
+        let last_block_hash = match bool::read_le(&mut reader)? {
+            true => Some(N::BlockHash::read_le(&mut reader)?),
+            false => None,
+        };
+
+        Ok(Self { next_height, last_block_hash })
+    }
+}
+
+impl<N: Network> ToBytes for ScanCursor<N> {
+    /// Writes the cursor to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the next height.
+        self.next_height.write_le(&mut writer)?;
+        // Write the last block hash.
+        match &self.last_block_hash {
+            Some(block_hash) => {
+                true.write_le(&mut writer)?;
+                block_hash.write_le(&mut writer)
+            }
+            None => false.write_le(&mut writer),
+        }
+    }
+}
+
+/// An incremental scanner for the records that belong to a view key.
+///
+/// The scanner walks the ledger in batches of blocks, starting from its cursor,
+/// and decrypts the records owned by the view key. The cursor may be persisted
+/// and passed to `RecordScanner::resume` to continue the scan at a later time.
+pub struct RecordScanner<N: Network> {
+    /// The view key of the account.
+    view_key: ViewKey<N>,
+    /// The x-coordinate of the address corresponding to the view key.
+    address_x_coordinate: Field<N>,
+    /// The `sk_tag` of the account, used to determine whether a record is spent.
+    sk_tag: Field<N>,
+    /// The program ID to filter by, if any.
+    program_id: Option<ProgramID<N>>,
+    /// The record name to filter by, if any.
+    record_name: Option<Identifier<N>>,
+    /// The number of blocks that are scanned in one batch.
+    batch_size: u32,
+    /// The position of the scanner in the ledger.
+    cursor: ScanCursor<N>,
+}
+
+impl<N: Network> RecordScanner<N> {
+    /// Initializes a new record scanner for the given view key, starting at the given block height.
+    pub fn new(view_key: ViewKey<N>, start_height: u32) -> Result<Self> {
+        Self::resume(view_key, ScanCursor::new(start_height))
+    }
+
+    /// Initializes a record scanner for the given view key, resuming from the given cursor.
+    pub fn resume(view_key: ViewKey<N>, cursor: ScanCursor<N>) -> Result<Self> {
+        // Derive the x-coordinate of the address corresponding to the given view key.
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+        // Derive the `sk_tag` from the graph key.
+        let sk_tag = match GraphKey::try_from(view_key) {
+            Ok(graph_key) => graph_key.sk_tag(),
+            Err(e) => bail!("Failed to derive the graph key from the view key: {e}"),
+        };
+        Ok(Self {
+            view_key,
+            address_x_coordinate,
+            sk_tag,
+            program_id: None,
+            record_name: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            cursor,
+        })
+    }
+
+    /// Only returns the records that are created by the given program.
+    pub fn with_program_id(mut self, program_id: ProgramID<N>) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    /// Only returns the records with the given record name.
+    pub fn with_record_name(mut self, record_name: Identifier<N>) -> Self {
+        self.record_name = Some(record_name);
+        self
+    }
+
+    /// Sets the number of blocks that are scanned in one batch.
+    pub fn with_batch_size(mut self, batch_size: u32) -> Result<Self> {
+        ensure!(batch_size > 0, "The batch size must be greater than zero");
+        self.batch_size = batch_size;
+        Ok(self)
+    }
+
+    /// Returns the position of the scanner in the ledger.
+    pub const fn cursor(&self) -> ScanCursor<N> {
+        self.cursor
+    }
+
+    /// Scans the next batch of blocks, and returns the records found in them, in block order.
+    /// Returns an empty list if the scanner has reached the latest block.
+    pub fn scan_next<C: ConsensusStorage<N>>(&mut self, ledger: &Ledger<N, C>) -> Result<Vec<ScannedRecord<N>>> {
+        // Ensure the ledger has not diverged since the last scanned block.
+        if let Some(last_block_hash) = self.cursor.last_block_hash {
+            let height = self.cursor.next_height.saturating_sub(1);
+            if ledger.get_hash(height)? != last_block_hash {
+                bail!("The ledger has diverged from the scan cursor at block {height}")
+            }
+        }
+
+        // Determine the range of blocks to scan.
+        let start_height = self.cursor.next_height;
+        let end_height = start_height.saturating_add(self.batch_size).min(ledger.latest_height().saturating_add(1));
+        if start_height >= end_height {
+            return Ok(vec![]);
+        }
+
+        // Scan the blocks in parallel.
+        let records = cfg_into_iter!(start_height..end_height)
+            .map(|height| self.scan_block(ledger, height))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Advance the cursor.
+        self.cursor = ScanCursor { next_height: end_height, last_block_hash: Some(ledger.get_hash(end_height - 1)?) };
+
+        Ok(records.into_iter().flatten().collect())
+    }
+
+    /// Returns an iterator that scans up to the latest block, and yields the records found, in block order.
+    /// The cursor is advanced as each batch of blocks is scanned.
+    pub fn scan<'a, C: ConsensusStorage<N>>(
+        &'a mut self,
+        ledger: &'a Ledger<N, C>,
+    ) -> impl 'a + Iterator<Item = Result<ScannedRecord<N>>> {
+        let mut buffer = VecDeque::new();
+        let mut is_done = false;
+        std::iter::from_fn(move || {
+            while buffer.is_empty() && !is_done {
+                match self.scan_next(ledger) {
+                    Ok(records) if records.is_empty() && self.cursor.next_height > ledger.latest_height() => {
+                        is_done = true
+                    }
+                    Ok(records) => buffer.extend(records),
+                    Err(e) => {
+                        is_done = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+            buffer.pop_front().map(Ok)
+        })
+    }
+
+    /// Returns the records in the block at the given height that belong to the view key.
+    fn scan_block<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>, height: u32) -> Result<Vec<ScannedRecord<N>>> {
+        let mut records = Vec::new();
+        for transition in ledger.get_transactions(height)?.transitions() {
+            // Skip the transition if it is not from the requested program.
+            if self.program_id.map_or(false, |program_id| program_id != *transition.program_id()) {
+                continue;
+            }
+            // Retrieve the output types of the function, if filtering by record name.
+            let output_types = match self.record_name {
+                Some(_) => Some(
+                    ledger
+                        .vm
+                        .process()
+                        .read()
+                        .get_program(*transition.program_id())?
+                        .get_function_ref(transition.function_name())?
+                        .output_types(),
+                ),
+                None => None,
+            };
+
+            for (index, output) in transition.outputs().iter().enumerate() {
+                // Retrieve the commitment and record.
+                let (commitment, record) = match output {
+                    Output::Record(commitment, _, Some(record)) => (commitment, record),
+                    _ => continue,
+                };
+                // Skip the record if it does not have the requested record name.
+                if let (Some(record_name), Some(output_types)) = (&self.record_name, &output_types) {
+                    match output_types.get(index) {
+                        Some(ValueType::Record(name)) if name == record_name => (),
+                        _ => continue,
+                    }
+                }
+                // Skip the record if it is not owned by the view key.
+                if !record.is_owner_with_address_x_coordinate(&self.view_key, &self.address_x_coordinate) {
+                    continue;
+                }
+
+                // Determine whether the record is spent.
+                let tag = Record::<N, Plaintext<N>>::tag(self.sk_tag, *commitment)?;
+                let is_spent = ledger.contains_tag(&tag)?;
+
+                records.push(ScannedRecord {
+                    height,
+                    transition_id: *transition.id(),
+                    commitment: *commitment,
+                    record: record.decrypt(&self.view_key)?,
+                    is_spent,
+                });
+            }
+        }
+        Ok(records)
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    network::prelude::*,
+    program::{Identifier, Plaintext, ProgramID, Value},
+    types::Field,
+};
+use ledger_block::{Block, Transaction};
+use ledger_committee::Committee;
+use synthesizer::program::FinalizeOperation;
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// The number of most recent blocks that are included in full in a ledger snapshot.
+pub const NUM_SNAPSHOT_BLOCKS: u32 = 100;
+
+/// The entries of a mapping, as `(program ID, mapping name, [(key, value)])`.
+pub type SnapshotMapping<N> = (ProgramID<N>, Identifier<N>, Vec<(Plaintext<N>, Value<N>)>);
+
+/// A snapshot of the ledger state at a given height, which allows a node to start
+/// from that height without replaying the blocks that precede it.
+///
+/// The snapshot contains the block hash (i.e. the leaf of the block tree), state root and committee
+/// for every height, the finalize state at the snapshot height, the latest edition of every deployment,
+/// and the genesis block and most recent blocks in full, along with the finalize operations of their ratifications.
+#[derive(Clone, PartialEq, Eq)]
+pub struct LedgerSnapshot<N: Network> {
+    /// The block hashes, for every block height up to the snapshot height.
+    block_hashes: Vec<N::BlockHash>,
+    /// The state roots, for every block height up to the snapshot height.
+    state_roots: Vec<N::StateRoot>,
+    /// The committees, for every block height up to the snapshot height.
+    committees: Vec<Committee<N>>,
+    /// The genesis block and the most recent blocks, with the finalize operations of their ratifications.
+    blocks: Vec<(Block<N>, Vec<FinalizeOperation<N>>)>,
+    /// The deployment transactions that are not included in the blocks of the snapshot.
+    deployments: Vec<Transaction<N>>,
+    /// The entries of every mapping, at the snapshot height.
+    mappings: Vec<SnapshotMapping<N>>,
+    /// The checksum of the finalize state, at the snapshot height.
+    finalize_checksum: Field<N>,
+}
+
+impl<N: Network> LedgerSnapshot<N> {
+    /// Initializes a new ledger snapshot.
+    pub fn new(
+        block_hashes: Vec<N::BlockHash>,
+        state_roots: Vec<N::StateRoot>,
+        committees: Vec<Committee<N>>,
+        blocks: Vec<(Block<N>, Vec<FinalizeOperation<N>>)>,
+        deployments: Vec<Transaction<N>>,
+        mappings: Vec<SnapshotMapping<N>>,
+        finalize_checksum: Field<N>,
+    ) -> Result<Self> {
+        // Ensure there is a block hash, state root and committee for every block height.
+        ensure!(!block_hashes.is_empty(), "The snapshot must contain at least the genesis block hash");
+        ensure!(block_hashes.len() == state_roots.len(), "The snapshot must contain a state root for every block");
+        ensure!(block_hashes.len() == committees.len(), "The snapshot must contain a committee for every block");
+        // Ensure the snapshot height fits in a block height.
+        let height = u32::try_from(block_hashes.len() - 1)?;
+        // Ensure the first block is the genesis block.
+        match blocks.first() {
+            Some((block, _)) => {
+                ensure!(block.height() == 0, "The first block in the snapshot must be the genesis block")
+            }
+            None => bail!("The snapshot must contain the genesis block"),
+        }
+        // Ensure the blocks are sequential, and end at the snapshot height.
+        for window in blocks[1..].windows(2) {
+            ensure!(window[1].0.height() == window[0].0.height() + 1, "The blocks in the snapshot must be sequential");
+        }
+        ensure!(blocks.last().map(|(block, _)| block.height()) == Some(height), "The snapshot must end at its height");
+        // Ensure every deployment is a deployment transaction.
+        ensure!(
+            deployments.iter().all(Transaction::is_deploy),
+            "The snapshot must only contain deployment transactions"
+        );
+
+        Ok(Self { block_hashes, state_roots, committees, blocks, deployments, mappings, finalize_checksum })
+    }
+
+    /// Loads the snapshot from a file at the given path, and ensures its checksum is correct.
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        // Split the bytes into the snapshot and its checksum.
+        ensure!(bytes.len() >= 32, "The snapshot file is too short");
+        let (snapshot, checksum) = bytes.split_at(bytes.len() - 32);
+        // Ensure the checksum is correct.
+        ensure!(Sha256::digest(snapshot).as_slice() == checksum, "The snapshot file has an incorrect checksum");
+        // Deserialize the snapshot.
+        Self::from_bytes_le(snapshot)
+    }
+
+    /// Saves the snapshot to a file at the given path, followed by its checksum.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut bytes = self.to_bytes_le()?;
+        // Append the checksum.
+        let checksum = Sha256::digest(&bytes);
+        bytes.extend_from_slice(&checksum);
+        Ok(std::fs::write(path, bytes)?)
+    }
+
+    /// Returns the height of the snapshot.
+    pub fn height(&self) -> u32 {
+        // Note: This conversion is safe, as the number of block hashes is checked upon initialization.
+        u32::try_from(self.block_hashes.len() - 1).unwrap_or(u32::MAX)
+    }
+
+    /// Returns the block hashes, for every block height up to the snapshot height.
+    pub fn block_hashes(&self) -> &[N::BlockHash] {
+        &self.block_hashes
+    }
+
+    /// Returns the state roots, for every block height up to the snapshot height.
+    pub fn state_roots(&self) -> &[N::StateRoot] {
+        &self.state_roots
+    }
+
+    /// Returns the committees, for every block height up to the snapshot height.
+    pub fn committees(&self) -> &[Committee<N>] {
+        &self.committees
+    }
+
+    /// Returns the genesis block and the most recent blocks, with the finalize operations of their ratifications.
+    pub fn blocks(&self) -> &[(Block<N>, Vec<FinalizeOperation<N>>)] {
+        &self.blocks
+    }
+
+    /// Returns the deployment transactions that are not included in the blocks of the snapshot.
+    pub fn deployments(&self) -> &[Transaction<N>] {
+        &self.deployments
+    }
+
+    /// Returns the entries of every mapping, at the snapshot height.
+    pub fn mappings(&self) -> &[SnapshotMapping<N>] {
+        &self.mappings
+    }
+
+    /// Returns the checksum of the finalize state, at the snapshot height.
+    pub const fn finalize_checksum(&self) -> Field<N> {
+        self.finalize_checksum
+    }
+}
+
+impl<N: Network> FromBytes for LedgerSnapshot<N> {
+    /// Reads the snapshot from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid ledger snapshot version"));
+        }
+
+        // Read the block hashes.
+        let num_block_hashes = u32::read_le(&mut reader)?;
+        let block_hashes =
+            (0..num_block_hashes).map(|_| N::BlockHash::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the state roots.
+        let num_state_roots = u32::read_le(&mut reader)?;
+        let state_roots =
+            (0..num_state_roots).map(|_| N::StateRoot::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the committees.
+        let num_committees = u32::read_le(&mut reader)?;
+        let committees = (0..num_committees).map(|_| Committee::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the blocks.
+        let num_blocks = u32::read_le(&mut reader)?;
+        let blocks = (0..num_blocks)
+            .map(|_| {
+                let block = Block::read_le(&mut reader)?;
+                let num_operations = u32::read_le(&mut reader)?;
+                let operations = (0..num_operations)
+                    .map(|_| FinalizeOperation::read_le(&mut reader))
+                    .collect::<IoResult<Vec<_>>>()?;
+                Ok((block, operations))
+            })
+            .collect::<IoResult<Vec<_>>>()?;
+        // Read the deployments.
+        let num_deployments = u32::read_le(&mut reader)?;
+        let deployments =
+            (0..num_deployments).map(|_| Transaction::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the mappings.
+        let num_mappings = u32::read_le(&mut reader)?;
+        let mappings = (0..num_mappings)
+            .map(|_| {
+                let program_id = ProgramID::read_le(&mut reader)?;
+                let mapping_name = Identifier::read_le(&mut reader)?;
+                let num_entries = u64::read_le(&mut reader)?;
+                let entries = (0..num_entries)
+                    .map(|_| Ok((Plaintext::read_le(&mut reader)?, Value::read_le(&mut reader)?)))
+                    .collect::<IoResult<Vec<_>>>()?;
+                Ok((program_id, mapping_name, entries))
+            })
+            .collect::<IoResult<Vec<_>>>()?;
+        // Read the finalize checksum.
+        let finalize_checksum = Field::read_le(&mut reader)?;
+
+        // Return the snapshot.
+        Self::new(block_hashes, state_roots, committees, blocks, deployments, mappings, finalize_checksum)
+            .map_err(error)
+    }
+}
+
+impl<N: Network> ToBytes for LedgerSnapshot<N> {
+    /// Writes the snapshot to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+
+        // Write the block hashes.
+        u32::try_from(self.block_hashes.len()).map_err(error)?.write_le(&mut writer)?;
+        self.block_hashes.iter().try_for_each(|block_hash| block_hash.write_le(&mut writer))?;
+        // Write the state roots.
+        u32::try_from(self.state_roots.len()).map_err(error)?.write_le(&mut writer)?;
+        self.state_roots.iter().try_for_each(|state_root| state_root.write_le(&mut writer))?;
+        // Write the committees.
+        u32::try_from(self.committees.len()).map_err(error)?.write_le(&mut writer)?;
+        self.committees.iter().try_for_each(|committee| committee.write_le(&mut writer))?;
+        // Write the blocks.
+        u32::try_from(self.blocks.len()).map_err(error)?.write_le(&mut writer)?;
+        for (block, operations) in &self.blocks {
+            block.write_le(&mut writer)?;
+            u32::try_from(operations.len()).map_err(error)?.write_le(&mut writer)?;
+            operations.iter().try_for_each(|operation| operation.write_le(&mut writer))?;
+        }
+        // Write the deployments.
+        u32::try_from(self.deployments.len()).map_err(error)?.write_le(&mut writer)?;
+        self.deployments.iter().try_for_each(|deployment| deployment.write_le(&mut writer))?;
+        // Write the mappings.
+        u32::try_from(self.mappings.len()).map_err(error)?.write_le(&mut writer)?;
+        for (program_id, mapping_name, entries) in &self.mappings {
+            program_id.write_le(&mut writer)?;
+            mapping_name.write_le(&mut writer)?;
+            (entries.len() as u64).write_le(&mut writer)?;
+            for (key, value) in entries {
+                key.write_le(&mut writer)?;
+                value.write_le(&mut writer)?;
+            }
+        }
+        // Write the finalize checksum.
+        self.finalize_checksum.write_le(&mut writer)
+    }
+}
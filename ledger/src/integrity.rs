@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Checks that the maps of the block, transaction, transition and finalize stores are mutually consistent,
+    /// and that the block tree recomputed from the block hashes matches the latest state root.
+    ///
+    /// If `repair` is set, and inconsistencies are attributed to a block, the ledger is rolled back
+    /// to the last block that precedes every such inconsistency, via `BlockStore::remove_last_n`.
+    /// The finalize state is rolled back using the finalize history, so the repair is refused
+    /// if the finalize history does not cover the removed blocks.
+    ///
+    /// Note: Pruned blocks, and blocks that deploy a program, cannot be removed.
+    /// Inconsistencies that are not attributed to a block are reported, but are not repaired.
+    pub fn check_integrity(&self, repair: bool) -> Result<IntegrityReport> {
+        let timer = timer!("Ledger::check_integrity");
+
+        // Retrieve the latest height in storage.
+        let latest_height = match self.vm.block_store().heights().max() {
+            Some(height) => *height,
+            None => bail!("Failed to check the ledger integrity: no blocks in storage"),
+        };
+
+        // Check the block store, which includes the transaction and transition stores.
+        let mut issues = self.vm.block_store().check_integrity()?;
+        lap!(timer, "Check the block store");
+        // Check the finalize store.
+        issues.extend(self.vm.finalize_store().check_integrity()?);
+        lap!(timer, "Check the finalize store");
+
+        let mut report = IntegrityReport::new(latest_height, issues);
+        if !report.is_consistent() {
+            warn!("{report}");
+        }
+
+        // Roll back the ledger to the last consistent height, if requested.
+        if repair {
+            match report.last_consistent_height() {
+                Some(height) if height < latest_height => {
+                    self.rollback_to(height, latest_height)?;
+                    report.set_repaired_height(height);
+                    info!("Rolled back the ledger from block {latest_height} to block {height}");
+                }
+                Some(_) => (),
+                None => bail!("Failed to repair the ledger: the genesis block is inconsistent"),
+            }
+        }
+
+        finish!(timer);
+        Ok(report)
+    }
+
+    /// Removes the blocks after the given height, rolls back the finalize state,
+    /// and updates the current block, committee, and epoch hash.
+    ///
+    /// The finalize state and the blocks are removed in a single atomic batch on persistent storage.
+    /// On the other storage, every block to remove is checked before anything is written.
+    pub(crate) fn rollback_to(&self, height: u32, latest_height: u32) -> Result<()> {
+        // Ensure the given latest height is the latest height in storage.
+        let current_height = self.vm.block_store().heights().max().map(|height| *height);
+        ensure!(
+            current_height == Some(latest_height),
+            "Failed to repair the ledger: block {latest_height} is not the latest block"
+        );
+        // Ensure each of the blocks to remove is in storage, and has not been pruned.
+        for block_height in height + 1..=latest_height {
+            let Some(block_hash) = self.vm.block_store().get_block_hash(block_height)? else {
+                bail!("Failed to repair the ledger: block {block_height} is missing its block hash")
+            };
+            if self.vm.block_store().is_pruned(&block_hash)? {
+                bail!("Failed to repair the ledger: block {block_height} has been pruned and cannot be removed")
+            }
+        }
+        // Ensure none of the blocks to remove has deployed a program, as the program cannot be removed from the VM.
+        for transaction_id in self.vm.transaction_store().deployment_store().deployment_transaction_ids() {
+            if let Some(block_hash) = self.vm.block_store().find_block_hash(&transaction_id)? {
+                if let Some(block_height) = self.vm.block_store().get_block_height(&block_hash)? {
+                    if block_height > height {
+                        bail!(
+                            "Failed to repair the ledger: block {block_height} deploys a program and cannot be removed"
+                        )
+                    }
+                }
+            }
+        }
+
+        // Pause the atomic writes, so that the finalize rollback and the block removal belong to a single batch.
+        #[cfg(any(feature = "rocks", feature = "kv"))]
+        self.vm.block_store().pause_atomic_writes()?;
+
+        // Roll back the finalize state, and then remove the blocks.
+        // Note: The finalize rollback fails if the finalize history does not cover the blocks to remove.
+        let outcome = self
+            .vm
+            .finalize_store()
+            .rollback_to(height)
+            .map_err(|error| anyhow!("Failed to repair the ledger: cannot roll back the finalize state - {error}"))
+            .and_then(|()| self.vm.block_store().remove_last_n(latest_height - height));
+
+        if let Err(error) = outcome {
+            if cfg!(any(feature = "rocks", feature = "kv")) {
+                // Clear all pending atomic operations so that unpausing the atomic writes
+                // doesn't execute any of the queued storage operations.
+                self.vm.block_store().abort_atomic();
+                self.vm.finalize_store().abort_atomic();
+                // Disable the atomic batch override.
+                // Note: This call is guaranteed to succeed (without error), because `DISCARD_BATCH == true`.
+                self.vm.block_store().unpause_atomic_writes::<true>()?;
+            }
+            return Err(error);
+        }
+
+        // Unpause the atomic writes, executing the ones queued from the finalize rollback and the block removal.
+        #[cfg(any(feature = "rocks", feature = "kv"))]
+        self.vm.block_store().unpause_atomic_writes::<false>()?;
+
+        // Update the current block.
+        *self.current_block.write() = self.get_block(height)?;
+        // Update the current committee.
+        *self.current_committee.write() = self.get_committee(height)?;
+        // Update the current epoch hash.
+        *self.current_epoch_hash.write() = Some(self.get_epoch_hash(height)?);
+        Ok(())
+    }
+}
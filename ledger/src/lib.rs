@@ -34,13 +34,16 @@ pub use ledger_test_helpers;
 mod helpers;
 pub use helpers::*;
 
+mod accounting;
 mod advance;
 mod check_next_block;
 mod check_transaction_basic;
 mod contains;
 mod find;
 mod get;
+mod integrity;
 mod iterators;
+mod snapshot;
 
 #[cfg(test)]
 mod tests;
@@ -59,7 +62,7 @@ use ledger_query::Query;
 use ledger_store::{ConsensusStorage, ConsensusStore};
 use synthesizer::{
     program::{FinalizeGlobalState, Program},
-    vm::VM,
+    vm::{Event, VM},
 };
 
 use aleo_std::{
@@ -71,7 +74,10 @@ use core::ops::Range;
 use indexmap::IndexMap;
 use parking_lot::RwLock;
 use rand::{prelude::IteratorRandom, rngs::OsRng};
-use std::{borrow::Cow, sync::Arc};
+use std::{
+    borrow::Cow,
+    sync::{mpsc::Receiver, Arc},
+};
 use time::OffsetDateTime;
 
 #[cfg(not(feature = "serial"))]
@@ -131,7 +137,11 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         let block_heights: Vec<u32> =
             (0..=latest_height).choose_multiple(&mut OsRng, (latest_height as usize).min(NUM_BLOCKS));
         cfg_into_iter!(block_heights).try_for_each(|height| {
-            ledger.get_block(height)?;
+            // Note: The contents of pruned blocks may be unavailable (e.g. for the blocks that precede a snapshot),
+            // so only their block hashes are checked.
+            if !ledger.vm.block_store().is_pruned(&ledger.get_hash(height)?)? {
+                ledger.get_block(height)?;
+            }
             Ok::<_, Error>(())
         })?;
         lap!(timer, "Check existence of {NUM_BLOCKS} random blocks");
@@ -152,6 +162,17 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         };
         lap!(timer, "Load consensus store");
 
+        // Initialize the ledger.
+        let ledger = Self::from_store(genesis_block, store)?;
+
+        finish!(timer, "Initialize ledger");
+        Ok(ledger)
+    }
+
+    /// Initializes the ledger from the given consensus store.
+    fn from_store(genesis_block: Block<N>, store: ConsensusStore<N, C>) -> Result<Self> {
+        let timer = timer!("Ledger::from_store");
+
         // Initialize a new VM.
         let vm = VM::from(store)?;
         lap!(timer, "Initialize a new VM");
@@ -204,6 +225,60 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         self.vm.puzzle()
     }
 
+    /// Returns the pruning depth, if pruning is enabled.
+    pub fn pruning_depth(&self) -> Result<Option<u32>> {
+        self.vm.block_store().pruning_depth()
+    }
+
+    /// Sets the pruning depth, which is the number of most recent blocks whose transactions are retained.
+    /// Pruned blocks keep their headers, state roots, serial numbers, tags and commitments,
+    /// while their proofs and transition values are deleted. The pruning depth is persisted in storage.
+    ///
+    /// Note: The state paths for the commitments in pruned blocks can no longer be built.
+    pub fn set_pruning_depth(&self, pruning_depth: Option<u32>) -> Result<()> {
+        self.vm.block_store().set_pruning_depth(pruning_depth)
+    }
+
+    /// Returns the height of the first block in the finalize history, if the finalize history is enabled.
+    pub fn finalize_history_start_height(&self) -> Result<Option<u32>> {
+        self.vm.finalize_store().history_start_height()
+    }
+
+    /// Enables or disables the finalize history, starting from the next block.
+    /// The finalize history allows mapping values to be queried as of any block,
+    /// via `FinalizeStore::get_value_confirmed_at`.
+    pub fn set_finalize_history(&self, enabled: bool) -> Result<()> {
+        self.vm.set_finalize_history(enabled)
+    }
+
+    /// Returns the height of the first block in the transition index, if the transition index is enabled.
+    pub fn transition_index_start_height(&self) -> Result<Option<u32>> {
+        self.vm.block_store().transition_index_start_height()
+    }
+
+    /// Enables or disables the transition index, starting from the next block.
+    /// The transition index allows transitions to be found by function and by public address argument,
+    /// via `Ledger::find_transitions_from_function` and `Ledger::find_transitions_from_address`.
+    pub fn set_transition_index(&self, enabled: bool) -> Result<()> {
+        self.vm.set_transition_index(enabled)
+    }
+
+    /// Returns a receiver for the events of the blocks that are added to the ledger from now on.
+    /// The subscription ends when the receiver is dropped, or when it falls behind by more than `MAX_BUFFERED_EVENTS`.
+    pub fn subscribe(&self) -> Receiver<Event<N>> {
+        self.vm.events().subscribe()
+    }
+
+    /// Registers the given view key, so that an event is published for each record it owns in the added blocks.
+    pub fn register_view_key(&self, view_key: ViewKey<N>) {
+        self.vm.events().register_view_key(view_key)
+    }
+
+    /// Unregisters the given view key. Returns `true` if the view key was registered.
+    pub fn unregister_view_key(&self, view_key: &ViewKey<N>) -> bool {
+        self.vm.events().unregister_view_key(view_key)
+    }
+
     /// Returns the latest committee.
     pub fn latest_committee(&self) -> Result<Committee<N>> {
         match self.current_committee.read().as_ref() {
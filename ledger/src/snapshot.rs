@@ -0,0 +1,363 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use ledger_store::helpers::memory::ConsensusMemory;
+use synthesizer::program::{FinalizeOperation, FinalizeStoreTrait};
+
+use std::{collections::HashSet, path::Path};
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Exports a snapshot of the ledger at the given height to a file at the given path.
+    ///
+    /// The snapshot contains the finalize state, the committee history, the block tree leaves,
+    /// and the latest `NUM_SNAPSHOT_BLOCKS` blocks. As the finalize state is only available
+    /// for the latest block, the given height must be the latest height.
+    pub fn export_snapshot(&self, height: u32, path: &Path) -> Result<()> {
+        let timer = timer!("Ledger::export_snapshot");
+
+        // Ensure the height is the latest height.
+        let latest_height = self.latest_height();
+        if height != latest_height {
+            bail!("Cannot export a snapshot at block {height}, as the finalize state is only at block {latest_height}")
+        }
+
+        // Retrieve the block hash, state root, and committee for every block height.
+        let block_hashes = (0..=height).map(|height| self.get_hash(height)).collect::<Result<Vec<_>>>()?;
+        let state_roots = (0..=height)
+            .map(|height| match self.get_state_root(height)? {
+                Some(state_root) => Ok(state_root),
+                None => bail!("Missing state root for block {height}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let committees = (0..=height)
+            .map(|height| match self.get_committee(height)? {
+                Some(committee) => Ok(committee),
+                None => bail!("Missing committee for block {height}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        lap!(timer, "Retrieve the block hashes, state roots, and committees");
+
+        // Retrieve the genesis block and the latest blocks, with the finalize operations of their ratifications.
+        // Note: The genesis block is always included, as the blocks preceding the latest blocks are pruned upon import.
+        let start_height = height.saturating_sub(NUM_SNAPSHOT_BLOCKS - 1).max(1);
+        let blocks = std::iter::once(0)
+            .chain(start_height..=height)
+            .map(|height| {
+                let block = self.get_block(height)?;
+                match self.vm.block_store().get_ratified_finalize_operations(&block.hash())? {
+                    Some(operations) => Ok((block, operations)),
+                    // Note: The operations of the genesis block are recomputed, as they only depend on the block.
+                    None if height == 0 => {
+                        let operations = Self::recompute_genesis_ratified_finalize_operations(&block)?;
+                        Ok((block, operations))
+                    }
+                    None => bail!(
+                        "The ratified finalize operations for block {height} were not recorded, \
+                         as the block was added before they were stored"
+                    ),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        lap!(timer, "Retrieve the latest blocks");
+
+        // Retrieve the latest edition of each deployment, along with the program IDs.
+        let transaction_ids =
+            blocks.iter().flat_map(|(block, _)| block.transaction_ids().copied()).collect::<HashSet<_>>();
+        let transaction_store = self.vm.transaction_store();
+        let mut program_ids = vec![ProgramID::from_str("credits.aleo")?];
+        let mut deployments = Vec::new();
+        for transaction_id in transaction_store.deployment_transaction_ids() {
+            let transaction_id = *transaction_id;
+            // Retrieve the program ID.
+            let program_id = match transaction_store.deployment_store().get_program_id(&transaction_id)? {
+                Some(program_id) => program_id,
+                None => bail!("Missing program ID for deployment transaction '{transaction_id}'"),
+            };
+            // Skip the deployment if it is not for the latest edition of the program.
+            if transaction_store.deployment_store().find_transaction_id_from_program_id(&program_id)?
+                != Some(transaction_id)
+            {
+                continue;
+            }
+            program_ids.push(program_id);
+            // Skip the deployment if it is already included in the blocks of the snapshot.
+            if transaction_ids.contains(&transaction_id) {
+                continue;
+            }
+            match transaction_store.get_transaction(&transaction_id)? {
+                Some(transaction) => deployments.push(transaction),
+                None => bail!("Missing deployment transaction '{transaction_id}'"),
+            }
+        }
+        lap!(timer, "Retrieve the deployments");
+
+        // Retrieve the entries of every mapping.
+        let finalize_store = self.vm.finalize_store();
+        let mut mappings = Vec::new();
+        for program_id in program_ids {
+            for mapping_name in finalize_store.get_mapping_names_confirmed(&program_id)?.unwrap_or_default() {
+                mappings.push((
+                    program_id,
+                    mapping_name,
+                    finalize_store.get_mapping_confirmed(program_id, mapping_name)?,
+                ));
+            }
+        }
+        // Retrieve the finalize checksum.
+        let finalize_checksum = finalize_store.get_checksum_confirmed()?;
+        lap!(timer, "Retrieve the finalize state");
+
+        // Ensure the ledger did not advance while the snapshot was being prepared.
+        ensure!(self.latest_height() == height, "The ledger advanced while exporting the snapshot at block {height}");
+
+        // Save the snapshot.
+        LedgerSnapshot::new(block_hashes, state_roots, committees, blocks, deployments, mappings, finalize_checksum)?
+            .save(path)?;
+
+        finish!(timer, "Exported the snapshot at block {height}");
+        Ok(())
+    }
+
+    /// Returns the finalize operations produced by the ratifications of the given genesis block,
+    /// by adding it to a new VM in memory.
+    fn recompute_genesis_ratified_finalize_operations(genesis_block: &Block<N>) -> Result<Vec<FinalizeOperation<N>>> {
+        // Add the genesis block to a new VM.
+        let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(StorageMode::Production)?)?;
+        vm.add_next_block(genesis_block)?;
+        // Retrieve the finalize operations of its ratifications.
+        match vm.block_store().get_ratified_finalize_operations(&genesis_block.hash())? {
+            Some(operations) => Ok(operations),
+            None => bail!("Failed to recompute the ratified finalize operations for the genesis block"),
+        }
+    }
+
+    /// Loads the ledger from the snapshot in a file at the given path, into empty storage.
+    ///
+    /// The given trusted anchor is the `(block height, block hash, finalize checksum)` of the latest block
+    /// of the snapshot, as obtained from a trusted source, where the finalize checksum is the checksum of the
+    /// finalize state after that block (i.e. `FinalizeStore::get_checksum_confirmed`). The block hashes are bound
+    /// to the anchor through the block tree, each of the latest blocks must be authorized by its committee lookback,
+    /// and the restored finalize state must match the finalize checksum of the anchor.
+    ///
+    /// The blocks in the snapshot are checked against the block hashes, and their headers are checked against
+    /// their transactions, ratifications and the finalize operations of their ratifications (i.e. the `finalize_root`),
+    /// and against the block tree (i.e. the `previous_state_root`). The blocks that precede the latest blocks
+    /// are restored without their contents.
+    pub fn load_from_snapshot(
+        genesis_block: Block<N>,
+        storage_mode: StorageMode,
+        path: &Path,
+        trusted_anchor: (u32, N::BlockHash, Field<N>),
+    ) -> Result<Self> {
+        let timer = timer!("Ledger::load_from_snapshot");
+
+        // Load the snapshot.
+        let snapshot = LedgerSnapshot::<N>::load(path)?;
+        lap!(timer, "Load the snapshot at block {}", snapshot.height());
+
+        // Ensure the snapshot is for the given genesis block.
+        ensure!(snapshot.blocks()[0].0 == genesis_block, "The snapshot is for a different genesis block");
+        // Ensure the latest block of the snapshot is the trusted anchor.
+        let (anchor_height, anchor_hash, anchor_finalize_checksum) = trusted_anchor;
+        ensure!(
+            snapshot
+                .blocks()
+                .last()
+                .is_some_and(|(block, _)| block.height() == anchor_height && block.hash() == anchor_hash),
+            "The latest block of the snapshot is not the trusted block '{anchor_hash}' at height {anchor_height}"
+        );
+        // Ensure the finalize checksum of the snapshot is the trusted finalize checksum.
+        ensure!(
+            snapshot.finalize_checksum() == anchor_finalize_checksum,
+            "The finalize checksum of the snapshot does not match the trusted finalize checksum"
+        );
+
+        // Check the blocks in the snapshot.
+        for (block, ratified_finalize_operations) in snapshot.blocks() {
+            let block_height = block.height();
+            // Ensure the block hash matches.
+            ensure!(
+                snapshot.block_hashes()[block_height as usize] == block.hash(),
+                "The block hash of block {block_height} does not match the snapshot"
+            );
+            // Ensure the previous block hash and state root match.
+            if block_height > 0 {
+                ensure!(
+                    snapshot.block_hashes()[block_height as usize - 1] == block.previous_hash(),
+                    "The previous block hash of block {block_height} does not match the snapshot"
+                );
+                ensure!(
+                    snapshot.state_roots()[block_height as usize - 1] == block.previous_state_root(),
+                    "The previous state root of block {block_height} does not match the snapshot"
+                );
+            }
+            // Ensure the transactions root, ratifications root, and finalize root match.
+            ensure!(
+                block.header().transactions_root() == block.transactions().to_transactions_root()?,
+                "The transactions root of block {block_height} is incorrect"
+            );
+            ensure!(
+                block.header().ratifications_root() == block.ratifications().to_ratifications_root()?,
+                "The ratifications root of block {block_height} is incorrect"
+            );
+            ensure!(
+                block.header().finalize_root()
+                    == block.transactions().to_finalize_root(ratified_finalize_operations.clone())?,
+                "The finalize root of block {block_height} is incorrect"
+            );
+            // Ensure the block is authorized by its committee lookback.
+            if block_height > 0 {
+                Self::check_snapshot_authority(block, snapshot.committees())?;
+            }
+        }
+        lap!(timer, "Check the blocks");
+
+        // Initialize the consensus store.
+        let store = match ConsensusStore::<N, C>::open(storage_mode) {
+            Ok(store) => store,
+            Err(e) => bail!("Failed to load ledger (run 'snarkos clean' and try again)\n\n{e}\n"),
+        };
+        // Ensure the store is empty.
+        ensure!(store.block_store().heights().max().is_none(), "Cannot load a snapshot into a non-empty ledger");
+
+        // Store the committees.
+        for (committee_height, committee) in (0..).zip(snapshot.committees()) {
+            store.finalize_store().committee_store().insert(committee_height, committee.clone())?;
+        }
+        // Store the deployments.
+        for deployment in snapshot.deployments() {
+            store.transaction_store().insert(deployment)?;
+        }
+        // Store the mappings.
+        for (program_id, mapping_name, entries) in snapshot.mappings() {
+            if !store.finalize_store().contains_mapping_confirmed(program_id, mapping_name)? {
+                store.finalize_store().initialize_mapping(*program_id, *mapping_name)?;
+            }
+            store.finalize_store().replace_mapping(*program_id, *mapping_name, entries.clone())?;
+        }
+        // Ensure the finalize state matches the trusted finalize checksum.
+        ensure!(
+            store.finalize_store().get_checksum_confirmed()? == anchor_finalize_checksum,
+            "The finalize state does not match the trusted finalize checksum"
+        );
+        lap!(timer, "Restore the finalize state");
+
+        // Store the blocks.
+        for (block, ratified_finalize_operations) in snapshot.blocks() {
+            // Store the blocks that precede this block, without their contents.
+            if block.height() > 0 {
+                let range = store.block_store().current_block_height() as usize + 1..block.height() as usize;
+                let pruned_blocks = snapshot.block_hashes()[range.clone()]
+                    .iter()
+                    .copied()
+                    .zip(snapshot.state_roots()[range].iter().copied())
+                    .collect::<Vec<_>>();
+                store.block_store().insert_pruned(&pruned_blocks)?;
+            }
+            // Store the block.
+            store.block_store().insert(block)?;
+            store
+                .block_store()
+                .insert_ratified_finalize_operations(&block.hash(), ratified_finalize_operations.clone())?;
+            // Ensure the state root matches.
+            ensure!(
+                store.block_store().current_state_root() == snapshot.state_roots()[block.height() as usize],
+                "The state root of block {} does not match the snapshot",
+                block.height()
+            );
+        }
+        lap!(timer, "Restore the blocks");
+
+        // Initialize the ledger.
+        let ledger = Self::from_store(genesis_block, store)?;
+
+        finish!(timer, "Loaded the snapshot at block {}", snapshot.height());
+        Ok(ledger)
+    }
+
+    /// Ensures the given block from a snapshot is authorized by its committee lookback, from the given committees.
+    /// A beacon block must be signed by a committee member, and the leader certificate of a quorum block
+    /// must be authored by the expected leader, and signed by a quorum of the committee.
+    fn check_snapshot_authority(block: &Block<N>, committees: &[Committee<N>]) -> Result<()> {
+        let block_height = block.height();
+
+        // Determine the committee lookback round. Note, we subtract 2 from odd rounds,
+        // because committees are updated in even rounds.
+        let previous_round = match block.round() % 2 == 0 {
+            true => block.round().saturating_sub(1),
+            false => block.round().saturating_sub(2),
+        };
+        let committee_lookback_round = previous_round.saturating_sub(Committee::<N>::COMMITTEE_LOOKBACK_RANGE);
+        // Retrieve the committee lookback, which is the latest committee starting at or before the round.
+        let Some(committee_lookback) =
+            committees.iter().rev().find(|committee| committee.starting_round() <= committee_lookback_round)
+        else {
+            bail!("Missing the committee lookback for block {block_height} (round {committee_lookback_round})")
+        };
+
+        match block.authority() {
+            Authority::Beacon(signature) => {
+                // Ensure the block is signed by a committee member.
+                let signer = signature.to_address();
+                ensure!(
+                    committee_lookback.is_committee_member(signer),
+                    "Beacon block {block_height} has a signer not in the committee (found '{signer}')"
+                );
+                ensure!(signature.verify(&signer, &[*block.hash()]), "Signature is invalid in block {block_height}");
+            }
+            Authority::Quorum(subdag) => {
+                // Ensure the block commits to the subdag.
+                ensure!(
+                    block.header().subdag_root() == subdag.to_subdag_root()?,
+                    "The subdag root of block {block_height} is incorrect"
+                );
+                // Ensure the block is authored by the expected leader.
+                let expected_leader = committee_lookback.get_leader(block.round())?;
+                ensure!(
+                    subdag.leader_address() == expected_leader,
+                    "Quorum block {block_height} is authored by an unexpected leader (found: {}, expected: {expected_leader})",
+                    subdag.leader_address()
+                );
+                // Ensure the leader certificate is for the committee lookback.
+                let certificate = subdag.leader_certificate();
+                ensure!(
+                    certificate.committee_id() == committee_lookback.id(),
+                    "Leader certificate has an incorrect committee ID in block {block_height}"
+                );
+                // Ensure the leader certificate is signed by its author, and by a quorum of the committee.
+                let batch_id = certificate.batch_id();
+                ensure!(
+                    certificate.batch_header().signature().verify(&certificate.author(), &[batch_id]),
+                    "The leader certificate of block {block_height} has an invalid author signature"
+                );
+                let mut signers = HashSet::from([certificate.author()]);
+                for signature in certificate.signatures() {
+                    let signer = signature.to_address();
+                    ensure!(
+                        signature.verify(&signer, &[batch_id]),
+                        "The leader certificate of block {block_height} has an invalid signature from '{signer}'"
+                    );
+                    signers.insert(signer);
+                }
+                ensure!(
+                    committee_lookback.is_quorum_threshold_reached(&signers),
+                    "The leader certificate of block {block_height} is not signed by a quorum of the committee"
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -15,15 +15,20 @@
 use crate::{
     advance::split_candidate_solutions,
     test_helpers::{CurrentAleo, CurrentLedger, CurrentNetwork},
+    AccountingEvent,
     Ledger,
+    LedgerSnapshot,
+    RecordScanner,
     RecordsFilter,
+    ScanCursor,
+    TransactionPool,
 };
 use aleo_std::StorageMode;
 use console::{
-    account::{Address, PrivateKey},
+    account::{Address, PrivateKey, ViewKey},
     network::{prelude::*, MainnetV0},
     program::{Entry, Identifier, Literal, Plaintext, ProgramID, Value},
-    types::U16,
+    types::{Field, U16},
 };
 use ledger_authority::Authority;
 use ledger_block::{Block, ConfirmedTransaction, Execution, Ratify, Rejected, Transaction};
@@ -31,7 +36,11 @@ use ledger_committee::{Committee, MIN_VALIDATOR_STAKE};
 use ledger_narwhal::{BatchCertificate, BatchHeader, Data, Subdag, Transmission, TransmissionID};
 use ledger_store::{helpers::memory::ConsensusMemory, ConsensusStore};
 use snarkvm_utilities::try_vm_runtime;
-use synthesizer::{program::Program, vm::VM, Stack};
+use synthesizer::{
+    program::Program,
+    vm::{Event, VM},
+    Stack,
+};
 
 use indexmap::{IndexMap, IndexSet};
 use rand::seq::SliceRandom;
@@ -229,6 +238,46 @@ fn test_state_path() {
     let _state_path = ledger.get_state_path_for_commitment(commitment).unwrap();
 }
 
+#[test]
+fn test_state_path_for_pruned_block() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Create a record in the next block.
+    let inputs = [Value::from_str(&format!("{address}")).unwrap(), Value::from_str("10u64").unwrap()];
+    let transaction = ledger
+        .vm
+        .execute(&private_key, ("credits.aleo", "transfer_public_to_private"), inputs.into_iter(), None, 0, None, rng)
+        .unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.check_next_block(&block, rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+    let commitment = *block.transactions().commitments().next().unwrap();
+
+    // Enable pruning, which retains the transactions of the latest block.
+    ledger.set_pruning_depth(Some(1)).unwrap();
+    assert_eq!(ledger.pruning_depth().unwrap(), Some(1));
+    assert!(ledger.get_state_path_for_commitment(&commitment).is_ok());
+
+    // Advance by one block, which prunes the block containing the record.
+    let next_block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+    ledger.advance_to_next_block(&next_block).unwrap();
+    assert!(ledger.vm.block_store().is_pruned(&block.hash()).unwrap());
+
+    // Ensure the state path for the commitment is unavailable.
+    let error = ledger.get_state_path_for_commitment(&commitment).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "The state path for commitment '{commitment}' is unavailable, as block '{}' has been pruned",
+            block.hash()
+        )
+    );
+}
+
 #[test]
 fn test_insufficient_private_fees() {
     let rng = &mut TestRng::default();
@@ -3141,3 +3190,435 @@ fn test_forged_block_subdags() {
         assert!(ledger.check_next_block(&forged_block_2_from_both_subdags, &mut rand::thread_rng()).is_err());
     }
 }
+
+#[test]
+fn test_record_scanner() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, view_key, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Find the records with a full pass over the ledger.
+    let expected = ledger.find_records(&view_key, RecordsFilter::All).unwrap().collect::<IndexMap<_, _>>();
+    assert!(!expected.is_empty());
+
+    // Scan the ledger, one block at a time.
+    let mut scanner = RecordScanner::new(view_key, 0).unwrap().with_batch_size(1).unwrap();
+    let records = scanner.scan(&ledger).collect::<Result<Vec<_>>>().unwrap();
+    assert_eq!(records.len(), expected.len());
+    for record in &records {
+        assert_eq!(record.height(), 0);
+        assert_eq!(expected.get(record.commitment()), Some(record.record()));
+        assert!(!record.is_spent());
+        assert_eq!(ledger.find_transition_id(record.commitment()).unwrap(), *record.transition_id());
+    }
+
+    // Ensure the cursor is at the latest block, and round-trips through bytes.
+    let cursor = scanner.cursor();
+    assert_eq!(cursor.next_height(), ledger.latest_height() + 1);
+    assert_eq!(cursor.last_block_hash(), Some(ledger.latest_hash()));
+    let cursor = ScanCursor::from_bytes_le(&cursor.to_bytes_le().unwrap()).unwrap();
+    assert_eq!(cursor, scanner.cursor());
+
+    // Ensure a resumed scanner has nothing left to scan.
+    let mut scanner = RecordScanner::resume(view_key, cursor).unwrap();
+    assert!(scanner.scan_next(&ledger).unwrap().is_empty());
+
+    // Ensure the filters are applied.
+    let credits = ProgramID::from_str("credits.aleo").unwrap();
+    let mut scanner = RecordScanner::new(view_key, 0).unwrap().with_program_id(credits);
+    assert_eq!(scanner.scan(&ledger).count(), expected.len());
+    let mut scanner =
+        RecordScanner::new(view_key, 0).unwrap().with_record_name(Identifier::from_str("credits").unwrap());
+    assert_eq!(scanner.scan(&ledger).count(), expected.len());
+    let mut scanner = RecordScanner::new(view_key, 0).unwrap().with_record_name(Identifier::from_str("token").unwrap());
+    assert_eq!(scanner.scan(&ledger).count(), 0);
+    let mut scanner =
+        RecordScanner::new(view_key, 0).unwrap().with_program_id(ProgramID::from_str("token.aleo").unwrap());
+    assert_eq!(scanner.scan(&ledger).count(), 0);
+}
+
+#[test]
+fn test_subscribe() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Sample a recipient, and register its view key.
+    let recipient_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let recipient_view_key = ViewKey::try_from(&recipient_private_key).unwrap();
+    let recipient_address = Address::try_from(&recipient_private_key).unwrap();
+    ledger.register_view_key(recipient_view_key);
+
+    // Subscribe to the ledger events.
+    let events = ledger.subscribe();
+
+    // Transfer credits from the public balance of the caller to a record of the recipient.
+    let inputs = [Value::from_str(&format!("{recipient_address}")).unwrap(), Value::from_str("10u64").unwrap()];
+    let transaction = ledger
+        .vm
+        .execute(&private_key, ("credits.aleo", "transfer_public_to_private"), inputs.iter(), None, 0, None, rng)
+        .unwrap();
+    let transaction_id = transaction.id();
+
+    // Construct the next block, and add it to the ledger.
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the events of the block are published.
+    let events = events.try_iter().collect::<Vec<_>>();
+    assert!(events.iter().all(|event| event.height() == 1));
+    assert_eq!(events[0], Event::BlockAdded(1, block.hash()));
+    assert_eq!(events[1], Event::TransactionConfirmed(1, transaction_id));
+
+    // Ensure the public balance of the caller is updated.
+    let credits = ProgramID::from_str("credits.aleo").unwrap();
+    let account = Identifier::from_str("account").unwrap();
+    let caller = Plaintext::from(Literal::Address(address));
+    assert!(events.iter().any(|event| matches!(event,
+        Event::MappingUpdated(_, diff) if *diff.program_id() == credits
+            && *diff.mapping_name() == account
+            && *diff.key() == caller
+            && diff.old_value().is_some()
+            && diff.old_value() != diff.new_value()
+    )));
+
+    // Ensure the record of the recipient is published.
+    let records = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::RecordCreated(_, owner, _, _, record) => Some((*owner, record)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].0, recipient_address);
+    assert_eq!(**records[0].1.owner(), recipient_address);
+
+    // Ensure no record is published once the view key is unregistered.
+    assert!(ledger.unregister_view_key(&recipient_view_key));
+    assert!(!ledger.unregister_view_key(&recipient_view_key));
+}
+
+#[test]
+fn test_transition_index() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Enable the transition index.
+    let credits = ProgramID::from_str("credits.aleo").unwrap();
+    let transfer_public = Identifier::from_str("transfer_public").unwrap();
+    assert!(ledger.find_transitions_from_function(&credits, &transfer_public, 0, 10).is_err());
+    ledger.set_transition_index(true).unwrap();
+    assert_eq!(ledger.transition_index_start_height().unwrap(), Some(1));
+
+    // Transfer credits to a recipient, twice.
+    let recipient = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    let mut transition_ids = Vec::new();
+    for height in 1..=2 {
+        let inputs = [Value::from_str(&format!("{recipient}")).unwrap(), Value::from_str("10u64").unwrap()];
+        let transaction = ledger
+            .vm
+            .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng)
+            .unwrap();
+        transition_ids.push((height, *transaction.transitions().next().unwrap().id()));
+        let block =
+            ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+    }
+
+    // Ensure the transitions are found by function, and paginated.
+    assert_eq!(ledger.find_transitions_from_function(&credits, &transfer_public, 0, 10).unwrap(), transition_ids);
+    assert_eq!(ledger.find_transitions_from_function(&credits, &transfer_public, 1, 10).unwrap(), transition_ids[1..]);
+    assert_eq!(ledger.find_transitions_from_function(&credits, &transfer_public, 0, 1).unwrap(), transition_ids[..1]);
+    // Ensure the transitions are found by the public address of the recipient.
+    assert_eq!(ledger.find_transitions_from_address(&recipient, 0, 10).unwrap(), transition_ids);
+    // Ensure the transitions are found by the address of the caller, which is an argument of the future.
+    let candidate = ledger.find_transitions_from_address(&address, 0, 10).unwrap();
+    assert!(transition_ids.iter().all(|entry| candidate.contains(entry)));
+
+    // Disable the transition index.
+    ledger.set_transition_index(false).unwrap();
+    assert_eq!(ledger.transition_index_start_height().unwrap(), None);
+    assert!(ledger.find_transitions_from_address(&recipient, 0, 10).is_err());
+}
+
+#[test]
+fn test_accounting_report() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Ensure the report requires the finalize history.
+    assert!(ledger.get_accounting_report(&address, 0..1).is_err());
+    ledger.set_finalize_history(true).unwrap();
+
+    // Transfer credits publicly to a recipient.
+    let recipient = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    let inputs = [Value::from_str(&format!("{recipient}")).unwrap(), Value::from_str("10u64").unwrap()];
+    let transaction = ledger
+        .vm
+        .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng)
+        .unwrap();
+    let (transaction_id, fee) = (transaction.id(), *transaction.fee_amount().unwrap());
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the range of block heights must be covered by the finalize history.
+    assert!(ledger.get_accounting_report(&address, 0..2).is_err());
+    assert!(ledger.get_accounting_report(&address, 1..3).is_err());
+
+    // Ensure the recipient received the credits.
+    let report = ledger.get_accounting_report(&recipient, 1..2).unwrap();
+    assert_eq!(report.events(), [AccountingEvent::PublicBalanceChanged(1, 0, 10)]);
+
+    // Ensure the sender paid the fee and the credits.
+    let report = ledger.get_accounting_report(&address, 1..2).unwrap();
+    assert!(report.events().contains(&AccountingEvent::FeePaid(1, transaction_id, fee)));
+    assert_eq!(report.total_fees_paid(), fee);
+    let balance_changes = report
+        .events()
+        .iter()
+        .filter_map(|event| match event {
+            AccountingEvent::PublicBalanceChanged(_, before, after) => Some(before - after),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(balance_changes, [fee + 10]);
+}
+
+#[test]
+fn test_rollback_finalize_state() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Prepare the public balances of the sender and the recipient.
+    let recipient = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    let credits = ProgramID::from_str("credits.aleo").unwrap();
+    let account = Identifier::from_str("account").unwrap();
+    let balance = |address: Address<CurrentNetwork>| {
+        let key = Plaintext::from(Literal::Address(address));
+        ledger.vm.finalize_store().get_value_confirmed(credits, account, &key).unwrap()
+    };
+    // Advances the ledger by a block that transfers credits publicly to the recipient.
+    let advance = |rng: &mut TestRng| {
+        let inputs = [Value::from_str(&format!("{recipient}")).unwrap(), Value::from_str("10u64").unwrap()];
+        let transaction = ledger
+            .vm
+            .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng)
+            .unwrap();
+        let block =
+            ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+    };
+
+    // Advance to block 1, and enable the finalize history from block 2.
+    advance(rng);
+    ledger.set_finalize_history(true).unwrap();
+    let (sender_balance, recipient_balance) = (balance(address), balance(recipient));
+    let committee = ledger.latest_committee().unwrap();
+    assert_eq!(recipient_balance, Some(Value::from_str("10u64").unwrap()));
+    // Advance to block 3.
+    advance(rng);
+    advance(rng);
+    assert_eq!(balance(recipient), Some(Value::from_str("30u64").unwrap()));
+
+    // Ensure the ledger is not rolled back before the finalize history.
+    assert!(ledger.rollback_to(0, 3).is_err());
+    assert_eq!(ledger.latest_height(), 3);
+    assert_eq!(balance(recipient), Some(Value::from_str("30u64").unwrap()));
+
+    // Roll back the ledger to block 1.
+    ledger.rollback_to(1, 3).unwrap();
+    assert_eq!(ledger.latest_height(), 1);
+    // Ensure the public balances and the committee are restored as of block 1.
+    assert_eq!(balance(address), sender_balance);
+    assert_eq!(balance(recipient), recipient_balance);
+    assert_eq!(ledger.latest_committee().unwrap(), committee);
+    assert!(ledger.check_integrity(false).unwrap().is_consistent());
+
+    // Ensure the ledger advances from block 1.
+    advance(rng);
+    assert_eq!(ledger.latest_height(), 2);
+    assert_eq!(balance(recipient), Some(Value::from_str("20u64").unwrap()));
+}
+
+#[test]
+fn test_transaction_pool() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Create two public transfers, with different priority fees.
+    let recipient = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    let mut transfer = |priority_fee: u64| {
+        let inputs = [Value::from_str(&format!("{recipient}")).unwrap(), Value::from_str("10u64").unwrap()];
+        ledger
+            .vm
+            .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, priority_fee, None, rng)
+            .unwrap()
+    };
+    let low_priority = transfer(0);
+    let high_priority = transfer(1_000);
+
+    // Add the transactions to the pools.
+    let mut pool = TransactionPool::new();
+    let mut expiring_pool = TransactionPool::new().with_max_anchor_age(0);
+    for transaction in [&low_priority, &high_priority] {
+        pool.add_transaction(&ledger, transaction.clone(), rng).unwrap();
+        expiring_pool.add_transaction(&ledger, transaction.clone(), rng).unwrap();
+    }
+    assert_eq!(pool.len(), 2);
+    // Ensure a transaction cannot be added twice.
+    assert!(pool.add_transaction(&ledger, low_priority.clone(), rng).is_err());
+
+    // Ensure the transactions are selected in order of priority fee per byte.
+    assert_eq!(pool.select_transactions(10), vec![high_priority.clone(), low_priority.clone()]);
+    assert_eq!(pool.select_transactions(1), vec![high_priority.clone()]);
+
+    // Add the high priority transaction to the ledger.
+    let transactions = pool.select_transactions(1);
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], transactions, rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the confirmed transaction is evicted.
+    assert_eq!(pool.refresh(&ledger).unwrap(), vec![high_priority.id()]);
+    assert_eq!(pool.select_transactions(10), vec![low_priority.clone()]);
+    // Ensure a confirmed transaction cannot be added.
+    assert!(pool.add_transaction(&ledger, high_priority, rng).is_err());
+
+    // Ensure the transactions whose global state root is too old are evicted.
+    let evicted_ids = expiring_pool.refresh(&ledger).unwrap();
+    assert_eq!(evicted_ids.len(), 2);
+    assert!(evicted_ids.contains(&low_priority.id()));
+    assert!(expiring_pool.is_empty());
+}
+
+#[test]
+fn test_snapshot() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Deploy a program with a mapping.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program snapshot.aleo;
+mapping counter:
+    key as u8.public;
+    value as u8.public;
+function increment:
+    async increment into r0;
+    output r0 as snapshot.aleo/increment.future;
+finalize increment:
+    set 1u8 into counter[0u8];",
+    )
+    .unwrap();
+    let transaction = ledger.vm.deploy(&private_key, &program, None, 0, None, rng).unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Execute the program.
+    let transaction = ledger
+        .vm
+        .execute(&private_key, ("snapshot.aleo", "increment"), Vec::<Value<_>>::new().iter(), None, 0, None, rng)
+        .unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure a snapshot may only be exported at the latest height.
+    let path = std::env::temp_dir().join(format!("snarkvm-ledger-snapshot-{}", rng.gen::<u64>()));
+    assert!(ledger.export_snapshot(1, &path).is_err());
+    ledger.export_snapshot(ledger.latest_height(), &path).unwrap();
+
+    // Ensure the snapshot is rejected unless its latest block and finalize state are the trusted anchor.
+    let genesis = ledger.genesis_block.clone();
+    let finalize_checksum = ledger.vm.finalize_store().get_checksum_confirmed().unwrap();
+    let anchor = (ledger.latest_height(), ledger.latest_hash(), finalize_checksum);
+    for wrong_anchor in [
+        (ledger.latest_height(), ledger.get_hash(1).unwrap(), finalize_checksum),
+        (1, ledger.get_hash(1).unwrap(), finalize_checksum),
+        (0, genesis.hash(), finalize_checksum),
+        (ledger.latest_height(), ledger.latest_hash(), finalize_checksum + Field::one()),
+    ] {
+        assert!(
+            CurrentLedger::load_from_snapshot(genesis.clone(), StorageMode::Production, &path, wrong_anchor).is_err()
+        );
+    }
+
+    // Ensure a snapshot with a forged mapping value, and a recomputed finalize checksum, is rejected.
+    let snapshot = LedgerSnapshot::<CurrentNetwork>::load(&path).unwrap();
+    let mut mappings = snapshot.mappings().to_vec();
+    for (program_id, mapping_name, entries) in mappings.iter_mut() {
+        if *program_id == *program.id() && mapping_name.to_string() == "counter" {
+            entries[0].1 = Value::from_str("2u8").unwrap();
+        }
+    }
+    let forged_store = ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(StorageMode::Production).unwrap();
+    for (program_id, mapping_name, entries) in &mappings {
+        forged_store.finalize_store().initialize_mapping(*program_id, *mapping_name).unwrap();
+        forged_store.finalize_store().replace_mapping(*program_id, *mapping_name, entries.clone()).unwrap();
+    }
+    let forged_checksum = forged_store.finalize_store().get_checksum_confirmed().unwrap();
+    assert_ne!(forged_checksum, finalize_checksum);
+    let forged_path = std::env::temp_dir().join(format!("snarkvm-ledger-snapshot-{}", rng.gen::<u64>()));
+    LedgerSnapshot::new(
+        snapshot.block_hashes().to_vec(),
+        snapshot.state_roots().to_vec(),
+        snapshot.committees().to_vec(),
+        snapshot.blocks().to_vec(),
+        snapshot.deployments().to_vec(),
+        mappings,
+        forged_checksum,
+    )
+    .unwrap()
+    .save(&forged_path)
+    .unwrap();
+    assert!(CurrentLedger::load_from_snapshot(genesis.clone(), StorageMode::Production, &forged_path, anchor).is_err());
+    std::fs::remove_file(&forged_path).unwrap();
+
+    // Load the ledger from the snapshot.
+    let snapshot_ledger =
+        CurrentLedger::load_from_snapshot(genesis.clone(), StorageMode::Production, &path, anchor).unwrap();
+    assert_eq!(snapshot_ledger.latest_height(), ledger.latest_height());
+    assert_eq!(snapshot_ledger.latest_hash(), ledger.latest_hash());
+    assert_eq!(snapshot_ledger.latest_state_root(), ledger.latest_state_root());
+    assert_eq!(snapshot_ledger.latest_committee().unwrap(), ledger.latest_committee().unwrap());
+    assert_eq!(
+        snapshot_ledger.vm.finalize_store().get_checksum_confirmed().unwrap(),
+        ledger.vm.finalize_store().get_checksum_confirmed().unwrap()
+    );
+    assert_eq!(snapshot_ledger.get_program(*program.id()).unwrap(), program);
+    let (mapping_name, key) = (Identifier::from_str("counter").unwrap(), Plaintext::from_str("0u8").unwrap());
+    assert_eq!(
+        snapshot_ledger.vm.finalize_store().get_value_confirmed(*program.id(), mapping_name, &key).unwrap(),
+        Some(Value::from_str("1u8").unwrap())
+    );
+
+    // Ensure the ledger loaded from the snapshot can advance.
+    let block =
+        snapshot_ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+    snapshot_ledger.check_next_block(&block, rng).unwrap();
+    snapshot_ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure a tampered snapshot is rejected.
+    let mut bytes = std::fs::read(&path).unwrap();
+    let index = bytes.len() / 2;
+    bytes[index] ^= 1;
+    std::fs::write(&path, bytes).unwrap();
+    assert!(CurrentLedger::load_from_snapshot(genesis, StorageMode::Production, &path, anchor).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
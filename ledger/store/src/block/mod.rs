@@ -17,9 +17,12 @@ pub use confirmed_tx_type::*;
 
 use crate::{
     atomic_batch_scope,
+    check_dangling_keys,
+    check_reverse_map,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{Map, MapRead, NestedMap, NestedMapRead},
+    IntegrityIssue,
     TransactionStorage,
     TransactionStore,
     TransitionStorage,
@@ -27,19 +30,22 @@ use crate::{
 };
 use console::{
     network::prelude::*,
-    program::{BlockTree, HeaderLeaf, ProgramID, StatePath},
-    types::Field,
+    program::{Argument, BlockTree, Future, HeaderLeaf, Identifier, Literal, Plaintext, ProgramID, StatePath},
+    types::{Address, Field},
 };
 use ledger_authority::Authority;
 use ledger_block::{
     Block,
     ConfirmedTransaction,
     Header,
+    Input,
+    Output,
     Ratifications,
     Rejected,
     Solutions,
     Transaction,
     Transactions,
+    Transition,
 };
 use ledger_narwhal_batch_certificate::BatchCertificate;
 use ledger_puzzle::{Solution, SolutionID};
@@ -47,6 +53,7 @@ use synthesizer_program::{FinalizeOperation, Program};
 
 use aleo_std_storage::StorageMode;
 use anyhow::Result;
+use indexmap::IndexSet;
 use parking_lot::RwLock;
 use std::{borrow::Cow, sync::Arc};
 
@@ -103,6 +110,68 @@ fn to_confirmed_transaction<N: Network>(
     }
 }
 
+/// The key of the height of the first block in the transition index.
+const TRANSITION_INDEX_START_KEY: u8 = 0;
+/// The key of the pruning depth.
+const PRUNING_DEPTH_KEY: u8 = 0;
+
+/// The key of an entry in the transition index, i.e. the big-endian (`block height`, `transition index`),
+/// so that the entries of a function or an address are stored in the order they were added to the ledger.
+pub type TransitionIndexKey = [u8; 8];
+
+/// Returns the transition index key for the given block height and transition index.
+fn to_transition_index_key(block_height: u32, index: usize) -> Result<TransitionIndexKey> {
+    let mut key = [0u8; 8];
+    key[..4].copy_from_slice(&block_height.to_be_bytes());
+    key[4..].copy_from_slice(&u32::try_from(index)?.to_be_bytes());
+    Ok(key)
+}
+
+/// Returns the block height of the given transition index key.
+fn to_transition_index_height(key: &TransitionIndexKey) -> u32 {
+    u32::from_be_bytes([key[0], key[1], key[2], key[3]])
+}
+
+/// Returns the addresses in the public inputs and in the future outputs of the given transition, without duplicates.
+fn to_transition_addresses<N: Network>(transition: &Transition<N>) -> IndexSet<Address<N>> {
+    /// Collects the addresses in the given plaintext.
+    fn plaintext_addresses<N: Network>(plaintext: &Plaintext<N>, addresses: &mut IndexSet<Address<N>>) {
+        match plaintext {
+            Plaintext::Literal(Literal::Address(address), _) => {
+                addresses.insert(*address);
+            }
+            Plaintext::Literal(..) => (),
+            Plaintext::Struct(members, _) => members.values().for_each(|member| plaintext_addresses(member, addresses)),
+            Plaintext::Array(elements, _) => {
+                elements.iter().for_each(|element| plaintext_addresses(element, addresses))
+            }
+        }
+    }
+
+    /// Collects the addresses in the arguments of the given future.
+    fn future_addresses<N: Network>(future: &Future<N>, addresses: &mut IndexSet<Address<N>>) {
+        for argument in future.arguments() {
+            match argument {
+                Argument::Plaintext(plaintext) => plaintext_addresses(plaintext, addresses),
+                Argument::Future(future) => future_addresses(future, addresses),
+            }
+        }
+    }
+
+    let mut addresses = IndexSet::new();
+    for input in transition.inputs() {
+        if let Input::Public(_, Some(plaintext)) = input {
+            plaintext_addresses(plaintext, &mut addresses);
+        }
+    }
+    for output in transition.outputs() {
+        if let Output::Future(_, Some(future)) = output {
+            future_addresses(future, &mut addresses);
+        }
+    }
+    addresses
+}
+
 /// A trait for block storage.
 pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     /// The mapping of `block height` to `state root`.
@@ -139,6 +208,18 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     type ConfirmedTransactionsMap: for<'a> Map<'a, N::TransactionID, (N::BlockHash, ConfirmedTxType<N>, Vec<FinalizeOperation<N>>)>;
     /// The rejected deployment or execution map.
     type RejectedDeploymentOrExecutionMap: for<'a> Map<'a, Field<N>, Rejected<N>>;
+    /// The mapping of pruned `block hash` to `block height`.
+    type PrunedMap: for<'a> Map<'a, N::BlockHash, u32>;
+    /// The mapping of `block hash` to `[ratified finalize operation]`.
+    type RatifiedFinalizeOperationsMap: for<'a> Map<'a, N::BlockHash, Vec<FinalizeOperation<N>>>;
+    /// The mapping of `TRANSITION_INDEX_START_KEY` to the height of the first block in the transition index.
+    type TransitionIndexStartMap: for<'a> Map<'a, u8, u32>;
+    /// The mapping of (`program ID`, `function name`) to `transition index key` to `transition ID`.
+    type FunctionIndexMap: for<'a> NestedMap<'a, (ProgramID<N>, Identifier<N>), TransitionIndexKey, N::TransitionID>;
+    /// The mapping of `address` to `transition index key` to `transition ID`.
+    type AddressIndexMap: for<'a> NestedMap<'a, Address<N>, TransitionIndexKey, N::TransitionID>;
+    /// The mapping of `PRUNING_DEPTH_KEY` to the pruning depth.
+    type PruningDepthMap: for<'a> Map<'a, u8, u32>;
     /// The transaction storage.
     type TransactionStorage: TransactionStorage<N, TransitionStorage = Self::TransitionStorage>;
     /// The transition storage.
@@ -181,6 +262,18 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     fn confirmed_transactions_map(&self) -> &Self::ConfirmedTransactionsMap;
     /// Returns the rejected deployment or execution map.
     fn rejected_deployment_or_execution_map(&self) -> &Self::RejectedDeploymentOrExecutionMap;
+    /// Returns the pruned map.
+    fn pruned_map(&self) -> &Self::PrunedMap;
+    /// Returns the ratified finalize operations map.
+    fn ratified_finalize_operations_map(&self) -> &Self::RatifiedFinalizeOperationsMap;
+    /// Returns the transition index start map.
+    fn transition_index_start_map(&self) -> &Self::TransitionIndexStartMap;
+    /// Returns the function index map.
+    fn function_index_map(&self) -> &Self::FunctionIndexMap;
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap;
+    /// Returns the pruning depth map.
+    fn pruning_depth_map(&self) -> &Self::PruningDepthMap;
     /// Returns the transaction store.
     fn transaction_store(&self) -> &TransactionStore<N, Self::TransactionStorage>;
 
@@ -213,6 +306,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().start_atomic();
         self.confirmed_transactions_map().start_atomic();
         self.rejected_deployment_or_execution_map().start_atomic();
+        self.pruned_map().start_atomic();
+        self.ratified_finalize_operations_map().start_atomic();
+        self.transition_index_start_map().start_atomic();
+        self.function_index_map().start_atomic();
+        self.address_index_map().start_atomic();
+        self.pruning_depth_map().start_atomic();
         self.transaction_store().start_atomic();
     }
 
@@ -235,6 +334,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             || self.rejected_or_aborted_transaction_id_map().is_atomic_in_progress()
             || self.confirmed_transactions_map().is_atomic_in_progress()
             || self.rejected_deployment_or_execution_map().is_atomic_in_progress()
+            || self.pruned_map().is_atomic_in_progress()
+            || self.ratified_finalize_operations_map().is_atomic_in_progress()
+            || self.transition_index_start_map().is_atomic_in_progress()
+            || self.function_index_map().is_atomic_in_progress()
+            || self.address_index_map().is_atomic_in_progress()
+            || self.pruning_depth_map().is_atomic_in_progress()
             || self.transaction_store().is_atomic_in_progress()
     }
 
@@ -257,6 +362,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().atomic_checkpoint();
         self.confirmed_transactions_map().atomic_checkpoint();
         self.rejected_deployment_or_execution_map().atomic_checkpoint();
+        self.pruned_map().atomic_checkpoint();
+        self.ratified_finalize_operations_map().atomic_checkpoint();
+        self.transition_index_start_map().atomic_checkpoint();
+        self.function_index_map().atomic_checkpoint();
+        self.address_index_map().atomic_checkpoint();
+        self.pruning_depth_map().atomic_checkpoint();
         self.transaction_store().atomic_checkpoint();
     }
 
@@ -279,6 +390,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().clear_latest_checkpoint();
         self.confirmed_transactions_map().clear_latest_checkpoint();
         self.rejected_deployment_or_execution_map().clear_latest_checkpoint();
+        self.pruned_map().clear_latest_checkpoint();
+        self.ratified_finalize_operations_map().clear_latest_checkpoint();
+        self.transition_index_start_map().clear_latest_checkpoint();
+        self.function_index_map().clear_latest_checkpoint();
+        self.address_index_map().clear_latest_checkpoint();
+        self.pruning_depth_map().clear_latest_checkpoint();
         self.transaction_store().clear_latest_checkpoint();
     }
 
@@ -301,6 +418,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().atomic_rewind();
         self.confirmed_transactions_map().atomic_rewind();
         self.rejected_deployment_or_execution_map().atomic_rewind();
+        self.pruned_map().atomic_rewind();
+        self.ratified_finalize_operations_map().atomic_rewind();
+        self.transition_index_start_map().atomic_rewind();
+        self.function_index_map().atomic_rewind();
+        self.address_index_map().atomic_rewind();
+        self.pruning_depth_map().atomic_rewind();
         self.transaction_store().atomic_rewind();
     }
 
@@ -323,6 +446,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().abort_atomic();
         self.confirmed_transactions_map().abort_atomic();
         self.rejected_deployment_or_execution_map().abort_atomic();
+        self.pruned_map().abort_atomic();
+        self.ratified_finalize_operations_map().abort_atomic();
+        self.transition_index_start_map().abort_atomic();
+        self.function_index_map().abort_atomic();
+        self.address_index_map().abort_atomic();
+        self.pruning_depth_map().abort_atomic();
         self.transaction_store().abort_atomic();
     }
 
@@ -345,6 +474,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().finish_atomic()?;
         self.confirmed_transactions_map().finish_atomic()?;
         self.rejected_deployment_or_execution_map().finish_atomic()?;
+        self.pruned_map().finish_atomic()?;
+        self.ratified_finalize_operations_map().finish_atomic()?;
+        self.transition_index_start_map().finish_atomic()?;
+        self.function_index_map().finish_atomic()?;
+        self.address_index_map().finish_atomic()?;
+        self.pruning_depth_map().finish_atomic()?;
         self.transaction_store().finish_atomic()
     }
 
@@ -388,6 +523,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             .map(|tx| tx.to_unconfirmed_transaction_id())
             .collect::<Result<Vec<_>>>()?;
 
+        // Determine whether the transition index covers the block.
+        let is_indexed = self.is_transition_index_enabled_for(block.height())?;
+
         atomic_batch_scope!(self, {
             // Store the (block height, state root) pair.
             self.state_root_map().insert(block.height(), state_root)?;
@@ -457,6 +595,21 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
                 self.transaction_store().insert(&transaction)?;
             }
 
+            // Store the transition index entries.
+            if is_indexed {
+                for (index, transition) in block.transitions().enumerate() {
+                    let key = to_transition_index_key(block.height(), index)?;
+                    self.function_index_map().insert(
+                        (*transition.program_id(), *transition.function_name()),
+                        key,
+                        *transition.id(),
+                    )?;
+                    for address in to_transition_addresses(transition) {
+                        self.address_index_map().insert(address, key, *transition.id())?;
+                    }
+                }
+            }
+
             Ok(())
         })
     }
@@ -468,6 +621,10 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             Some(height) => height,
             None => bail!("Failed to remove block: missing block height for block hash '{block_hash}'"),
         };
+        // Ensure the block has not been pruned.
+        if self.pruned_map().contains_key_confirmed(block_hash)? {
+            bail!("Failed to remove block: block '{block_height}' ('{block_hash}') has been pruned")
+        }
         // Retrieve the state root.
         let state_root = match self.state_root_map().get_confirmed(&block_height)? {
             Some(state_root) => cow_to_copied!(state_root),
@@ -519,6 +676,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             None => bail!("Failed to remove block: missing authority for block '{block_height}' ('{block_hash}')"),
         };
 
+        // Retrieve the transactions, if the transition index covers the block.
+        let indexed_transactions = match self.is_transition_index_enabled_for(block_height)? {
+            true => self.get_block_transactions(block_hash)?,
+            false => None,
+        };
+
         atomic_batch_scope!(self, {
             // Remove the (block height, state root) pair.
             self.state_root_map().remove(&block_height)?;
@@ -586,10 +749,128 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
                 self.transaction_store().remove(transaction_id)?;
             }
 
+            // Remove the ratified finalize operations.
+            self.ratified_finalize_operations_map().remove(block_hash)?;
+
+            // Remove the transition index entries.
+            if let Some(transactions) = indexed_transactions {
+                for (index, transition) in transactions.transitions().enumerate() {
+                    let key = to_transition_index_key(block_height, index)?;
+                    self.function_index_map()
+                        .remove_key(&(*transition.program_id(), *transition.function_name()), &key)?;
+                    for address in to_transition_addresses(transition) {
+                        self.address_index_map().remove_key(&address, &key)?;
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Stores the given `(state root, block height, block hash)` for a block whose contents are unavailable,
+    /// such as a block that precedes the blocks of a ledger snapshot. The block is marked as pruned.
+    fn insert_pruned(&self, state_root: N::StateRoot, block_height: u32, block_hash: N::BlockHash) -> Result<()> {
+        atomic_batch_scope!(self, {
+            // Store the (block height, state root) pair.
+            self.state_root_map().insert(block_height, state_root)?;
+            // Store the (state root, block height) pair.
+            self.reverse_state_root_map().insert(state_root, block_height)?;
+
+            // Store the block hash.
+            self.id_map().insert(block_height, block_hash)?;
+            // Store the block height.
+            self.reverse_id_map().insert(block_hash, block_height)?;
+
+            // Mark the block as pruned.
+            self.pruned_map().insert(block_hash, block_height)?;
+
+            Ok(())
+        })
+    }
+
+    /// Stores the finalize operations produced by the ratifications of the block for the given `block hash`.
+    fn insert_ratified_finalize_operations(
+        &self,
+        block_hash: &N::BlockHash,
+        ratified_finalize_operations: Vec<FinalizeOperation<N>>,
+    ) -> Result<()> {
+        self.ratified_finalize_operations_map().insert(*block_hash, ratified_finalize_operations)
+    }
+
+    /// Prunes the transactions of the block for the given `block hash`.
+    /// The block header, the transaction IDs, and the serial numbers, tags and commitments are retained.
+    fn prune(&self, block_hash: &N::BlockHash) -> Result<()> {
+        // Retrieve the block height.
+        let block_height = match self.get_block_height(block_hash)? {
+            Some(height) => height,
+            None => bail!("Failed to prune block: missing block height for block hash '{block_hash}'"),
+        };
+        // Return early if the block has already been pruned.
+        if self.pruned_map().contains_key_confirmed(block_hash)? {
+            return Ok(());
+        }
+        // Retrieve the transaction IDs.
+        let transaction_ids = match self.transactions_map().get_confirmed(block_hash)? {
+            Some(transaction_ids) => transaction_ids,
+            None => bail!("Failed to prune block: missing transactions for block '{block_height}' ('{block_hash}')"),
+        };
+
+        atomic_batch_scope!(self, {
+            // Prune the block transactions.
+            for transaction_id in transaction_ids.iter() {
+                self.transaction_store().prune(transaction_id)?;
+            }
+            // Mark the block as pruned.
+            self.pruned_map().insert(*block_hash, block_height)?;
+
             Ok(())
         })
     }
 
+    /// Returns the finalize operations produced by the ratifications of the block for the given `block hash`.
+    /// Returns `None` if they were not recorded, as is the case for blocks inserted before they were stored.
+    fn get_ratified_finalize_operations(&self, block_hash: &N::BlockHash) -> Result<Option<Vec<FinalizeOperation<N>>>> {
+        match self.ratified_finalize_operations_map().get_confirmed(block_hash)? {
+            Some(operations) => Ok(Some(cow_to_cloned!(operations))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the height of the first block in the transition index, if the transition index is enabled.
+    fn transition_index_start_height(&self) -> Result<Option<u32>> {
+        match self.transition_index_start_map().get_confirmed(&TRANSITION_INDEX_START_KEY)? {
+            Some(height) => Ok(Some(cow_to_copied!(height))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the pruning depth, if pruning is enabled.
+    fn pruning_depth(&self) -> Result<Option<u32>> {
+        match self.pruning_depth_map().get_confirmed(&PRUNING_DEPTH_KEY)? {
+            Some(depth) => Ok(Some(cow_to_copied!(depth))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the transition index is enabled for the block at the given height.
+    fn is_transition_index_enabled_for(&self, block_height: u32) -> Result<bool> {
+        Ok(self.transition_index_start_height()?.map_or(false, |start_height| block_height >= start_height))
+    }
+
+    /// Returns `true` if the block for the given `block hash` has been pruned.
+    fn is_pruned(&self, block_hash: &N::BlockHash) -> Result<bool> {
+        self.pruned_map().contains_key_confirmed(block_hash)
+    }
+
+    /// Returns `true` if the transaction for the given `transaction ID` is in a pruned block.
+    fn is_transaction_pruned(&self, transaction_id: &N::TransactionID) -> Result<bool> {
+        match self.confirmed_transactions_map().get_confirmed(transaction_id)? {
+            Some(confirmed_attributes) => self.is_pruned(&confirmed_attributes.0),
+            None => Ok(false),
+        }
+    }
+
     /// Returns `true` if the given transaction ID exists.
     fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> Result<bool> {
         Ok(self.transaction_store().contains_transaction_id(transaction_id)?
@@ -638,6 +919,35 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         }
     }
 
+    /// Returns the (`block height`, `transition ID`) pairs of the transitions that call the given function,
+    /// in the order they were added to the ledger, skipping the first `offset` pairs and returning at most `limit`.
+    fn find_transitions_from_function(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransitionID)>> {
+        ensure!(self.transition_index_start_height()?.is_some(), "The transition index is not enabled");
+        let entries =
+            self.function_index_map().get_map_range_confirmed(&(*program_id, *function_name), offset, limit)?;
+        Ok(entries.into_iter().map(|(key, id)| (to_transition_index_height(&key), id)).collect())
+    }
+
+    /// Returns the (`block height`, `transition ID`) pairs of the transitions that have the given address
+    /// as a public argument, in the order they were added to the ledger, skipping the first `offset` pairs
+    /// and returning at most `limit`.
+    fn find_transitions_from_address(
+        &self,
+        address: &Address<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransitionID)>> {
+        ensure!(self.transition_index_start_height()?.is_some(), "The transition index is not enabled");
+        let entries = self.address_index_map().get_map_range_confirmed(address, offset, limit)?;
+        Ok(entries.into_iter().map(|(key, id)| (to_transition_index_height(&key), id)).collect())
+    }
+
     /// Returns the state root that contains the given `block height`.
     fn get_state_root(&self, block_height: u32) -> Result<Option<N::StateRoot>> {
         match self.state_root_map().get_confirmed(&block_height)? {
@@ -665,6 +975,12 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             Some(block_hash) => block_hash,
             None => bail!("The block hash for commitment '{commitment}' is missing in storage"),
         };
+        // Ensure the block has not been pruned.
+        if self.is_pruned(&block_hash)? {
+            bail!(
+                "The state path for commitment '{commitment}' is unavailable, as block '{block_hash}' has been pruned"
+            )
+        }
 
         // Retrieve the transition.
         let transition = match self.transition_store().get_transition(&transition_id)? {
@@ -858,6 +1174,10 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
 
     /// Returns the block transactions for the given `block hash`.
     fn get_block_transactions(&self, block_hash: &N::BlockHash) -> Result<Option<Transactions<N>>> {
+        // Ensure the block has not been pruned.
+        if self.is_pruned(block_hash)? {
+            bail!("The transactions for block '{block_hash}' have been pruned");
+        }
         // Retrieve the transaction IDs.
         let transaction_ids = match self.transactions_map().get_confirmed(block_hash)? {
             Some(transaction_ids) => transaction_ids,
@@ -900,7 +1220,13 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
                 }
                 None => bail!("Missing transactions for block '{block_hash}' in block storage"),
             },
-            None => self.transaction_store().get_transaction(transaction_id),
+            None => {
+                // Ensure the transaction has not been pruned.
+                if self.is_transaction_pruned(transaction_id)? {
+                    bail!("Transaction '{transaction_id}' has been pruned");
+                }
+                self.transaction_store().get_transaction(transaction_id)
+            }
         }
     }
 
@@ -936,7 +1262,13 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
                 }
                 None => bail!("Missing transactions for block '{block_hash}' in block storage"),
             },
-            None => self.transaction_store().get_transaction(transaction_id),
+            None => {
+                // Ensure the transaction has not been pruned.
+                if self.is_transaction_pruned(transaction_id)? {
+                    bail!("Transaction '{transaction_id}' has been pruned");
+                }
+                self.transaction_store().get_transaction(transaction_id)
+            }
         }
     }
 
@@ -995,6 +1327,163 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             aborted_transaction_ids,
         )?))
     }
+
+    /// Checks that the maps of the block storage, and of the stores it contains, are mutually consistent.
+    /// Returns the inconsistent entries, along with the height of the block they belong to, if it is known.
+    fn check_integrity(&self) -> Result<Vec<IntegrityIssue>> {
+        let mut issues = Vec::new();
+        // Check the block hashes and state roots against their reverse maps.
+        let reverse = [
+            check_reverse_map(self.id_map(), ("reverse block ID", self.reverse_id_map()), |hash| vec![*hash])?,
+            check_reverse_map(self.state_root_map(), ("reverse state root", self.reverse_state_root_map()), |root| {
+                vec![*root]
+            })?,
+        ];
+        issues.extend(reverse.into_iter().flatten().map(|(height, issue)| issue.at_height(height)));
+        // Check that each block has its hash and state root.
+        if let Some(latest_height) = self.id_map().keys_confirmed().map(|height| cow_to_copied!(height)).max() {
+            for height in 0..=latest_height {
+                if !self.id_map().contains_key_confirmed(&height)? {
+                    issues.push(IntegrityIssue::missing("block ID", height).at_height(height));
+                }
+                if !self.state_root_map().contains_key_confirmed(&height)? {
+                    issues.push(IntegrityIssue::missing("state root", height).at_height(height));
+                }
+            }
+        }
+        for (height, issue) in check_dangling_keys(("state root", self.state_root_map()), self.id_map())? {
+            issues.push(issue.at_height(height));
+        }
+
+        // Check the contents of each block.
+        for (height, block_hash) in self.id_map().iter_confirmed() {
+            let (height, block_hash) = (*height, *block_hash);
+            // Skip the blocks that were restored without their contents.
+            if self.pruned_map().contains_key_confirmed(&block_hash)?
+                && !self.header_map().contains_key_confirmed(&block_hash)?
+            {
+                continue;
+            }
+            // Check the block header.
+            match self.header_map().get_confirmed(&block_hash)? {
+                Some(header) if header.height() == height => (),
+                Some(_) => issues.push(IntegrityIssue::mismatch("block header", block_hash).at_height(height)),
+                None => issues.push(IntegrityIssue::missing("block header", block_hash).at_height(height)),
+            }
+            // Check that the block has its authority, ratifications, solutions, and aborted IDs.
+            let contents = [
+                ("block authority", self.authority_map().contains_key_confirmed(&block_hash)?),
+                ("block ratifications", self.ratifications_map().contains_key_confirmed(&block_hash)?),
+                ("block aborted solution IDs", self.aborted_solution_ids_map().contains_key_confirmed(&block_hash)?),
+                (
+                    "block aborted transaction IDs",
+                    self.aborted_transaction_ids_map().contains_key_confirmed(&block_hash)?,
+                ),
+            ];
+            for (map, _) in contents.into_iter().filter(|(_, is_stored)| !is_stored) {
+                issues.push(IntegrityIssue::missing(map, block_hash).at_height(height));
+            }
+            // Check the block solutions.
+            match self.solutions_map().get_confirmed(&block_hash)? {
+                Some(solutions) => {
+                    for solution_id in solutions.solution_ids() {
+                        match self.solution_ids_map().get_confirmed(solution_id)? {
+                            Some(solution_height) if *solution_height == height => (),
+                            Some(_) => {
+                                issues.push(IntegrityIssue::mismatch("solution ID", solution_id).at_height(height))
+                            }
+                            None => issues.push(IntegrityIssue::missing("solution ID", solution_id).at_height(height)),
+                        }
+                    }
+                }
+                None => issues.push(IntegrityIssue::missing("block solutions", block_hash).at_height(height)),
+            }
+            // Check the block transactions.
+            match self.transactions_map().get_confirmed(&block_hash)? {
+                Some(transaction_ids) => {
+                    for transaction_id in transaction_ids.iter() {
+                        match self.confirmed_transactions_map().get_confirmed(transaction_id)? {
+                            Some(confirmed) if confirmed.0 == block_hash => (),
+                            Some(_) => issues.push(
+                                IntegrityIssue::mismatch("confirmed transaction", transaction_id).at_height(height),
+                            ),
+                            None => issues.push(
+                                IntegrityIssue::missing("confirmed transaction", transaction_id).at_height(height),
+                            ),
+                        }
+                        if !self.transaction_store().contains_transaction_id(transaction_id)? {
+                            issues.push(IntegrityIssue::missing("transaction ID", transaction_id).at_height(height));
+                        }
+                    }
+                }
+                None => issues.push(IntegrityIssue::missing("block transactions", block_hash).at_height(height)),
+            }
+        }
+
+        // Check that each entry belongs to a block.
+        let dangling = [
+            check_dangling_keys(("block header", self.header_map()), self.reverse_id_map())?,
+            check_dangling_keys(("block authority", self.authority_map()), self.reverse_id_map())?,
+            check_dangling_keys(("block ratifications", self.ratifications_map()), self.reverse_id_map())?,
+            check_dangling_keys(("block solutions", self.solutions_map()), self.reverse_id_map())?,
+            check_dangling_keys(
+                ("block aborted solution IDs", self.aborted_solution_ids_map()),
+                self.reverse_id_map(),
+            )?,
+            check_dangling_keys(("block transactions", self.transactions_map()), self.reverse_id_map())?,
+            check_dangling_keys(
+                ("block aborted transaction IDs", self.aborted_transaction_ids_map()),
+                self.reverse_id_map(),
+            )?,
+            check_dangling_keys(("pruned block", self.pruned_map()), self.reverse_id_map())?,
+            check_dangling_keys(
+                ("ratified finalize operations", self.ratified_finalize_operations_map()),
+                self.reverse_id_map(),
+            )?,
+        ];
+        issues.extend(dangling.into_iter().flatten().map(|(_, issue)| issue));
+        for (certificate_id, certificate) in self.certificate_map().iter_confirmed() {
+            if !self.id_map().contains_key_confirmed(&certificate.0)? {
+                issues.push(IntegrityIssue::dangling("block certificate", *certificate_id).at_height(certificate.0));
+            }
+        }
+        for (solution_id, height) in self.solution_ids_map().iter_confirmed() {
+            if !self.id_map().contains_key_confirmed(&height)? {
+                issues.push(IntegrityIssue::dangling("solution ID", *solution_id).at_height(*height));
+            }
+        }
+        for (solution_id, height) in self.aborted_solution_heights_map().iter_confirmed() {
+            if !self.id_map().contains_key_confirmed(&height)? {
+                issues.push(IntegrityIssue::dangling("aborted solution height", *solution_id).at_height(*height));
+            }
+        }
+        for (transaction_id, confirmed) in self.confirmed_transactions_map().iter_confirmed() {
+            if !self.reverse_id_map().contains_key_confirmed(&confirmed.0)? {
+                issues.push(IntegrityIssue::dangling("confirmed transaction", *transaction_id));
+            }
+        }
+        for (transaction_id, block_hash) in self.rejected_or_aborted_transaction_id_map().iter_confirmed() {
+            if !self.reverse_id_map().contains_key_confirmed(&block_hash)? {
+                issues.push(IntegrityIssue::dangling("rejected or aborted transaction ID", *transaction_id));
+            }
+        }
+        for transaction_id in self.transaction_store().transaction_ids() {
+            if !self.confirmed_transactions_map().contains_key_confirmed(&transaction_id)? {
+                issues.push(IntegrityIssue::dangling("transaction ID", *transaction_id));
+            }
+        }
+
+        // Check the transactions.
+        for (transaction_id, mut issue) in self.transaction_store().check_integrity()? {
+            if let Some(transaction_id) = transaction_id {
+                if let Some(block_hash) = self.find_block_hash(&transaction_id)? {
+                    issue.height = self.get_block_height(&block_hash)?;
+                }
+            }
+            issues.push(issue);
+        }
+        Ok(issues)
+    }
 }
 
 /// The block store.
@@ -1044,14 +1533,66 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         if block.height() != u32::try_from(updated_tree.number_of_leaves())? - 1 {
             bail!("Attempted to insert a block at the incorrect height into storage")
         }
-        // Insert the (state root, block height) pair.
-        self.storage.insert((*updated_tree.root()).into(), block)?;
+        // Determine the block height to prune, if pruning is enabled.
+        // Note: The genesis block is never pruned.
+        let prune_height = self.storage.pruning_depth()?.and_then(|depth| block.height().checked_sub(depth));
+        atomic_batch_scope!(self, {
+            // Insert the (state root, block height) pair.
+            self.storage.insert((*updated_tree.root()).into(), block)?;
+            // Prune the block that is now beyond the pruning depth.
+            if let Some(height) = prune_height.filter(|height| *height > 0) {
+                self.prune_block(height)?;
+            }
+            Ok(())
+        })?;
         // Update the block tree.
         *tree = updated_tree;
         // Return success.
         Ok(())
     }
 
+    /// Stores the given `(block hash, state root)` pairs as the next blocks, without their contents.
+    /// The blocks are marked as pruned, and are used to restore the blocks that precede a ledger snapshot.
+    ///
+    /// Note: Only the last state root is checked against the block tree, as checking each of them
+    /// would require recomputing the block tree for every block.
+    pub fn insert_pruned(&self, blocks: &[(N::BlockHash, N::StateRoot)]) -> Result<()> {
+        // Retrieve the last state root.
+        let Some((_, last_state_root)) = blocks.last() else { return Ok(()) };
+        // Acquire the write lock on the block tree.
+        let mut tree = self.tree.write();
+        // Determine the height of the first block.
+        let start_height = u32::try_from(tree.number_of_leaves())?;
+        // Prepare an updated Merkle tree containing the new block hashes.
+        let leaves = blocks.iter().map(|(block_hash, _)| block_hash.to_bits_le()).collect::<Vec<_>>();
+        let updated_tree = tree.prepare_append(&leaves)?;
+        // Ensure the last state root is correct.
+        if N::StateRoot::from(*updated_tree.root()) != *last_state_root {
+            let last_height = start_height as usize + blocks.len() - 1;
+            bail!("The state root of block {last_height} does not match the block hashes")
+        }
+        atomic_batch_scope!(self, {
+            // Insert the (state root, block height, block hash) triples.
+            for (block_height, (block_hash, state_root)) in (start_height..).zip(blocks) {
+                self.storage.insert_pruned(*state_root, block_height, *block_hash)?;
+            }
+            Ok(())
+        })?;
+        // Update the block tree.
+        *tree = updated_tree;
+        // Return success.
+        Ok(())
+    }
+
+    /// Stores the finalize operations produced by the ratifications of the block for the given `block hash`.
+    pub fn insert_ratified_finalize_operations(
+        &self,
+        block_hash: &N::BlockHash,
+        ratified_finalize_operations: Vec<FinalizeOperation<N>>,
+    ) -> Result<()> {
+        self.storage.insert_ratified_finalize_operations(block_hash, ratified_finalize_operations)
+    }
+
     /// Reverts the Merkle tree to its shape before the insertion of the last 'n' blocks.
     pub fn remove_last_n_from_tree_only(&self, n: u32) -> Result<()> {
         // Ensure 'n' is non-zero.
@@ -1115,6 +1656,138 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         Ok(())
     }
 
+    /// Checks that the maps of the block store, and of the stores it contains, are mutually consistent,
+    /// and that the block tree recomputed from the block hashes matches the latest state root.
+    /// Returns the inconsistent entries, along with the height of the block they belong to, if it is known.
+    ///
+    /// Note: A mismatch between the block tree and the latest state root is attributed to the latest block,
+    /// as locating the first mismatching state root would require recomputing the block tree for every block.
+    pub fn check_integrity(&self) -> Result<Vec<IntegrityIssue>> {
+        let mut issues = self.storage.check_integrity()?;
+
+        // Retrieve the latest block height.
+        let Some(latest_height) = self.storage.id_map().keys_confirmed().map(|height| cow_to_copied!(height)).max()
+        else {
+            return Ok(issues);
+        };
+        // Prepare the leaves of the block tree, which are only available if no block hash is missing.
+        let mut leaves = Vec::with_capacity(latest_height as usize + 1);
+        for height in 0..=latest_height {
+            match self.storage.get_block_hash(height)? {
+                Some(hash) => leaves.push(hash.to_bits_le()),
+                None => return Ok(issues),
+            }
+        }
+        // Recompute the block tree.
+        let tree: BlockTree<N> = N::merkle_tree_bhp(&leaves)?;
+        let state_root = N::StateRoot::from(*tree.root());
+        // Ensure the latest state root matches the block tree.
+        if let Some(latest_state_root) = self.storage.get_state_root(latest_height)? {
+            if latest_state_root != state_root {
+                issues.push(IntegrityIssue::mismatch("state root", latest_height).at_height(latest_height));
+            }
+        }
+        // Ensure the block tree in memory matches the block tree.
+        if self.current_state_root() != state_root {
+            issues.push(IntegrityIssue::mismatch("block tree", state_root).at_height(latest_height));
+        }
+        Ok(issues)
+    }
+
+    /// Returns the pruning depth, if pruning is enabled.
+    pub fn pruning_depth(&self) -> Result<Option<u32>> {
+        self.storage.pruning_depth()
+    }
+
+    /// Sets the pruning depth, which is the number of most recent blocks whose transactions are retained.
+    /// If enabled, the transactions of all older blocks are pruned immediately, and as new blocks are inserted.
+    /// The pruning depth is persisted in storage, so pruning continues when the block store is reopened.
+    ///
+    /// Note: The state paths for the commitments in pruned blocks can no longer be built,
+    /// so their records can only be spent using a state path that was retrieved before they were pruned.
+    pub fn set_pruning_depth(&self, pruning_depth: Option<u32>) -> Result<()> {
+        // Ensure the pruning depth is non-zero.
+        if let Some(depth) = pruning_depth {
+            ensure!(depth > 0, "The pruning depth must be greater than zero");
+        }
+        atomic_batch_scope!(self, {
+            match pruning_depth {
+                Some(depth) => {
+                    // Prune the blocks that are beyond the pruning depth.
+                    // Note: The genesis block is never pruned.
+                    let end_height = self.current_block_height().saturating_sub(depth);
+                    for height in 1..=end_height {
+                        self.prune_block(height)?;
+                    }
+                    // Store the pruning depth.
+                    self.storage.pruning_depth_map().insert(PRUNING_DEPTH_KEY, depth)
+                }
+                None => self.storage.pruning_depth_map().remove(&PRUNING_DEPTH_KEY),
+            }
+        })
+    }
+
+    /// Returns the height of the first block in the transition index, if the transition index is enabled.
+    pub fn transition_index_start_height(&self) -> Result<Option<u32>> {
+        self.storage.transition_index_start_height()
+    }
+
+    /// Enables the transition index, which indexes the transitions of each block by function and by address,
+    /// starting from the block at the given height. If the transition index is already enabled, this is a no-op.
+    ///
+    /// As the existing blocks are not indexed, the start height must be above the height of the latest block.
+    pub fn enable_transition_index(&self, start_height: u32) -> Result<()> {
+        if self.transition_index_start_height()?.is_none() {
+            // Ensure the start height is above the latest block, so that the index does not miss any block.
+            if let Some(latest_height) = self.heights().max() {
+                ensure!(
+                    start_height > *latest_height,
+                    "The transition index must start after the latest block ({}), found {start_height}",
+                    *latest_height
+                );
+            }
+            self.storage.transition_index_start_map().insert(TRANSITION_INDEX_START_KEY, start_height)?;
+        }
+        Ok(())
+    }
+
+    /// Disables the transition index, and removes it from storage.
+    pub fn disable_transition_index(&self) -> Result<()> {
+        // Retrieve the entries of the transition index.
+        let function_entries = self
+            .storage
+            .function_index_map()
+            .keys_confirmed()
+            .map(|(function, key)| (cow_to_copied!(function), cow_to_copied!(key)))
+            .collect::<Vec<_>>();
+        let address_entries = self
+            .storage
+            .address_index_map()
+            .keys_confirmed()
+            .map(|(address, key)| (cow_to_copied!(address), cow_to_copied!(key)))
+            .collect::<Vec<_>>();
+        atomic_batch_scope!(self, {
+            // Remove the start height.
+            self.storage.transition_index_start_map().remove(&TRANSITION_INDEX_START_KEY)?;
+            // Remove the entries.
+            for (function, key) in function_entries {
+                self.storage.function_index_map().remove_key(&function, &key)?;
+            }
+            for (address, key) in address_entries {
+                self.storage.address_index_map().remove_key(&address, &key)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Prunes the transactions of the block at the given height.
+    fn prune_block(&self, height: u32) -> Result<()> {
+        match self.storage.get_block_hash(height)? {
+            Some(block_hash) => self.storage.prune(&block_hash),
+            None => bail!("Failed to prune block {height}: missing block hash"),
+        }
+    }
+
     /// Returns the transaction store.
     pub fn transaction_store(&self) -> &TransactionStore<N, B::TransactionStorage> {
         self.storage.transaction_store()
@@ -1191,6 +1864,30 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
     pub fn find_block_height_from_solution_id(&self, solution_id: &SolutionID<N>) -> Result<Option<u32>> {
         self.storage.find_block_height_from_solution_id(solution_id)
     }
+
+    /// Returns the (`block height`, `transition ID`) pairs of the transitions that call the given function,
+    /// in the order they were added to the ledger, skipping the first `offset` pairs and returning at most `limit`.
+    pub fn find_transitions_from_function(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransitionID)>> {
+        self.storage.find_transitions_from_function(program_id, function_name, offset, limit)
+    }
+
+    /// Returns the (`block height`, `transition ID`) pairs of the transitions that have the given address
+    /// as a public argument, in the order they were added to the ledger, skipping the first `offset` pairs
+    /// and returning at most `limit`.
+    pub fn find_transitions_from_address(
+        &self,
+        address: &Address<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransitionID)>> {
+        self.storage.find_transitions_from_address(address, offset, limit)
+    }
 }
 
 impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
@@ -1210,6 +1907,7 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
     }
 
     /// Returns a state path for the given `commitment`.
+    /// Returns an error if the block containing the commitment has been pruned.
     pub fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
         self.storage.get_state_path_for_commitment(commitment, &self.tree.read())
     }
@@ -1290,6 +1988,14 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         self.storage.get_block(block_hash)
     }
 
+    /// Returns the finalize operations produced by the ratifications of the block for the given `block hash`.
+    pub fn get_ratified_finalize_operations(
+        &self,
+        block_hash: &N::BlockHash,
+    ) -> Result<Option<Vec<FinalizeOperation<N>>>> {
+        self.storage.get_ratified_finalize_operations(block_hash)
+    }
+
     /// Returns the program for the given `program ID`.
     pub fn get_program(&self, program_id: &ProgramID<N>) -> Result<Option<Program<N>>> {
         self.storage.transaction_store().get_program(program_id)
@@ -1302,6 +2008,11 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
 }
 
 impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
+    /// Returns `true` if the transactions of the block for the given `block hash` have been pruned.
+    pub fn is_pruned(&self, block_hash: &N::BlockHash) -> Result<bool> {
+        self.storage.is_pruned(block_hash)
+    }
+
     /// Returns `true` if the given state root exists.
     pub fn contains_state_root(&self, state_root: &N::StateRoot) -> Result<bool> {
         self.storage.reverse_state_root_map().contains_key_confirmed(state_root)
@@ -1408,6 +2119,94 @@ mod tests {
         assert_eq!(None, candidate);
     }
 
+    #[test]
+    fn test_check_integrity() {
+        let rng = &mut TestRng::default();
+
+        // Sample the block.
+        let block = ledger_test_helpers::sample_genesis_block(rng);
+        let block_hash = block.hash();
+        let transaction_id = *block.transaction_ids().next().unwrap();
+
+        // Initialize a new block store.
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        // Insert the block.
+        block_store.insert(&block).unwrap();
+        // Ensure the block store is consistent.
+        assert_eq!(block_store.check_integrity().unwrap(), vec![]);
+
+        // Remove the reverse block ID and the confirmed transaction.
+        block_store.storage.reverse_id_map().remove(&block_hash).unwrap();
+        block_store.storage.confirmed_transactions_map().remove(&transaction_id).unwrap();
+        // Overwrite the state root.
+        let state_root = <CurrentNetwork as Network>::StateRoot::from(Field::zero());
+        block_store.storage.state_root_map().insert(0, state_root).unwrap();
+
+        // Ensure the inconsistencies are found.
+        let issues = block_store.check_integrity().unwrap();
+        assert!(issues.contains(&IntegrityIssue::missing("reverse block ID", block_hash).at_height(0)));
+        assert!(issues.contains(&IntegrityIssue::missing("confirmed transaction", transaction_id).at_height(0)));
+        assert!(issues.contains(&IntegrityIssue::dangling("transaction ID", transaction_id)));
+        assert!(issues.contains(&IntegrityIssue::dangling("block header", block_hash)));
+        assert!(issues.contains(&IntegrityIssue::mismatch("state root", 0).at_height(0)));
+    }
+
+    #[test]
+    fn test_transition_index() {
+        let rng = &mut TestRng::default();
+
+        // Sample the block.
+        let block = ledger_test_helpers::sample_genesis_block(rng);
+        let transition = block.transitions().next().unwrap();
+        let (program_id, function_name) = (*transition.program_id(), *transition.function_name());
+
+        // Initialize a new block store.
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        // Ensure the transition index is not enabled.
+        assert_eq!(block_store.transition_index_start_height().unwrap(), None);
+        assert!(block_store.find_transitions_from_function(&program_id, &function_name, 0, 10).is_err());
+
+        // Enable the transition index, and insert the block.
+        block_store.enable_transition_index(0).unwrap();
+        assert_eq!(block_store.transition_index_start_height().unwrap(), Some(0));
+        block_store.insert(&block).unwrap();
+
+        // Ensure the transitions are indexed by function, in order.
+        let expected = block
+            .transitions()
+            .filter(|t| *t.program_id() == program_id && *t.function_name() == function_name)
+            .map(|t| (0, *t.id()))
+            .collect::<Vec<_>>();
+        let candidate = block_store.find_transitions_from_function(&program_id, &function_name, 0, usize::MAX).unwrap();
+        assert_eq!(candidate, expected);
+        // Ensure the results are paginated.
+        let candidate = block_store.find_transitions_from_function(&program_id, &function_name, 1, 1).unwrap();
+        assert_eq!(candidate, expected.iter().skip(1).take(1).copied().collect::<Vec<_>>());
+        // Ensure the transitions are indexed by address.
+        for transition in block.transitions() {
+            for address in to_transition_addresses(transition) {
+                let candidate = block_store.find_transitions_from_address(&address, 0, usize::MAX).unwrap();
+                assert!(candidate.contains(&(0, *transition.id())));
+            }
+        }
+
+        // Remove the block, and ensure its transitions are no longer indexed.
+        block_store.remove_last_n(1).unwrap();
+        assert!(block_store.find_transitions_from_function(&program_id, &function_name, 0, 10).unwrap().is_empty());
+
+        // Disable the transition index.
+        block_store.disable_transition_index().unwrap();
+        assert_eq!(block_store.transition_index_start_height().unwrap(), None);
+
+        // Ensure the transition index can not be enabled from an existing block, as it would not be indexed.
+        block_store.insert(&block).unwrap();
+        assert!(block_store.enable_transition_index(0).is_err());
+        assert_eq!(block_store.transition_index_start_height().unwrap(), None);
+        // Ensure the transition index can be enabled from the next block.
+        block_store.enable_transition_index(1).unwrap();
+        assert_eq!(block_store.transition_index_start_height().unwrap(), Some(1));
+    }
+
     #[test]
     fn test_find_block_hash() {
         let rng = &mut TestRng::default();
@@ -1508,4 +2307,61 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_prune() {
+        let rng = &mut TestRng::default();
+
+        // Sample the block.
+        let block = ledger_test_helpers::sample_genesis_block(rng);
+        let block_hash = block.hash();
+        assert!(block.transactions().num_accepted() > 0, "This test must be run with at least one transaction.");
+
+        // Initialize a new block store.
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        // Ensure the pruning depth must be non-zero.
+        assert!(block_store.set_pruning_depth(Some(0)).is_err());
+        // Ensure the pruning depth is stored.
+        block_store.set_pruning_depth(Some(1)).unwrap();
+        assert_eq!(
+            block_store.storage.pruning_depth_map().get_confirmed(&PRUNING_DEPTH_KEY).unwrap().as_deref(),
+            Some(&1)
+        );
+        assert_eq!(block_store.pruning_depth().unwrap(), Some(1));
+        // Ensure the genesis block is not pruned.
+        block_store.insert(&block).unwrap();
+        assert!(!block_store.is_pruned(&block_hash).unwrap());
+        assert_eq!(Some(block.clone()), block_store.get_block(&block_hash).unwrap());
+
+        // Prune the block.
+        block_store.storage.prune(&block_hash).unwrap();
+        assert!(block_store.is_pruned(&block_hash).unwrap());
+
+        // Ensure the block header and transaction IDs are retained.
+        assert_eq!(Some(*block.header()), block_store.get_block_header(&block_hash).unwrap());
+        for transaction_id in block.transaction_ids() {
+            assert_eq!(Some(block_hash), block_store.find_block_hash(transaction_id).unwrap());
+            // Ensure the transaction is pruned.
+            assert!(block_store.get_transaction(transaction_id).unwrap_err().to_string().contains("pruned"));
+        }
+        // Ensure the serial numbers, tags and commitments are retained.
+        for serial_number in block.serial_numbers() {
+            assert!(block_store.transition_store().contains_serial_number(serial_number).unwrap());
+        }
+        for tag in block.tags() {
+            assert!(block_store.transition_store().contains_tag(tag).unwrap());
+        }
+        for commitment in block.commitments() {
+            assert!(block_store.transition_store().contains_commitment(commitment).unwrap());
+            // Ensure the state path is unavailable.
+            assert!(block_store.get_state_path_for_commitment(commitment).unwrap_err().to_string().contains("pruned"));
+        }
+        // Ensure the record ciphertexts are pruned.
+        assert_eq!(block_store.transition_store().records().count(), 0);
+
+        // Ensure the block is pruned.
+        assert!(block_store.get_block(&block_hash).unwrap_err().to_string().contains("pruned"));
+        // Ensure a pruned block cannot be removed.
+        assert!(block_store.remove_last_n(1).is_err());
+    }
 }
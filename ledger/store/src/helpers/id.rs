@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-/// The RocksDB map prefix broken down into the entry category and the specific type of the entry.
+/// The storage map prefix broken down into the entry category and the specific type of the entry.
 // Note: the order of these variants can be changed at any point in time.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u16)]
@@ -52,7 +52,7 @@ impl From<MapID> for u16 {
     }
 }
 
-/// The RocksDB map prefix for BFT-related entries.
+/// The storage map prefix for BFT-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -62,7 +62,7 @@ pub enum BFTMap {
     AbortedTransmissionIDs = DataID::BFTAbortedTransmissionIDsMap as u16,
 }
 
-/// The RocksDB map prefix for block-related entries.
+/// The storage map prefix for block-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -85,9 +85,15 @@ pub enum BlockMap {
     RejectedOrAbortedTransactionID = DataID::BlockRejectedOrAbortedTransactionIDMap as u16,
     ConfirmedTransactions = DataID::BlockConfirmedTransactionsMap as u16,
     RejectedDeploymentOrExecution = DataID::BlockRejectedDeploymentOrExecutionMap as u16,
+    Pruned = DataID::BlockPrunedMap as u16,
+    RatifiedFinalizeOperations = DataID::BlockRatifiedFinalizeOperationsMap as u16,
+    TransitionIndexStart = DataID::BlockTransitionIndexStartMap as u16,
+    FunctionIndex = DataID::BlockFunctionIndexMap as u16,
+    AddressIndex = DataID::BlockAddressIndexMap as u16,
+    PruningDepth = DataID::BlockPruningDepthMap as u16,
 }
 
-/// The RocksDB map prefix for committee-related entries.
+/// The storage map prefix for committee-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -98,7 +104,7 @@ pub enum CommitteeMap {
     Committee = DataID::CommitteeMap as u16,
 }
 
-/// The RocksDB map prefix for deployment-related entries.
+/// The storage map prefix for deployment-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -111,9 +117,10 @@ pub enum DeploymentMap {
     Program = DataID::DeploymentProgramMap as u16,
     VerifyingKey = DataID::DeploymentVerifyingKeyMap as u16,
     Certificate = DataID::DeploymentCertificateMap as u16,
+    UpgradePolicy = DataID::DeploymentUpgradePolicyMap as u16,
 }
 
-/// The RocksDB map prefix for execution-related entries.
+/// The storage map prefix for execution-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -124,7 +131,7 @@ pub enum ExecutionMap {
     Inclusion = DataID::ExecutionInclusionMap as u16,
 }
 
-/// The RocksDB map prefix for fee-related entries.
+/// The storage map prefix for fee-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -134,7 +141,7 @@ pub enum FeeMap {
     ReverseFee = DataID::FeeReverseFeeMap as u16,
 }
 
-/// The RocksDB map prefix for transition input entries.
+/// The storage map prefix for transition input entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -150,7 +157,7 @@ pub enum TransitionInputMap {
     ExternalRecord = DataID::InputExternalRecordMap as u16,
 }
 
-/// The RocksDB map prefix for transition output entries.
+/// The storage map prefix for transition output entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -167,7 +174,7 @@ pub enum TransitionOutputMap {
     Future = DataID::OutputFutureMap as u16,
 }
 
-/// The RocksDB map prefix for transaction-related entries.
+/// The storage map prefix for transaction-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -176,7 +183,7 @@ pub enum TransactionMap {
     ID = DataID::TransactionIDMap as u16,
 }
 
-/// The RocksDB map prefix for transition-related entries.
+/// The storage map prefix for transition-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -190,7 +197,7 @@ pub enum TransitionMap {
     SCM = DataID::TransitionSCMMap as u16,
 }
 
-/// The RocksDB map prefix for program-related entries.
+/// The storage map prefix for program-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -198,9 +205,14 @@ pub enum TransitionMap {
 pub enum ProgramMap {
     ProgramID = DataID::ProgramIDMap as u16,
     KeyValueID = DataID::KeyValueMap as u16,
+    History = DataID::ProgramHistoryMap as u16,
+    HistoryStart = DataID::ProgramHistoryStartMap as u16,
+    KeyPosition = DataID::ProgramKeyPositionMap as u16,
+    PositionKey = DataID::ProgramPositionKeyMap as u16,
+    MappingLength = DataID::ProgramMappingLengthMap as u16,
 }
 
-/// The RocksDB map prefix for test-related entries.
+/// The storage map prefix for test-related entries.
 // Note: the order of these variants can be changed at any point in time.
 #[cfg(test)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -213,7 +225,7 @@ pub enum TestMap {
     Test5 = DataID::Test5 as u16,
 }
 
-/// The RocksDB map prefix.
+/// The storage map prefix.
 // Note: the order of these variants can NOT be changed once the database is populated:
 // - any new variant MUST be added as the last one (ignoring the Test one)
 // - any deprecated variant MUST remain in its position (it can't be removed)
@@ -292,6 +304,24 @@ enum DataID {
     // Program
     ProgramIDMap,
     KeyValueMap,
+    // Deployment (continued)
+    DeploymentUpgradePolicyMap,
+    // Block (continued)
+    BlockPrunedMap,
+    BlockRatifiedFinalizeOperationsMap,
+    // Program (continued)
+    ProgramHistoryMap,
+    ProgramHistoryStartMap,
+    // Block (continued)
+    BlockTransitionIndexStartMap,
+    BlockFunctionIndexMap,
+    BlockAddressIndexMap,
+    // Program (continued)
+    ProgramKeyPositionMap,
+    ProgramPositionKeyMap,
+    ProgramMappingLengthMap,
+    // Block (continued)
+    BlockPruningDepthMap,
 
     // Testing
     #[cfg(test)]
@@ -0,0 +1,272 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    helpers::kv::{BlockMap, KVDatabase, KVMap, KeyValueEngine, MapID, NestedKVMap, TransactionKV, TransitionKV},
+    BlockStorage,
+    ConfirmedTxType,
+    TransactionStore,
+    TransitionIndexKey,
+    TransitionStore,
+};
+use console::{
+    prelude::*,
+    program::{Identifier, ProgramID},
+    types::{Address, Field},
+};
+use ledger_authority::Authority;
+use ledger_block::{Header, Ratifications, Rejected, Solutions};
+use ledger_puzzle::SolutionID;
+use synthesizer_program::FinalizeOperation;
+
+use aleo_std_storage::StorageMode;
+
+/// A key-value block storage.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct BlockKV<N: Network, E: KeyValueEngine> {
+    /// The mapping of `block height` to `state root`.
+    state_root_map: KVMap<u32, N::StateRoot, E>,
+    /// The mapping of `state root` to `block height`.
+    reverse_state_root_map: KVMap<N::StateRoot, u32, E>,
+    /// The mapping of `block height` to `block hash`.
+    id_map: KVMap<u32, N::BlockHash, E>,
+    /// The mapping of `block hash` to `block height`.
+    reverse_id_map: KVMap<N::BlockHash, u32, E>,
+    /// The header map.
+    header_map: KVMap<N::BlockHash, Header<N>, E>,
+    /// The authority map.
+    authority_map: KVMap<N::BlockHash, Authority<N>, E>,
+    /// The certificate map.
+    certificate_map: KVMap<Field<N>, (u32, u64), E>,
+    /// The ratifications map.
+    ratifications_map: KVMap<N::BlockHash, Ratifications<N>, E>,
+    /// The solutions map.
+    solutions_map: KVMap<N::BlockHash, Solutions<N>, E>,
+    /// The solution IDs map.
+    solution_ids_map: KVMap<SolutionID<N>, u32, E>,
+    /// The aborted solution IDs map.
+    aborted_solution_ids_map: KVMap<N::BlockHash, Vec<SolutionID<N>>, E>,
+    /// The aborted solution heights map.
+    aborted_solution_heights_map: KVMap<SolutionID<N>, u32, E>,
+    /// The transactions map.
+    transactions_map: KVMap<N::BlockHash, Vec<N::TransactionID>, E>,
+    /// The aborted transaction IDs map.
+    aborted_transaction_ids_map: KVMap<N::BlockHash, Vec<N::TransactionID>, E>,
+    /// The rejected or aborted transaction ID map.
+    rejected_or_aborted_transaction_id_map: KVMap<N::TransactionID, N::BlockHash, E>,
+    /// The confirmed transactions map.
+    confirmed_transactions_map:
+        KVMap<N::TransactionID, (N::BlockHash, ConfirmedTxType<N>, Vec<FinalizeOperation<N>>), E>,
+    /// The rejected deployment or execution map.
+    rejected_deployment_or_execution_map: KVMap<Field<N>, Rejected<N>, E>,
+    /// The pruned map.
+    pruned_map: KVMap<N::BlockHash, u32, E>,
+    /// The ratified finalize operations map.
+    ratified_finalize_operations_map: KVMap<N::BlockHash, Vec<FinalizeOperation<N>>, E>,
+    /// The transition index start map.
+    transition_index_start_map: KVMap<u8, u32, E>,
+    /// The function index map.
+    function_index_map: NestedKVMap<(ProgramID<N>, Identifier<N>), TransitionIndexKey, N::TransitionID, E>,
+    /// The address index map.
+    address_index_map: NestedKVMap<Address<N>, TransitionIndexKey, N::TransitionID, E>,
+    /// The pruning depth map.
+    pruning_depth_map: KVMap<u8, u32, E>,
+    /// The transaction store.
+    transaction_store: TransactionStore<N, TransactionKV<N, E>>,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> BlockStorage<N> for BlockKV<N, E> {
+    type StateRootMap = KVMap<u32, N::StateRoot, E>;
+    type ReverseStateRootMap = KVMap<N::StateRoot, u32, E>;
+    type IDMap = KVMap<u32, N::BlockHash, E>;
+    type ReverseIDMap = KVMap<N::BlockHash, u32, E>;
+    type HeaderMap = KVMap<N::BlockHash, Header<N>, E>;
+    type AuthorityMap = KVMap<N::BlockHash, Authority<N>, E>;
+    type CertificateMap = KVMap<Field<N>, (u32, u64), E>;
+    type RatificationsMap = KVMap<N::BlockHash, Ratifications<N>, E>;
+    type SolutionsMap = KVMap<N::BlockHash, Solutions<N>, E>;
+    type SolutionIDsMap = KVMap<SolutionID<N>, u32, E>;
+    type AbortedSolutionIDsMap = KVMap<N::BlockHash, Vec<SolutionID<N>>, E>;
+    type AbortedSolutionHeightsMap = KVMap<SolutionID<N>, u32, E>;
+    type TransactionsMap = KVMap<N::BlockHash, Vec<N::TransactionID>, E>;
+    type AbortedTransactionIDsMap = KVMap<N::BlockHash, Vec<N::TransactionID>, E>;
+    type RejectedOrAbortedTransactionIDMap = KVMap<N::TransactionID, N::BlockHash, E>;
+    type ConfirmedTransactionsMap = KVMap<N::TransactionID, (N::BlockHash, ConfirmedTxType<N>, Vec<FinalizeOperation<N>>), E>;
+    type RejectedDeploymentOrExecutionMap = KVMap<Field<N>, Rejected<N>, E>;
+    type PrunedMap = KVMap<N::BlockHash, u32, E>;
+    type RatifiedFinalizeOperationsMap = KVMap<N::BlockHash, Vec<FinalizeOperation<N>>, E>;
+    type TransitionIndexStartMap = KVMap<u8, u32, E>;
+    type FunctionIndexMap = NestedKVMap<(ProgramID<N>, Identifier<N>), TransitionIndexKey, N::TransitionID, E>;
+    type AddressIndexMap = NestedKVMap<Address<N>, TransitionIndexKey, N::TransitionID, E>;
+    type PruningDepthMap = KVMap<u8, u32, E>;
+    type TransactionStorage = TransactionKV<N, E>;
+    type TransitionStorage = TransitionKV<N, E>;
+
+    /// Initializes the block storage.
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        // Initialize the transition store.
+        let transition_store = TransitionStore::<N, TransitionKV<N, E>>::open(storage.clone())?;
+        // Initialize the transaction store.
+        let transaction_store = TransactionStore::<N, TransactionKV<N, E>>::open(transition_store)?;
+        // Return the block storage.
+        Ok(Self {
+            state_root_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::StateRoot))?,
+            reverse_state_root_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::ReverseStateRoot))?,
+            id_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::ID))?,
+            reverse_id_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::ReverseID))?,
+            header_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::Header))?,
+            authority_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::Authority))?,
+            certificate_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::Certificate))?,
+            ratifications_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::Ratifications))?,
+            solutions_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::Solutions))?,
+            solution_ids_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::PuzzleCommitments))?,
+            aborted_solution_ids_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::AbortedSolutionIDs))?,
+            aborted_solution_heights_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::AbortedSolutionHeights))?,
+            transactions_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::Transactions))?,
+            aborted_transaction_ids_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::AbortedTransactionIDs))?,
+            rejected_or_aborted_transaction_id_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::RejectedOrAbortedTransactionID))?,
+            confirmed_transactions_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::ConfirmedTransactions))?,
+            rejected_deployment_or_execution_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::RejectedDeploymentOrExecution))?,
+            pruned_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::Pruned))?,
+            ratified_finalize_operations_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::RatifiedFinalizeOperations))?,
+            transition_index_start_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::TransitionIndexStart))?,
+            function_index_map: KVDatabase::<E>::open_nested_map(N::ID, storage.clone(), MapID::Block(BlockMap::FunctionIndex))?,
+            address_index_map: KVDatabase::<E>::open_nested_map(N::ID, storage, MapID::Block(BlockMap::AddressIndex))?,
+            pruning_depth_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::PruningDepth))?,
+            transaction_store,
+        })
+    }
+
+    /// Returns the state root map.
+    fn state_root_map(&self) -> &Self::StateRootMap {
+        &self.state_root_map
+    }
+
+    /// Returns the reverse state root map.
+    fn reverse_state_root_map(&self) -> &Self::ReverseStateRootMap {
+        &self.reverse_state_root_map
+    }
+
+    /// Returns the ID map.
+    fn id_map(&self) -> &Self::IDMap {
+        &self.id_map
+    }
+
+    /// Returns the reverse ID map.
+    fn reverse_id_map(&self) -> &Self::ReverseIDMap {
+        &self.reverse_id_map
+    }
+
+    /// Returns the header map.
+    fn header_map(&self) -> &Self::HeaderMap {
+        &self.header_map
+    }
+
+    /// Returns the authority map.
+    fn authority_map(&self) -> &Self::AuthorityMap {
+        &self.authority_map
+    }
+
+    /// Returns the certificate map.
+    fn certificate_map(&self) -> &Self::CertificateMap {
+        &self.certificate_map
+    }
+
+    /// Returns the ratifications map.
+    fn ratifications_map(&self) -> &Self::RatificationsMap {
+        &self.ratifications_map
+    }
+
+    /// Returns the solutions map.
+    fn solutions_map(&self) -> &Self::SolutionsMap {
+        &self.solutions_map
+    }
+
+    /// Returns the solution IDs map.
+    fn solution_ids_map(&self) -> &Self::SolutionIDsMap {
+        &self.solution_ids_map
+    }
+
+    /// Returns the aborted solution IDs map.
+    fn aborted_solution_ids_map(&self) -> &Self::AbortedSolutionIDsMap {
+        &self.aborted_solution_ids_map
+    }
+
+    /// Returns the aborted solution heights map.
+    fn aborted_solution_heights_map(&self) -> &Self::AbortedSolutionHeightsMap {
+        &self.aborted_solution_heights_map
+    }
+
+    /// Returns the transactions map.
+    fn transactions_map(&self) -> &Self::TransactionsMap {
+        &self.transactions_map
+    }
+
+    /// Returns the aborted transaction IDs map.
+    fn aborted_transaction_ids_map(&self) -> &Self::AbortedTransactionIDsMap {
+        &self.aborted_transaction_ids_map
+    }
+
+    /// Returns the rejected transaction ID or aborted transaction ID map.
+    fn rejected_or_aborted_transaction_id_map(&self) -> &Self::RejectedOrAbortedTransactionIDMap {
+        &self.rejected_or_aborted_transaction_id_map
+    }
+
+    /// Returns the confirmed transactions map.
+    fn confirmed_transactions_map(&self) -> &Self::ConfirmedTransactionsMap {
+        &self.confirmed_transactions_map
+    }
+
+    /// Returns the rejected deployment or execution map.
+    fn rejected_deployment_or_execution_map(&self) -> &Self::RejectedDeploymentOrExecutionMap {
+        &self.rejected_deployment_or_execution_map
+    }
+
+    /// Returns the pruned map.
+    fn pruned_map(&self) -> &Self::PrunedMap {
+        &self.pruned_map
+    }
+
+    /// Returns the ratified finalize operations map.
+    fn ratified_finalize_operations_map(&self) -> &Self::RatifiedFinalizeOperationsMap {
+        &self.ratified_finalize_operations_map
+    }
+
+    /// Returns the transition index start map.
+    fn transition_index_start_map(&self) -> &Self::TransitionIndexStartMap {
+        &self.transition_index_start_map
+    }
+
+    /// Returns the function index map.
+    fn function_index_map(&self) -> &Self::FunctionIndexMap {
+        &self.function_index_map
+    }
+
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap {
+        &self.address_index_map
+    }
+
+    /// Returns the pruning depth map.
+    fn pruning_depth_map(&self) -> &Self::PruningDepthMap {
+        &self.pruning_depth_map
+    }
+
+    /// Returns the transaction store.
+    fn transaction_store(&self) -> &TransactionStore<N, Self::TransactionStorage> {
+        &self.transaction_store
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use parking_lot::RwLock;
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, ErrorKind, Read, Seek, SeekFrom, Write},
+    ops::Bound,
+};
+
+/// The name of the log file in the directory of a `LogEngine`.
+const LOG_FILE_NAME: &str = "ledger.log";
+/// The length of the header of a record in the log, i.e. the length and checksum of its batch,
+/// followed by the checksum of the header.
+const RECORD_HEADER_LEN: u64 = 24;
+
+/// An embedded key-value engine, written in pure Rust, which appends every atomic write batch to a log file.
+///
+/// The entries are indexed in memory, so this engine is intended for small databases (e.g. for testing);
+/// `RedbEngine` stores the entries on disk. The log is replayed when the engine is opened, and it is only
+/// compacted if it is more than twice the size of the entries it contains.
+///
+/// Each record in the log is `(length, checksum, header checksum, batch)`, and it is synced to disk before
+/// the batch is applied. A record that was only partially written (i.e. if the process stopped while appending it)
+/// is truncated when the log is replayed, which is only the case for a partial header, or for a valid header
+/// whose batch extends beyond the end of the file. Any other corrupt record is reported as an error.
+#[derive(Clone)]
+pub struct LogEngine {
+    /// The ordered index of the entries.
+    entries: Arc<RwLock<BTreeMap<Vec<u8>, Vec<u8>>>>,
+    /// The log file, and its length, which ends at the last record that was fully written.
+    log: Arc<Mutex<(File, u64)>>,
+}
+
+impl KeyValueEngine for LogEngine {
+    /// Opens the engine in the given directory, creating it if it does not exist.
+    fn open(path: &Path) -> Result<Self> {
+        // Ensure the directory exists.
+        fs::create_dir_all(path)?;
+        let log_path = path.join(LOG_FILE_NAME);
+
+        // Replay the log, if it exists.
+        let mut entries = BTreeMap::new();
+        let mut log_len = 0;
+        match File::open(&log_path) {
+            Ok(file) => {
+                let file_len = file.metadata()?.len();
+                let mut reader = BufReader::new(file);
+                while log_len < file_len {
+                    match read_record(&mut reader, file_len - log_len)? {
+                        Some((batch, record_len)) => {
+                            apply_batch(&mut entries, batch);
+                            log_len += record_len;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => (),
+            Err(e) => return Err(e.into()),
+        }
+
+        // Compact the log if it is more than twice the size of its entries, by writing the entries
+        // as a single record to a new log, which then replaces the old one.
+        let compacted_batch = entries.iter().map(|(key, value)| (key.clone(), Some(value.clone()))).collect();
+        let compacted_record = match entries.is_empty() {
+            true => vec![],
+            false => to_record(&compacted_batch)?,
+        };
+        if log_len > 2 * compacted_record.len() as u64 {
+            let compacted_path = path.join(format!("{LOG_FILE_NAME}.compacted"));
+            let mut file = File::create(&compacted_path)?;
+            file.write_all(&compacted_record)?;
+            file.sync_all()?;
+            fs::rename(&compacted_path, &log_path)?;
+            log_len = compacted_record.len() as u64;
+        }
+
+        // Open the log for writing, truncating any partially written record.
+        let mut log = OpenOptions::new().write(true).create(true).truncate(false).open(&log_path)?;
+        log.set_len(log_len)?;
+        log.seek(SeekFrom::Start(log_len))?;
+        log.sync_all()?;
+
+        Ok(Self { entries: Arc::new(RwLock::new(entries)), log: Arc::new(Mutex::new((log, log_len))) })
+    }
+
+    /// Returns the value for the given key, if it exists.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.read().get(key).cloned())
+    }
+
+    /// Inserts the given key-value pair.
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.write_batch(vec![(key.to_vec(), Some(value.to_vec()))])
+    }
+
+    /// Removes the key-value pair for the given key.
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        self.write_batch(vec![(key.to_vec(), None)])
+    }
+
+    /// Returns the key-value pairs whose key starts with the given prefix, in ascending order of their keys.
+    ///
+    /// Note: The entries are only locked while each pair is read, so that a scan does not block the writes.
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<PrefixIterator> {
+        let entries = self.entries.clone();
+        let prefix = prefix.to_vec();
+        // The bound from which the next pair is read, i.e. after the key of the previous pair.
+        let mut lower_bound = Bound::Included(prefix.clone());
+        Ok(Box::new(std::iter::from_fn(move || {
+            let entries = entries.read();
+            let (key, value) = entries.range((lower_bound.clone(), Bound::Unbounded)).next()?;
+            if !key.starts_with(&prefix) {
+                return None;
+            }
+            lower_bound = Bound::Excluded(key.clone());
+            Some(Ok((key.clone(), value.clone())))
+        })))
+    }
+
+    /// Performs the given writes atomically, in order.
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let record = to_record(&batch)?;
+        // Acquire the lock on the log first, so that the batches are applied in the order they are logged.
+        let mut log = self.log.lock();
+        let (file, log_len) = &mut *log;
+        // Append the record to the log, and sync it to disk.
+        if let Err(error) = file.write_all(&record).and_then(|_| file.sync_data()) {
+            // Remove the partially written record, so that the next record follows the last complete one.
+            file.set_len(*log_len)?;
+            file.seek(SeekFrom::Start(*log_len))?;
+            return Err(error.into());
+        }
+        *log_len += record.len() as u64;
+        // Apply the batch to the entries.
+        apply_batch(&mut self.entries.write(), batch);
+        Ok(())
+    }
+}
+
+/// Applies the given batch to the given entries.
+fn apply_batch(entries: &mut BTreeMap<Vec<u8>, Vec<u8>>, batch: WriteBatch) {
+    for (key, value) in batch {
+        match value {
+            Some(value) => entries.insert(key, value),
+            None => entries.remove(&key),
+        };
+    }
+}
+
+/// Returns the checksum of the given bytes, which is their 64-bit FNV-1a hash.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Returns the given batch as a record.
+fn to_record(batch: &WriteBatch) -> Result<Vec<u8>> {
+    let bytes = bincode::serialize(batch)?;
+    let mut record = Vec::with_capacity(RECORD_HEADER_LEN as usize + bytes.len());
+    record.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    record.extend_from_slice(&checksum(&bytes).to_le_bytes());
+    record.extend_from_slice(&checksum(&record).to_le_bytes());
+    record.extend_from_slice(&bytes);
+    Ok(record)
+}
+
+/// Reads the next record from the given reader, which has the given number of bytes remaining
+/// until the end of the log file, and returns its batch and length.
+///
+/// Returns `None` if the remainder of the log is a partially written record, i.e. a partial header,
+/// or a valid header whose batch extends beyond the end of the file. Returns an error if the record is corrupt.
+fn read_record<R: Read>(mut reader: R, remaining_len: u64) -> Result<Option<(WriteBatch, u64)>> {
+    // Ensure the header of the record was fully written.
+    if remaining_len < RECORD_HEADER_LEN {
+        return Ok(None);
+    }
+    // Read the header of the record.
+    let mut header = [0u8; RECORD_HEADER_LEN as usize];
+    reader.read_exact(&mut header)?;
+    let (fields, header_checksum) = header.split_at(16);
+    // Ensure the checksum of the header is valid, so that the length of the record can be trusted.
+    ensure!(
+        checksum(fields) == u64::from_le_bytes(header_checksum.try_into()?),
+        "The log contains a record with a corrupt header"
+    );
+    let (length, expected_checksum) = fields.split_at(8);
+    let length = u64::from_le_bytes(length.try_into()?);
+    let expected_checksum = u64::from_le_bytes(expected_checksum.try_into()?);
+
+    // Ensure the batch of the record was fully written, i.e. it does not extend beyond the end of the file.
+    // Note: This also ensures the bytes are not overallocated.
+    let record_len = match RECORD_HEADER_LEN.checked_add(length) {
+        Some(record_len) if record_len <= remaining_len => record_len,
+        Some(_) => return Ok(None),
+        None => bail!("The log contains a record with an invalid length"),
+    };
+    // Read the batch of the record.
+    let mut bytes = vec![0u8; length as usize];
+    reader.read_exact(&mut bytes)?;
+    // Ensure the checksum of the batch is valid.
+    ensure!(checksum(&bytes) == expected_checksum, "The log contains a record with a corrupt batch");
+    Ok(Some((bincode::deserialize(&bytes)?, record_len)))
+}
@@ -0,0 +1,395 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(clippy::type_complexity)]
+
+use super::*;
+use crate::helpers::{Map, MapRead};
+
+use core::{fmt, fmt::Debug, hash::Hash};
+use indexmap::IndexMap;
+use std::{borrow::Cow, ops::Deref};
+
+pub struct KVMap<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, E: KeyValueEngine>(
+    Arc<InnerKVMap<K, V, E>>,
+);
+
+impl<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, E: KeyValueEngine> Clone for KVMap<K, V, E> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, E: KeyValueEngine> Deref for KVMap<K, V, E> {
+    type Target = InnerKVMap<K, V, E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub struct InnerKVMap<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, E: KeyValueEngine> {
+    database: KVDatabase<E>,
+    context: Vec<u8>,
+    /// The tracker for whether a database transaction is in progress.
+    batch_in_progress: AtomicBool,
+    /// The database transaction.
+    atomic_batch: Mutex<Vec<(K, Option<V>)>>,
+    /// The checkpoint stack for the batched operations within the map.
+    checkpoints: Mutex<Vec<usize>>,
+}
+
+impl<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, E: KeyValueEngine> KVMap<K, V, E> {
+    /// Initializes a new map with the given `map_id` in the given database.
+    pub(super) fn new(database: KVDatabase<E>, map_id: u16) -> Self {
+        let context = database.map_context(map_id);
+        Self(Arc::new(InnerKVMap {
+            database,
+            context,
+            batch_in_progress: Default::default(),
+            atomic_batch: Default::default(),
+            checkpoints: Default::default(),
+        }))
+    }
+
+    #[inline]
+    fn create_prefixed_key<Q>(&self, key: &Q) -> Result<Vec<u8>>
+    where
+        K: Borrow<Q>,
+        Q: Serialize + ?Sized,
+    {
+        let mut raw_key = self.context.clone();
+        bincode::serialize_into(&mut raw_key, &key)?;
+        Ok(raw_key)
+    }
+
+    fn get_raw<Q>(&self, key: &Q) -> Result<Option<Vec<u8>>>
+    where
+        K: Borrow<Q>,
+        Q: Serialize + ?Sized,
+    {
+        self.database.engine.get(&self.create_prefixed_key(key)?)
+    }
+
+    /// Returns the deserialized entries of the map, stopping at the first entry that fails to deserialize.
+    fn entries<'a>(&self) -> impl Iterator<Item = (Cow<'a, K>, Cow<'a, V>)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.database.prefix_scan_or_log(&self.context).map_while(|(key, value)| {
+            let key = bincode::deserialize(&key[PREFIX_LEN..])
+                .map_err(|e| tracing::error!("KVMap deserialize(key) error: {e}"))
+                .ok()?;
+            let value = bincode::deserialize(&value)
+                .map_err(|e| tracing::error!("KVMap deserialize(value) error: {e}"))
+                .ok()?;
+            Some((Cow::Owned(key), Cow::Owned(value)))
+        })
+    }
+}
+
+impl<
+    'a,
+    K: 'a + Copy + Clone + Debug + PartialEq + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    V: 'a + Clone + PartialEq + Eq + Serialize + DeserializeOwned + Send + Sync,
+    E: KeyValueEngine,
+> Map<'a, K, V> for KVMap<K, V, E>
+{
+    ///
+    /// Inserts the given key-value pair into the map.
+    ///
+    fn insert(&self, key: K, value: V) -> Result<()> {
+        // Determine if an atomic batch is in progress.
+        match self.is_atomic_in_progress() {
+            // If a batch is in progress, add the key-value pair to the batch.
+            true => self.atomic_batch.lock().push((key, Some(value))),
+            // Otherwise, insert the key-value pair directly into the map.
+            false => self.database.engine.put(&self.create_prefixed_key(&key)?, &bincode::serialize(&value)?)?,
+        }
+        Ok(())
+    }
+
+    ///
+    /// Removes the key-value pair for the given key from the map.
+    ///
+    fn remove(&self, key: &K) -> Result<()> {
+        // Determine if an atomic batch is in progress.
+        match self.is_atomic_in_progress() {
+            // If a batch is in progress, add the key to the batch.
+            true => self.atomic_batch.lock().push((*key, None)),
+            // Otherwise, remove the key-value pair directly from the map.
+            false => self.database.engine.delete(&self.create_prefixed_key(key)?)?,
+        }
+        Ok(())
+    }
+
+    ///
+    /// Begins an atomic operation. Any further calls to `insert` and `remove` will be queued
+    /// without an actual write taking place until `finish_atomic` is called.
+    ///
+    fn start_atomic(&self) {
+        // Set the atomic batch flag to `true`.
+        self.batch_in_progress.store(true, Ordering::SeqCst);
+        // Increment the atomic depth index.
+        self.database.atomic_depth.fetch_add(1, Ordering::SeqCst);
+
+        // Ensure that the atomic batch is empty.
+        assert!(self.atomic_batch.lock().is_empty());
+        // Ensure that the database atomic batch is empty; skip this check if the atomic
+        // writes are paused, as there may be pending operations.
+        if !self.database.are_atomic_writes_paused() {
+            assert!(self.database.atomic_batch.lock().is_empty());
+        }
+    }
+
+    ///
+    /// Checks whether an atomic operation is currently in progress. This can be done to ensure
+    /// that lower-level operations don't start and finish their individual atomic write batch
+    /// if they are already part of a larger one.
+    ///
+    fn is_atomic_in_progress(&self) -> bool {
+        self.batch_in_progress.load(Ordering::SeqCst)
+    }
+
+    ///
+    /// Saves the current list of pending operations, so that if `atomic_rewind` is called,
+    /// we roll back all future operations, and return to the start of this checkpoint.
+    ///
+    fn atomic_checkpoint(&self) {
+        // Push the current length of the atomic batch to the checkpoint stack.
+        self.checkpoints.lock().push(self.atomic_batch.lock().len());
+    }
+
+    ///
+    /// Removes the latest atomic checkpoint.
+    ///
+    fn clear_latest_checkpoint(&self) {
+        // Removes the latest checkpoint.
+        let _ = self.checkpoints.lock().pop();
+    }
+
+    ///
+    /// Removes all pending operations to the last `atomic_checkpoint`
+    /// (or to `start_atomic` if no checkpoints have been created).
+    ///
+    fn atomic_rewind(&self) {
+        // Acquire the write lock on the atomic batch.
+        let mut atomic_batch = self.atomic_batch.lock();
+
+        // Retrieve the last checkpoint.
+        let checkpoint = self.checkpoints.lock().pop().unwrap_or(0);
+
+        // Remove all operations after the checkpoint.
+        atomic_batch.truncate(checkpoint);
+    }
+
+    ///
+    /// Aborts the current atomic operation.
+    ///
+    fn abort_atomic(&self) {
+        // Clear the atomic batch.
+        self.atomic_batch.lock().clear();
+        // Clear the checkpoint stack.
+        self.checkpoints.lock().clear();
+        // Set the atomic batch flag to `false`.
+        self.batch_in_progress.store(false, Ordering::SeqCst);
+        // Clear the database-wide atomic batch.
+        self.database.atomic_batch.lock().clear();
+        // Reset the atomic batch depth.
+        self.database.atomic_depth.store(0, Ordering::SeqCst);
+    }
+
+    ///
+    /// Finishes an atomic operation, performing all the queued writes.
+    ///
+    fn finish_atomic(&self) -> Result<()> {
+        // Retrieve the atomic batch belonging to the map.
+        let operations = mem::take(&mut *self.atomic_batch.lock());
+
+        if !operations.is_empty() {
+            // Insert the operations into an index map to remove any operations that would have been overwritten anyways.
+            let operations: IndexMap<_, _> = IndexMap::from_iter(operations);
+
+            // Prepare the key and value for each queued operation.
+            //
+            // Note: This step is taken to ensure (with 100% certainty) that there will be
+            // no chance to fail partway through committing the queued operations.
+            let prepared_operations = operations
+                .into_iter()
+                .map(|(key, value)| match value {
+                    Some(value) => Ok((self.create_prefixed_key(&key)?, Some(bincode::serialize(&value)?))),
+                    None => Ok((self.create_prefixed_key(&key)?, None)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            // Enqueue all the operations from the map in the database-wide batch.
+            self.database.atomic_batch.lock().extend(prepared_operations);
+        }
+
+        // Clear the checkpoint stack.
+        self.checkpoints.lock().clear();
+        // Set the atomic batch flag to `false`.
+        self.batch_in_progress.store(false, Ordering::SeqCst);
+
+        // Perform the database-wide batch, if this is the final call to `finish_atomic`.
+        self.database.finish_atomic_depth()
+    }
+
+    ///
+    /// Once called, the subsequent atomic write batches will be queued instead of being executed
+    /// at the end of their scope. `unpause_atomic_writes` needs to be called in order to
+    /// restore the usual behavior.
+    ///
+    fn pause_atomic_writes(&self) -> Result<()> {
+        self.database.pause_atomic_writes()
+    }
+
+    ///
+    /// Executes all of the queued writes as a single atomic operation and restores the usual
+    /// behavior of atomic write batches that was altered by calling `pause_atomic_writes`.
+    ///
+    fn unpause_atomic_writes<const DISCARD_BATCH: bool>(&self) -> Result<()> {
+        self.database.unpause_atomic_writes::<DISCARD_BATCH>()
+    }
+}
+
+impl<
+    'a,
+    K: 'a + Copy + Clone + Debug + PartialEq + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    V: 'a + Clone + PartialEq + Eq + Serialize + DeserializeOwned + Send + Sync,
+    E: KeyValueEngine,
+> MapRead<'a, K, V> for KVMap<K, V, E>
+{
+    type Iterator = std::vec::IntoIter<(Cow<'a, K>, Cow<'a, V>)>;
+    type Keys = std::vec::IntoIter<Cow<'a, K>>;
+    type PendingIterator =
+        core::iter::Map<indexmap::map::IntoIter<K, Option<V>>, fn((K, Option<V>)) -> (Cow<'a, K>, Option<Cow<'a, V>>)>;
+    type Values = std::vec::IntoIter<Cow<'a, V>>;
+
+    ///
+    /// Returns the number of confirmed entries in the map.
+    ///
+    fn len_confirmed(&self) -> usize {
+        self.database.prefix_scan_or_log(&self.context).count()
+    }
+
+    ///
+    /// Returns `true` if the given key exists in the map.
+    ///
+    fn contains_key_confirmed<Q>(&self, key: &Q) -> Result<bool>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + Eq + Hash + Serialize + ?Sized,
+    {
+        self.get_raw(key).map(|v| v.is_some())
+    }
+
+    ///
+    /// Returns `true` if the given key exists in the map.
+    /// This method first checks the atomic batch, and if it does not exist, then checks the map.
+    ///
+    fn contains_key_speculative<Q>(&self, key: &Q) -> Result<bool>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + Eq + Hash + Serialize + ?Sized,
+    {
+        // If a batch is in progress, check the atomic batch first.
+        if self.is_atomic_in_progress() {
+            // If the key is present in the atomic batch, then check if the value is 'Some(V)'.
+            // We iterate from the back of the `atomic_batch` to find the latest value.
+            if let Some((_, value)) = self.atomic_batch.lock().iter().rev().find(|&(k, _)| k.borrow() == key) {
+                // If the value is 'Some(V)', then the key exists.
+                // If the value is 'Some(None)', then the key is scheduled to be removed.
+                return Ok(value.is_some());
+            }
+        }
+
+        // Otherwise, check the map for the key.
+        self.contains_key_confirmed(key)
+    }
+
+    ///
+    /// Returns the value for the given key from the map, if it exists.
+    ///
+    fn get_confirmed<Q>(&'a self, key: &Q) -> Result<Option<Cow<'a, V>>>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + Eq + Hash + Serialize + ?Sized,
+    {
+        match self.get_raw(key)? {
+            Some(bytes) => Ok(Some(Cow::Owned(bincode::deserialize(&bytes)?))),
+            None => Ok(None),
+        }
+    }
+
+    ///
+    /// Returns the current value for the given key if it is scheduled
+    /// to be inserted as part of an atomic batch.
+    ///
+    /// If the key does not exist, returns `None`.
+    /// If the key is removed in the batch, returns `Some(None)`.
+    /// If the key is inserted in the batch, returns `Some(Some(value))`.
+    ///
+    fn get_pending<Q>(&self, key: &Q) -> Option<Option<V>>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + Eq + Hash + Serialize + ?Sized,
+    {
+        // Return early if there is no atomic batch in progress.
+        if self.is_atomic_in_progress() {
+            // We iterate from the back of the `atomic_batch` to find the latest value.
+            self.atomic_batch.lock().iter().rev().find(|&(k, _)| k.borrow() == key).map(|(_, value)| value).cloned()
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Returns an iterator visiting each key-value pair in the atomic batch.
+    ///
+    fn iter_pending(&'a self) -> Self::PendingIterator {
+        let filtered_atomic_batch: IndexMap<_, _> = IndexMap::from_iter(self.atomic_batch.lock().clone());
+        filtered_atomic_batch.into_iter().map(|(k, v)| (Cow::Owned(k), v.map(|v| Cow::Owned(v))))
+    }
+
+    ///
+    /// Returns an iterator visiting each key-value pair in the map.
+    ///
+    fn iter_confirmed(&'a self) -> Self::Iterator {
+        self.entries().collect::<Vec<_>>().into_iter()
+    }
+
+    ///
+    /// Returns an iterator over each key in the map.
+    ///
+    fn keys_confirmed(&'a self) -> Self::Keys {
+        self.entries().map(|(key, _)| key).collect::<Vec<_>>().into_iter()
+    }
+
+    ///
+    /// Returns an iterator over each value in the map.
+    ///
+    fn values_confirmed(&'a self) -> Self::Values {
+        self.entries().map(|(_, value)| value).collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, E: KeyValueEngine> fmt::Debug
+    for KVMap<K, V, E>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KVMap").field("context", &self.context).finish()
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use crate::helpers::id::*;
+
+mod log;
+pub use log::*;
+
+mod map;
+pub use map::*;
+
+mod nested_map;
+pub use nested_map::*;
+
+mod redb_engine;
+pub use redb_engine::*;
+
+#[cfg(test)]
+mod tests;
+
+use aleo_std_storage::StorageMode;
+use anyhow::{bail, ensure, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    any::Any,
+    borrow::Borrow,
+    collections::HashMap,
+    mem,
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        Weak,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+pub const PREFIX_LEN: usize = 4; // N::ID (u16) + DataID (u16)
+
+/// A list of writes that are performed atomically, as `(key, Some(value))` for an insertion,
+/// and as `(key, None)` for a removal.
+pub type WriteBatch = Vec<(Vec<u8>, Option<Vec<u8>>)>;
+
+/// An iterator over the key-value pairs whose key starts with a given prefix, in ascending order of their keys.
+pub type PrefixIterator = Box<dyn Iterator<Item = Result<(Vec<u8>, Vec<u8>)>> + Send>;
+
+/// An ordered key-value engine, which stores the entries of a `KVDatabase`.
+///
+/// This is the only trait that a new storage engine needs to implement, as the maps
+/// and the storage of the ledger are implemented on top of it. An engine is a handle,
+/// i.e. its clones share the same underlying entries.
+pub trait KeyValueEngine: 'static + Clone + Send + Sync + Sized {
+    /// Opens the engine in the given directory, creating it if it does not exist.
+    fn open(path: &Path) -> Result<Self>;
+
+    /// Returns the value for the given key, if it exists.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Inserts the given key-value pair.
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()>;
+
+    /// Removes the key-value pair for the given key.
+    fn delete(&self, key: &[u8]) -> Result<()>;
+
+    /// Returns the key-value pairs whose key starts with the given prefix, in ascending order of their keys.
+    /// The pairs are read lazily, so that a scan does not hold all the pairs in memory at once.
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<PrefixIterator>;
+
+    /// Returns the key-value pairs whose key starts with the given prefix, in ascending order of their keys,
+    /// skipping the first `offset` pairs and returning at most `limit` pairs.
+    fn prefix_scan_range(&self, prefix: &[u8], offset: usize, limit: usize) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.prefix_scan(prefix)?.skip(offset).take(limit).collect()
+    }
+
+    /// Performs the given writes atomically, in order.
+    fn write_batch(&self, batch: WriteBatch) -> Result<()>;
+}
+
+/// The databases that are currently open, indexed by their directory.
+/// A database is closed once all of its instances are dropped.
+static DATABASES: Lazy<Mutex<HashMap<PathBuf, Weak<dyn Any + Send + Sync>>>> = Lazy::new(Default::default);
+
+/// An instance of a database on top of a key-value engine.
+pub struct KVDatabase<E: KeyValueEngine>(Arc<InnerKVDatabase<E>>);
+
+impl<E: KeyValueEngine> Clone for KVDatabase<E> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<E: KeyValueEngine> Deref for KVDatabase<E> {
+    type Target = InnerKVDatabase<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub struct InnerKVDatabase<E: KeyValueEngine> {
+    /// The key-value engine.
+    engine: E,
+    /// The network ID.
+    network_id: u16,
+    /// The storage mode.
+    storage_mode: StorageMode,
+    /// The low-level database transaction that gets executed atomically at the end
+    /// of a real-run `atomic_finalize` or the outermost `atomic_batch_scope`.
+    pub(super) atomic_batch: Mutex<WriteBatch>,
+    /// The depth of the current atomic write batch; it gets incremented with every call
+    /// to `start_atomic` and decremented with each call to `finish_atomic`.
+    pub(super) atomic_depth: AtomicUsize,
+    /// A flag indicating whether the atomic writes are currently paused.
+    pub(super) atomic_writes_paused: AtomicBool,
+}
+
+impl<E: KeyValueEngine> KVDatabase<E> {
+    /// Opens the database.
+    ///
+    /// In production mode, the database opens directory `~/.aleo/storage/ledger-{network}`.
+    /// In development mode, the database opens directory `/path/to/repo/.ledger-{network}-{id}`.
+    pub fn open<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<Self> {
+        let storage_mode: StorageMode = storage.into();
+        // Determine the directory of the database.
+        let path = aleo_std_storage::aleo_ledger_dir(network_id, storage_mode.clone());
+        // Retrieve the database.
+        let database = Self::open_at(path, network_id, storage_mode.clone())?;
+
+        // Ensure the database network ID and storage mode match.
+        match database.network_id == network_id && database.storage_mode == storage_mode {
+            true => Ok(database),
+            false => bail!("Mismatching network ID or storage mode in the database"),
+        }
+    }
+
+    /// Opens the database in the given directory, or returns it if it is already open.
+    fn open_at(path: PathBuf, network_id: u16, storage_mode: StorageMode) -> Result<Self> {
+        let mut databases = DATABASES.lock();
+        // Remove the databases that have been closed.
+        databases.retain(|_, database| database.strong_count() > 0);
+        // If the database is already open, return it.
+        if let Some(database) = databases.get(&path).and_then(Weak::upgrade) {
+            match database.downcast::<InnerKVDatabase<E>>() {
+                Ok(database) => return Ok(Self(database)),
+                Err(_) => bail!("The database in '{}' is already open with a different engine", path.display()),
+            }
+        }
+        // Otherwise, open the database.
+        let database = Self::new(E::open(&path)?, network_id, storage_mode);
+        let inner: Arc<dyn Any + Send + Sync> = database.0.clone();
+        databases.insert(path, Arc::downgrade(&inner));
+        Ok(database)
+    }
+
+    /// Initializes a new database with the given key-value engine.
+    fn new(engine: E, network_id: u16, storage_mode: StorageMode) -> Self {
+        Self(Arc::new(InnerKVDatabase {
+            engine,
+            network_id,
+            storage_mode,
+            atomic_batch: Default::default(),
+            atomic_depth: Default::default(),
+            atomic_writes_paused: Default::default(),
+        }))
+    }
+
+    /// Opens the map with the given `network_id`, `storage mode`, and `map_id` from storage.
+    pub fn open_map<
+        S: Clone + Into<StorageMode>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        network_id: u16,
+        storage: S,
+        map_id: T,
+    ) -> Result<KVMap<K, V, E>> {
+        Ok(KVMap::new(Self::open(network_id, storage)?, map_id.into()))
+    }
+
+    /// Opens the nested map with the given `network_id`, `storage mode`, and `map_id` from storage.
+    pub fn open_nested_map<
+        S: Clone + Into<StorageMode>,
+        M: Serialize + DeserializeOwned,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        network_id: u16,
+        storage: S,
+        map_id: T,
+    ) -> Result<NestedKVMap<M, K, V, E>> {
+        Ok(NestedKVMap::new(Self::open(network_id, storage)?, map_id.into()))
+    }
+
+    /// Returns the prefix of the keys of the map with the given `map_id`.
+    fn map_context(&self, map_id: u16) -> Vec<u8> {
+        let mut context = self.network_id.to_le_bytes().to_vec();
+        context.extend_from_slice(&map_id.to_le_bytes());
+        context
+    }
+
+    /// Returns the key-value pairs whose key starts with the given prefix, stopping at the first error, which is logged.
+    fn prefix_scan_or_log(&self, prefix: &[u8]) -> impl Iterator<Item = (Vec<u8>, Vec<u8>)> {
+        let entries: PrefixIterator = self.engine.prefix_scan(prefix).unwrap_or_else(|e| {
+            tracing::error!("KVDatabase prefix scan error: {e}");
+            Box::new(std::iter::empty())
+        });
+        entries.map_while(|entry| entry.map_err(|e| tracing::error!("KVDatabase prefix scan error: {e}")).ok())
+    }
+
+    /// Ends an atomic operation of a map, and performs the atomic write batch if it was the last one.
+    fn finish_atomic_depth(&self) -> Result<()> {
+        // Subtract the atomic depth index.
+        let previous_atomic_depth = self.atomic_depth.fetch_sub(1, Ordering::SeqCst);
+
+        // Ensure that the value of `atomic_depth` doesn't overflow, meaning that all the
+        // calls to `start_atomic` have corresponding calls to `finish_atomic`.
+        assert!(previous_atomic_depth != 0);
+
+        // If we're at depth 0, it is the final call to `finish_atomic` and the
+        // atomic write batch can be physically executed. This is skipped if the
+        // atomic writes are paused.
+        if previous_atomic_depth == 1 && !self.are_atomic_writes_paused() {
+            // Empty the collection of pending operations.
+            let batch = mem::take(&mut *self.atomic_batch.lock());
+            // Execute all the operations atomically.
+            self.engine.write_batch(batch)?;
+        }
+
+        Ok(())
+    }
+
+    /// Pause the execution of atomic writes for the entire database.
+    fn pause_atomic_writes(&self) -> Result<()> {
+        // This operation is only intended to be performed before or after
+        // atomic batches - never in the middle of them.
+        ensure!(self.atomic_depth.load(Ordering::SeqCst) == 0, "Atomic depth must be 0 to pause atomic writes");
+
+        // Set the flag indicating that the pause is in effect.
+        let already_paused = self.atomic_writes_paused.swap(true, Ordering::SeqCst);
+        // Make sure that we haven't already paused atomic writes (which would
+        // indicate a logic bug).
+        ensure!(!already_paused, "Atomic writes are already paused");
+
+        Ok(())
+    }
+
+    /// Unpause the execution of atomic writes for the entire database; this
+    /// executes all the writes that have been queued since they were paused.
+    fn unpause_atomic_writes<const DISCARD_BATCH: bool>(&self) -> Result<()> {
+        // Ensure the call to unpause is only performed before or after an atomic batch scope
+        // - and never in the middle of one (otherwise there is a fundamental logic bug).
+        ensure!(self.atomic_depth.load(Ordering::SeqCst) == 0, "Atomic depth must be 0 to unpause atomic writes");
+        // Ensure the database is paused (otherwise there is a fundamental logic bug).
+        ensure!(self.are_atomic_writes_paused(), "Atomic writes must be paused to unpause them");
+
+        // Execute every atomic storage operation that has accumulated from the moment
+        // the writes have been paused as a single atomic batch.
+        let batch = mem::take(&mut *self.atomic_batch.lock());
+        if !DISCARD_BATCH {
+            self.engine.write_batch(batch)?;
+        }
+
+        // Unset the flag indicating that the pause is in effect.
+        self.atomic_writes_paused.store(false, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Checks whether the atomic writes are currently paused.
+    fn are_atomic_writes_paused(&self) -> bool {
+        self.atomic_writes_paused.load(Ordering::SeqCst)
+    }
+
+    /// Opens the test database.
+    #[cfg(any(test, feature = "test"))]
+    pub fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
+        use console::prelude::{Rng, TestRng};
+
+        // Ensure the `temp_dir` is unique.
+        let temp_dir = temp_dir.join(Rng::gen::<u64>(&mut TestRng::default()).to_string());
+
+        // Construct the directory for the test database.
+        let primary = match dev {
+            Some(dev) => temp_dir.join(dev.to_string()),
+            None => temp_dir,
+        };
+
+        // Open the test database.
+        Self::open_at(primary.clone(), u16::MAX, StorageMode::from(primary))
+    }
+
+    /// Opens the test map.
+    #[cfg(any(test, feature = "test"))]
+    pub fn open_map_testing<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, T: Into<u16>>(
+        temp_dir: std::path::PathBuf,
+        dev: Option<u16>,
+        map_id: T,
+    ) -> Result<KVMap<K, V, E>> {
+        Ok(KVMap::new(Self::open_testing(temp_dir, dev)?, map_id.into()))
+    }
+
+    /// Opens the test nested map.
+    #[cfg(any(test, feature = "test"))]
+    pub fn open_nested_map_testing<
+        M: Serialize + DeserializeOwned,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        temp_dir: std::path::PathBuf,
+        dev: Option<u16>,
+        map_id: T,
+    ) -> Result<NestedKVMap<M, K, V, E>> {
+        Ok(NestedKVMap::new(Self::open_testing(temp_dir, dev)?, map_id.into()))
+    }
+}
@@ -0,0 +1,540 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(clippy::type_complexity)]
+
+use super::*;
+use crate::helpers::{NestedMap, NestedMapRead};
+use console::prelude::{anyhow, FromBytes};
+
+use core::{fmt, fmt::Debug, hash::Hash};
+use std::{borrow::Cow, ops::Deref};
+
+pub struct NestedKVMap<
+    M: Serialize + DeserializeOwned,
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+    E: KeyValueEngine,
+>(Arc<InnerNestedKVMap<M, K, V, E>>);
+
+impl<
+    M: Serialize + DeserializeOwned,
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+    E: KeyValueEngine,
+> Clone for NestedKVMap<M, K, V, E>
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<
+    M: Serialize + DeserializeOwned,
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+    E: KeyValueEngine,
+> Deref for NestedKVMap<M, K, V, E>
+{
+    type Target = InnerNestedKVMap<M, K, V, E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub struct InnerNestedKVMap<
+    M: Serialize + DeserializeOwned,
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+    E: KeyValueEngine,
+> {
+    database: KVDatabase<E>,
+    context: Vec<u8>,
+    /// The tracker for whether a database transaction is in progress.
+    batch_in_progress: AtomicBool,
+    /// The database transaction.
+    atomic_batch: Mutex<Vec<(M, Option<K>, Option<V>)>>,
+    /// The checkpoint stack for the batched operations within the map.
+    checkpoints: Mutex<Vec<usize>>,
+}
+
+impl<
+    M: Serialize + DeserializeOwned,
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+    E: KeyValueEngine,
+> NestedKVMap<M, K, V, E>
+{
+    /// Initializes a new nested map with the given `map_id` in the given database.
+    pub(super) fn new(database: KVDatabase<E>, map_id: u16) -> Self {
+        let context = database.map_context(map_id);
+        Self(Arc::new(InnerNestedKVMap {
+            database,
+            context,
+            batch_in_progress: Default::default(),
+            atomic_batch: Default::default(),
+            checkpoints: Default::default(),
+        }))
+    }
+
+    #[inline]
+    fn create_prefixed_map(&self, map: &M) -> Result<Vec<u8>> {
+        let mut raw_map = self.context.clone();
+
+        let map_size: u32 = bincode::serialized_size(&map)?.try_into()?;
+        raw_map.extend_from_slice(&map_size.to_le_bytes());
+
+        bincode::serialize_into(&mut raw_map, map)?;
+        Ok(raw_map)
+    }
+
+    #[inline]
+    fn create_prefixed_map_key(&self, map: &M, key: &K) -> Result<Vec<u8>> {
+        let mut raw_map_key = self.create_prefixed_map(map)?;
+        bincode::serialize_into(&mut raw_map_key, key)?;
+        Ok(raw_map_key)
+    }
+
+    /// Returns the removals of all the confirmed entries of the given map, as a write batch.
+    ///
+    /// Note: As the map size is part of the prefix, the prefix scan only returns the entries of the given map.
+    fn remove_map_batch(&self, map: &M) -> Result<WriteBatch> {
+        let prefix = self.create_prefixed_map(map)?;
+        self.database.engine.prefix_scan(&prefix)?.map(|entry| entry.map(|(map_key, _)| (map_key, None))).collect()
+    }
+
+    /// Returns the deserialized entries of the nested map, stopping at the first entry that fails to deserialize.
+    fn entries(&self) -> impl Iterator<Item = (M, K, V)> {
+        self.database.prefix_scan_or_log(&self.context).map_while(|(map_key, value)| {
+            // Extract the bytes belonging to the map and the key.
+            let (entry_map, entry_key) = get_map_and_key(&map_key)
+                .map_err(|e| tracing::error!("NestedKVMap get_map_and_key error: {e}"))
+                .ok()?;
+            // Deserialize the map, key, and value.
+            let map = bincode::deserialize(entry_map)
+                .map_err(|e| tracing::error!("NestedKVMap deserialize(map) error: {e}"))
+                .ok()?;
+            let key = bincode::deserialize(entry_key)
+                .map_err(|e| tracing::error!("NestedKVMap deserialize(key) error: {e}"))
+                .ok()?;
+            let value = bincode::deserialize(&value)
+                .map_err(|e| tracing::error!("NestedKVMap deserialize(value) error: {e}"))
+                .ok()?;
+            Some((map, key, value))
+        })
+    }
+}
+
+#[inline]
+fn get_map_and_key(map_key: &[u8]) -> Result<(&[u8], &[u8])> {
+    // Retrieve the map length.
+    let map_len = u32::from_bytes_le(
+        map_key.get(PREFIX_LEN..PREFIX_LEN + 4).ok_or_else(|| anyhow!("NestedMap map_len index out of range"))?,
+    )? as usize;
+
+    // Retrieve the map bytes.
+    let map = map_key
+        .get(PREFIX_LEN + 4..PREFIX_LEN + 4 + map_len)
+        .ok_or_else(|| anyhow!("NestedMap map index out of range"))?;
+
+    // Retrieve the key bytes.
+    let key = map_key.get(PREFIX_LEN + 4 + map_len..).ok_or_else(|| anyhow!("NestedMap key index out of range"))?;
+
+    // Return the map and key bytes.
+    Ok((map, key))
+}
+
+impl<
+    'a,
+    M: 'a + Copy + Clone + Debug + PartialEq + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    K: 'a + Clone + Debug + PartialEq + Eq + Serialize + DeserializeOwned + Send + Sync,
+    V: 'a + Clone + PartialEq + Eq + Serialize + DeserializeOwned + Send + Sync,
+    E: KeyValueEngine,
+> NestedMap<'a, M, K, V> for NestedKVMap<M, K, V, E>
+{
+    ///
+    /// Inserts the given map-key-value pair.
+    ///
+    fn insert(&self, map: M, key: K, value: V) -> Result<()> {
+        // Determine if an atomic batch is in progress.
+        match self.is_atomic_in_progress() {
+            // If a batch is in progress, add the map-key-value pair to the batch.
+            true => self.atomic_batch.lock().push((map, Some(key), Some(value))),
+            // Otherwise, insert the key-value pair directly into the map.
+            false => {
+                self.database.engine.put(&self.create_prefixed_map_key(&map, &key)?, &bincode::serialize(&value)?)?
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Removes the given map.
+    ///
+    fn remove_map(&self, map: &M) -> Result<()> {
+        // Determine if an atomic batch is in progress.
+        match self.is_atomic_in_progress() {
+            // If a batch is in progress, add the map-None pair to the batch.
+            true => self.atomic_batch.lock().push((*map, None, None)),
+            // Otherwise, remove the map directly from the map, as a single atomic write.
+            false => self.database.engine.write_batch(self.remove_map_batch(map)?)?,
+        }
+        Ok(())
+    }
+
+    ///
+    /// Removes the key-value pair for the given map and key.
+    ///
+    fn remove_key(&self, map: &M, key: &K) -> Result<()> {
+        // Determine if an atomic batch is in progress.
+        match self.is_atomic_in_progress() {
+            // If a batch is in progress, add the key to the batch.
+            true => self.atomic_batch.lock().push((*map, Some(key.clone()), None)),
+            // Otherwise, remove the key-value pair directly from the map.
+            false => self.database.engine.delete(&self.create_prefixed_map_key(map, key)?)?,
+        }
+        Ok(())
+    }
+
+    ///
+    /// Begins an atomic operation. Any further calls to `insert` and `remove` will be queued
+    /// without an actual write taking place until `finish_atomic` is called.
+    ///
+    fn start_atomic(&self) {
+        // Set the atomic batch flag to `true`.
+        self.batch_in_progress.store(true, Ordering::SeqCst);
+        // Increment the atomic depth index.
+        self.database.atomic_depth.fetch_add(1, Ordering::SeqCst);
+
+        // Ensure that the atomic batch is empty.
+        assert!(self.atomic_batch.lock().is_empty());
+        // Ensure that the database atomic batch is empty; skip this check if the atomic
+        // writes are paused, as there may be pending operations.
+        if !self.database.are_atomic_writes_paused() {
+            assert!(self.database.atomic_batch.lock().is_empty());
+        }
+    }
+
+    ///
+    /// Checks whether an atomic operation is currently in progress. This can be done to ensure
+    /// that lower-level operations don't start and finish their individual atomic write batch
+    /// if they are already part of a larger one.
+    ///
+    fn is_atomic_in_progress(&self) -> bool {
+        self.batch_in_progress.load(Ordering::SeqCst)
+    }
+
+    ///
+    /// Saves the current list of pending operations, so that if `atomic_rewind` is called,
+    /// we roll back all future operations, and return to the start of this checkpoint.
+    ///
+    fn atomic_checkpoint(&self) {
+        // Push the current length of the atomic batch to the checkpoint stack.
+        self.checkpoints.lock().push(self.atomic_batch.lock().len());
+    }
+
+    ///
+    /// Removes the latest atomic checkpoint.
+    ///
+    fn clear_latest_checkpoint(&self) {
+        // Removes the latest checkpoint.
+        let _ = self.checkpoints.lock().pop();
+    }
+
+    ///
+    /// Removes all pending operations to the last `atomic_checkpoint`
+    /// (or to `start_atomic` if no checkpoints have been created).
+    ///
+    fn atomic_rewind(&self) {
+        // Acquire the write lock on the atomic batch.
+        let mut atomic_batch = self.atomic_batch.lock();
+
+        // Retrieve the last checkpoint.
+        let checkpoint = self.checkpoints.lock().pop().unwrap_or(0);
+
+        // Remove all operations after the checkpoint.
+        atomic_batch.truncate(checkpoint);
+    }
+
+    ///
+    /// Aborts the current atomic operation.
+    ///
+    fn abort_atomic(&self) {
+        // Clear the atomic batch.
+        self.atomic_batch.lock().clear();
+        // Clear the checkpoint stack.
+        self.checkpoints.lock().clear();
+        // Set the atomic batch flag to `false`.
+        self.batch_in_progress.store(false, Ordering::SeqCst);
+        // Clear the database-wide atomic batch.
+        self.database.atomic_batch.lock().clear();
+        // Reset the atomic batch depth.
+        self.database.atomic_depth.store(0, Ordering::SeqCst);
+    }
+
+    ///
+    /// Finishes an atomic operation, performing all the queued writes.
+    ///
+    fn finish_atomic(&self) -> Result<()> {
+        // Retrieve the atomic batch belonging to the map.
+        let operations = mem::take(&mut *self.atomic_batch.lock());
+
+        if !operations.is_empty() {
+            // Enqueue all the operations from the map in the database-wide batch.
+            let mut atomic_batch = self.database.atomic_batch.lock();
+
+            for (map, key, value) in operations {
+                match (key, value) {
+                    (Some(key), Some(value)) => atomic_batch
+                        .push((self.create_prefixed_map_key(&map, &key)?, Some(bincode::serialize(&value)?))),
+                    (Some(key), None) => atomic_batch.push((self.create_prefixed_map_key(&map, &key)?, None)),
+                    (None, None) => {
+                        // Drop the queued writes to the map, as they are superseded by its removal.
+                        let prefix = self.create_prefixed_map(&map)?;
+                        atomic_batch.retain(|(map_key, _)| !map_key.starts_with(&prefix));
+                        // Remove the confirmed entries of the map.
+                        atomic_batch.extend(self.remove_map_batch(&map)?);
+                    }
+                    (None, Some(_)) => unreachable!("Cannot insert a value without a key"),
+                }
+            }
+        }
+
+        // Clear the checkpoint stack.
+        self.checkpoints.lock().clear();
+        // Set the atomic batch flag to `false`.
+        self.batch_in_progress.store(false, Ordering::SeqCst);
+
+        // Perform the database-wide batch, if this is the final call to `finish_atomic`.
+        self.database.finish_atomic_depth()
+    }
+}
+
+impl<
+    'a,
+    M: 'a + Copy + Clone + Debug + PartialEq + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    K: 'a + Clone + Debug + PartialEq + Eq + Serialize + DeserializeOwned + Send + Sync,
+    V: 'a + Clone + PartialEq + Eq + Serialize + DeserializeOwned + Send + Sync,
+    E: KeyValueEngine,
+> NestedMapRead<'a, M, K, V> for NestedKVMap<M, K, V, E>
+{
+    type Iterator = std::vec::IntoIter<(Cow<'a, M>, Cow<'a, K>, Cow<'a, V>)>;
+    type Keys = std::vec::IntoIter<(Cow<'a, M>, Cow<'a, K>)>;
+    type PendingIterator = core::iter::Map<
+        std::vec::IntoIter<(M, Option<K>, Option<V>)>,
+        fn((M, Option<K>, Option<V>)) -> (Cow<'a, M>, Option<Cow<'a, K>>, Option<Cow<'a, V>>),
+    >;
+    type Values = std::vec::IntoIter<Cow<'a, V>>;
+
+    ///
+    /// Returns the number of confirmed entries in the map.
+    ///
+    fn len_map_confirmed(&self, map: &M) -> Result<usize> {
+        self.database
+            .engine
+            .prefix_scan(&self.create_prefixed_map(map)?)?
+            .try_fold(0, |len, entry| entry.map(|_| len + 1))
+    }
+
+    ///
+    /// Returns `true` if the given map and key exists.
+    ///
+    fn contains_key_confirmed(&self, map: &M, key: &K) -> Result<bool> {
+        Ok(self.database.engine.get(&self.create_prefixed_map_key(map, key)?)?.is_some())
+    }
+
+    ///
+    /// Returns `true` if the given map and key exists.
+    /// This method first checks the atomic batch, and if it does not exist, then checks the map.
+    ///
+    fn contains_key_speculative(&self, map: &M, key: &K) -> Result<bool> {
+        // If a batch is in progress, check the atomic batch first.
+        if self.is_atomic_in_progress() {
+            // We iterate from the back of the `atomic_batch` to find the latest value.
+            for (m, k, v) in self.atomic_batch.lock().iter().rev() {
+                // If the map does not match the given map, then continue.
+                if m != map {
+                    continue;
+                }
+                // If the key is 'None', then the map is scheduled to be removed.
+                if k.is_none() {
+                    return Ok(false);
+                }
+                // If the key matches the given key, then return whether the value is 'Some(V)'.
+                if k.as_ref().unwrap() == key {
+                    // If the value is 'Some(V)', then the key exists.
+                    // If the value is 'None', then the key is scheduled to be removed.
+                    return Ok(v.is_some());
+                }
+            }
+        }
+        // Otherwise, check the map for the key.
+        self.contains_key_confirmed(map, key)
+    }
+
+    ///
+    /// Returns the key-value pairs for the given map, if it exists.
+    ///
+    fn get_map_confirmed(&'a self, map: &M) -> Result<Vec<(K, V)>> {
+        // Retrieve the bytes of the entries of the map.
+        let entries = self.database.engine.prefix_scan(&self.create_prefixed_map(map)?)?;
+
+        // Deserialize the entries.
+        entries
+            .map(|entry| {
+                let (map_key, value) = entry?;
+                let (_, entry_key) = get_map_and_key(&map_key)?;
+                Ok((bincode::deserialize(entry_key)?, bincode::deserialize(&value)?))
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the key-value pairs for the given map, in ascending order of their serialized keys,
+    /// skipping the first `offset` pairs and returning at most `limit` pairs.
+    ///
+    fn get_map_range_confirmed(&'a self, map: &M, offset: usize, limit: usize) -> Result<Vec<(K, V)>> {
+        // Retrieve the bytes of the requested entries of the map.
+        let entries = self.database.engine.prefix_scan_range(&self.create_prefixed_map(map)?, offset, limit)?;
+
+        // Deserialize the entries.
+        entries
+            .into_iter()
+            .map(|(map_key, value)| {
+                let (_, entry_key) = get_map_and_key(&map_key)?;
+                Ok((bincode::deserialize(entry_key)?, bincode::deserialize(&value)?))
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the speculative key-value pairs for the given map, if it exists.
+    ///
+    fn get_map_speculative(&'a self, map: &M) -> Result<Vec<(K, V)>> {
+        // Retrieve the confirmed key-value pairs for the given map.
+        let mut key_values = self.get_map_confirmed(map)?;
+
+        // Retrieve the atomic batch.
+        let operations = self.atomic_batch.lock().clone();
+
+        // Traverse the queued operations.
+        for (m, k, v) in operations {
+            // If the map does not match the given map, then continue.
+            if &m != map {
+                continue;
+            }
+
+            // Update the confirmed pairs based on the pending operations.
+            match (k, v) {
+                // Insert or update the key-value pair for the key.
+                (Some(k), Some(v)) => match key_values.iter_mut().find(|(key, _)| key == &k) {
+                    Some((_, value)) => *value = v,
+                    None => key_values.push((k, v)),
+                },
+                // Clear the key-value pairs for the map.
+                (None, None) => key_values.clear(),
+                // Remove the key-value pair for the key.
+                (Some(k), None) => key_values.retain(|(key, _)| key != &k),
+                (None, Some(_)) => unreachable!("Cannot remove a key-value pair from a map without a key."),
+            }
+        }
+
+        // Return the key-value pairs for the map.
+        Ok(key_values)
+    }
+
+    ///
+    /// Returns the value for the given map and key, if it exists.
+    ///
+    fn get_value_confirmed(&'a self, map: &M, key: &K) -> Result<Option<Cow<'a, V>>> {
+        match self.database.engine.get(&self.create_prefixed_map_key(map, key)?)? {
+            Some(bytes) => Ok(Some(Cow::Owned(bincode::deserialize(&bytes)?))),
+            None => Ok(None),
+        }
+    }
+
+    ///
+    /// Returns the current value for the given key if it is scheduled
+    /// to be inserted as part of an atomic batch.
+    ///
+    /// If the key does not exist, returns `None`.
+    /// If the key is removed in the batch, returns `Some(None)`.
+    /// If the key is inserted in the batch, returns `Some(Some(value))`.
+    ///
+    fn get_value_pending(&self, map: &M, key: &K) -> Option<Option<V>> {
+        // Return early if there is no atomic batch in progress.
+        if !self.is_atomic_in_progress() {
+            return None;
+        }
+        // We iterate from the back of the `atomic_batch` to find the latest value.
+        for (m, k, v) in self.atomic_batch.lock().iter().rev() {
+            // If the map does not match the given map, then continue.
+            if m != map {
+                continue;
+            }
+            match k {
+                // If the key is 'None', then the map is scheduled to be removed.
+                None => return Some(None),
+                // If the key matches the given key, then return the scheduled value.
+                Some(k) if k == key => return Some(v.clone()),
+                Some(_) => continue,
+            }
+        }
+        None
+    }
+
+    ///
+    /// Returns an iterator visiting each key-value pair in the atomic batch.
+    ///
+    fn iter_pending(&'a self) -> Self::PendingIterator {
+        self.atomic_batch.lock().clone().into_iter().map(|(m, k, v)| {
+            // Return the map-key-value triple.
+            (Cow::Owned(m), k.map(Cow::Owned), v.map(Cow::Owned))
+        })
+    }
+
+    ///
+    /// Returns an iterator visiting each key-value pair in the map.
+    ///
+    fn iter_confirmed(&'a self) -> Self::Iterator {
+        self.entries().map(|(m, k, v)| (Cow::Owned(m), Cow::Owned(k), Cow::Owned(v))).collect::<Vec<_>>().into_iter()
+    }
+
+    ///
+    /// Returns an iterator over each key.
+    ///
+    fn keys_confirmed(&'a self) -> Self::Keys {
+        self.entries().map(|(m, k, _)| (Cow::Owned(m), Cow::Owned(k))).collect::<Vec<_>>().into_iter()
+    }
+
+    ///
+    /// Returns an iterator over each value.
+    ///
+    fn values_confirmed(&'a self) -> Self::Values {
+        self.entries().map(|(_, _, v)| Cow::Owned(v)).collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<
+    M: Serialize + DeserializeOwned,
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+    E: KeyValueEngine,
+> fmt::Debug for NestedKVMap<M, K, V, E>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NestedKVMap").field("context", &self.context).finish()
+    }
+}
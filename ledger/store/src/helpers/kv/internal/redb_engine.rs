@@ -0,0 +1,103 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use redb::{Database, ReadableTable, TableDefinition};
+use std::fs;
+
+/// The name of the database file in the directory of a `RedbEngine`.
+const DATABASE_FILE_NAME: &str = "ledger.redb";
+/// The table that contains the entries of a `RedbEngine`.
+const ENTRIES: TableDefinition<&[u8], &[u8]> = TableDefinition::new("entries");
+
+/// An embedded key-value engine, written in pure Rust, which stores the entries on disk in a `redb` database.
+///
+/// Every write batch is a `redb` write transaction, which is durable once it is committed.
+#[derive(Clone)]
+pub struct RedbEngine {
+    /// The database.
+    database: Arc<Database>,
+}
+
+impl KeyValueEngine for RedbEngine {
+    /// Opens the engine in the given directory, creating it if it does not exist.
+    fn open(path: &Path) -> Result<Self> {
+        // Ensure the directory exists.
+        fs::create_dir_all(path)?;
+        // Open the database.
+        let database = Database::create(path.join(DATABASE_FILE_NAME))?;
+        // Ensure the table of the entries exists, so that it can be read from.
+        let transaction = database.begin_write()?;
+        transaction.open_table(ENTRIES)?;
+        transaction.commit()?;
+
+        Ok(Self { database: Arc::new(database) })
+    }
+
+    /// Returns the value for the given key, if it exists.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let table = self.database.begin_read()?.open_table(ENTRIES)?;
+        Ok(table.get(key)?.map(|value| value.value().to_vec()))
+    }
+
+    /// Inserts the given key-value pair.
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.write_batch(vec![(key.to_vec(), Some(value.to_vec()))])
+    }
+
+    /// Removes the key-value pair for the given key.
+    fn delete(&self, key: &[u8]) -> Result<()> {
+        self.write_batch(vec![(key.to_vec(), None)])
+    }
+
+    /// Returns the key-value pairs whose key starts with the given prefix, in ascending order of their keys.
+    ///
+    /// Note: The pairs are read from a snapshot of the entries, as of the start of the scan.
+    fn prefix_scan(&self, prefix: &[u8]) -> Result<PrefixIterator> {
+        let table = self.database.begin_read()?.open_table(ENTRIES)?;
+        let entries = table.range::<&[u8]>(prefix..)?;
+        let prefix = prefix.to_vec();
+        Ok(Box::new(
+            entries
+                .map(|entry| -> Result<(Vec<u8>, Vec<u8>)> {
+                    let (key, value) = entry?;
+                    Ok((key.value().to_vec(), value.value().to_vec()))
+                })
+                .take_while(move |entry| match entry {
+                    Ok((key, _)) => key.starts_with(&prefix),
+                    Err(_) => true,
+                }),
+        ))
+    }
+
+    /// Performs the given writes atomically, in order.
+    fn write_batch(&self, batch: WriteBatch) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let transaction = self.database.begin_write()?;
+        {
+            let mut table = transaction.open_table(ENTRIES)?;
+            for (key, value) in &batch {
+                match value {
+                    Some(value) => table.insert(key.as_slice(), value.as_slice())?,
+                    None => table.remove(key.as_slice())?,
+                };
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::helpers::{
+    kv::{MapID, TestMap as TestMapID},
+    Map,
+    MapRead,
+    NestedMap,
+    NestedMapRead,
+};
+
+use std::{fs::OpenOptions, io::Write};
+
+type Database = KVDatabase<LogEngine>;
+
+fn temp_dir() -> std::path::PathBuf {
+    tempfile::tempdir().expect("Failed to open temporary directory").into_path()
+}
+
+/// Opens the test database in the given directory, bypassing the registry of open databases.
+fn reopen(path: &std::path::Path) -> Database {
+    Database::new(LogEngine::open(path).expect("Failed to reopen the database"), u16::MAX, path.to_path_buf().into())
+}
+
+/// Opens the test map with the given `map_id` in the given database.
+fn open_map<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned>(
+    database: Database,
+    map_id: MapID,
+) -> KVMap<K, V, LogEngine> {
+    KVMap::new(database, map_id.into())
+}
+
+#[test]
+fn test_insert_get_and_remove() {
+    let map = Database::open_map_testing(temp_dir(), None, MapID::Test(TestMapID::Test)).expect("Failed to open map");
+
+    map.insert(123456789u32, "123456789".to_string()).expect("Failed to insert");
+    assert!(map.contains_key_confirmed(&123456789).unwrap());
+    assert!(!map.contains_key_confirmed(&0).unwrap());
+    assert_eq!(map.get_confirmed(&123456789).unwrap().map(|v| v.to_string()), Some("123456789".to_string()));
+
+    map.remove(&123456789).expect("Failed to remove");
+    assert!(map.get_confirmed(&123456789).unwrap().is_none());
+    assert_eq!(map.len_confirmed(), 0);
+}
+
+#[test]
+fn test_maps_are_separate() {
+    let database = Database::open_testing(temp_dir(), None).expect("Failed to open database");
+    let map1 = open_map::<u32, u32>(database.clone(), MapID::Test(TestMapID::Test));
+    let map2 = open_map::<u32, u32>(database, MapID::Test(TestMapID::Test2));
+
+    for i in 0..10 {
+        map1.insert(i, i).unwrap();
+    }
+    map2.insert(0, 100).unwrap();
+
+    assert_eq!(map1.len_confirmed(), 10);
+    assert_eq!(map2.len_confirmed(), 1);
+    assert_eq!(map1.keys_confirmed().map(|k| *k).collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+    assert_eq!(map2.iter_confirmed().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(), vec![(0, 100)]);
+}
+
+#[test]
+fn test_atomic_batch_and_rewind() {
+    let map = Database::open_map_testing(temp_dir(), None, MapID::Test(TestMapID::Test)).expect("Failed to open map");
+
+    map.start_atomic();
+    map.insert(1u32, 1u32).unwrap();
+    map.atomic_checkpoint();
+    map.insert(2, 2).unwrap();
+    // The writes are pending until the batch is finished.
+    assert_eq!(map.get_speculative(&2).unwrap().map(|v| *v), Some(2));
+    assert!(map.get_confirmed(&1).unwrap().is_none());
+
+    map.atomic_rewind();
+    assert!(map.get_speculative(&2).unwrap().is_none());
+    map.finish_atomic().unwrap();
+
+    assert_eq!(map.get_confirmed(&1).unwrap().map(|v| *v), Some(1));
+    assert!(map.get_confirmed(&2).unwrap().is_none());
+}
+
+#[test]
+fn test_reopen_and_recover_torn_write() {
+    let path = temp_dir();
+
+    // Write a few entries, both directly and as an atomic batch.
+    {
+        let map = open_map::<u32, String>(reopen(&path), MapID::Test(TestMapID::Test));
+        map.insert(1, "one".to_string()).unwrap();
+        map.start_atomic();
+        map.insert(2, "two".to_string()).unwrap();
+        map.insert(3, "three".to_string()).unwrap();
+        map.remove(&1).unwrap();
+        map.finish_atomic().unwrap();
+    }
+
+    // Simulate a write that was interrupted partway through.
+    let mut log = OpenOptions::new().append(true).open(path.join("ledger.log")).unwrap();
+    log.write_all(&[42u8; 20]).unwrap();
+    drop(log);
+
+    // The committed entries survive, and the incomplete record is discarded.
+    let map = open_map::<u32, String>(reopen(&path), MapID::Test(TestMapID::Test));
+    assert!(map.get_confirmed(&1).unwrap().is_none());
+    assert_eq!(map.get_confirmed(&2).unwrap().map(|v| v.to_string()), Some("two".to_string()));
+    assert_eq!(map.get_confirmed(&3).unwrap().map(|v| v.to_string()), Some("three".to_string()));
+
+    // The log remains writable after the recovery.
+    map.insert(4, "four".to_string()).unwrap();
+    let map = open_map::<u32, String>(reopen(&path), MapID::Test(TestMapID::Test));
+    assert_eq!(map.len_confirmed(), 3);
+}
+
+#[test]
+fn test_reject_corrupt_record() {
+    let path = temp_dir();
+
+    // Write two records.
+    {
+        let map = open_map::<u32, String>(reopen(&path), MapID::Test(TestMapID::Test));
+        map.insert(1, "one".to_string()).unwrap();
+        map.insert(2, "two".to_string()).unwrap();
+    }
+
+    // Corrupt the batch of the first record, which follows its 24-byte header.
+    let log_path = path.join("ledger.log");
+    let mut bytes = std::fs::read(&log_path).unwrap();
+    bytes[24] ^= 0xff;
+    std::fs::write(&log_path, bytes).unwrap();
+
+    // The corrupt record is followed by a valid record, so the log is not silently truncated.
+    assert!(LogEngine::open(&path).is_err());
+}
+
+#[test]
+fn test_reject_corrupt_record_length() {
+    let path = temp_dir();
+
+    // Write two records.
+    {
+        let map = open_map::<u32, String>(reopen(&path), MapID::Test(TestMapID::Test));
+        map.insert(1, "one".to_string()).unwrap();
+        map.insert(2, "two".to_string()).unwrap();
+    }
+
+    // Corrupt the length of the first record, so that it appears to extend beyond the end of the file.
+    let log_path = path.join("ledger.log");
+    let mut bytes = std::fs::read(&log_path).unwrap();
+    bytes[7] ^= 0xff;
+    std::fs::write(&log_path, &bytes).unwrap();
+
+    // The corrupt record is not mistaken for a partially written one, so the valid records are not truncated.
+    assert!(LogEngine::open(&path).is_err());
+    assert_eq!(std::fs::read(&log_path).unwrap(), bytes);
+
+    // Corrupt the batch of the last record instead.
+    bytes[7] ^= 0xff;
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    std::fs::write(&log_path, &bytes).unwrap();
+
+    // A fully written record with a corrupt batch is not truncated either.
+    assert!(LogEngine::open(&path).is_err());
+    assert_eq!(std::fs::read(&log_path).unwrap(), bytes);
+}
+
+#[test]
+fn test_recover_truncated_record() {
+    let path = temp_dir();
+
+    // Write two records.
+    {
+        let map = open_map::<u32, String>(reopen(&path), MapID::Test(TestMapID::Test));
+        map.insert(1, "one".to_string()).unwrap();
+        map.insert(2, "two".to_string()).unwrap();
+    }
+
+    // Simulate a write of the last record that was interrupted after its header.
+    let log = OpenOptions::new().write(true).open(path.join("ledger.log")).unwrap();
+    let log_len = log.metadata().unwrap().len();
+    log.set_len(log_len - 1).unwrap();
+    drop(log);
+
+    // Only the partially written record is discarded.
+    let map = open_map::<u32, String>(reopen(&path), MapID::Test(TestMapID::Test));
+    assert_eq!(map.get_confirmed(&1).unwrap().map(|v| v.to_string()), Some("one".to_string()));
+    assert!(map.get_confirmed(&2).unwrap().is_none());
+}
+
+#[test]
+fn test_redb_engine_reopen() {
+    let path = temp_dir();
+
+    // Write a few entries, both directly and as an atomic batch.
+    {
+        let database = KVDatabase::new(RedbEngine::open(&path).unwrap(), u16::MAX, path.clone().into());
+        let map = KVMap::<u32, String, RedbEngine>::new(database, MapID::Test(TestMapID::Test).into());
+        map.insert(1, "one".to_string()).unwrap();
+        map.start_atomic();
+        map.insert(2, "two".to_string()).unwrap();
+        map.remove(&1).unwrap();
+        map.finish_atomic().unwrap();
+    }
+
+    // The entries are read back from disk.
+    let database = KVDatabase::new(RedbEngine::open(&path).unwrap(), u16::MAX, path.clone().into());
+    let map = KVMap::<u32, String, RedbEngine>::new(database, MapID::Test(TestMapID::Test).into());
+    assert!(map.get_confirmed(&1).unwrap().is_none());
+    assert_eq!(map.get_confirmed(&2).unwrap().map(|v| v.to_string()), Some("two".to_string()));
+    assert_eq!(map.len_confirmed(), 1);
+}
+
+#[test]
+fn test_close_database() {
+    let path = temp_dir();
+    let is_open =
+        |path: &std::path::Path| DATABASES.lock().get(path).is_some_and(|database| database.strong_count() > 0);
+
+    // Open the database twice, which shares the same instance.
+    let database = Database::open_at(path.clone(), u16::MAX, path.clone().into()).unwrap();
+    let database_clone = Database::open_at(path.clone(), u16::MAX, path.clone().into()).unwrap();
+    assert!(Arc::ptr_eq(&database.0, &database_clone.0));
+    assert!(is_open(&path));
+
+    // The database is closed once all of its instances are dropped.
+    drop(database);
+    assert!(is_open(&path));
+    drop(database_clone);
+    assert!(!is_open(&path));
+
+    // Opening another database removes the closed one.
+    let other_path = temp_dir();
+    let _database = Database::open_at(other_path.clone(), u16::MAX, other_path.into()).unwrap();
+    assert!(!DATABASES.lock().contains_key(&path));
+}
+
+#[test]
+fn test_nested_map_remove_map() {
+    let map = Database::open_nested_map_testing(temp_dir(), None, MapID::Test(TestMapID::Test))
+        .expect("Failed to open nested map");
+
+    map.insert(0u32, 0u32, "a".to_string()).unwrap();
+    map.insert(0, 1, "b".to_string()).unwrap();
+    map.insert(1, 0, "c".to_string()).unwrap();
+    assert_eq!(map.len_map_confirmed(&0).unwrap(), 2);
+
+    // Queue a write to the map, then remove the map within the same batch.
+    map.start_atomic();
+    map.insert(0, 2, "d".to_string()).unwrap();
+    map.remove_map(&0).unwrap();
+    map.insert(1, 1, "e".to_string()).unwrap();
+    assert!(map.get_map_speculative(&0).unwrap().is_empty());
+    map.finish_atomic().unwrap();
+
+    assert!(map.get_map_confirmed(&0).unwrap().is_empty());
+    assert_eq!(map.get_map_confirmed(&1).unwrap(), vec![(0, "c".to_string()), (1, "e".to_string())]);
+    assert_eq!(map.keys_confirmed().count(), 2);
+}
@@ -0,0 +1,210 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(clippy::type_complexity)]
+
+use crate::{
+    helpers::kv::{CommitteeMap, KVDatabase, KVMap, KeyValueEngine, MapID, NestedKVMap, ProgramMap},
+    CommitteeStorage,
+    CommitteeStore,
+    FinalizeStorage,
+    JournalEntry,
+};
+use console::{
+    prelude::*,
+    program::{Identifier, Plaintext, ProgramID, Value},
+    types::Field,
+};
+use ledger_committee::Committee;
+
+use aleo_std_storage::StorageMode;
+use indexmap::IndexSet;
+
+/// A key-value finalize storage.
+#[derive(Clone)]
+pub struct FinalizeKV<N: Network, E: KeyValueEngine> {
+    /// The committee store.
+    committee_store: CommitteeStore<N, CommitteeKV<N, E>>,
+    /// The program ID map.
+    program_id_map: KVMap<ProgramID<N>, IndexSet<Identifier<N>>, E>,
+    /// The key-value map.
+    key_value_map: NestedKVMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>, E>,
+    /// The key-position map.
+    key_position_map: NestedKVMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, u64, E>,
+    /// The position-key map.
+    position_key_map: NestedKVMap<(ProgramID<N>, Identifier<N>), u64, Plaintext<N>, E>,
+    /// The mapping length map.
+    mapping_length_map: KVMap<(ProgramID<N>, Identifier<N>), u64, E>,
+    /// The history map.
+    history_map: NestedKVMap<Field<N>, u32, JournalEntry<N>, E>,
+    /// The history start map.
+    history_start_map: KVMap<u8, u32, E>,
+    /// The storage mode.
+    storage_mode: StorageMode,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> FinalizeStorage<N> for FinalizeKV<N, E> {
+    type CommitteeStorage = CommitteeKV<N, E>;
+    type ProgramIDMap = KVMap<ProgramID<N>, IndexSet<Identifier<N>>, E>;
+    type KeyValueMap = NestedKVMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>, E>;
+    type KeyPositionMap = NestedKVMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, u64, E>;
+    type PositionKeyMap = NestedKVMap<(ProgramID<N>, Identifier<N>), u64, Plaintext<N>, E>;
+    type MappingLengthMap = KVMap<(ProgramID<N>, Identifier<N>), u64, E>;
+    type HistoryMap = NestedKVMap<Field<N>, u32, JournalEntry<N>, E>;
+    type HistoryStartMap = KVMap<u8, u32, E>;
+
+    /// Initializes the finalize storage.
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        // Initialize the committee store.
+        let committee_store = CommitteeStore::<N, CommitteeKV<N, E>>::open(storage.clone())?;
+        // Return the finalize storage.
+        Ok(Self {
+            committee_store,
+            program_id_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Program(ProgramMap::ProgramID))?,
+            key_value_map: KVDatabase::<E>::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::KeyValueID))?,
+            key_position_map: KVDatabase::<E>::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::KeyPosition))?,
+            position_key_map: KVDatabase::<E>::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::PositionKey))?,
+            mapping_length_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Program(ProgramMap::MappingLength))?,
+            history_map: KVDatabase::<E>::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::History))?,
+            history_start_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Program(ProgramMap::HistoryStart))?,
+            storage_mode: storage.into(),
+        })
+    }
+
+    /// Initializes the test-variant of the storage.
+    #[cfg(any(test, feature = "test"))]
+    fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
+        // Initialize the committee store.
+        let committee_store = CommitteeStore::<N, CommitteeKV<N, E>>::open_testing(temp_dir.clone(), dev)?;
+        // Return the finalize storage.
+        Ok(Self {
+            committee_store,
+            program_id_map: KVDatabase::<E>::open_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::ProgramID))?,
+            key_value_map: KVDatabase::<E>::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::KeyValueID))?,
+            key_position_map: KVDatabase::<E>::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::KeyPosition))?,
+            position_key_map: KVDatabase::<E>::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::PositionKey))?,
+            mapping_length_map: KVDatabase::<E>::open_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::MappingLength))?,
+            history_map: KVDatabase::<E>::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::History))?,
+            history_start_map: KVDatabase::<E>::open_map_testing(temp_dir, dev, MapID::Program(ProgramMap::HistoryStart))?,
+            storage_mode: dev.into(),
+        })
+    }
+
+    /// Returns the committee store.
+    fn committee_store(&self) -> &CommitteeStore<N, Self::CommitteeStorage> {
+        &self.committee_store
+    }
+
+    /// Returns the program ID map.
+    fn program_id_map(&self) -> &Self::ProgramIDMap {
+        &self.program_id_map
+    }
+
+    /// Returns the key-value map.
+    fn key_value_map(&self) -> &Self::KeyValueMap {
+        &self.key_value_map
+    }
+
+    /// Returns the key-position map.
+    fn key_position_map(&self) -> &Self::KeyPositionMap {
+        &self.key_position_map
+    }
+
+    /// Returns the position-key map.
+    fn position_key_map(&self) -> &Self::PositionKeyMap {
+        &self.position_key_map
+    }
+
+    /// Returns the mapping length map.
+    fn mapping_length_map(&self) -> &Self::MappingLengthMap {
+        &self.mapping_length_map
+    }
+
+    /// Returns the history map.
+    fn history_map(&self) -> &Self::HistoryMap {
+        &self.history_map
+    }
+
+    /// Returns the history start map.
+    fn history_start_map(&self) -> &Self::HistoryStartMap {
+        &self.history_start_map
+    }
+
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
+    }
+}
+
+/// A key-value committee storage.
+#[derive(Clone)]
+pub struct CommitteeKV<N: Network, E: KeyValueEngine> {
+    /// The current round map.
+    current_round_map: KVMap<u8, u64, E>,
+    /// The round to height map.
+    round_to_height_map: KVMap<u64, u32, E>,
+    /// The committee map.
+    committee_map: KVMap<u32, Committee<N>, E>,
+    /// The storage mode.
+    storage_mode: StorageMode,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> CommitteeStorage<N> for CommitteeKV<N, E> {
+    type CurrentRoundMap = KVMap<u8, u64, E>;
+    type RoundToHeightMap = KVMap<u64, u32, E>;
+    type CommitteeMap = KVMap<u32, Committee<N>, E>;
+
+    /// Initializes the committee storage.
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        Ok(Self {
+            current_round_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Committee(CommitteeMap::CurrentRound))?,
+            round_to_height_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Committee(CommitteeMap::RoundToHeight))?,
+            committee_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Committee(CommitteeMap::Committee))?,
+            storage_mode: storage.into(),
+        })
+    }
+
+    /// Initializes the test-variant of the storage.
+    #[cfg(any(test, feature = "test"))]
+    fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
+        Ok(Self {
+            current_round_map: KVDatabase::<E>::open_map_testing(temp_dir.clone(), dev, MapID::Committee(CommitteeMap::CurrentRound))?,
+            round_to_height_map: KVDatabase::<E>::open_map_testing(temp_dir.clone(), dev, MapID::Committee(CommitteeMap::RoundToHeight))?,
+            committee_map: KVDatabase::<E>::open_map_testing(temp_dir, dev, MapID::Committee(CommitteeMap::Committee))?,
+            storage_mode: dev.into(),
+        })
+    }
+
+    /// Returns the current round map.
+    fn current_round_map(&self) -> &Self::CurrentRoundMap {
+        &self.current_round_map
+    }
+
+    /// Returns the round to height map.
+    fn round_to_height_map(&self) -> &Self::RoundToHeightMap {
+        &self.round_to_height_map
+    }
+
+    /// Returns the committee map.
+    fn committee_map(&self) -> &Self::CommitteeMap {
+        &self.committee_map
+    }
+
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    helpers::kv::{
+        DeploymentMap,
+        ExecutionMap,
+        FeeMap,
+        KVDatabase,
+        KVMap,
+        KeyValueEngine,
+        MapID,
+        TransactionMap,
+        TransitionKV,
+    },
+    DeploymentStorage,
+    DeploymentStore,
+    ExecutionStorage,
+    ExecutionStore,
+    FeeStorage,
+    FeeStore,
+    TransactionStorage,
+    TransactionType,
+    TransitionStore,
+};
+use console::{
+    prelude::*,
+    program::{Identifier, ProgramID, ProgramOwner, UpgradePolicy},
+};
+use synthesizer_program::Program;
+use synthesizer_snark::{Certificate, Proof, VerifyingKey};
+
+/// A database transaction storage.
+#[derive(Clone)]
+pub struct TransactionKV<N: Network, E: KeyValueEngine> {
+    /// The mapping of `transaction ID` to `transaction type`.
+    id_map: KVMap<N::TransactionID, TransactionType, E>,
+    /// The deployment store.
+    deployment_store: DeploymentStore<N, DeploymentKV<N, E>>,
+    /// The execution store.
+    execution_store: ExecutionStore<N, ExecutionKV<N, E>>,
+    /// The fee store.
+    fee_store: FeeStore<N, FeeKV<N, E>>,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> TransactionStorage<N> for TransactionKV<N, E> {
+    type IDMap = KVMap<N::TransactionID, TransactionType, E>;
+    type DeploymentStorage = DeploymentKV<N, E>;
+    type ExecutionStorage = ExecutionKV<N, E>;
+    type FeeStorage = FeeKV<N, E>;
+    type TransitionStorage = TransitionKV<N, E>;
+
+    /// Initializes the transaction storage.
+    fn open(transition_store: TransitionStore<N, Self::TransitionStorage>) -> Result<Self> {
+        // Initialize the fee store.
+        let fee_store = FeeStore::<N, FeeKV<N, E>>::open(transition_store)?;
+        // Initialize the deployment store.
+        let deployment_store = DeploymentStore::<N, DeploymentKV<N, E>>::open(fee_store.clone())?;
+        // Initialize the execution store.
+        let execution_store = ExecutionStore::<N, ExecutionKV<N, E>>::open(fee_store.clone())?;
+        // Return the transaction storage.
+        Ok(Self { id_map: KVDatabase::<E>::open_map(N::ID, execution_store.storage_mode().clone(), MapID::Transaction(TransactionMap::ID))?, deployment_store, execution_store, fee_store })
+    }
+
+    /// Returns the ID map.
+    fn id_map(&self) -> &Self::IDMap {
+        &self.id_map
+    }
+
+    /// Returns the deployment store.
+    fn deployment_store(&self) -> &DeploymentStore<N, Self::DeploymentStorage> {
+        &self.deployment_store
+    }
+
+    /// Returns the execution store.
+    fn execution_store(&self) -> &ExecutionStore<N, Self::ExecutionStorage> {
+        &self.execution_store
+    }
+
+    /// Returns the fee store.
+    fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage> {
+        &self.fee_store
+    }
+}
+
+/// A database deployment storage.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct DeploymentKV<N: Network, E: KeyValueEngine> {
+    /// The ID map.
+    id_map: KVMap<N::TransactionID, ProgramID<N>, E>,
+    /// The edition map.
+    edition_map: KVMap<ProgramID<N>, u16, E>,
+    /// The reverse ID map.
+    reverse_id_map: KVMap<(ProgramID<N>, u16), N::TransactionID, E>,
+    /// The program owner map.
+    owner_map: KVMap<(ProgramID<N>, u16), ProgramOwner<N>, E>,
+    /// The program map.
+    program_map: KVMap<(ProgramID<N>, u16), Program<N>, E>,
+    /// The verifying key map.
+    verifying_key_map: KVMap<(ProgramID<N>, Identifier<N>, u16), VerifyingKey<N>, E>,
+    /// The certificate map.
+    certificate_map: KVMap<(ProgramID<N>, Identifier<N>, u16), Certificate<N>, E>,
+    /// The upgrade policy map.
+    upgrade_policy_map: KVMap<(ProgramID<N>, u16), UpgradePolicy<N>, E>,
+    /// The fee store.
+    fee_store: FeeStore<N, FeeKV<N, E>>,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> DeploymentStorage<N> for DeploymentKV<N, E> {
+    type IDMap = KVMap<N::TransactionID, ProgramID<N>, E>;
+    type EditionMap = KVMap<ProgramID<N>, u16, E>;
+    type ReverseIDMap = KVMap<(ProgramID<N>, u16), N::TransactionID, E>;
+    type OwnerMap = KVMap<(ProgramID<N>, u16), ProgramOwner<N>, E>;
+    type ProgramMap = KVMap<(ProgramID<N>, u16), Program<N>, E>;
+    type VerifyingKeyMap = KVMap<(ProgramID<N>, Identifier<N>, u16), VerifyingKey<N>, E>;
+    type CertificateMap = KVMap<(ProgramID<N>, Identifier<N>, u16), Certificate<N>, E>;
+    type UpgradePolicyMap = KVMap<(ProgramID<N>, u16), UpgradePolicy<N>, E>;
+    type FeeStorage = FeeKV<N, E>;
+
+    /// Initializes the deployment storage.
+    fn open(fee_store: FeeStore<N, Self::FeeStorage>) -> Result<Self> {
+        // Retrieve the storage mode.
+        let storage_mode = fee_store.storage_mode();
+        Ok(Self {
+            id_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Deployment(DeploymentMap::ID))?,
+            edition_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Deployment(DeploymentMap::Edition))?,
+            reverse_id_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Deployment(DeploymentMap::ReverseID))?,
+            owner_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Deployment(DeploymentMap::Owner))?,
+            program_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Deployment(DeploymentMap::Program))?,
+            verifying_key_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Deployment(DeploymentMap::VerifyingKey))?,
+            certificate_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Deployment(DeploymentMap::Certificate))?,
+            upgrade_policy_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Deployment(DeploymentMap::UpgradePolicy))?,
+            fee_store,
+        })
+    }
+
+    /// Returns the ID map.
+    fn id_map(&self) -> &Self::IDMap {
+        &self.id_map
+    }
+
+    /// Returns the edition map.
+    fn edition_map(&self) -> &Self::EditionMap {
+        &self.edition_map
+    }
+
+    /// Returns the reverse ID map.
+    fn reverse_id_map(&self) -> &Self::ReverseIDMap {
+        &self.reverse_id_map
+    }
+
+    /// Returns the program owner map.
+    fn owner_map(&self) -> &Self::OwnerMap {
+        &self.owner_map
+    }
+
+    /// Returns the program map.
+    fn program_map(&self) -> &Self::ProgramMap {
+        &self.program_map
+    }
+
+    /// Returns the verifying key map.
+    fn verifying_key_map(&self) -> &Self::VerifyingKeyMap {
+        &self.verifying_key_map
+    }
+
+    /// Returns the certificate map.
+    fn certificate_map(&self) -> &Self::CertificateMap {
+        &self.certificate_map
+    }
+
+    /// Returns the upgrade policy map.
+    fn upgrade_policy_map(&self) -> &Self::UpgradePolicyMap {
+        &self.upgrade_policy_map
+    }
+
+    /// Returns the fee store.
+    fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage> {
+        &self.fee_store
+    }
+}
+
+/// A database execution storage.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct ExecutionKV<N: Network, E: KeyValueEngine> {
+    /// The ID map.
+    id_map: KVMap<N::TransactionID, (Vec<N::TransitionID>, bool), E>,
+    /// The reverse ID map.
+    reverse_id_map: KVMap<N::TransitionID, N::TransactionID, E>,
+    /// The inclusion map.
+    inclusion_map: KVMap<N::TransactionID, (N::StateRoot, Option<Proof<N>>), E>,
+    /// The fee store.
+    fee_store: FeeStore<N, FeeKV<N, E>>,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> ExecutionStorage<N> for ExecutionKV<N, E> {
+    type IDMap = KVMap<N::TransactionID, (Vec<N::TransitionID>, bool), E>;
+    type ReverseIDMap = KVMap<N::TransitionID, N::TransactionID, E>;
+    type InclusionMap = KVMap<N::TransactionID, (N::StateRoot, Option<Proof<N>>), E>;
+    type FeeStorage = FeeKV<N, E>;
+
+    /// Initializes the execution storage.
+    fn open(fee_store: FeeStore<N, Self::FeeStorage>) -> Result<Self> {
+        // Retrieve the storage mode.
+        let storage_mode = fee_store.storage_mode();
+        Ok(Self {
+            id_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Execution(ExecutionMap::ID))?,
+            reverse_id_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Execution(ExecutionMap::ReverseID))?,
+            inclusion_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Execution(ExecutionMap::Inclusion))?,
+            fee_store,
+        })
+    }
+
+    /// Returns the ID map.
+    fn id_map(&self) -> &Self::IDMap {
+        &self.id_map
+    }
+
+    /// Returns the reverse ID map.
+    fn reverse_id_map(&self) -> &Self::ReverseIDMap {
+        &self.reverse_id_map
+    }
+
+    /// Returns the inclusion map.
+    fn inclusion_map(&self) -> &Self::InclusionMap {
+        &self.inclusion_map
+    }
+
+    /// Returns the fee store.
+    fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage> {
+        &self.fee_store
+    }
+}
+
+/// A database for fee storage.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct FeeKV<N: Network, E: KeyValueEngine> {
+    /// The fee map.
+    fee_map: KVMap<N::TransactionID, (N::TransitionID, N::StateRoot, Option<Proof<N>>), E>,
+    /// The reverse fee map.
+    reverse_fee_map: KVMap<N::TransitionID, N::TransactionID, E>,
+    /// The transition store.
+    transition_store: TransitionStore<N, TransitionKV<N, E>>,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> FeeStorage<N> for FeeKV<N, E> {
+    type FeeMap = KVMap<N::TransactionID, (N::TransitionID, N::StateRoot, Option<Proof<N>>), E>;
+    type ReverseFeeMap = KVMap<N::TransitionID, N::TransactionID, E>;
+    type TransitionStorage = TransitionKV<N, E>;
+
+    /// Initializes the fee storage.
+    fn open(transition_store: TransitionStore<N, Self::TransitionStorage>) -> Result<Self> {
+        // Retrieve the storage mode.
+        let storage_mode = transition_store.storage_mode();
+        Ok(Self {
+            fee_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Fee(FeeMap::Fee))?,
+            reverse_fee_map: KVDatabase::<E>::open_map(N::ID, storage_mode.clone(), MapID::Fee(FeeMap::ReverseFee))?,
+            transition_store,
+        })
+    }
+
+    /// Returns the fee map.
+    fn fee_map(&self) -> &Self::FeeMap {
+        &self.fee_map
+    }
+
+    /// Returns the reverse fee map.
+    fn reverse_fee_map(&self) -> &Self::ReverseFeeMap {
+        &self.reverse_fee_map
+    }
+
+    /// Returns the transition store.
+    fn transition_store(&self) -> &TransitionStore<N, Self::TransitionStorage> {
+        &self.transition_store
+    }
+}
@@ -0,0 +1,324 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    helpers::kv::{
+        KVDatabase,
+        KVMap,
+        KeyValueEngine,
+        MapID,
+        TransitionInputMap,
+        TransitionMap,
+        TransitionOutputMap,
+    },
+    InputStorage,
+    InputStore,
+    OutputStorage,
+    OutputStore,
+    TransitionStorage,
+};
+use console::{
+    prelude::*,
+    program::{Ciphertext, Future, Identifier, Plaintext, ProgramID, Record},
+    types::{Field, Group},
+};
+
+use aleo_std_storage::StorageMode;
+
+/// A database transition storage.
+#[derive(Clone)]
+pub struct TransitionKV<N: Network, E: KeyValueEngine> {
+    /// The transition program IDs and function names.
+    locator_map: KVMap<N::TransitionID, (ProgramID<N>, Identifier<N>), E>,
+    /// The transition input store.
+    input_store: InputStore<N, InputKV<N, E>>,
+    /// The transition output store.
+    output_store: OutputStore<N, OutputKV<N, E>>,
+    /// The transition public keys.
+    tpk_map: KVMap<N::TransitionID, Group<N>, E>,
+    /// The reverse `tpk` map.
+    reverse_tpk_map: KVMap<Group<N>, N::TransitionID, E>,
+    /// The transition commitments.
+    tcm_map: KVMap<N::TransitionID, Field<N>, E>,
+    /// The reverse `tcm` map.
+    reverse_tcm_map: KVMap<Field<N>, N::TransitionID, E>,
+    /// The signer commitments.
+    scm_map: KVMap<N::TransitionID, Field<N>, E>,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> TransitionStorage<N> for TransitionKV<N, E> {
+    type LocatorMap = KVMap<N::TransitionID, (ProgramID<N>, Identifier<N>), E>;
+    type InputStorage = InputKV<N, E>;
+    type OutputStorage = OutputKV<N, E>;
+    type TPKMap = KVMap<N::TransitionID, Group<N>, E>;
+    type ReverseTPKMap = KVMap<Group<N>, N::TransitionID, E>;
+    type TCMMap = KVMap<N::TransitionID, Field<N>, E>;
+    type ReverseTCMMap = KVMap<Field<N>, N::TransitionID, E>;
+    type SCMMap = KVMap<N::TransitionID, Field<N>, E>;
+
+    /// Initializes the transition storage.
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        Ok(Self {
+            locator_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::Locator))?,
+            input_store: InputStore::open(storage.clone())?,
+            output_store: OutputStore::open(storage.clone())?,
+            tpk_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::TPK))?,
+            reverse_tpk_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::ReverseTPK))?,
+            tcm_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::TCM))?,
+            reverse_tcm_map: KVDatabase::<E>::open_map(N::ID, storage.clone(),  MapID::Transition(TransitionMap::ReverseTCM))?,
+            scm_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::SCM))?,
+        })
+    }
+
+    /// Returns the transition program IDs and function names.
+    fn locator_map(&self) -> &Self::LocatorMap {
+        &self.locator_map
+    }
+
+    /// Returns the transition input store.
+    fn input_store(&self) -> &InputStore<N, Self::InputStorage> {
+        &self.input_store
+    }
+
+    /// Returns the transition output store.
+    fn output_store(&self) -> &OutputStore<N, Self::OutputStorage> {
+        &self.output_store
+    }
+
+    /// Returns the transition public keys.
+    fn tpk_map(&self) -> &Self::TPKMap {
+        &self.tpk_map
+    }
+
+    /// Returns the reverse `tpk` map.
+    fn reverse_tpk_map(&self) -> &Self::ReverseTPKMap {
+        &self.reverse_tpk_map
+    }
+
+    /// Returns the transition commitments.
+    fn tcm_map(&self) -> &Self::TCMMap {
+        &self.tcm_map
+    }
+
+    /// Returns the reverse `tcm` map.
+    fn reverse_tcm_map(&self) -> &Self::ReverseTCMMap {
+        &self.reverse_tcm_map
+    }
+
+    /// Returns the signer commitments.
+    fn scm_map(&self) -> &Self::SCMMap {
+        &self.scm_map
+    }
+}
+
+/// An database transition input storage.
+#[derive(Clone)]
+pub struct InputKV<N: Network, E: KeyValueEngine> {
+    /// The mapping of `transition ID` to `input IDs`.
+    id_map: KVMap<N::TransitionID, Vec<Field<N>>, E>,
+    /// The mapping of `input ID` to `transition ID`.
+    reverse_id_map: KVMap<Field<N>, N::TransitionID, E>,
+    /// The mapping of `plaintext hash` to `(optional) plaintext`.
+    constant: KVMap<Field<N>, Option<Plaintext<N>>, E>,
+    /// The mapping of `plaintext hash` to `(optional) plaintext`.
+    public: KVMap<Field<N>, Option<Plaintext<N>>, E>,
+    /// The mapping of `ciphertext hash` to `(optional) ciphertext`.
+    private: KVMap<Field<N>, Option<Ciphertext<N>>, E>,
+    /// The mapping of `serial number` to `tag`.
+    record: KVMap<Field<N>, Field<N>, E>,
+    /// The mapping of `record tag` to `serial number`.
+    record_tag: KVMap<Field<N>, Field<N>, E>,
+    /// The mapping of `external commitment` to `()`. Note: This is **not** the record commitment.
+    external_record: KVMap<Field<N>, (), E>,
+    /// The storage mode.
+    storage_mode: StorageMode,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> InputStorage<N> for InputKV<N, E> {
+    type IDMap = KVMap<N::TransitionID, Vec<Field<N>>, E>;
+    type ReverseIDMap = KVMap<Field<N>, N::TransitionID, E>;
+    type ConstantMap = KVMap<Field<N>, Option<Plaintext<N>>, E>;
+    type PublicMap = KVMap<Field<N>, Option<Plaintext<N>>, E>;
+    type PrivateMap = KVMap<Field<N>, Option<Ciphertext<N>>, E>;
+    type RecordMap = KVMap<Field<N>, Field<N>, E>;
+    type RecordTagMap = KVMap<Field<N>, Field<N>, E>;
+    type ExternalRecordMap = KVMap<Field<N>, (), E>;
+
+    /// Initializes the transition input storage.
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        Ok(Self {
+            id_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionInput(TransitionInputMap::ID))?,
+            reverse_id_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionInput(TransitionInputMap::ReverseID))?,
+            constant: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionInput(TransitionInputMap::Constant))?,
+            public: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionInput(TransitionInputMap::Public))?,
+            private: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionInput(TransitionInputMap::Private))?,
+            record: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionInput(TransitionInputMap::Record))?,
+            record_tag: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionInput(TransitionInputMap::RecordTag))?,
+            external_record: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionInput(TransitionInputMap::ExternalRecord))?,
+            storage_mode: storage.into(),
+        })
+    }
+
+    /// Returns the ID map.
+    fn id_map(&self) -> &Self::IDMap {
+        &self.id_map
+    }
+
+    /// Returns the reverse ID map.
+    fn reverse_id_map(&self) -> &Self::ReverseIDMap {
+        &self.reverse_id_map
+    }
+
+    /// Returns the constant map.
+    fn constant_map(&self) -> &Self::ConstantMap {
+        &self.constant
+    }
+
+    /// Returns the public map.
+    fn public_map(&self) -> &Self::PublicMap {
+        &self.public
+    }
+
+    /// Returns the private map.
+    fn private_map(&self) -> &Self::PrivateMap {
+        &self.private
+    }
+
+    /// Returns the record map.
+    fn record_map(&self) -> &Self::RecordMap {
+        &self.record
+    }
+
+    /// Returns the record tag map.
+    fn record_tag_map(&self) -> &Self::RecordTagMap {
+        &self.record_tag
+    }
+
+    /// Returns the external record map.
+    fn external_record_map(&self) -> &Self::ExternalRecordMap {
+        &self.external_record
+    }
+
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
+    }
+}
+
+/// A database transition output storage.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct OutputKV<N: Network, E: KeyValueEngine> {
+    /// The mapping of `transition ID` to `output IDs`.
+    id_map: KVMap<N::TransitionID, Vec<Field<N>>, E>,
+    /// The mapping of `output ID` to `transition ID`.
+    reverse_id_map: KVMap<Field<N>, N::TransitionID, E>,
+    /// The mapping of `plaintext hash` to `(optional) plaintext`.
+    constant: KVMap<Field<N>, Option<Plaintext<N>>, E>,
+    /// The mapping of `plaintext hash` to `(optional) plaintext`.
+    public: KVMap<Field<N>, Option<Plaintext<N>>, E>,
+    /// The mapping of `ciphertext hash` to `(optional) ciphertext`.
+    private: KVMap<Field<N>, Option<Ciphertext<N>>, E>,
+    /// The mapping of `commitment` to `(checksum, (optional) record ciphertext)`.
+    record: KVMap<Field<N>, (Field<N>, Option<Record<N, Ciphertext<N>>>), E>,
+    /// The mapping of `record nonce` to `commitment`.
+    record_nonce: KVMap<Group<N>, Field<N>, E>,
+    /// The mapping of `external commitment` to `()`. Note: This is **not** the record commitment.
+    external_record: KVMap<Field<N>, (), E>,
+    /// The mapping of `future hash` to `(optional) future`.
+    future: KVMap<Field<N>, Option<Future<N>>, E>,
+    /// The storage mode.
+    storage_mode: StorageMode,
+}
+
+#[rustfmt::skip]
+impl<N: Network, E: KeyValueEngine> OutputStorage<N> for OutputKV<N, E> {
+    type IDMap = KVMap<N::TransitionID, Vec<Field<N>>, E>;
+    type ReverseIDMap = KVMap<Field<N>, N::TransitionID, E>;
+    type ConstantMap = KVMap<Field<N>, Option<Plaintext<N>>, E>;
+    type PublicMap = KVMap<Field<N>, Option<Plaintext<N>>, E>;
+    type PrivateMap = KVMap<Field<N>, Option<Ciphertext<N>>, E>;
+    type RecordMap = KVMap<Field<N>, (Field<N>, Option<Record<N, Ciphertext<N>>>), E>;
+    type RecordNonceMap = KVMap<Group<N>, Field<N>, E>;
+    type ExternalRecordMap = KVMap<Field<N>, (), E>;
+    type FutureMap = KVMap<Field<N>, Option<Future<N>>, E>;
+
+    /// Initializes the transition output storage.
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        Ok(Self {
+            id_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::ID))?,
+            reverse_id_map: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::ReverseID))?,
+            constant: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::Constant))?,
+            public: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::Public))?,
+            private: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::Private))?,
+            record: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::Record))?,
+            record_nonce: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::RecordNonce))?,
+            external_record: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::ExternalRecord))?,
+            future: KVDatabase::<E>::open_map(N::ID, storage.clone(), MapID::TransitionOutput(TransitionOutputMap::Future))?,
+            storage_mode: storage.into(),
+        })
+    }
+
+    /// Returns the ID map.
+    fn id_map(&self) -> &Self::IDMap {
+        &self.id_map
+    }
+
+    /// Returns the reverse ID map.
+    fn reverse_id_map(&self) -> &Self::ReverseIDMap {
+        &self.reverse_id_map
+    }
+
+    /// Returns the constant map.
+    fn constant_map(&self) -> &Self::ConstantMap {
+        &self.constant
+    }
+
+    /// Returns the public map.
+    fn public_map(&self) -> &Self::PublicMap {
+        &self.public
+    }
+
+    /// Returns the private map.
+    fn private_map(&self) -> &Self::PrivateMap {
+        &self.private
+    }
+
+    /// Returns the record map.
+    fn record_map(&self) -> &Self::RecordMap {
+        &self.record
+    }
+
+    /// Returns the record nonce map.
+    fn record_nonce_map(&self) -> &Self::RecordNonceMap {
+        &self.record_nonce
+    }
+
+    /// Returns the external record map.
+    fn external_record_map(&self) -> &Self::ExternalRecordMap {
+        &self.external_record
+    }
+
+    /// Returns the future map.
+    fn future_map(&self) -> &Self::FutureMap {
+        &self.future
+    }
+
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
+    }
+}
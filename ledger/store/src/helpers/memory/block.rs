@@ -13,13 +13,18 @@
 // limitations under the License.
 
 use crate::{
-    helpers::memory::{MemoryMap, TransactionMemory, TransitionMemory},
+    helpers::memory::{MemoryMap, NestedMemoryMap, TransactionMemory, TransitionMemory},
     BlockStorage,
     ConfirmedTxType,
     TransactionStore,
+    TransitionIndexKey,
     TransitionStore,
 };
-use console::{prelude::*, types::Field};
+use console::{
+    prelude::*,
+    program::{Identifier, ProgramID},
+    types::{Address, Field},
+};
 use ledger_authority::Authority;
 use ledger_block::{Header, Ratifications, Rejected, Solutions};
 use ledger_puzzle::SolutionID;
@@ -66,6 +71,18 @@ pub struct BlockMemory<N: Network> {
         MemoryMap<N::TransactionID, (N::BlockHash, ConfirmedTxType<N>, Vec<FinalizeOperation<N>>)>,
     /// The rejected deployment or execution map.
     rejected_deployment_or_execution_map: MemoryMap<Field<N>, Rejected<N>>,
+    /// The pruned map.
+    pruned_map: MemoryMap<N::BlockHash, u32>,
+    /// The ratified finalize operations map.
+    ratified_finalize_operations_map: MemoryMap<N::BlockHash, Vec<FinalizeOperation<N>>>,
+    /// The transition index start map.
+    transition_index_start_map: MemoryMap<u8, u32>,
+    /// The function index map.
+    function_index_map: NestedMemoryMap<(ProgramID<N>, Identifier<N>), TransitionIndexKey, N::TransitionID>,
+    /// The address index map.
+    address_index_map: NestedMemoryMap<Address<N>, TransitionIndexKey, N::TransitionID>,
+    /// The pruning depth map.
+    pruning_depth_map: MemoryMap<u8, u32>,
     /// The transaction store.
     transaction_store: TransactionStore<N, TransactionMemory<N>>,
 }
@@ -89,6 +106,12 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
     type RejectedOrAbortedTransactionIDMap = MemoryMap<N::TransactionID, N::BlockHash>;
     type ConfirmedTransactionsMap = MemoryMap<N::TransactionID, (N::BlockHash, ConfirmedTxType<N>, Vec<FinalizeOperation<N>>)>;
     type RejectedDeploymentOrExecutionMap = MemoryMap<Field<N>, Rejected<N>>;
+    type PrunedMap = MemoryMap<N::BlockHash, u32>;
+    type RatifiedFinalizeOperationsMap = MemoryMap<N::BlockHash, Vec<FinalizeOperation<N>>>;
+    type TransitionIndexStartMap = MemoryMap<u8, u32>;
+    type FunctionIndexMap = NestedMemoryMap<(ProgramID<N>, Identifier<N>), TransitionIndexKey, N::TransitionID>;
+    type AddressIndexMap = NestedMemoryMap<Address<N>, TransitionIndexKey, N::TransitionID>;
+    type PruningDepthMap = MemoryMap<u8, u32>;
     type TransactionStorage = TransactionMemory<N>;
     type TransitionStorage = TransitionMemory<N>;
 
@@ -117,6 +140,12 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
             rejected_or_aborted_transaction_id_map: MemoryMap::default(),
             confirmed_transactions_map: MemoryMap::default(),
             rejected_deployment_or_execution_map: MemoryMap::default(),
+            pruned_map: MemoryMap::default(),
+            ratified_finalize_operations_map: MemoryMap::default(),
+            transition_index_start_map: MemoryMap::default(),
+            function_index_map: NestedMemoryMap::default(),
+            address_index_map: NestedMemoryMap::default(),
+            pruning_depth_map: MemoryMap::default(),
             transaction_store,
         })
     }
@@ -206,6 +235,36 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
         &self.rejected_deployment_or_execution_map
     }
 
+    /// Returns the pruned map.
+    fn pruned_map(&self) -> &Self::PrunedMap {
+        &self.pruned_map
+    }
+
+    /// Returns the ratified finalize operations map.
+    fn ratified_finalize_operations_map(&self) -> &Self::RatifiedFinalizeOperationsMap {
+        &self.ratified_finalize_operations_map
+    }
+
+    /// Returns the transition index start map.
+    fn transition_index_start_map(&self) -> &Self::TransitionIndexStartMap {
+        &self.transition_index_start_map
+    }
+
+    /// Returns the function index map.
+    fn function_index_map(&self) -> &Self::FunctionIndexMap {
+        &self.function_index_map
+    }
+
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap {
+        &self.address_index_map
+    }
+
+    /// Returns the pruning depth map.
+    fn pruning_depth_map(&self) -> &Self::PruningDepthMap {
+        &self.pruning_depth_map
+    }
+
     /// Returns the transaction store.
     fn transaction_store(&self) -> &TransactionStore<N, Self::TransactionStorage> {
         &self.transaction_store
@@ -344,6 +344,36 @@ impl<
         Ok(key_values)
     }
 
+    ///
+    /// Returns the confirmed key-value pairs for the given map, in ascending order of their serialized keys,
+    /// skipping the first `offset` pairs and returning at most `limit` pairs.
+    ///
+    fn get_map_range_confirmed(&'a self, map: &M, offset: usize, limit: usize) -> Result<Vec<(K, V)>> {
+        // Serialize 'm'.
+        let m = bincode::serialize(map)?;
+        // Acquire the read locks on 'map' and 'map_inner'.
+        let keys = self.map.read();
+        let map_inner = self.map_inner.read();
+        // Retrieve the keys for the serialized map.
+        let Some(keys) = keys.get(&m) else {
+            return Ok(Default::default());
+        };
+
+        // Return the requested key-value pairs.
+        keys.iter()
+            .skip(offset)
+            .take(limit)
+            .map(|k| {
+                // Concatenate 'm' and 'k' with a 0-byte separator.
+                let mk = to_map_key(&m, k);
+                // Retrieve the value.
+                let value = map_inner.get(&mk).ok_or_else(|| anyhow!("Missing the value of a nested map entry"))?;
+                // Return the key-value pair.
+                Ok((bincode::deserialize(k)?, value.clone()))
+            })
+            .collect()
+    }
+
     ///
     /// Returns the speculative key-value pairs for the given map, if it exists.
     ///
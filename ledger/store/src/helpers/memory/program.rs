@@ -19,10 +19,12 @@ use crate::{
     CommitteeStorage,
     CommitteeStore,
     FinalizeStorage,
+    JournalEntry,
 };
 use console::{
     prelude::*,
     program::{Identifier, Plaintext, ProgramID, Value},
+    types::Field,
 };
 use ledger_committee::Committee;
 
@@ -38,6 +40,16 @@ pub struct FinalizeMemory<N: Network> {
     program_id_map: MemoryMap<ProgramID<N>, IndexSet<Identifier<N>>>,
     /// The key-value map.
     key_value_map: NestedMemoryMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>,
+    /// The key-position map.
+    key_position_map: NestedMemoryMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, u64>,
+    /// The position-key map.
+    position_key_map: NestedMemoryMap<(ProgramID<N>, Identifier<N>), u64, Plaintext<N>>,
+    /// The mapping length map.
+    mapping_length_map: MemoryMap<(ProgramID<N>, Identifier<N>), u64>,
+    /// The history map.
+    history_map: NestedMemoryMap<Field<N>, u32, JournalEntry<N>>,
+    /// The history start map.
+    history_start_map: MemoryMap<u8, u32>,
     /// The storage mode.
     storage_mode: StorageMode,
 }
@@ -47,6 +59,11 @@ impl<N: Network> FinalizeStorage<N> for FinalizeMemory<N> {
     type CommitteeStorage = CommitteeMemory<N>;
     type ProgramIDMap = MemoryMap<ProgramID<N>, IndexSet<Identifier<N>>>;
     type KeyValueMap = NestedMemoryMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>;
+    type KeyPositionMap = NestedMemoryMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, u64>;
+    type PositionKeyMap = NestedMemoryMap<(ProgramID<N>, Identifier<N>), u64, Plaintext<N>>;
+    type MappingLengthMap = MemoryMap<(ProgramID<N>, Identifier<N>), u64>;
+    type HistoryMap = NestedMemoryMap<Field<N>, u32, JournalEntry<N>>;
+    type HistoryStartMap = MemoryMap<u8, u32>;
 
     /// Initializes the finalize storage.
     fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
@@ -57,6 +74,11 @@ impl<N: Network> FinalizeStorage<N> for FinalizeMemory<N> {
             committee_store,
             program_id_map: MemoryMap::default(),
             key_value_map: NestedMemoryMap::default(),
+            key_position_map: NestedMemoryMap::default(),
+            position_key_map: NestedMemoryMap::default(),
+            mapping_length_map: MemoryMap::default(),
+            history_map: NestedMemoryMap::default(),
+            history_start_map: MemoryMap::default(),
             storage_mode: storage.into(),
         })
     }
@@ -82,6 +104,31 @@ impl<N: Network> FinalizeStorage<N> for FinalizeMemory<N> {
         &self.key_value_map
     }
 
+    /// Returns the key-position map.
+    fn key_position_map(&self) -> &Self::KeyPositionMap {
+        &self.key_position_map
+    }
+
+    /// Returns the position-key map.
+    fn position_key_map(&self) -> &Self::PositionKeyMap {
+        &self.position_key_map
+    }
+
+    /// Returns the mapping length map.
+    fn mapping_length_map(&self) -> &Self::MappingLengthMap {
+        &self.mapping_length_map
+    }
+
+    /// Returns the history map.
+    fn history_map(&self) -> &Self::HistoryMap {
+        &self.history_map
+    }
+
+    /// Returns the history start map.
+    fn history_start_map(&self) -> &Self::HistoryStartMap {
+        &self.history_start_map
+    }
+
     /// Returns the storage mode.
     fn storage_mode(&self) -> &StorageMode {
         &self.storage_mode
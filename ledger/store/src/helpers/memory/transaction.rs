@@ -26,7 +26,7 @@ use crate::{
 };
 use console::{
     prelude::*,
-    program::{Identifier, ProgramID, ProgramOwner},
+    program::{Identifier, ProgramID, ProgramOwner, UpgradePolicy},
 };
 use synthesizer_program::Program;
 use synthesizer_snark::{Certificate, Proof, VerifyingKey};
@@ -103,6 +103,8 @@ pub struct DeploymentMemory<N: Network> {
     verifying_key_map: MemoryMap<(ProgramID<N>, Identifier<N>, u16), VerifyingKey<N>>,
     /// The certificate map.
     certificate_map: MemoryMap<(ProgramID<N>, Identifier<N>, u16), Certificate<N>>,
+    /// The upgrade policy map.
+    upgrade_policy_map: MemoryMap<(ProgramID<N>, u16), UpgradePolicy<N>>,
     /// The fee store.
     fee_store: FeeStore<N, FeeMemory<N>>,
 }
@@ -116,6 +118,7 @@ impl<N: Network> DeploymentStorage<N> for DeploymentMemory<N> {
     type ProgramMap = MemoryMap<(ProgramID<N>, u16), Program<N>>;
     type VerifyingKeyMap = MemoryMap<(ProgramID<N>, Identifier<N>, u16), VerifyingKey<N>>;
     type CertificateMap = MemoryMap<(ProgramID<N>, Identifier<N>, u16), Certificate<N>>;
+    type UpgradePolicyMap = MemoryMap<(ProgramID<N>, u16), UpgradePolicy<N>>;
     type FeeStorage = FeeMemory<N>;
 
     /// Initializes the deployment storage.
@@ -128,6 +131,7 @@ impl<N: Network> DeploymentStorage<N> for DeploymentMemory<N> {
             program_map: MemoryMap::default(),
             verifying_key_map: MemoryMap::default(),
             certificate_map: MemoryMap::default(),
+            upgrade_policy_map: MemoryMap::default(),
             fee_store,
         })
     }
@@ -167,6 +171,11 @@ impl<N: Network> DeploymentStorage<N> for DeploymentMemory<N> {
         &self.certificate_map
     }
 
+    /// Returns the upgrade policy map.
+    fn upgrade_policy_map(&self) -> &Self::UpgradePolicyMap {
+        &self.upgrade_policy_map
+    }
+
     /// Returns the fee store.
     fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage> {
         &self.fee_store
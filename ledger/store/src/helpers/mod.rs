@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(any(feature = "rocks", feature = "kv"))]
+mod id;
+
+#[cfg(feature = "kv")]
+pub mod kv;
 pub mod memory;
 #[cfg(feature = "rocks")]
 pub mod rocksdb;
@@ -14,7 +14,7 @@
 
 use crate::{
     helpers::rocksdb::{
-        internal::{self, DataMap, Database},
+        internal::{self, DataMap, Database, NestedDataMap},
         BlockMap,
         MapID,
         TransactionDB,
@@ -23,9 +23,14 @@ use crate::{
     BlockStorage,
     ConfirmedTxType,
     TransactionStore,
+    TransitionIndexKey,
     TransitionStore,
 };
-use console::{prelude::*, types::Field};
+use console::{
+    prelude::*,
+    program::{Identifier, ProgramID},
+    types::{Address, Field},
+};
 use ledger_authority::Authority;
 use ledger_block::{Header, Ratifications, Rejected, Solutions};
 use ledger_puzzle::SolutionID;
@@ -72,6 +77,18 @@ pub struct BlockDB<N: Network> {
         DataMap<N::TransactionID, (N::BlockHash, ConfirmedTxType<N>, Vec<FinalizeOperation<N>>)>,
     /// The rejected deployment or execution map.
     rejected_deployment_or_execution_map: DataMap<Field<N>, Rejected<N>>,
+    /// The pruned map.
+    pruned_map: DataMap<N::BlockHash, u32>,
+    /// The ratified finalize operations map.
+    ratified_finalize_operations_map: DataMap<N::BlockHash, Vec<FinalizeOperation<N>>>,
+    /// The transition index start map.
+    transition_index_start_map: DataMap<u8, u32>,
+    /// The function index map.
+    function_index_map: NestedDataMap<(ProgramID<N>, Identifier<N>), TransitionIndexKey, N::TransitionID>,
+    /// The address index map.
+    address_index_map: NestedDataMap<Address<N>, TransitionIndexKey, N::TransitionID>,
+    /// The pruning depth map.
+    pruning_depth_map: DataMap<u8, u32>,
     /// The transaction store.
     transaction_store: TransactionStore<N, TransactionDB<N>>,
 }
@@ -95,6 +112,12 @@ impl<N: Network> BlockStorage<N> for BlockDB<N> {
     type RejectedOrAbortedTransactionIDMap = DataMap<N::TransactionID, N::BlockHash>;
     type ConfirmedTransactionsMap = DataMap<N::TransactionID, (N::BlockHash, ConfirmedTxType<N>, Vec<FinalizeOperation<N>>)>;
     type RejectedDeploymentOrExecutionMap = DataMap<Field<N>, Rejected<N>>;
+    type PrunedMap = DataMap<N::BlockHash, u32>;
+    type RatifiedFinalizeOperationsMap = DataMap<N::BlockHash, Vec<FinalizeOperation<N>>>;
+    type TransitionIndexStartMap = DataMap<u8, u32>;
+    type FunctionIndexMap = NestedDataMap<(ProgramID<N>, Identifier<N>), TransitionIndexKey, N::TransitionID>;
+    type AddressIndexMap = NestedDataMap<Address<N>, TransitionIndexKey, N::TransitionID>;
+    type PruningDepthMap = DataMap<u8, u32>;
     type TransactionStorage = TransactionDB<N>;
     type TransitionStorage = TransitionDB<N>;
 
@@ -122,7 +145,13 @@ impl<N: Network> BlockStorage<N> for BlockDB<N> {
             aborted_transaction_ids_map: internal::RocksDB::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::AbortedTransactionIDs))?,
             rejected_or_aborted_transaction_id_map: internal::RocksDB::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::RejectedOrAbortedTransactionID))?,
             confirmed_transactions_map: internal::RocksDB::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::ConfirmedTransactions))?,
-            rejected_deployment_or_execution_map: internal::RocksDB::open_map(N::ID, storage, MapID::Block(BlockMap::RejectedDeploymentOrExecution))?,
+            rejected_deployment_or_execution_map: internal::RocksDB::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::RejectedDeploymentOrExecution))?,
+            pruned_map: internal::RocksDB::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::Pruned))?,
+            ratified_finalize_operations_map: internal::RocksDB::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::RatifiedFinalizeOperations))?,
+            transition_index_start_map: internal::RocksDB::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::TransitionIndexStart))?,
+            function_index_map: internal::RocksDB::open_nested_map(N::ID, storage.clone(), MapID::Block(BlockMap::FunctionIndex))?,
+            address_index_map: internal::RocksDB::open_nested_map(N::ID, storage, MapID::Block(BlockMap::AddressIndex))?,
+            pruning_depth_map: internal::RocksDB::open_map(N::ID, storage.clone(), MapID::Block(BlockMap::PruningDepth))?,
             transaction_store,
         })
     }
@@ -212,6 +241,36 @@ impl<N: Network> BlockStorage<N> for BlockDB<N> {
         &self.rejected_deployment_or_execution_map
     }
 
+    /// Returns the pruned map.
+    fn pruned_map(&self) -> &Self::PrunedMap {
+        &self.pruned_map
+    }
+
+    /// Returns the ratified finalize operations map.
+    fn ratified_finalize_operations_map(&self) -> &Self::RatifiedFinalizeOperationsMap {
+        &self.ratified_finalize_operations_map
+    }
+
+    /// Returns the transition index start map.
+    fn transition_index_start_map(&self) -> &Self::TransitionIndexStartMap {
+        &self.transition_index_start_map
+    }
+
+    /// Returns the function index map.
+    fn function_index_map(&self) -> &Self::FunctionIndexMap {
+        &self.function_index_map
+    }
+
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap {
+        &self.address_index_map
+    }
+
+    /// Returns the pruning depth map.
+    fn pruning_depth_map(&self) -> &Self::PruningDepthMap {
+        &self.pruning_depth_map
+    }
+
     /// Returns the transaction store.
     fn transaction_store(&self) -> &TransactionStore<N, Self::TransactionStorage> {
         &self.transaction_store
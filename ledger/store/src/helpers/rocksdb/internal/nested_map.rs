@@ -468,6 +468,58 @@ impl<
             .collect::<Result<_, bincode::Error>>()?)
     }
 
+    ///
+    /// Returns the key-value pairs for the given map, in ascending order of their serialized keys,
+    /// skipping the first `offset` pairs and returning at most `limit` pairs.
+    ///
+    fn get_map_range_confirmed(&'a self, map: &M, offset: usize, limit: usize) -> Result<Vec<(K, V)>> {
+        // Serialize the map.
+        let serialized_map = bincode::serialize(map)?;
+
+        // Initialize a vector for the entries.
+        let mut entries = Vec::new();
+
+        // Construct an iterator over the DB with the specified prefix.
+        let iterator = self.database.prefix_iterator(&self.create_prefixed_map(map)?);
+
+        // Iterate over the entries in the DB with the specified prefix.
+        for (position, entry) in iterator.enumerate() {
+            // If enough entries were retrieved, stop.
+            if entries.len() >= limit {
+                break;
+            }
+
+            let (map_key, value) = entry?;
+
+            // Extract the bytes belonging to the map and the key.
+            let Ok((entry_map, entry_key)) = get_map_and_key(&map_key) else {
+                break;
+            };
+
+            // If the 'entry_map' no longer matches the 'serialized_map',
+            // we've moved past the relevant keys and can break the loop.
+            if entry_map != serialized_map {
+                break;
+            }
+
+            // Skip the entries before the offset, without deserializing them.
+            if position >= offset {
+                entries.push((entry_key.to_owned(), value));
+            }
+        }
+
+        // Deserialize the entries.
+        Ok(entries
+            .into_iter()
+            .map(|(k, v)| {
+                let k = bincode::deserialize::<K>(&k);
+                let v = bincode::deserialize::<V>(&v);
+
+                k.and_then(|k| v.map(|v| (k, v)))
+            })
+            .collect::<Result<_, bincode::Error>>()?)
+    }
+
     ///
     /// Returns the speculative key-value pairs for the given map, if it exists.
     ///
@@ -19,10 +19,12 @@ use crate::{
     CommitteeStorage,
     CommitteeStore,
     FinalizeStorage,
+    JournalEntry,
 };
 use console::{
     prelude::*,
     program::{Identifier, Plaintext, ProgramID, Value},
+    types::Field,
 };
 use ledger_committee::Committee;
 
@@ -38,6 +40,16 @@ pub struct FinalizeDB<N: Network> {
     program_id_map: DataMap<ProgramID<N>, IndexSet<Identifier<N>>>,
     /// The key-value map.
     key_value_map: NestedDataMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>,
+    /// The key-position map.
+    key_position_map: NestedDataMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, u64>,
+    /// The position-key map.
+    position_key_map: NestedDataMap<(ProgramID<N>, Identifier<N>), u64, Plaintext<N>>,
+    /// The mapping length map.
+    mapping_length_map: DataMap<(ProgramID<N>, Identifier<N>), u64>,
+    /// The history map.
+    history_map: NestedDataMap<Field<N>, u32, JournalEntry<N>>,
+    /// The history start map.
+    history_start_map: DataMap<u8, u32>,
     /// The storage mode.
     storage_mode: StorageMode,
 }
@@ -47,6 +59,11 @@ impl<N: Network> FinalizeStorage<N> for FinalizeDB<N> {
     type CommitteeStorage = CommitteeDB<N>;
     type ProgramIDMap = DataMap<ProgramID<N>, IndexSet<Identifier<N>>>;
     type KeyValueMap = NestedDataMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>;
+    type KeyPositionMap = NestedDataMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, u64>;
+    type PositionKeyMap = NestedDataMap<(ProgramID<N>, Identifier<N>), u64, Plaintext<N>>;
+    type MappingLengthMap = DataMap<(ProgramID<N>, Identifier<N>), u64>;
+    type HistoryMap = NestedDataMap<Field<N>, u32, JournalEntry<N>>;
+    type HistoryStartMap = DataMap<u8, u32>;
 
     /// Initializes the finalize storage.
     fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
@@ -57,6 +74,11 @@ impl<N: Network> FinalizeStorage<N> for FinalizeDB<N> {
             committee_store,
             program_id_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(), MapID::Program(ProgramMap::ProgramID))?,
             key_value_map: rocksdb::RocksDB::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::KeyValueID))?,
+            key_position_map: rocksdb::RocksDB::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::KeyPosition))?,
+            position_key_map: rocksdb::RocksDB::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::PositionKey))?,
+            mapping_length_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(), MapID::Program(ProgramMap::MappingLength))?,
+            history_map: rocksdb::RocksDB::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::History))?,
+            history_start_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(), MapID::Program(ProgramMap::HistoryStart))?,
             storage_mode: storage.into(),
         })
     }
@@ -70,7 +92,12 @@ impl<N: Network> FinalizeStorage<N> for FinalizeDB<N> {
         Ok(Self {
             committee_store,
             program_id_map: rocksdb::RocksDB::open_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::ProgramID))?,
-            key_value_map: rocksdb::RocksDB::open_nested_map_testing(temp_dir, dev, MapID::Program(ProgramMap::KeyValueID))?,
+            key_value_map: rocksdb::RocksDB::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::KeyValueID))?,
+            key_position_map: rocksdb::RocksDB::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::KeyPosition))?,
+            position_key_map: rocksdb::RocksDB::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::PositionKey))?,
+            mapping_length_map: rocksdb::RocksDB::open_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::MappingLength))?,
+            history_map: rocksdb::RocksDB::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::History))?,
+            history_start_map: rocksdb::RocksDB::open_map_testing(temp_dir, dev, MapID::Program(ProgramMap::HistoryStart))?,
             storage_mode: dev.into(),
         })
     }
@@ -90,6 +117,31 @@ impl<N: Network> FinalizeStorage<N> for FinalizeDB<N> {
         &self.key_value_map
     }
 
+    /// Returns the key-position map.
+    fn key_position_map(&self) -> &Self::KeyPositionMap {
+        &self.key_position_map
+    }
+
+    /// Returns the position-key map.
+    fn position_key_map(&self) -> &Self::PositionKeyMap {
+        &self.position_key_map
+    }
+
+    /// Returns the mapping length map.
+    fn mapping_length_map(&self) -> &Self::MappingLengthMap {
+        &self.mapping_length_map
+    }
+
+    /// Returns the history map.
+    fn history_map(&self) -> &Self::HistoryMap {
+        &self.history_map
+    }
+
+    /// Returns the history start map.
+    fn history_start_map(&self) -> &Self::HistoryStartMap {
+        &self.history_start_map
+    }
+
     /// Returns the storage mode.
     fn storage_mode(&self) -> &StorageMode {
         &self.storage_mode
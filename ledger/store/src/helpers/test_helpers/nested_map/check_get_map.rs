@@ -121,10 +121,57 @@ fn check_get_same_map(map: &impl for<'a> NestedMap<'a, usize, usize, String>) {
     ensure_map_is_empty(map);
 }
 
+fn check_get_map_range(map: &impl for<'a> NestedMap<'a, usize, usize, String>) {
+    ensure_map_is_empty(map);
+
+    const MAP: usize = 0;
+    const OTHER_MAP: usize = 1;
+
+    // Insert the items in reverse order, alongside the items of another map.
+    for i in (0..NUM_ITEMS).rev() {
+        map.insert(MAP, i, i.to_string()).unwrap();
+        map.insert(OTHER_MAP, i, i.to_string()).unwrap();
+    }
+    let expected = (0..NUM_ITEMS).map(|i| (i, i.to_string())).collect::<Vec<_>>();
+
+    assert_eq!(map.get_map_range_confirmed(&MAP, 0, NUM_TOTAL_ITEMS).unwrap(), expected);
+    assert_eq!(map.get_map_range_confirmed(&MAP, 3, 4).unwrap(), expected[3..7]);
+    assert_eq!(map.get_map_range_confirmed(&MAP, NUM_ITEMS - 1, NUM_TOTAL_ITEMS).unwrap(), expected[NUM_ITEMS - 1..]);
+    assert_eq!(map.get_map_range_confirmed(&MAP, NUM_ITEMS, NUM_TOTAL_ITEMS).unwrap(), Vec::new());
+    assert_eq!(map.get_map_range_confirmed(&MAP, 0, 0).unwrap(), Vec::new());
+    assert_eq!(map.get_map_range_confirmed(&NUM_TOTAL_ITEMS, 0, NUM_TOTAL_ITEMS).unwrap(), Vec::new());
+
+    /* test atomic insertions */
+
+    {
+        // Start an atomic write batch.
+        map.start_atomic();
+
+        // Insert an item into the map.
+        map.insert(MAP, NUM_ITEMS, NUM_ITEMS.to_string()).unwrap();
+        assert_eq!(map.get_map_range_confirmed(&MAP, 0, NUM_TOTAL_ITEMS).unwrap(), expected);
+
+        // Finish the current atomic write batch.
+        map.finish_atomic().unwrap();
+        assert_eq!(map.get_map_range_confirmed(&MAP, NUM_ITEMS, NUM_TOTAL_ITEMS).unwrap(), vec![(
+            NUM_ITEMS,
+            NUM_ITEMS.to_string()
+        )]);
+    }
+
+    map.remove_map(&MAP).unwrap();
+    map.remove_map(&OTHER_MAP).unwrap();
+
+    ensure_map_is_empty(map);
+}
+
 pub fn check_get_map(map: impl for<'a> NestedMap<'a, usize, usize, String>) {
     println!("Checking get unique maps");
     check_get_unique_maps(&map);
 
     println!("Checking get same map");
     check_get_same_map(&map);
+
+    println!("Checking get map range");
+    check_get_map_range(&map);
 }
@@ -122,6 +122,12 @@ pub trait NestedMapRead<
     ///
     fn get_map_confirmed(&'a self, map: &M) -> Result<Vec<(K, V)>>;
 
+    ///
+    /// Returns the confirmed key-value pairs for the given map, in ascending order of their serialized keys,
+    /// skipping the first `offset` pairs and returning at most `limit` pairs.
+    ///
+    fn get_map_range_confirmed(&'a self, map: &M, offset: usize, limit: usize) -> Result<Vec<(K, V)>>;
+
     ///
     /// Returns the speculative key-value pairs for the given map, if it exists.
     ///
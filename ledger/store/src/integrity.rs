@@ -0,0 +1,142 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::MapRead;
+use console::network::prelude::*;
+
+use anyhow::Result;
+use core::hash::Hash;
+
+/// The kind of an inconsistency between the maps of a store.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IntegrityIssueKind {
+    /// An entry that is referenced by another map is missing.
+    Missing,
+    /// An entry is not referenced by the map that it should be referenced by.
+    Dangling,
+    /// An entry does not match the entry that references it.
+    Mismatch,
+}
+
+impl Display for IntegrityIssueKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "missing"),
+            Self::Dangling => write!(f, "dangling"),
+            Self::Mismatch => write!(f, "mismatched"),
+        }
+    }
+}
+
+/// An inconsistency between the maps of a store.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntegrityIssue {
+    /// The height of the block that the entry belongs to, if it is known.
+    pub height: Option<u32>,
+    /// The kind of inconsistency.
+    pub kind: IntegrityIssueKind,
+    /// The name of the map that contains, or should contain, the entry.
+    pub map: &'static str,
+    /// The key of the entry.
+    pub key: String,
+}
+
+impl IntegrityIssue {
+    /// Initializes a new issue for an entry that is missing from the given map.
+    pub fn missing(map: &'static str, key: impl Display) -> Self {
+        Self { height: None, kind: IntegrityIssueKind::Missing, map, key: key.to_string() }
+    }
+
+    /// Initializes a new issue for an entry of the given map that is not referenced.
+    pub fn dangling(map: &'static str, key: impl Display) -> Self {
+        Self { height: None, kind: IntegrityIssueKind::Dangling, map, key: key.to_string() }
+    }
+
+    /// Initializes a new issue for an entry of the given map that does not match its reference.
+    pub fn mismatch(map: &'static str, key: impl Display) -> Self {
+        Self { height: None, kind: IntegrityIssueKind::Mismatch, map, key: key.to_string() }
+    }
+
+    /// Returns the issue, attributed to the block at the given height.
+    pub fn at_height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+}
+
+impl Display for IntegrityIssue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} entry '{}' in the {} map", self.kind, self.key, self.map)?;
+        match self.height {
+            Some(height) => write!(f, " (block {height})"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Checks that the `reverse` map is the inverse of the `forward` map, where `to_reverse_keys` returns the keys
+/// of `reverse` that an entry of `forward` is referenced by. The issues are returned along with the key of `forward`
+/// that they belong to.
+pub(crate) fn check_reverse_map<K, V, R>(
+    forward: &impl for<'a> MapRead<'a, K, V>,
+    (reverse_name, reverse): (&'static str, &impl for<'a> MapRead<'a, R, K>),
+    to_reverse_keys: impl Fn(&V) -> Vec<R>,
+) -> Result<Vec<(K, IntegrityIssue)>>
+where
+    K: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Sync,
+    V: 'static + Clone + Eq + Serialize + DeserializeOwned + Sync,
+    R: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Sync + Display,
+{
+    let mut issues = Vec::new();
+    // Ensure each entry of the forward map is referenced by the reverse map.
+    for (key, value) in forward.iter_confirmed() {
+        for reverse_key in to_reverse_keys(&value) {
+            match reverse.get_confirmed(&reverse_key)? {
+                Some(reverse_value) if *reverse_value == *key => (),
+                Some(_) => issues.push((*key, IntegrityIssue::mismatch(reverse_name, reverse_key))),
+                None => issues.push((*key, IntegrityIssue::missing(reverse_name, reverse_key))),
+            }
+        }
+    }
+    // Ensure each entry of the reverse map references an entry of the forward map.
+    for (reverse_key, key) in reverse.iter_confirmed() {
+        let is_referenced = match forward.get_confirmed(&*key)? {
+            Some(value) => to_reverse_keys(&value).contains(&*reverse_key),
+            None => false,
+        };
+        if !is_referenced {
+            issues.push((*key, IntegrityIssue::dangling(reverse_name, *reverse_key)));
+        }
+    }
+    Ok(issues)
+}
+
+/// Returns an issue for each key of the given map that is not a key of the `reference` map, along with the key.
+pub(crate) fn check_dangling_keys<K, V, W>(
+    (name, map): (&'static str, &impl for<'a> MapRead<'a, K, V>),
+    reference: &impl for<'a> MapRead<'a, K, W>,
+) -> Result<Vec<(K, IntegrityIssue)>>
+where
+    K: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Sync + Display,
+    V: 'static + Clone + Eq + Serialize + DeserializeOwned + Sync,
+    W: 'static + Clone + Eq + Serialize + DeserializeOwned + Sync,
+{
+    let mut issues = Vec::new();
+    for key in map.keys_confirmed() {
+        if !reference.contains_key_confirmed(&*key)? {
+            issues.push((*key, IntegrityIssue::dangling(name, *key)));
+        }
+    }
+    Ok(issues)
+}
@@ -23,6 +23,9 @@ pub use block::*;
 mod consensus;
 pub use consensus::*;
 
+mod integrity;
+pub use integrity::*;
+
 mod program;
 pub use program::*;
 
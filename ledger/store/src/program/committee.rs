@@ -235,6 +235,43 @@ pub trait CommitteeStorage<N: Network>: 'static + Clone + Send + Sync {
         })
     }
 
+    /// Removes the committees after the given `height`, in the process
+    /// removing all round to height entries back to the committee for the given `height`.
+    /// This is equivalent to removing each committee after the given `height`, in a single atomic batch.
+    fn remove_after(&self, height: u32) -> Result<()> {
+        // Retrieve the current round.
+        let current_round = self.current_round()?;
+        // Retrieve the current height.
+        let current_height = self.current_height()?;
+        // Ensure there are committees after the given height.
+        ensure!(current_height > height, "No committees after height {height} in committee storage");
+        // Ensure the committee for the given height exists.
+        if self.get_committee(height)?.is_none() {
+            bail!("Committee not found for height {height} in committee storage");
+        }
+        // Retrieve the committee for the next height.
+        let Some(next_committee) = self.get_committee(height + 1)? else {
+            bail!("Committee not found for height {} in committee storage", height + 1);
+        };
+        // Retrieve the earliest round to be removed (inclusive), as the rounds before it belong to the given height.
+        let earliest_round = next_committee.starting_round();
+
+        // Start an atomic batch.
+        atomic_batch_scope!(self, {
+            // Update the current round, to the last round of the given height.
+            self.current_round_map().insert(ROUND_KEY, earliest_round.saturating_sub(1))?;
+            // Remove the round to height mappings.
+            for round in earliest_round..=current_round {
+                self.round_to_height_map().remove(&round)?;
+            }
+            // Remove the committees.
+            for height in height + 1..=current_height {
+                self.committee_map().remove(&height)?;
+            }
+            Ok(())
+        })
+    }
+
     /// Returns the current round.
     fn current_round(&self) -> Result<u64> {
         match self.current_round_map().get_confirmed(&ROUND_KEY)? {
@@ -374,6 +411,12 @@ impl<N: Network, C: CommitteeStorage<N>> CommitteeStore<N, C> {
     pub fn remove(&self, height: u32) -> Result<()> {
         self.storage.remove(height)
     }
+
+    /// Removes the committees after the given `height`, in the process
+    /// removing all round to height entries back to the committee for the given `height`.
+    pub fn remove_after(&self, height: u32) -> Result<()> {
+        self.storage.remove_after(height)
+    }
 }
 
 impl<N: Network, C: CommitteeStorage<N>> CommitteeStore<N, C> {
@@ -536,6 +579,50 @@ mod tests {
         assert_eq!(store.get_committee_for_round(5).unwrap(), None);
     }
 
+    #[test]
+    fn test_remove_after() {
+        let rng = &mut TestRng::default();
+
+        // Sample the committees.
+        let committees = [0, 5, 6, 9]
+            .into_iter()
+            .map(|round| ledger_committee::test_helpers::sample_committee_for_round(round, rng))
+            .collect::<Vec<_>>();
+
+        // Initialize a new committee store, and a second one from which the committees are removed one by one.
+        let store = CommitteeStore::<CurrentNetwork, CommitteeMemory<_>>::open(None).unwrap();
+        let expected = CommitteeStore::<CurrentNetwork, CommitteeMemory<_>>::open(None).unwrap();
+        // Insert the committees.
+        for (height, committee) in committees.iter().enumerate() {
+            store.insert(height as u32, committee.clone()).unwrap();
+            expected.insert(height as u32, committee.clone()).unwrap();
+        }
+
+        // Ensure the committees cannot be removed after the current height.
+        assert!(store.remove_after(3).is_err());
+
+        // Remove the committees after height 1.
+        store.remove_after(1).unwrap();
+        expected.remove(3).unwrap();
+        expected.remove(2).unwrap();
+        assert_eq!(store.current_round().unwrap(), 5);
+        assert_eq!(store.current_round().unwrap(), expected.current_round().unwrap());
+        assert_eq!(store.current_height().unwrap(), 1);
+        assert_eq!(store.current_committee().unwrap(), committees[1]);
+        for round in 0..10 {
+            assert_eq!(store.get_height_for_round(round).unwrap(), expected.get_height_for_round(round).unwrap());
+        }
+        for height in 0..4 {
+            assert_eq!(store.get_committee(height).unwrap(), expected.get_committee(height).unwrap());
+        }
+
+        // Ensure the next committee can be inserted.
+        store.insert(2, committees[3].clone()).unwrap();
+        assert_eq!(store.current_round().unwrap(), 9);
+        assert_eq!(store.get_height_for_round(8).unwrap(), Some(1));
+        assert_eq!(store.get_committee_for_round(9).unwrap().unwrap(), committees[3]);
+    }
+
     #[test]
     fn test_remove_hole() {
         let rng = &mut TestRng::default();
@@ -18,6 +18,7 @@ use crate::{
     cow_to_copied,
     helpers::{Map, MapRead, NestedMap, NestedMapRead},
     program::{CommitteeStorage, CommitteeStore},
+    IntegrityIssue,
 };
 use console::{
     network::prelude::*,
@@ -29,7 +30,13 @@ use synthesizer_program::{FinalizeOperation, FinalizeStoreTrait};
 use aleo_std_storage::StorageMode;
 use anyhow::Result;
 use core::marker::PhantomData;
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
+
+/// The key of the height of the first block in the finalize history.
+const HISTORY_START_KEY: u8 = 0;
 
 /// TODO (howardwu): Remove this.
 /// Returns the mapping ID for the given `program ID` and `mapping name`.
@@ -44,7 +51,7 @@ fn to_mapping_id<N: Network>(program_id: &ProgramID<N>, mapping_name: &Identifie
 }
 
 /// Returns the key ID for the given `program ID`, `mapping name`, and `key`.
-fn to_key_id<N: Network>(
+pub fn to_key_id<N: Network>(
     program_id: &ProgramID<N>,
     mapping_name: &Identifier<N>,
     key: &Plaintext<N>,
@@ -70,6 +77,11 @@ fn to_key_id<N: Network>(
 /// // (program_id => (mapping_name => (key => value)))
 /// BTreeMap<ProgramID<N>, BTreeMap<Identifier<N>, BTreeMap<Key, Value>>>
 /// ```
+///
+/// In addition, each mapping stores its length, and the position of each of its keys.
+/// A new key is appended at the end of the mapping, and a removed key is replaced by the last key of the mapping.
+/// As a result, the positions are determined by the order of the writes to the mapping,
+/// and a position observed during speculation is the same once the block is committed.
 pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
     /// The committee storage.
     type CommitteeStorage: CommitteeStorage<N>;
@@ -77,6 +89,16 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
     type ProgramIDMap: for<'a> Map<'a, ProgramID<N>, IndexSet<Identifier<N>>>;
     /// The mapping of `(program ID, mapping name)` to `[(key, value)]`.
     type KeyValueMap: for<'a> NestedMap<'a, (ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>;
+    /// The mapping of `(program ID, mapping name)` to `[(key, position)]`.
+    type KeyPositionMap: for<'a> NestedMap<'a, (ProgramID<N>, Identifier<N>), Plaintext<N>, u64>;
+    /// The mapping of `(program ID, mapping name)` to `[(position, key)]`.
+    type PositionKeyMap: for<'a> NestedMap<'a, (ProgramID<N>, Identifier<N>), u64, Plaintext<N>>;
+    /// The mapping of `(program ID, mapping name)` to the number of entries in the mapping.
+    type MappingLengthMap: for<'a> Map<'a, (ProgramID<N>, Identifier<N>), u64>;
+    /// The mapping of `key ID` to `[(block height, (program ID, mapping name, key, value before the block))]`.
+    type HistoryMap: for<'a> NestedMap<'a, Field<N>, u32, JournalEntry<N>>;
+    /// The mapping of `HISTORY_START_KEY` to the height of the first block in the history.
+    type HistoryStartMap: for<'a> Map<'a, u8, u32>;
 
     /// Initializes the program state storage.
     fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
@@ -91,6 +113,16 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
     fn program_id_map(&self) -> &Self::ProgramIDMap;
     /// Returns the key-value map.
     fn key_value_map(&self) -> &Self::KeyValueMap;
+    /// Returns the key-position map.
+    fn key_position_map(&self) -> &Self::KeyPositionMap;
+    /// Returns the position-key map.
+    fn position_key_map(&self) -> &Self::PositionKeyMap;
+    /// Returns the mapping length map.
+    fn mapping_length_map(&self) -> &Self::MappingLengthMap;
+    /// Returns the history map.
+    fn history_map(&self) -> &Self::HistoryMap;
+    /// Returns the history start map.
+    fn history_start_map(&self) -> &Self::HistoryStartMap;
 
     /// Returns the storage mode.
     fn storage_mode(&self) -> &StorageMode;
@@ -100,6 +132,11 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().start_atomic();
         self.program_id_map().start_atomic();
         self.key_value_map().start_atomic();
+        self.key_position_map().start_atomic();
+        self.position_key_map().start_atomic();
+        self.mapping_length_map().start_atomic();
+        self.history_map().start_atomic();
+        self.history_start_map().start_atomic();
     }
 
     /// Checks if an atomic batch is in progress.
@@ -107,6 +144,11 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().is_atomic_in_progress()
             || self.program_id_map().is_atomic_in_progress()
             || self.key_value_map().is_atomic_in_progress()
+            || self.key_position_map().is_atomic_in_progress()
+            || self.position_key_map().is_atomic_in_progress()
+            || self.mapping_length_map().is_atomic_in_progress()
+            || self.history_map().is_atomic_in_progress()
+            || self.history_start_map().is_atomic_in_progress()
     }
 
     /// Checkpoints the atomic batch.
@@ -114,6 +156,11 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().atomic_checkpoint();
         self.program_id_map().atomic_checkpoint();
         self.key_value_map().atomic_checkpoint();
+        self.key_position_map().atomic_checkpoint();
+        self.position_key_map().atomic_checkpoint();
+        self.mapping_length_map().atomic_checkpoint();
+        self.history_map().atomic_checkpoint();
+        self.history_start_map().atomic_checkpoint();
     }
 
     /// Clears the latest atomic batch checkpoint.
@@ -121,6 +168,11 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().clear_latest_checkpoint();
         self.program_id_map().clear_latest_checkpoint();
         self.key_value_map().clear_latest_checkpoint();
+        self.key_position_map().clear_latest_checkpoint();
+        self.position_key_map().clear_latest_checkpoint();
+        self.mapping_length_map().clear_latest_checkpoint();
+        self.history_map().clear_latest_checkpoint();
+        self.history_start_map().clear_latest_checkpoint();
     }
 
     /// Rewinds the atomic batch to the previous checkpoint.
@@ -128,6 +180,11 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().atomic_rewind();
         self.program_id_map().atomic_rewind();
         self.key_value_map().atomic_rewind();
+        self.key_position_map().atomic_rewind();
+        self.position_key_map().atomic_rewind();
+        self.mapping_length_map().atomic_rewind();
+        self.history_map().atomic_rewind();
+        self.history_start_map().atomic_rewind();
     }
 
     /// Aborts an atomic batch write operation.
@@ -135,13 +192,23 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().abort_atomic();
         self.program_id_map().abort_atomic();
         self.key_value_map().abort_atomic();
+        self.key_position_map().abort_atomic();
+        self.position_key_map().abort_atomic();
+        self.mapping_length_map().abort_atomic();
+        self.history_map().abort_atomic();
+        self.history_start_map().abort_atomic();
     }
 
     /// Finishes an atomic batch write operation.
     fn finish_atomic(&self) -> Result<()> {
         self.committee_store().finish_atomic()?;
         self.program_id_map().finish_atomic()?;
-        self.key_value_map().finish_atomic()
+        self.key_value_map().finish_atomic()?;
+        self.key_position_map().finish_atomic()?;
+        self.position_key_map().finish_atomic()?;
+        self.mapping_length_map().finish_atomic()?;
+        self.history_map().finish_atomic()?;
+        self.history_start_map().finish_atomic()
     }
 
     /// Initializes the given `program ID` and `mapping name` in storage.
@@ -170,6 +237,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         atomic_batch_scope!(self, {
             // Update the program ID map with the new mapping name.
             self.program_id_map().insert(program_id, mapping_names)?;
+            // Initialize the length of the mapping.
+            self.mapping_length_map().insert((program_id, mapping_name), 0)?;
 
             Ok(())
         })?;
@@ -205,6 +274,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         let value_id = N::hash_bhp1024(&(key_id, N::hash_bhp1024(&value.to_bits_le())?).to_bits_le())?;
 
         atomic_batch_scope!(self, {
+            // Append the key to the positions of the mapping.
+            self.append_key(program_id, mapping_name, &key)?;
             // Update the key-value map with the new key-value.
             self.key_value_map().insert((program_id, mapping_name), key, value)?;
 
@@ -236,7 +307,14 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         // Compute the value ID.
         let value_id = N::hash_bhp1024(&(key_id, N::hash_bhp1024(&value.to_bits_le())?).to_bits_le())?;
 
+        // Determine whether the key is new to the mapping.
+        let is_new_key = !self.contains_key_speculative(program_id, mapping_name, &key)?;
+
         atomic_batch_scope!(self, {
+            // If the key is new, append it to the positions of the mapping.
+            if is_new_key {
+                self.append_key(program_id, mapping_name, &key)?;
+            }
             // Update the key-value map with the new key-value.
             self.key_value_map().insert((program_id, mapping_name), key, value)?;
 
@@ -268,6 +346,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         let key_id = to_key_id(&program_id, &mapping_name, key)?;
 
         atomic_batch_scope!(self, {
+            // Remove the key from the positions of the mapping.
+            self.swap_remove_key(program_id, mapping_name, key)?;
             // Update the key-value map with the new key.
             self.key_value_map().remove_key(&(program_id, mapping_name), key)?;
 
@@ -279,7 +359,7 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
     }
 
     /// Replaces the mapping for the given `program ID` and `mapping name` from storage,
-    /// with the given `key-value` pairs.
+    /// with the given `key-value` pairs. The keys are positioned in the given order.
     fn replace_mapping(
         &self,
         program_id: ProgramID<N>,
@@ -291,12 +371,25 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
             bail!("Illegal operation: '{program_id}/{mapping_name}' is not initialized - cannot replace mapping.")
         }
 
+        // Ensure the keys are unique, as each key is assigned its own position.
+        let key_bytes = entries.iter().map(|(key, _)| key.to_bytes_le()).collect::<Result<Vec<_>>>()?;
+        if has_duplicates(key_bytes) {
+            bail!("Illegal operation: '{program_id}/{mapping_name}' cannot be replaced with duplicate keys.")
+        }
+
         atomic_batch_scope!(self, {
-            // Remove the existing key-value entries.
+            // Remove the existing key-value entries, and their positions.
             self.key_value_map().remove_map(&(program_id, mapping_name))?;
+            self.key_position_map().remove_map(&(program_id, mapping_name))?;
+            self.position_key_map().remove_map(&(program_id, mapping_name))?;
+            // Update the length of the mapping.
+            self.mapping_length_map().insert((program_id, mapping_name), entries.len() as u64)?;
 
             // Insert the new key-value entries.
-            for (key, value) in entries {
+            for (position, (key, value)) in entries.into_iter().enumerate() {
+                // Insert the position of the key.
+                self.key_position_map().insert((program_id, mapping_name), key.clone(), position as u64)?;
+                self.position_key_map().insert((program_id, mapping_name), position as u64, key.clone())?;
                 // Insert the key-value entry.
                 self.key_value_map().insert((program_id, mapping_name), key, value)?;
             }
@@ -325,7 +418,7 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
             // Update the mapping names.
             self.program_id_map().insert(program_id, mapping_names)?;
             // Remove the mapping.
-            self.key_value_map().remove_map(&(program_id, mapping_name))?;
+            self.remove_mapping_entries(program_id, mapping_name)?;
 
             Ok(())
         })?;
@@ -349,12 +442,71 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
             // Remove each mapping.
             for mapping_name in mapping_names.iter() {
                 // Remove the mapping.
-                self.key_value_map().remove_map(&(*program_id, *mapping_name))?;
+                self.remove_mapping_entries(*program_id, *mapping_name)?;
             }
             Ok(())
         })
     }
 
+    /// Appends the given key at the end of the positions of the given mapping, and increments its length.
+    /// Note: This method must be called within an atomic batch, and only for a key that is not in the mapping.
+    fn append_key(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>, key: &Plaintext<N>) -> Result<()> {
+        // Retrieve the length of the mapping, which is the position of the key.
+        let position = self.get_mapping_length_speculative(program_id, mapping_name)?;
+        // Insert the position of the key.
+        self.key_position_map().insert((program_id, mapping_name), key.clone(), position)?;
+        self.position_key_map().insert((program_id, mapping_name), position, key.clone())?;
+        // Increment the length of the mapping.
+        self.mapping_length_map().insert((program_id, mapping_name), position.saturating_add(1))
+    }
+
+    /// Removes the given key from the positions of the given mapping, and decrements its length.
+    /// The last key of the mapping is moved to the position of the removed key.
+    /// Note: This method must be called within an atomic batch, and only for a key that is in the mapping.
+    fn swap_remove_key(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>, key: &Plaintext<N>) -> Result<()> {
+        let mapping = (program_id, mapping_name);
+        // Retrieve the position of the key.
+        let Some(position) = self.key_position_map().get_value_speculative(&mapping, key)? else {
+            bail!("Illegal operation: '{program_id}/{mapping_name}' key '{key}' is missing its position.")
+        };
+        let position = cow_to_copied!(position);
+        // Retrieve the position of the last key.
+        let Some(last_position) = self.get_mapping_length_speculative(program_id, mapping_name)?.checked_sub(1) else {
+            bail!("Illegal operation: '{program_id}/{mapping_name}' is empty - cannot remove key '{key}'.")
+        };
+        // If the key is not the last key, move the last key to the position of the key.
+        if position != last_position {
+            let Some(last_key) = self.position_key_map().get_value_speculative(&mapping, &last_position)? else {
+                bail!("Illegal operation: '{program_id}/{mapping_name}' is missing the key at {last_position}.")
+            };
+            let last_key = cow_to_cloned!(last_key);
+            self.key_position_map().insert(mapping, last_key.clone(), position)?;
+            self.position_key_map().insert(mapping, position, last_key)?;
+        }
+        // Remove the last position, and the position of the key.
+        self.position_key_map().remove_key(&mapping, &last_position)?;
+        self.key_position_map().remove_key(&mapping, key)?;
+        // Decrement the length of the mapping.
+        self.mapping_length_map().insert(mapping, last_position)
+    }
+
+    /// Removes the key-value entries of the given mapping, along with their positions and the length of the mapping.
+    /// Note: This method must be called within an atomic batch.
+    fn remove_mapping_entries(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>) -> Result<()> {
+        self.key_value_map().remove_map(&(program_id, mapping_name))?;
+        self.key_position_map().remove_map(&(program_id, mapping_name))?;
+        self.position_key_map().remove_map(&(program_id, mapping_name))?;
+        self.mapping_length_map().remove(&(program_id, mapping_name))
+    }
+
+    /// Returns the speculative number of entries for the given `program ID` and `mapping name`.
+    fn get_mapping_length_speculative(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>) -> Result<u64> {
+        match self.mapping_length_map().get_speculative(&(program_id, mapping_name))? {
+            Some(length) => Ok(cow_to_copied!(length)),
+            None => bail!("Illegal operation: '{program_id}/{mapping_name}' is missing its length."),
+        }
+    }
+
     /// Returns `true` if the given `program ID` exist.
     fn contains_program_confirmed(&self, program_id: &ProgramID<N>) -> Result<bool> {
         self.program_id_map().contains_key_confirmed(program_id)
@@ -428,6 +580,52 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.key_value_map().get_map_speculative(&(program_id, mapping_name))
     }
 
+    /// Returns the number of speculative mapping entries for the given `program ID` and `mapping name`.
+    fn len_mapping_speculative(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>) -> Result<usize> {
+        // Ensure the mapping name exists.
+        if !self.contains_mapping_speculative(&program_id, &mapping_name)? {
+            bail!("Illegal operation: '{program_id}/{mapping_name}' is not initialized - cannot get length (S).")
+        }
+        Ok(usize::try_from(self.get_mapping_length_speculative(program_id, mapping_name)?)?)
+    }
+
+    /// Returns the speculative mapping key at the given `index` for the given `program ID` and `mapping name`.
+    /// If the `index` is out of bounds, returns `None`.
+    fn get_key_at_speculative(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        index: usize,
+    ) -> Result<Option<Plaintext<N>>> {
+        // Ensure the mapping name exists.
+        if !self.contains_mapping_speculative(&program_id, &mapping_name)? {
+            bail!("Illegal operation: '{program_id}/{mapping_name}' is not initialized - cannot get key (S).")
+        }
+        match self.position_key_map().get_value_speculative(&(program_id, mapping_name), &(index as u64))? {
+            Some(key) => Ok(Some(cow_to_cloned!(key))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the speculative mapping entry at the given `index` for the given `program ID` and `mapping name`.
+    /// If the `index` is out of bounds, returns `None`.
+    fn get_entry_at_speculative(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        index: usize,
+    ) -> Result<Option<(Plaintext<N>, Value<N>)>> {
+        // Retrieve the key at the given index.
+        let Some(key) = self.get_key_at_speculative(program_id, mapping_name, index)? else {
+            return Ok(None);
+        };
+        // Retrieve the value of the key.
+        match self.get_value_speculative(program_id, mapping_name, &key)? {
+            Some(value) => Ok(Some((key, value))),
+            None => bail!("Illegal operation: '{program_id}/{mapping_name}' is missing the value of key '{key}'."),
+        }
+    }
+
     /// Returns the confirmed value for the given `program ID`, `mapping name`, and `key`.
     fn get_value_confirmed(
         &self,
@@ -521,13 +719,64 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         // Compute the checksum as `Hash( all mapping checksums )`.
         N::hash_bhp1024(&preimage.into_values().flatten().collect::<Vec<_>>())
     }
+
+    /// Checks that each key-value entry belongs to a mapping that is initialized for its program.
+    /// Returns the inconsistent entries, once for each mapping.
+    fn check_integrity(&self) -> Result<Vec<IntegrityIssue>> {
+        let mut issues = Vec::new();
+        // Track the mappings that were already checked.
+        let mut is_checked = HashSet::new();
+        for (mapping, _) in self.key_value_map().keys_confirmed() {
+            let (program_id, mapping_name) = *mapping;
+            if !is_checked.insert((program_id, mapping_name)) {
+                continue;
+            }
+            let is_initialized = match self.program_id_map().get_confirmed(&program_id)? {
+                Some(mapping_names) => mapping_names.contains(&mapping_name),
+                None => false,
+            };
+            if !is_initialized {
+                issues.push(IntegrityIssue::dangling("key-value", format!("{program_id}/{mapping_name}")));
+            }
+        }
+        Ok(issues)
+    }
 }
 
+/// The original state of an entry that was written to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalEntry<N: Network> {
+    /// The original position and value of a key-value entry, as (program ID, mapping name, key, (position, value)).
+    KeyValue(ProgramID<N>, Identifier<N>, Plaintext<N>, Option<(u64, Value<N>)>),
+    /// The original index of a removed mapping among the mappings of its program, as (program ID, mapping name, index).
+    Mapping(ProgramID<N>, Identifier<N>, u64),
+}
+
+impl<N: Network> JournalEntry<N> {
+    /// Returns the ID of the entry in the finalize history, i.e. the key ID of a key-value entry,
+    /// or the mapping ID of a mapping.
+    fn to_history_id(&self) -> Result<Field<N>> {
+        match self {
+            Self::KeyValue(program_id, mapping_name, key, _) => to_key_id(program_id, mapping_name, key),
+            Self::Mapping(program_id, mapping_name, _) => to_mapping_id(program_id, mapping_name),
+        }
+    }
+}
+
+/// The ID of an entry in the journal, as (program ID, mapping name, key bytes),
+/// where the key bytes are `None` for a mapping.
+pub type JournalID<N> = (ProgramID<N>, Identifier<N>, Option<Vec<u8>>);
+
+/// The original states of the entries that were written to, in the order they were first written to.
+pub type Journal<N> = IndexMap<JournalID<N>, JournalEntry<N>>;
+
 /// The finalize store.
 #[derive(Clone)]
 pub struct FinalizeStore<N: Network, P: FinalizeStorage<N>> {
     /// The finalize storage.
     storage: P,
+    /// The journal of the key-value entries that were written to, if journaling is in progress.
+    journal: Arc<Mutex<Option<Journal<N>>>>,
     /// PhantomData.
     _phantom: PhantomData<N>,
 }
@@ -546,8 +795,44 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
 
     /// Initializes a finalize store from storage.
     pub fn from(storage: P) -> Result<Self> {
+        // Initialize the finalize store.
+        let store = Self { storage, journal: Default::default(), _phantom: PhantomData };
+        // Store the lengths and key positions of the mappings that were stored without them.
+        store.backfill_positions()?;
         // Return the finalize store.
-        Ok(Self { storage, _phantom: PhantomData })
+        Ok(store)
+    }
+
+    /// Stores the length and the key positions of each mapping that has no length in storage,
+    /// i.e. each mapping that was stored before the mappings had lengths and key positions.
+    ///
+    /// Note: The keys of such a mapping are positioned in the order of their serialized keys,
+    /// and the keys that are written to afterwards are positioned as usual.
+    fn backfill_positions(&self) -> Result<()> {
+        // Retrieve the mappings without a length.
+        let mut mappings = Vec::new();
+        for (program_id, mapping_names) in self.storage.program_id_map().iter_confirmed() {
+            for mapping_name in mapping_names.iter() {
+                let mapping = (cow_to_copied!(program_id), *mapping_name);
+                if !self.storage.mapping_length_map().contains_key_confirmed(&mapping)? {
+                    mappings.push(mapping);
+                }
+            }
+        }
+
+        // Store the length and the key positions of each mapping, in its own atomic batch.
+        for mapping in mappings {
+            let entries = self.storage.key_value_map().get_map_confirmed(&mapping)?;
+            atomic_batch_scope!(self, {
+                for (position, (key, _)) in entries.iter().enumerate() {
+                    self.storage.key_position_map().insert(mapping, key.clone(), position as u64)?;
+                    self.storage.position_key_map().insert(mapping, position as u64, key.clone())?;
+                }
+                self.storage.mapping_length_map().insert(mapping, entries.len() as u64)?;
+                Ok(())
+            })?;
+        }
+        Ok(())
     }
 
     /// Starts an atomic batch write operation.
@@ -589,6 +874,361 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Starts journaling the original states of the entries that are written to.
+    /// If journaling is already in progress, the journal is cleared.
+    pub fn start_journal(&self) {
+        *self.journal.lock() = Some(Journal::default());
+    }
+
+    /// Stops journaling, and returns the original state of each entry that was written to since `start_journal`,
+    /// in the order they were first written to.
+    ///
+    /// Note: Only the key-value writes in finalize (i.e. `insert`, `update` and `remove`),
+    /// and the mapping writes of the store (i.e. `replace_mapping` and `remove_mapping`) are journaled.
+    pub fn finish_journal(&self) -> Journal<N> {
+        self.journal.lock().take().unwrap_or_default()
+    }
+
+    /// Records the original position and value of the given key-value entry, if journaling is in progress
+    /// and the entry has not been written to since `start_journal`.
+    fn journal_key_value(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<()> {
+        if let Some(journal) = self.journal.lock().as_mut() {
+            self.record_key_value(journal, program_id, mapping_name, key)?;
+        }
+        Ok(())
+    }
+
+    /// Records the original positions and values of the given key-value entry, and of the last key of its mapping,
+    /// which is moved to the position of the given key when it is removed, if journaling is in progress.
+    fn journal_remove_key_value(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<()> {
+        if let Some(journal) = self.journal.lock().as_mut() {
+            self.record_key_value(journal, program_id, mapping_name, key)?;
+            // If the key will be removed, record the last key of the mapping.
+            if self.storage.contains_key_speculative(program_id, mapping_name, key)? {
+                let last_index = self.storage.len_mapping_speculative(program_id, mapping_name)?.saturating_sub(1);
+                if let Some(last_key) = self.storage.get_key_at_speculative(program_id, mapping_name, last_index)? {
+                    self.record_key_value(journal, program_id, mapping_name, &last_key)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the original positions and values of the entries of the given mapping whose position or value differ
+    /// from the given entries, and of the new entries, if journaling is in progress.
+    ///
+    /// Note: Entries that are unchanged by the given entries are not journaled.
+    fn journal_replace_mapping(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        entries: &[(Plaintext<N>, Value<N>)],
+    ) -> Result<()> {
+        if let Some(journal) = self.journal.lock().as_mut() {
+            // Retrieve the new positions and values, as the keys are positioned in the given order.
+            let new_entries = entries
+                .iter()
+                .enumerate()
+                .map(|(position, (key, value))| Ok((key.to_bytes_le()?, (position as u64, value))))
+                .collect::<Result<IndexMap<_, _>>>()?;
+            // Record each original entry whose position or value is changed.
+            let length = self.storage.len_mapping_speculative(program_id, mapping_name)?;
+            for index in 0..length {
+                let Some((key, value)) = self.storage.get_entry_at_speculative(program_id, mapping_name, index)? else {
+                    bail!("Illegal operation: '{program_id}/{mapping_name}' is missing the key at {index}.")
+                };
+                let is_unchanged = matches!(
+                    new_entries.get(&key.to_bytes_le()?),
+                    Some((position, new_value)) if *position == index as u64 && **new_value == value
+                );
+                if !is_unchanged {
+                    self.record_key_value(journal, program_id, mapping_name, &key)?;
+                }
+            }
+            // Record each new entry.
+            for (key, _) in entries {
+                if !self.storage.contains_key_speculative(program_id, mapping_name, key)? {
+                    self.record_key_value(journal, program_id, mapping_name, key)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the original positions and values of the entries of the given mapping,
+    /// and the original index of the mapping, if journaling is in progress.
+    fn journal_remove_mapping(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>) -> Result<()> {
+        if let Some(journal) = self.journal.lock().as_mut() {
+            // Retrieve the index of the mapping.
+            let mapping_names = self.storage.get_mapping_names_speculative(&program_id)?.unwrap_or_default();
+            let Some(index) = mapping_names.get_index_of(&mapping_name) else {
+                return Ok(());
+            };
+            // Record each entry of the mapping.
+            for (key, _) in self.storage.get_mapping_speculative(program_id, mapping_name)? {
+                self.record_key_value(journal, program_id, mapping_name, &key)?;
+            }
+            // Record the index of the mapping.
+            let entry = JournalEntry::Mapping(program_id, mapping_name, index as u64);
+            journal.entry((program_id, mapping_name, None)).or_insert(entry);
+        }
+        Ok(())
+    }
+
+    /// Records the original position and value of the given key-value entry in the given journal,
+    /// if the entry is not in the journal.
+    fn record_key_value(
+        &self,
+        journal: &mut Journal<N>,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<()> {
+        // Skip the entry, if it has already been written to.
+        let journal_id = (program_id, mapping_name, Some(key.to_bytes_le()?));
+        if journal.contains_key(&journal_id) {
+            return Ok(());
+        }
+        // Retrieve the original position and value.
+        let position = self.storage.key_position_map().get_value_speculative(&(program_id, mapping_name), key)?;
+        let value = self.storage.get_value_speculative(program_id, mapping_name, key)?;
+        let original = match (position, value) {
+            (Some(position), Some(value)) => Some((cow_to_copied!(position), value)),
+            (None, None) => None,
+            _ => bail!("Illegal operation: '{program_id}/{mapping_name}' key '{key}' is missing its position."),
+        };
+        // Record the original position and value.
+        journal.insert(journal_id, JournalEntry::KeyValue(program_id, mapping_name, key.clone(), original));
+        Ok(())
+    }
+
+    /// Checks that each key-value entry belongs to a mapping that is initialized for its program.
+    /// Returns the inconsistent entries, once for each mapping.
+    pub fn check_integrity(&self) -> Result<Vec<IntegrityIssue>> {
+        self.storage.check_integrity()
+    }
+}
+
+impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
+    /// Returns the height of the first block in the finalize history, if the finalize history is enabled.
+    pub fn history_start_height(&self) -> Result<Option<u32>> {
+        Ok(self.storage.history_start_map().get_confirmed(&HISTORY_START_KEY)?.map(|height| cow_to_copied!(height)))
+    }
+
+    /// Enables the finalize history, which records the values that each block overwrites,
+    /// starting from the block at the given height. If the finalize history is already enabled, this is a no-op.
+    pub fn enable_history(&self, start_height: u32) -> Result<()> {
+        if self.history_start_height()?.is_none() {
+            self.storage.history_start_map().insert(HISTORY_START_KEY, start_height)?;
+        }
+        Ok(())
+    }
+
+    /// Disables the finalize history, and removes it from storage.
+    pub fn disable_history(&self) -> Result<()> {
+        // Retrieve the entries of the finalize history.
+        let entries = self
+            .storage
+            .history_map()
+            .keys_confirmed()
+            .map(|(key_id, height)| (cow_to_copied!(key_id), cow_to_copied!(height)))
+            .collect::<Vec<_>>();
+        atomic_batch_scope!(self, {
+            // Remove the start height.
+            self.storage.history_start_map().remove(&HISTORY_START_KEY)?;
+            // Remove the entries.
+            for (key_id, height) in entries {
+                self.storage.history_map().remove_key(&key_id, &height)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Records the original states in the given journal of the block at the given height in the finalize history,
+    /// if the finalize history is enabled for the block.
+    pub fn insert_history(&self, block_height: u32, journal: Journal<N>) -> Result<()> {
+        // Ensure the finalize history is enabled for the block.
+        match self.history_start_height()? {
+            Some(start_height) if block_height >= start_height => (),
+            _ => return Ok(()),
+        }
+        atomic_batch_scope!(self, {
+            for entry in journal.into_values() {
+                // Insert the state before the block.
+                self.storage.history_map().insert(entry.to_history_id()?, block_height, entry)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the confirmed value for the given `program ID`, `mapping name`, and `key`,
+    /// as of the block at the given height (i.e. after the block was finalized).
+    /// If the given height is beyond the latest block, the latest value is returned.
+    ///
+    /// Note: The finalize history must have been enabled before the block after the given height.
+    pub fn get_value_confirmed_at(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+        block_height: u32,
+    ) -> Result<Option<Value<N>>> {
+        // Ensure the finalize history covers the given height.
+        let Some(start_height) = self.history_start_height()? else { bail!("The finalize history is not enabled") };
+        ensure!(
+            block_height >= start_height.saturating_sub(1),
+            "The finalize history does not cover block {block_height}, as it starts at block {start_height}"
+        );
+        // Retrieve the blocks that wrote to the entry, with the values before each of them.
+        let key_id = to_key_id(&program_id, &mapping_name, key)?;
+        let versions = self.storage.history_map().get_map_confirmed(&key_id)?;
+        // Return the value before the first block after the given height that wrote to the entry.
+        match versions.into_iter().filter(|(height, _)| *height > block_height).min_by_key(|(height, _)| *height) {
+            Some((_, JournalEntry::KeyValue(_, _, _, original))) => Ok(original.map(|(_, value)| value)),
+            Some((_, JournalEntry::Mapping(..))) => bail!("The finalize history of key '{key}' is malformed"),
+            // If no block after the given height wrote to the entry, return the latest value.
+            None => self.get_value_confirmed(program_id, mapping_name, key),
+        }
+    }
+
+    /// Rolls the finalize state back to the block at the given height, by restoring the positions and values
+    /// that the subsequent blocks overwrote, and the mappings they removed, and by removing their committees.
+    /// The finalize state and the committees are rolled back in a single atomic batch.
+    ///
+    /// Note: The finalize history must have been enabled before the block after the given height.
+    /// The programs that were deployed after the given height are not removed.
+    pub fn rollback_to(&self, block_height: u32) -> Result<()> {
+        // Ensure the finalize history covers the blocks after the given height.
+        let Some(start_height) = self.history_start_height()? else { bail!("The finalize history is not enabled") };
+        ensure!(
+            block_height >= start_height.saturating_sub(1),
+            "The finalize history does not cover block {}, as it starts at block {start_height}",
+            block_height.saturating_add(1)
+        );
+
+        // Retrieve the entries of the finalize history after the given height, and for each entry,
+        // the state before the first block after the given height that wrote to it.
+        let mut history_keys = Vec::new();
+        let mut original_states = IndexMap::<Field<N>, (u32, JournalEntry<N>)>::new();
+        for (history_id, height, entry) in self.storage.history_map().iter_confirmed() {
+            let (history_id, height) = (cow_to_copied!(history_id), cow_to_copied!(height));
+            if height > block_height {
+                history_keys.push((history_id, height));
+                let is_first = match original_states.get(&history_id) {
+                    Some((first_height, _)) => height < *first_height,
+                    None => true,
+                };
+                if is_first {
+                    original_states.insert(history_id, (height, cow_to_cloned!(entry)));
+                }
+            }
+        }
+
+        // Group the original states into the removed mappings, and the original entries of each mapping.
+        let mut removed_mappings = Vec::new();
+        let mut original_entries = IndexMap::<_, Vec<_>>::new();
+        for (height, entry) in original_states.into_values() {
+            match entry {
+                JournalEntry::KeyValue(program_id, mapping_name, key, original) => {
+                    original_entries.entry((program_id, mapping_name)).or_default().push((key, original))
+                }
+                JournalEntry::Mapping(program_id, mapping_name, index) => {
+                    removed_mappings.push((height, program_id, mapping_name, index))
+                }
+            }
+        }
+        // Order the removed mappings from the latest removal, so that each is restored at its original index.
+        removed_mappings.sort_by_key(|(height, _, _, index)| (core::cmp::Reverse(*height), *index));
+
+        // Determine whether there are committees after the given height.
+        let has_committees_after = match self.committee_store().current_height() {
+            Ok(current_height) => current_height > block_height,
+            Err(_) => false,
+        };
+
+        atomic_batch_scope!(self, {
+            // Restore the removed mappings.
+            for (_, program_id, mapping_name, index) in removed_mappings {
+                self.restore_mapping(program_id, mapping_name, index)?;
+            }
+            // Restore the positions and values as of the given height.
+            for ((program_id, mapping_name), entries) in original_entries {
+                self.restore_entries(program_id, mapping_name, entries)?;
+            }
+            // Remove the entries of the finalize history after the given height.
+            for (history_id, height) in history_keys {
+                self.storage.history_map().remove_key(&history_id, &height)?;
+            }
+            // Remove the committees after the given height.
+            if has_committees_after {
+                self.committee_store().remove_after(block_height)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Initializes the given mapping at the given index among the mappings of its program,
+    /// if the mapping is not initialized.
+    /// Note: This method must be called within an atomic batch.
+    fn restore_mapping(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>, index: u64) -> Result<()> {
+        // Insert the mapping name at its original index.
+        let mapping_names = self.storage.get_mapping_names_speculative(&program_id)?.unwrap_or_default();
+        if !mapping_names.contains(&mapping_name) {
+            let mut mapping_names = mapping_names.into_iter().collect::<Vec<_>>();
+            mapping_names.insert((index as usize).min(mapping_names.len()), mapping_name);
+            self.storage.program_id_map().insert(program_id, mapping_names.into_iter().collect())?;
+        }
+        // Initialize the length of the mapping, as its entries are restored separately.
+        if !self.storage.mapping_length_map().contains_key_speculative(&(program_id, mapping_name))? {
+            self.storage.mapping_length_map().insert((program_id, mapping_name), 0)?;
+        }
+        Ok(())
+    }
+
+    /// Restores the given original positions and values of the given entries of the given mapping.
+    ///
+    /// Note: This method must be called within an atomic batch, and with every entry whose position has changed,
+    /// so that the positions of the other entries are unchanged.
+    fn restore_entries(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        entries: Vec<(Plaintext<N>, Option<(u64, Value<N>)>)>,
+    ) -> Result<()> {
+        let mapping = (program_id, mapping_name);
+        let mut length = self.storage.get_mapping_length_speculative(program_id, mapping_name)?;
+        // Remove the current positions and values of the entries.
+        for (key, _) in &entries {
+            if let Some(position) = self.storage.key_position_map().get_value_speculative(&mapping, key)? {
+                self.storage.position_key_map().remove_key(&mapping, &cow_to_copied!(position))?;
+                self.storage.key_position_map().remove_key(&mapping, key)?;
+                self.storage.key_value_map().remove_key(&mapping, key)?;
+                length = length.saturating_sub(1);
+            }
+        }
+        // Insert the original positions and values of the entries.
+        for (key, original) in entries {
+            if let Some((position, value)) = original {
+                self.storage.key_position_map().insert(mapping, key.clone(), position)?;
+                self.storage.position_key_map().insert(mapping, position, key.clone())?;
+                self.storage.key_value_map().insert(mapping, key, value)?;
+                length = length.saturating_add(1);
+            }
+        }
+        // Update the length of the mapping.
+        self.storage.mapping_length_map().insert(mapping, length)
+    }
 }
 
 impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
@@ -624,6 +1264,31 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStoreTrait<N> for FinalizeStore<
         self.storage.get_value_speculative(program_id, mapping_name, key)
     }
 
+    /// Returns the number of speculative entries for the given `program ID` and `mapping name`.
+    fn len_mapping_speculative(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>) -> Result<usize> {
+        self.storage.len_mapping_speculative(program_id, mapping_name)
+    }
+
+    /// Returns the speculative key at the given `index` for the given `program ID` and `mapping name`.
+    fn get_key_at_speculative(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        index: usize,
+    ) -> Result<Option<Plaintext<N>>> {
+        self.storage.get_key_at_speculative(program_id, mapping_name, index)
+    }
+
+    /// Returns the speculative entry at the given `index` for the given `program ID` and `mapping name`.
+    fn get_entry_at_speculative(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        index: usize,
+    ) -> Result<Option<(Plaintext<N>, Value<N>)>> {
+        self.storage.get_entry_at_speculative(program_id, mapping_name, index)
+    }
+
     /// Stores the given `(key, value)` pair at the given `program ID` and `mapping name` in storage.
     /// If the `mapping name` is not initialized, an error is returned.
     /// If the `key` already exists, the method returns an error.
@@ -634,6 +1299,7 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStoreTrait<N> for FinalizeStore<
         key: Plaintext<N>,
         value: Value<N>,
     ) -> Result<FinalizeOperation<N>> {
+        self.journal_key_value(program_id, mapping_name, &key)?;
         self.storage.insert_key_value(program_id, mapping_name, key, value)
     }
 
@@ -648,6 +1314,7 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStoreTrait<N> for FinalizeStore<
         key: Plaintext<N>,
         value: Value<N>,
     ) -> Result<FinalizeOperation<N>> {
+        self.journal_key_value(program_id, mapping_name, &key)?;
         self.storage.update_key_value(program_id, mapping_name, key, value)
     }
 
@@ -658,6 +1325,7 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStoreTrait<N> for FinalizeStore<
         mapping_name: Identifier<N>,
         key: &Plaintext<N>,
     ) -> Result<Option<FinalizeOperation<N>>> {
+        self.journal_remove_key_value(program_id, mapping_name, key)?;
         self.storage.remove_key_value(program_id, mapping_name, key)
     }
 }
@@ -681,6 +1349,7 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
         mapping_name: Identifier<N>,
         entries: Vec<(Plaintext<N>, Value<N>)>,
     ) -> Result<FinalizeOperation<N>> {
+        self.journal_replace_mapping(program_id, mapping_name, &entries)?;
         self.storage.replace_mapping(program_id, mapping_name, entries)
     }
 
@@ -691,6 +1360,7 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
         program_id: ProgramID<N>,
         mapping_name: Identifier<N>,
     ) -> Result<FinalizeOperation<N>> {
+        self.journal_remove_mapping(program_id, mapping_name)?;
         self.storage.remove_mapping(program_id, mapping_name)
     }
 
@@ -707,6 +1377,15 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
         self.storage.contains_program_confirmed(program_id)
     }
 
+    /// Returns `true` if the given `program ID` and `mapping name` exist, including pending (speculative) mappings.
+    pub fn contains_mapping_speculative(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+    ) -> Result<bool> {
+        self.storage.contains_mapping_speculative(program_id, mapping_name)
+    }
+
     /// Returns `true` if the given `program ID`, `mapping name`, and `key` exist.
     pub fn contains_key_confirmed(
         &self,
@@ -1080,6 +1759,447 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_entry_positions() {
+        // Initialize a program ID and mapping name.
+        let program_id = ProgramID::<CurrentNetwork>::from_str("hello.aleo").unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+
+        // Initialize a new finalize store.
+        let program_memory = FinalizeMemory::open(None).unwrap();
+        let finalize_store = FinalizeStore::from(program_memory).unwrap();
+        // Initialize the mapping.
+        finalize_store.initialize_mapping(program_id, mapping_name).unwrap();
+
+        // Prepare the keys and values.
+        let key = |item: u64| Plaintext::from_str(&format!("{item}field")).unwrap();
+        let value = |item: u64| Value::from_str(&format!("{item}u64")).unwrap();
+        // Returns the keys of the mapping, in the order of their positions.
+        let keys = || {
+            let length = finalize_store.len_mapping_speculative(program_id, mapping_name).unwrap();
+            (0..=length)
+                .map(|index| finalize_store.get_key_at_speculative(program_id, mapping_name, index).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // Ensure the keys are positioned in the order they are inserted.
+        for item in 0..5 {
+            finalize_store.insert_key_value(program_id, mapping_name, key(item), value(item)).unwrap();
+        }
+        assert_eq!(keys(), vec![Some(key(0)), Some(key(1)), Some(key(2)), Some(key(3)), Some(key(4)), None]);
+
+        // Ensure a removed key is replaced by the last key.
+        finalize_store.remove_key_value(program_id, mapping_name, &key(1)).unwrap();
+        assert_eq!(keys(), vec![Some(key(0)), Some(key(4)), Some(key(2)), Some(key(3)), None]);
+        // Ensure removing the last key does not move any key.
+        finalize_store.remove_key_value(program_id, mapping_name, &key(3)).unwrap();
+        assert_eq!(keys(), vec![Some(key(0)), Some(key(4)), Some(key(2)), None]);
+
+        // Ensure updating an existing key keeps its position, and updating a new key appends it.
+        finalize_store.update_key_value(program_id, mapping_name, key(4), value(0)).unwrap();
+        finalize_store.update_key_value(program_id, mapping_name, key(5), value(5)).unwrap();
+        assert_eq!(keys(), vec![Some(key(0)), Some(key(4)), Some(key(2)), Some(key(5)), None]);
+        assert_eq!(
+            finalize_store.get_entry_at_speculative(program_id, mapping_name, 1).unwrap(),
+            Some((key(4), value(0)))
+        );
+
+        // Ensure the positions observed during speculation are the positions once committed.
+        finalize_store.start_atomic();
+        finalize_store.insert_key_value(program_id, mapping_name, key(6), value(6)).unwrap();
+        finalize_store.remove_key_value(program_id, mapping_name, &key(0)).unwrap();
+        finalize_store.insert_key_value(program_id, mapping_name, key(7), value(7)).unwrap();
+        let speculative_keys = keys();
+        assert_eq!(speculative_keys, vec![Some(key(6)), Some(key(4)), Some(key(2)), Some(key(5)), Some(key(7)), None]);
+        finalize_store.finish_atomic().unwrap();
+        assert_eq!(keys(), speculative_keys);
+
+        // Ensure an aborted batch leaves the positions unchanged.
+        finalize_store.start_atomic();
+        finalize_store.remove_key_value(program_id, mapping_name, &key(6)).unwrap();
+        finalize_store.abort_atomic();
+        assert_eq!(keys(), speculative_keys);
+
+        // Ensure replacing the mapping positions the keys in the given order.
+        let entries = vec![(key(9), value(9)), (key(8), value(8))];
+        finalize_store.replace_mapping(program_id, mapping_name, entries).unwrap();
+        assert_eq!(keys(), vec![Some(key(9)), Some(key(8)), None]);
+        // Ensure replacing the mapping with duplicate keys fails.
+        let entries = vec![(key(9), value(9)), (key(9), value(8))];
+        assert!(finalize_store.replace_mapping(program_id, mapping_name, entries).is_err());
+
+        // Ensure removing the mapping removes its positions and length.
+        finalize_store.remove_mapping(program_id, mapping_name).unwrap();
+        assert!(finalize_store.len_mapping_speculative(program_id, mapping_name).is_err());
+        finalize_store.initialize_mapping(program_id, mapping_name).unwrap();
+        assert_eq!(keys(), vec![None]);
+    }
+
+    #[test]
+    fn test_backfill_positions() {
+        // Initialize a program ID and mapping name.
+        let program_id = ProgramID::<CurrentNetwork>::from_str("hello.aleo").unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+        let mapping = (program_id, mapping_name);
+
+        // Prepare the keys and values.
+        let key = |item: u64| Plaintext::from_str(&format!("{item}field")).unwrap();
+        let value = |item: u64| Value::from_str(&format!("{item}u64")).unwrap();
+
+        // Write a mapping to storage without its length and key positions, as before they were stored.
+        let program_memory = FinalizeMemory::<CurrentNetwork>::open(None).unwrap();
+        program_memory.program_id_map().insert(program_id, IndexSet::from([mapping_name])).unwrap();
+        for item in 0..3 {
+            program_memory.key_value_map().insert(mapping, key(item), value(item)).unwrap();
+        }
+
+        // Open the finalize store.
+        let finalize_store = FinalizeStore::from(program_memory).unwrap();
+        // Returns the keys of the mapping, in the order of their positions.
+        let keys = |store: &FinalizeStore<CurrentNetwork, FinalizeMemory<CurrentNetwork>>| {
+            let length = store.len_mapping_speculative(program_id, mapping_name).unwrap();
+            (0..=length)
+                .map(|index| store.get_key_at_speculative(program_id, mapping_name, index).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // Ensure the keys are positioned in the order of their serialized keys.
+        let mut expected = (0..3).map(key).collect::<Vec<_>>();
+        expected.sort_by_key(|key| bincode::serialize(key).unwrap());
+        let expected_keys = expected.iter().cloned().map(Some).chain([None]).collect::<Vec<_>>();
+        assert_eq!(keys(&finalize_store), expected_keys);
+
+        // Ensure reopening the finalize store leaves the positions unchanged.
+        let finalize_store = FinalizeStore::from(finalize_store.storage.clone()).unwrap();
+        assert_eq!(keys(&finalize_store), expected_keys);
+
+        // Ensure keys can be inserted and removed.
+        finalize_store.insert_key_value(program_id, mapping_name, key(3), value(3)).unwrap();
+        finalize_store.remove_key_value(program_id, mapping_name, &expected[0]).unwrap();
+        assert_eq!(keys(&finalize_store), vec![
+            Some(key(3)),
+            Some(expected[1].clone()),
+            Some(expected[2].clone()),
+            None
+        ]);
+        finalize_store.remove_key_value(program_id, mapping_name, &key(3)).unwrap();
+        finalize_store.update_key_value(program_id, mapping_name, expected[1].clone(), value(4)).unwrap();
+        assert_eq!(keys(&finalize_store), vec![Some(expected[2].clone()), Some(expected[1].clone()), None]);
+        assert_eq!(
+            finalize_store.get_entry_at_speculative(program_id, mapping_name, 1).unwrap(),
+            Some((expected[1].clone(), value(4)))
+        );
+    }
+
+    #[test]
+    fn test_journal() {
+        // Initialize a program ID and mapping name.
+        let program_id = ProgramID::<CurrentNetwork>::from_str("hello.aleo").unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+
+        // Initialize a new finalize store.
+        let program_memory = FinalizeMemory::open(None).unwrap();
+        let finalize_store = FinalizeStore::from(program_memory).unwrap();
+        // Initialize the mapping.
+        finalize_store.initialize_mapping(program_id, mapping_name).unwrap();
+
+        // Prepare the keys and values.
+        let key_0 = Plaintext::from_str("0field").unwrap();
+        let key_1 = Plaintext::from_str("1field").unwrap();
+        let key_2 = Plaintext::from_str("2field").unwrap();
+        let value_0 = Value::from_str("0u64").unwrap();
+        let value_1 = Value::from_str("1u64").unwrap();
+
+        // Ensure writes are not journaled before the journal is started.
+        finalize_store.insert_key_value(program_id, mapping_name, key_0.clone(), value_0.clone()).unwrap();
+        assert!(finalize_store.finish_journal().is_empty());
+
+        // Start the journal.
+        finalize_store.start_journal();
+        // Write to the keys, with repeated writes to the same key.
+        finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_1.clone()).unwrap();
+        finalize_store.insert_key_value(program_id, mapping_name, key_1.clone(), value_0.clone()).unwrap();
+        finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_0.clone()).unwrap();
+        finalize_store.remove_key_value(program_id, mapping_name, &key_1).unwrap();
+        finalize_store.remove_key_value(program_id, mapping_name, &key_2).unwrap();
+
+        // Ensure the journal contains the original position and value of each key,
+        // in the order they were first written to.
+        let journal = finalize_store.finish_journal();
+        assert_eq!(journal.into_values().collect::<Vec<_>>(), vec![
+            JournalEntry::KeyValue(program_id, mapping_name, key_0.clone(), Some((0, value_0.clone()))),
+            JournalEntry::KeyValue(program_id, mapping_name, key_1.clone(), None),
+            JournalEntry::KeyValue(program_id, mapping_name, key_2, None),
+        ]);
+
+        // Ensure removing a key journals the last key, which is moved to the position of the removed key.
+        finalize_store.insert_key_value(program_id, mapping_name, key_1.clone(), value_1.clone()).unwrap();
+        finalize_store.start_journal();
+        finalize_store.remove_key_value(program_id, mapping_name, &key_0).unwrap();
+        assert_eq!(finalize_store.finish_journal().into_values().collect::<Vec<_>>(), vec![
+            JournalEntry::KeyValue(program_id, mapping_name, key_0.clone(), Some((0, value_0.clone()))),
+            JournalEntry::KeyValue(program_id, mapping_name, key_1, Some((1, value_1.clone()))),
+        ]);
+        finalize_store.insert_key_value(program_id, mapping_name, key_0.clone(), value_0.clone()).unwrap();
+
+        // Ensure the journal is stopped.
+        finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_1).unwrap();
+        assert!(finalize_store.finish_journal().is_empty());
+
+        // Ensure restarting the journal clears it.
+        finalize_store.start_journal();
+        finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_0).unwrap();
+        finalize_store.start_journal();
+        assert!(finalize_store.finish_journal().is_empty());
+    }
+
+    #[test]
+    fn test_journal_mapping() {
+        // Initialize a program ID and mapping name.
+        let program_id = ProgramID::<CurrentNetwork>::from_str("hello.aleo").unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+
+        // Initialize a new finalize store.
+        let program_memory = FinalizeMemory::open(None).unwrap();
+        let finalize_store = FinalizeStore::from(program_memory).unwrap();
+        // Initialize the mapping.
+        finalize_store.initialize_mapping(program_id, mapping_name).unwrap();
+
+        // Prepare the keys and values.
+        let key_0 = Plaintext::from_str("0field").unwrap();
+        let key_1 = Plaintext::from_str("1field").unwrap();
+        let key_2 = Plaintext::from_str("2field").unwrap();
+        let key_3 = Plaintext::from_str("3field").unwrap();
+        let value_0 = Value::from_str("0u64").unwrap();
+        let value_1 = Value::from_str("1u64").unwrap();
+
+        // Populate the mapping.
+        finalize_store
+            .replace_mapping(program_id, mapping_name, vec![
+                (key_0.clone(), value_0.clone()),
+                (key_1.clone(), value_0.clone()),
+                (key_2.clone(), value_0.clone()),
+            ])
+            .unwrap();
+
+        // Replace the mapping, with an unchanged, an updated, a removed, and a new entry.
+        finalize_store.start_journal();
+        finalize_store
+            .replace_mapping(program_id, mapping_name, vec![
+                (key_0.clone(), value_0.clone()),
+                (key_1.clone(), value_1.clone()),
+                (key_3.clone(), value_1.clone()),
+            ])
+            .unwrap();
+
+        // Ensure the journal contains only the original positions and values of the changed entries.
+        let journal = finalize_store.finish_journal();
+        assert_eq!(journal.into_values().collect::<Vec<_>>(), vec![
+            JournalEntry::KeyValue(program_id, mapping_name, key_1.clone(), Some((1, value_0.clone()))),
+            JournalEntry::KeyValue(program_id, mapping_name, key_2, Some((2, value_0.clone()))),
+            JournalEntry::KeyValue(program_id, mapping_name, key_3.clone(), None),
+        ]);
+
+        // Ensure reordering the mapping journals the entries whose position changes.
+        finalize_store.start_journal();
+        finalize_store
+            .replace_mapping(program_id, mapping_name, vec![
+                (key_1.clone(), value_1.clone()),
+                (key_0.clone(), value_0.clone()),
+                (key_3.clone(), value_1.clone()),
+            ])
+            .unwrap();
+        assert_eq!(finalize_store.finish_journal().into_values().collect::<Vec<_>>(), vec![
+            JournalEntry::KeyValue(program_id, mapping_name, key_0, Some((0, value_0))),
+            JournalEntry::KeyValue(program_id, mapping_name, key_1, Some((1, value_1))),
+        ]);
+
+        // Ensure removing the mapping journals all of its entries, and the index of the mapping.
+        finalize_store.start_journal();
+        finalize_store.remove_mapping(program_id, mapping_name).unwrap();
+        let journal = finalize_store.finish_journal();
+        assert_eq!(journal.len(), 4);
+        assert_eq!(journal.values().last(), Some(&JournalEntry::Mapping(program_id, mapping_name, 0)));
+    }
+
+    #[test]
+    fn test_history() {
+        // Initialize a program ID and mapping name.
+        let program_id = ProgramID::<CurrentNetwork>::from_str("hello.aleo").unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+
+        // Initialize a new finalize store.
+        let program_memory = FinalizeMemory::open(None).unwrap();
+        let finalize_store = FinalizeStore::from(program_memory).unwrap();
+        // Initialize the mapping.
+        finalize_store.initialize_mapping(program_id, mapping_name).unwrap();
+
+        // Prepare the keys and values.
+        let key_0 = Plaintext::from_str("0field").unwrap();
+        let key_1 = Plaintext::from_str("1field").unwrap();
+        let value_0 = Value::from_str("0u64").unwrap();
+        let value_1 = Value::from_str("1u64").unwrap();
+        let value_2 = Value::from_str("2u64").unwrap();
+
+        // Finalizes a block at the given height, with the given writes.
+        let finalize_block = |height: u32, writes: &dyn Fn()| {
+            finalize_store.start_journal();
+            writes();
+            finalize_store.insert_history(height, finalize_store.finish_journal()).unwrap();
+        };
+
+        // Block 0, which precedes the finalize history.
+        finalize_block(0, &|| {
+            finalize_store.insert_key_value(program_id, mapping_name, key_0.clone(), value_0.clone()).unwrap();
+        });
+        // Ensure the finalize history is not enabled.
+        assert_eq!(finalize_store.history_start_height().unwrap(), None);
+        assert!(finalize_store.get_value_confirmed_at(program_id, mapping_name, &key_0, 0).is_err());
+
+        // Enable the finalize history, starting from block 2.
+        finalize_store.enable_history(2).unwrap();
+        assert_eq!(finalize_store.history_start_height().unwrap(), Some(2));
+        // Ensure enabling the finalize history again does not change the start height.
+        finalize_store.enable_history(3).unwrap();
+        assert_eq!(finalize_store.history_start_height().unwrap(), Some(2));
+
+        // Block 1, which precedes the finalize history.
+        finalize_block(1, &|| {
+            finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_1.clone()).unwrap();
+        });
+        // Block 2 updates key 0 twice, and inserts key 1.
+        finalize_block(2, &|| {
+            finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_0.clone()).unwrap();
+            finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_2.clone()).unwrap();
+            finalize_store.insert_key_value(program_id, mapping_name, key_1.clone(), value_1.clone()).unwrap();
+        });
+        // Block 3 does not write to the mapping.
+        finalize_block(3, &|| ());
+        // Block 4 replaces the mapping.
+        finalize_block(4, &|| {
+            let entries = vec![(key_1.clone(), value_2.clone())];
+            finalize_store.replace_mapping(program_id, mapping_name, entries).unwrap();
+        });
+        // Block 5 removes the mapping.
+        finalize_block(5, &|| {
+            finalize_store.remove_mapping(program_id, mapping_name).unwrap();
+        });
+
+        // Ensure the values are correct as of each block.
+        let get = |key: &Plaintext<CurrentNetwork>, height| {
+            finalize_store.get_value_confirmed_at(program_id, mapping_name, key, height).unwrap()
+        };
+        assert!(finalize_store.get_value_confirmed_at(program_id, mapping_name, &key_0, 0).is_err());
+        assert_eq!(get(&key_0, 1), Some(value_1.clone()));
+        assert_eq!(get(&key_1, 1), None);
+        assert_eq!(get(&key_0, 2), Some(value_2.clone()));
+        assert_eq!(get(&key_1, 2), Some(value_1.clone()));
+        assert_eq!(get(&key_0, 3), Some(value_2.clone()));
+        assert_eq!(get(&key_1, 3), Some(value_1));
+        assert_eq!(get(&key_0, 4), None);
+        assert_eq!(get(&key_1, 4), Some(value_2));
+        assert_eq!(get(&key_0, 5), None);
+        assert_eq!(get(&key_1, 5), None);
+        assert_eq!(get(&key_1, 6), None);
+
+        // Disable the finalize history.
+        finalize_store.disable_history().unwrap();
+        assert_eq!(finalize_store.history_start_height().unwrap(), None);
+        assert!(finalize_store.get_value_confirmed_at(program_id, mapping_name, &key_1, 5).is_err());
+        // Ensure the finalize history is removed.
+        assert_eq!(finalize_store.storage.history_map().keys_confirmed().count(), 0);
+    }
+
+    #[test]
+    fn test_rollback() {
+        // Initialize a program ID and mapping names.
+        let program_id = ProgramID::<CurrentNetwork>::from_str("hello.aleo").unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+        let other_mapping_name = Identifier::from_str("other").unwrap();
+
+        // Initialize a new finalize store.
+        let program_memory = FinalizeMemory::open(None).unwrap();
+        let finalize_store = FinalizeStore::from(program_memory).unwrap();
+        // Initialize the mappings.
+        finalize_store.initialize_mapping(program_id, mapping_name).unwrap();
+        finalize_store.initialize_mapping(program_id, other_mapping_name).unwrap();
+
+        // Prepare the keys and values.
+        let key_0 = Plaintext::from_str("0field").unwrap();
+        let key_1 = Plaintext::from_str("1field").unwrap();
+        let key_2 = Plaintext::from_str("2field").unwrap();
+        let value_0 = Value::from_str("0u64").unwrap();
+        let value_1 = Value::from_str("1u64").unwrap();
+        let value_2 = Value::from_str("2u64").unwrap();
+
+        // Finalizes a block at the given height, with the given writes.
+        let finalize_block = |height: u32, writes: &dyn Fn()| {
+            finalize_store.start_journal();
+            writes();
+            finalize_store.insert_history(height, finalize_store.finish_journal()).unwrap();
+        };
+        // Returns the entries of the mapping, in the order of their positions.
+        let entries = || {
+            let length = finalize_store.len_mapping_speculative(program_id, mapping_name).unwrap();
+            (0..=length)
+                .map(|index| finalize_store.get_entry_at_speculative(program_id, mapping_name, index).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // Block 0, which precedes the finalize history.
+        finalize_block(0, &|| {
+            finalize_store.insert_key_value(program_id, mapping_name, key_0.clone(), value_0.clone()).unwrap();
+            finalize_store.insert_key_value(program_id, mapping_name, key_1.clone(), value_0.clone()).unwrap();
+        });
+        // Ensure the finalize state cannot be rolled back without the finalize history.
+        assert!(finalize_store.rollback_to(0).is_err());
+
+        // Enable the finalize history, starting from block 1.
+        finalize_store.enable_history(1).unwrap();
+        // Block 1 updates key 0.
+        finalize_block(1, &|| {
+            finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_1.clone()).unwrap();
+        });
+        // Block 2 updates key 0 again, removes key 0, which moves key 1 to its position, and inserts key 2.
+        finalize_block(2, &|| {
+            finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value_2.clone()).unwrap();
+            finalize_store.remove_key_value(program_id, mapping_name, &key_0).unwrap();
+            finalize_store.insert_key_value(program_id, mapping_name, key_2.clone(), value_2.clone()).unwrap();
+        });
+        assert_eq!(entries(), vec![Some((key_1.clone(), value_0.clone())), Some((key_2.clone(), value_2)), None]);
+        // Block 3 updates key 2, and removes the mapping.
+        finalize_block(3, &|| {
+            finalize_store.update_key_value(program_id, mapping_name, key_2.clone(), value_0.clone()).unwrap();
+            finalize_store.remove_mapping(program_id, mapping_name).unwrap();
+        });
+        assert!(!finalize_store.contains_mapping_confirmed(&program_id, &mapping_name).unwrap());
+
+        // Ensure the finalize state cannot be rolled back before the finalize history.
+        assert!(finalize_store.rollback_to(0).is_err());
+
+        // Roll back to block 1.
+        finalize_store.rollback_to(1).unwrap();
+
+        // Ensure the mapping is restored at its original index.
+        let mapping_names = finalize_store.get_mapping_names_confirmed(&program_id).unwrap().unwrap();
+        assert_eq!(mapping_names.into_iter().collect::<Vec<_>>(), vec![mapping_name, other_mapping_name]);
+        // Ensure the positions and values are restored as of block 1.
+        assert_eq!(entries(), vec![
+            Some((key_0.clone(), value_1.clone())),
+            Some((key_1.clone(), value_0.clone())),
+            None
+        ]);
+        assert_eq!(finalize_store.get_value_confirmed(program_id, mapping_name, &key_2).unwrap(), None);
+        // Ensure the finalize history of the removed blocks is removed.
+        assert_eq!(finalize_store.storage.history_map().keys_confirmed().count(), 1);
+        assert_eq!(finalize_store.get_value_confirmed_at(program_id, mapping_name, &key_0, 1).unwrap(), Some(value_1));
+
+        // Ensure keys can be inserted and removed after the rollback.
+        finalize_store.insert_key_value(program_id, mapping_name, key_2.clone(), value_0.clone()).unwrap();
+        finalize_store.remove_key_value(program_id, mapping_name, &key_0).unwrap();
+        assert_eq!(entries(), vec![Some((key_2, value_0.clone())), Some((key_1, value_0)), None]);
+    }
+
     #[test]
     fn test_remove_mapping() {
         // Initialize a program ID and mapping name.
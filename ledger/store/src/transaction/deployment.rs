@@ -19,10 +19,11 @@ use crate::{
     helpers::{Map, MapRead},
     FeeStorage,
     FeeStore,
+    IntegrityIssue,
 };
 use console::{
     network::prelude::*,
-    program::{Identifier, ProgramID, ProgramOwner},
+    program::{Identifier, ProgramID, ProgramOwner, UpgradePolicy},
 };
 use ledger_block::{Deployment, Fee, Transaction};
 use synthesizer_program::Program;
@@ -49,6 +50,8 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
     type VerifyingKeyMap: for<'a> Map<'a, (ProgramID<N>, Identifier<N>, u16), VerifyingKey<N>>;
     /// The mapping of `(program ID, function name, edition)` to `certificate`.
     type CertificateMap: for<'a> Map<'a, (ProgramID<N>, Identifier<N>, u16), Certificate<N>>;
+    /// The mapping of `(program ID, edition)` to `upgrade policy`.
+    type UpgradePolicyMap: for<'a> Map<'a, (ProgramID<N>, u16), UpgradePolicy<N>>;
     /// The fee storage.
     type FeeStorage: FeeStorage<N>;
 
@@ -69,6 +72,8 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
     fn verifying_key_map(&self) -> &Self::VerifyingKeyMap;
     /// Returns the certificate map.
     fn certificate_map(&self) -> &Self::CertificateMap;
+    /// Returns the upgrade policy map.
+    fn upgrade_policy_map(&self) -> &Self::UpgradePolicyMap;
     /// Returns the fee storage.
     fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage>;
 
@@ -86,6 +91,7 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         self.program_map().start_atomic();
         self.verifying_key_map().start_atomic();
         self.certificate_map().start_atomic();
+        self.upgrade_policy_map().start_atomic();
         self.fee_store().start_atomic();
     }
 
@@ -98,6 +104,7 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
             || self.program_map().is_atomic_in_progress()
             || self.verifying_key_map().is_atomic_in_progress()
             || self.certificate_map().is_atomic_in_progress()
+            || self.upgrade_policy_map().is_atomic_in_progress()
             || self.fee_store().is_atomic_in_progress()
     }
 
@@ -110,6 +117,7 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         self.program_map().atomic_checkpoint();
         self.verifying_key_map().atomic_checkpoint();
         self.certificate_map().atomic_checkpoint();
+        self.upgrade_policy_map().atomic_checkpoint();
         self.fee_store().atomic_checkpoint();
     }
 
@@ -122,6 +130,7 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         self.program_map().clear_latest_checkpoint();
         self.verifying_key_map().clear_latest_checkpoint();
         self.certificate_map().clear_latest_checkpoint();
+        self.upgrade_policy_map().clear_latest_checkpoint();
         self.fee_store().clear_latest_checkpoint();
     }
 
@@ -134,6 +143,7 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         self.program_map().atomic_rewind();
         self.verifying_key_map().atomic_rewind();
         self.certificate_map().atomic_rewind();
+        self.upgrade_policy_map().atomic_rewind();
         self.fee_store().atomic_rewind();
     }
 
@@ -146,6 +156,7 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         self.program_map().abort_atomic();
         self.verifying_key_map().abort_atomic();
         self.certificate_map().abort_atomic();
+        self.upgrade_policy_map().abort_atomic();
         self.fee_store().abort_atomic();
     }
 
@@ -158,6 +169,7 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         self.program_map().finish_atomic()?;
         self.verifying_key_map().finish_atomic()?;
         self.certificate_map().finish_atomic()?;
+        self.upgrade_policy_map().finish_atomic()?;
         self.fee_store().finish_atomic()
     }
 
@@ -202,6 +214,8 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
                 // Store the certificate.
                 self.certificate_map().insert((program_id, *function_name, edition), certificate.clone())?;
             }
+            // Store the upgrade policy.
+            self.upgrade_policy_map().insert((program_id, edition), *deployment.upgrade_policy())?;
 
             // Store the fee transition.
             self.fee_store().insert(*transaction_id, fee)?;
@@ -222,6 +236,10 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
             Some(edition) => edition,
             None => bail!("Failed to locate the edition for program '{program_id}'"),
         };
+        // Ensure the transaction deployed the latest edition, as editions must be removed in reverse order.
+        if self.find_edition_from_transaction_id(&program_id, transaction_id)? != Some(edition) {
+            bail!("Failed to remove transaction '{transaction_id}', as it is not the latest edition of '{program_id}'")
+        }
         // Retrieve the program.
         let program = match self.program_map().get_confirmed(&(program_id, edition))? {
             Some(program) => cow_to_cloned!(program),
@@ -231,8 +249,11 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         atomic_batch_scope!(self, {
             // Remove the program ID.
             self.id_map().remove(transaction_id)?;
-            // Remove the edition.
-            self.edition_map().remove(&program_id)?;
+            // Remove the edition, or revert to the previous edition if this was an upgrade.
+            match edition > N::EDITION {
+                true => self.edition_map().insert(program_id, edition - 1)?,
+                false => self.edition_map().remove(&program_id)?,
+            }
 
             // Remove the reverse program ID.
             self.reverse_id_map().remove(&(program_id, edition))?;
@@ -248,6 +269,8 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
                 // Remove the certificate.
                 self.certificate_map().remove(&(program_id, *function_name, edition))?;
             }
+            // Remove the upgrade policy.
+            self.upgrade_policy_map().remove(&(program_id, edition))?;
 
             // Remove the fee transition.
             self.fee_store().remove(transaction_id)?;
@@ -256,6 +279,49 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the deployment transaction for the given `transaction ID`.
+    /// The verifying keys and certificates are only removed if the edition has been superseded,
+    /// as the latest edition of each program is required to load the process.
+    fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        // Retrieve the program ID.
+        let program_id = match self.get_program_id(transaction_id)? {
+            Some(program_id) => program_id,
+            None => bail!("Failed to get the program ID for transaction '{transaction_id}'"),
+        };
+        // Retrieve the latest edition.
+        let latest_edition = match self.get_edition(&program_id)? {
+            Some(edition) => edition,
+            None => bail!("Failed to locate the edition for program '{program_id}'"),
+        };
+        // Retrieve the edition that was deployed in the transaction.
+        let edition = match self.find_edition_from_transaction_id(&program_id, transaction_id)? {
+            Some(edition) => edition,
+            None => bail!("Failed to get the edition for program '{program_id}' in transaction '{transaction_id}'"),
+        };
+        // Retrieve the program.
+        let program = match self.program_map().get_confirmed(&(program_id, edition))? {
+            Some(program) => cow_to_cloned!(program),
+            None => bail!("Failed to locate program '{program_id}' for transaction '{transaction_id}'"),
+        };
+
+        atomic_batch_scope!(self, {
+            // Remove the verifying keys and certificates, if the edition has been superseded.
+            if edition < latest_edition {
+                for function_name in program.functions().keys() {
+                    // Remove the verifying key.
+                    self.verifying_key_map().remove(&(program_id, *function_name, edition))?;
+                    // Remove the certificate.
+                    self.certificate_map().remove(&(program_id, *function_name, edition))?;
+                }
+            }
+
+            // Prune the fee.
+            self.fee_store().prune(transaction_id)?;
+
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `program ID`.
     fn find_transaction_id_from_program_id(&self, program_id: &ProgramID<N>) -> Result<Option<N::TransactionID>> {
         // Check if the program ID is for 'credits.aleo'.
@@ -277,6 +343,28 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         }
     }
 
+    /// Returns the edition of the given `program ID` that was deployed in the given `transaction ID`.
+    fn find_edition_from_transaction_id(
+        &self,
+        program_id: &ProgramID<N>,
+        transaction_id: &N::TransactionID,
+    ) -> Result<Option<u16>> {
+        // Retrieve the latest edition.
+        let latest_edition = match self.get_edition(program_id)? {
+            Some(edition) => edition,
+            None => return Ok(None),
+        };
+        // Search the editions, starting from the latest edition.
+        for edition in (N::EDITION..=latest_edition).rev() {
+            if let Some(candidate) = self.reverse_id_map().get_confirmed(&(*program_id, edition))? {
+                if cow_to_copied!(candidate) == *transaction_id {
+                    return Ok(Some(edition));
+                }
+            }
+        }
+        Ok(None)
+    }
+
     /// Returns the transaction ID that contains the given `transition ID`.
     fn find_transaction_id_from_transition_id(
         &self,
@@ -330,6 +418,21 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         }
     }
 
+    /// Returns the program for the given `program ID` and `edition`.
+    fn get_program_for_edition(&self, program_id: &ProgramID<N>, edition: u16) -> Result<Option<Program<N>>> {
+        // Check if the program ID is for 'credits.aleo'.
+        // This case is handled separately, as it is a default program of the VM.
+        if program_id == &ProgramID::from_str("credits.aleo")? {
+            return Ok(Some(Program::credits()?));
+        }
+
+        // Retrieve the program.
+        match self.program_map().get_confirmed(&(*program_id, edition))? {
+            Some(program) => Ok(Some(cow_to_cloned!(program))),
+            None => Ok(None),
+        }
+    }
+
     /// Returns the verifying key for the given `program ID` and `function name`.
     fn get_verifying_key(
         &self,
@@ -394,10 +497,10 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
             Some(edition) => edition,
             None => return Ok(None),
         };
-        // Retrieve the edition.
-        let edition = match self.get_edition(&program_id)? {
+        // Retrieve the edition that was deployed in the transaction.
+        let edition = match self.find_edition_from_transaction_id(&program_id, transaction_id)? {
             Some(edition) => edition,
-            None => bail!("Failed to get the edition for program '{program_id}'"),
+            None => bail!("Failed to get the edition for program '{program_id}' in transaction '{transaction_id}'"),
         };
         // Retrieve the program.
         let program = match self.program_map().get_confirmed(&(program_id, edition))? {
@@ -424,8 +527,15 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
             verifying_keys.push((*function_name, (verifying_key, certificate)));
         }
 
+        // Retrieve the upgrade policy.
+        // Note: Deployments stored prior to the upgrade policy map have an immutable upgrade policy.
+        let upgrade_policy = match self.upgrade_policy_map().get_confirmed(&(program_id, edition))? {
+            Some(upgrade_policy) => cow_to_copied!(upgrade_policy),
+            None => UpgradePolicy::Immutable,
+        };
+
         // Return the deployment.
-        Ok(Some(Deployment::new(edition, program, verifying_keys)?))
+        Ok(Some(Deployment::new_with_upgrade_policy(edition, program, verifying_keys, upgrade_policy)?))
     }
 
     /// Returns the fee for the given `transaction ID`.
@@ -442,8 +552,7 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
             return Ok(None);
         }
 
-        // TODO (raychu86): Consider program upgrades and edition changes.
-        // Retrieve the edition.
+        // Retrieve the latest edition.
         let edition = match self.get_edition(program_id)? {
             Some(edition) => edition,
             None => return Ok(None),
@@ -469,9 +578,9 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
             None => bail!("Failed to get the fee for transaction '{transaction_id}'"),
         };
 
-        // Retrieve the owner.
-        let owner = match self.get_owner(deployment.program_id())? {
-            Some(owner) => owner,
+        // Retrieve the owner of the deployed edition.
+        let owner = match self.owner_map().get_confirmed(&(*deployment.program_id(), deployment.edition()))? {
+            Some(owner) => cow_to_copied!(owner),
             None => bail!("Failed to get the owner for transaction '{transaction_id}'"),
         };
 
@@ -483,6 +592,62 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
             false => bail!("The deployment transaction ID does not match '{transaction_id}'"),
         }
     }
+
+    /// Checks that the maps of the deployment storage are mutually consistent, and that each fee exists.
+    /// Returns the inconsistent entries, along with the transaction ID they belong to, if it is known.
+    fn check_integrity(&self) -> Result<Vec<(Option<N::TransactionID>, IntegrityIssue)>> {
+        // A helper function to display the key of a program edition.
+        let to_key = |program_id: &ProgramID<N>, edition: u16| format!("{program_id} (edition {edition})");
+
+        let mut issues = Vec::new();
+        // Check that each deployment has its edition, owner, program, upgrade policy, and fee.
+        for (transaction_id, program_id) in self.id_map().iter_confirmed() {
+            if !self.edition_map().contains_key_confirmed(&program_id)? {
+                issues.push((Some(*transaction_id), IntegrityIssue::missing("deployment edition", *program_id)));
+            }
+            match self.find_edition_from_transaction_id(&program_id, &transaction_id)? {
+                Some(edition) => {
+                    let key = (*program_id, edition);
+                    if !self.owner_map().contains_key_confirmed(&key)? {
+                        let issue = IntegrityIssue::missing("deployment owner", to_key(&program_id, edition));
+                        issues.push((Some(*transaction_id), issue));
+                    }
+                    if !self.program_map().contains_key_confirmed(&key)? {
+                        let issue = IntegrityIssue::missing("deployment program", to_key(&program_id, edition));
+                        issues.push((Some(*transaction_id), issue));
+                    }
+                    if !self.upgrade_policy_map().contains_key_confirmed(&key)? {
+                        let issue = IntegrityIssue::missing("deployment upgrade policy", to_key(&program_id, edition));
+                        issues.push((Some(*transaction_id), issue));
+                    }
+                }
+                None => {
+                    issues.push((Some(*transaction_id), IntegrityIssue::missing("reverse deployment ID", *program_id)))
+                }
+            }
+            if !self.fee_store().contains_transaction_id(&transaction_id)? {
+                issues.push((Some(*transaction_id), IntegrityIssue::missing("fee", *transaction_id)));
+            }
+        }
+        // Check that each deployed edition belongs to a deployment.
+        for (key, transaction_id) in self.reverse_id_map().iter_confirmed() {
+            let (program_id, edition) = *key;
+            match self.id_map().get_confirmed(&transaction_id)? {
+                Some(deployed_program_id) if *deployed_program_id == program_id => (),
+                _ => {
+                    let issue = IntegrityIssue::dangling("reverse deployment ID", to_key(&program_id, edition));
+                    issues.push((Some(*transaction_id), issue));
+                }
+            }
+        }
+        // Check that the latest edition of each program is deployed.
+        for (program_id, edition) in self.edition_map().iter_confirmed() {
+            if !self.reverse_id_map().contains_key_confirmed(&(*program_id, *edition))? {
+                issues.push((None, IntegrityIssue::missing("reverse deployment ID", to_key(&program_id, *edition))));
+            }
+        }
+        Ok(issues)
+    }
 }
 
 /// The deployment store.
@@ -518,6 +683,11 @@ impl<N: Network, D: DeploymentStorage<N>> DeploymentStore<N, D> {
         self.storage.remove(transaction_id)
     }
 
+    /// Prunes the deployment transaction for the given `transaction ID`.
+    pub fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        self.storage.prune(transaction_id)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -557,6 +727,12 @@ impl<N: Network, D: DeploymentStorage<N>> DeploymentStore<N, D> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Checks that the maps of the deployment store are mutually consistent, and that each fee exists.
+    /// Returns the inconsistent entries, along with the transaction ID they belong to, if it is known.
+    pub fn check_integrity(&self) -> Result<Vec<(Option<N::TransactionID>, IntegrityIssue)>> {
+        self.storage.check_integrity()
+    }
 }
 
 impl<N: Network, D: DeploymentStorage<N>> DeploymentStore<N, D> {
@@ -585,6 +761,11 @@ impl<N: Network, D: DeploymentStorage<N>> DeploymentStore<N, D> {
         self.storage.get_program(program_id)
     }
 
+    /// Returns the program for the given `program ID` and `edition`.
+    pub fn get_program_for_edition(&self, program_id: &ProgramID<N>, edition: u16) -> Result<Option<Program<N>>> {
+        self.storage.get_program_for_edition(program_id, edition)
+    }
+
     /// Returns the verifying key for the given `(program ID, function name)`.
     pub fn get_verifying_key(
         &self,
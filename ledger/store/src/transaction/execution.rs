@@ -14,11 +14,14 @@
 
 use crate::{
     atomic_batch_scope,
+    check_dangling_keys,
+    check_reverse_map,
     cow_to_cloned,
     cow_to_copied,
     helpers::{Map, MapRead},
     FeeStorage,
     FeeStore,
+    IntegrityIssue,
     TransitionStore,
 };
 use console::network::prelude::*;
@@ -194,6 +197,37 @@ pub trait ExecutionStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the proofs and transition values of the execution transaction for the given `transaction ID`.
+    fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        // Retrieve the transition IDs and fee boolean.
+        let (transition_ids, has_fee) = match self.id_map().get_confirmed(transaction_id)? {
+            Some(ids) => cow_to_cloned!(ids),
+            None => bail!("Failed to get the transition IDs for the transaction '{transaction_id}'"),
+        };
+        // Retrieve the global state root.
+        let global_state_root = match self.inclusion_map().get_confirmed(transaction_id)? {
+            Some(inclusion) => inclusion.0,
+            None => bail!("Failed to get the proof for the transaction '{transaction_id}'"),
+        };
+
+        atomic_batch_scope!(self, {
+            // Prune the transitions.
+            for transition_id in transition_ids {
+                self.transition_store().prune(&transition_id)?;
+            }
+
+            // Remove the proof.
+            self.inclusion_map().insert(*transaction_id, (global_state_root, None))?;
+
+            // Prune the fee.
+            if has_fee {
+                self.fee_store().prune(transaction_id)?;
+            }
+
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `transition ID`.
     fn find_transaction_id_from_transition_id(
         &self,
@@ -284,6 +318,35 @@ pub trait ExecutionStorage<N: Network>: Clone + Send + Sync {
             false => bail!("Mismatching transaction ID for transaction '{transaction_id}'"),
         }
     }
+
+    /// Checks that the maps of the execution storage are mutually consistent, and that each transition exists.
+    /// Returns the inconsistent entries, along with the transaction ID they belong to.
+    fn check_integrity(&self) -> Result<Vec<(Option<N::TransactionID>, IntegrityIssue)>> {
+        // Check the transition IDs against the reverse transition IDs.
+        let mut issues = check_reverse_map(
+            self.id_map(),
+            ("reverse execution ID", self.reverse_id_map()),
+            |(transition_ids, _)| transition_ids.clone(),
+        )?;
+        // Check that each inclusion belongs to an execution.
+        issues.extend(check_dangling_keys(("execution inclusion", self.inclusion_map()), self.id_map())?);
+        // Check that each execution has its inclusion, transitions, and fee.
+        for (transaction_id, execution) in self.id_map().iter_confirmed() {
+            let (transition_ids, has_fee) = &*execution;
+            if !self.inclusion_map().contains_key_confirmed(&transaction_id)? {
+                issues.push((*transaction_id, IntegrityIssue::missing("execution inclusion", *transaction_id)));
+            }
+            for transition_id in transition_ids {
+                if !self.transition_store().contains_transition_id(transition_id)? {
+                    issues.push((*transaction_id, IntegrityIssue::missing("transition", transition_id)));
+                }
+            }
+            if *has_fee && !self.fee_store().contains_transaction_id(&transaction_id)? {
+                issues.push((*transaction_id, IntegrityIssue::missing("fee", *transaction_id)));
+            }
+        }
+        Ok(issues.into_iter().map(|(transaction_id, issue)| (Some(transaction_id), issue)).collect())
+    }
 }
 
 /// The execution store.
@@ -319,6 +382,11 @@ impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
         self.storage.remove(transaction_id)
     }
 
+    /// Prunes the proofs and transition values of the execution transaction for the given `transaction ID`.
+    pub fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        self.storage.prune(transaction_id)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -358,6 +426,12 @@ impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Checks that the maps of the execution store are mutually consistent, and that each transition exists.
+    /// Returns the inconsistent entries, along with the transaction ID they belong to.
+    pub fn check_integrity(&self) -> Result<Vec<(Option<N::TransactionID>, IntegrityIssue)>> {
+        self.storage.check_integrity()
+    }
 }
 
 impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
@@ -382,6 +456,13 @@ impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
     }
 }
 
+impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
+    /// Returns `true` if the given `transaction ID` is an execution.
+    pub fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> Result<bool> {
+        self.storage.id_map().contains_key_confirmed(transaction_id)
+    }
+}
+
 impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
     /// Returns an iterator over the execution transaction IDs, for all executions.
     pub fn execution_transaction_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, N::TransactionID>> {
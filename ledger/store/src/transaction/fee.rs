@@ -14,9 +14,11 @@
 
 use crate::{
     atomic_batch_scope,
+    check_reverse_map,
     cow_to_cloned,
     cow_to_copied,
     helpers::{Map, MapRead},
+    IntegrityIssue,
     TransitionStorage,
     TransitionStore,
 };
@@ -27,6 +29,7 @@ use synthesizer_snark::Proof;
 use aleo_std_storage::StorageMode;
 use anyhow::Result;
 use core::marker::PhantomData;
+use std::borrow::Cow;
 
 /// A trait for fee storage.
 pub trait FeeStorage<N: Network>: Clone + Send + Sync {
@@ -137,6 +140,24 @@ pub trait FeeStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the fee proof and fee transition values for the given `transaction ID`.
+    fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        // Retrieve the fee transition ID and global state root.
+        let (transition_id, global_state_root, _) = match self.fee_map().get_confirmed(transaction_id)? {
+            Some(fee_id) => cow_to_cloned!(fee_id),
+            None => bail!("Failed to locate the fee transition ID for transaction '{transaction_id}'"),
+        };
+
+        atomic_batch_scope!(self, {
+            // Remove the fee proof.
+            self.fee_map().insert(*transaction_id, (transition_id, global_state_root, None))?;
+            // Prune the fee transition.
+            self.transition_store().prune(&transition_id)?;
+
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `transition ID`.
     fn find_transaction_id_from_transition_id(
         &self,
@@ -161,6 +182,26 @@ pub trait FeeStorage<N: Network>: Clone + Send + Sync {
             None => bail!("Failed to locate the fee transition for transaction '{transaction_id}'"),
         }
     }
+
+    /// Checks that the maps of the fee storage are mutually consistent, and that each fee transition exists.
+    /// Returns the inconsistent entries, along with the transaction ID they belong to.
+    fn check_integrity(&self) -> Result<Vec<(Option<N::TransactionID>, IntegrityIssue)>> {
+        // Check the fees against the reverse fees.
+        let mut issues: Vec<_> =
+            check_reverse_map(self.fee_map(), ("reverse fee", self.reverse_fee_map()), |(transition_id, _, _)| {
+                vec![*transition_id]
+            })?
+            .into_iter()
+            .map(|(transaction_id, issue)| (Some(transaction_id), issue))
+            .collect();
+        // Check that each fee transition exists.
+        for (transaction_id, fee) in self.fee_map().iter_confirmed() {
+            if !self.transition_store().contains_transition_id(&fee.0)? {
+                issues.push((Some(*transaction_id), IntegrityIssue::missing("transition", fee.0)));
+            }
+        }
+        Ok(issues)
+    }
 }
 
 /// The fee store.
@@ -196,6 +237,11 @@ impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
         self.storage.remove(transaction_id)
     }
 
+    /// Prunes the fee proof and fee transition values for the given `transaction ID`.
+    pub fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        self.storage.prune(transaction_id)
+    }
+
     /// Returns the transition store.
     pub fn transition_store(&self) -> &TransitionStore<N, F::TransitionStorage> {
         self.storage.transition_store()
@@ -240,6 +286,12 @@ impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Checks that the maps of the fee store are mutually consistent, and that each fee transition exists.
+    /// Returns the inconsistent entries, along with the transaction ID they belong to.
+    pub fn check_integrity(&self) -> Result<Vec<(Option<N::TransactionID>, IntegrityIssue)>> {
+        self.storage.check_integrity()
+    }
 }
 
 impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
@@ -249,6 +301,18 @@ impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
     }
 }
 
+impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
+    /// Returns `true` if the given `transaction ID` has a fee.
+    pub fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> Result<bool> {
+        self.storage.fee_map().contains_key_confirmed(transaction_id)
+    }
+
+    /// Returns an iterator over the transaction IDs, for all fees.
+    pub fn fee_transaction_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, N::TransactionID>> {
+        self.storage.fee_map().keys_confirmed()
+    }
+}
+
 impl<N: Network, F: FeeStorage<N>> FeeStore<N, F> {
     /// Returns the transaction ID that deployed the given `transition ID`.
     pub fn find_transaction_id_from_transition_id(
@@ -25,6 +25,7 @@ use crate::{
     atomic_batch_scope,
     cow_to_copied,
     helpers::{Map, MapRead},
+    IntegrityIssue,
     TransitionStorage,
     TransitionStore,
 };
@@ -194,6 +195,27 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the proofs and transition values of the transaction for the given `transaction ID`.
+    fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        // Retrieve the transaction type.
+        let transaction_type = match self.id_map().get_confirmed(transaction_id)? {
+            Some(transaction_type) => cow_to_copied!(transaction_type),
+            None => bail!("Failed to get the type for transaction '{transaction_id}'"),
+        };
+
+        atomic_batch_scope!(self, {
+            match transaction_type {
+                // Prune the deployment transaction.
+                TransactionType::Deploy => self.deployment_store().prune(transaction_id)?,
+                // Prune the execution transaction.
+                TransactionType::Execute => self.execution_store().prune(transaction_id)?,
+                // Prune the fee transaction.
+                TransactionType::Fee => self.fee_store().prune(transaction_id)?,
+            }
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `transition ID`.
     fn find_transaction_id_from_transition_id(
         &self,
@@ -227,6 +249,70 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
             },
         }
     }
+
+    /// Checks that the maps of the transaction storage, and of the stores it contains, are mutually consistent.
+    /// Returns the inconsistent entries, along with the transaction ID they belong to, if it is known.
+    fn check_integrity(&self) -> Result<Vec<(Option<N::TransactionID>, IntegrityIssue)>> {
+        let mut issues = Vec::new();
+        // Check that each transaction is stored as its type.
+        for (transaction_id, transaction_type) in self.id_map().iter_confirmed() {
+            let (map, is_stored) = match *transaction_type {
+                TransactionType::Deploy => {
+                    ("deployment ID", self.deployment_store().get_program_id(&transaction_id)?.is_some())
+                }
+                TransactionType::Execute => {
+                    ("execution ID", self.execution_store().contains_transaction_id(&transaction_id)?)
+                }
+                TransactionType::Fee => ("fee", self.fee_store().contains_transaction_id(&transaction_id)?),
+            };
+            if !is_stored {
+                issues.push((Some(*transaction_id), IntegrityIssue::missing(map, *transaction_id)));
+            }
+        }
+        // Check that each deployment, execution, and fee belongs to a transaction.
+        for transaction_id in self.deployment_store().deployment_transaction_ids() {
+            if self.id_map().get_confirmed(&transaction_id)?.as_deref() != Some(&TransactionType::Deploy) {
+                issues.push((Some(*transaction_id), IntegrityIssue::dangling("deployment ID", *transaction_id)));
+            }
+        }
+        for transaction_id in self.execution_store().execution_transaction_ids() {
+            if self.id_map().get_confirmed(&transaction_id)?.as_deref() != Some(&TransactionType::Execute) {
+                issues.push((Some(*transaction_id), IntegrityIssue::dangling("execution ID", *transaction_id)));
+            }
+        }
+        for transaction_id in self.fee_store().fee_transaction_ids() {
+            if !self.id_map().contains_key_confirmed(&transaction_id)? {
+                issues.push((Some(*transaction_id), IntegrityIssue::dangling("fee", *transaction_id)));
+            }
+        }
+        // Check the deployments, executions, and fees.
+        issues.extend(self.deployment_store().check_integrity()?);
+        issues.extend(self.execution_store().check_integrity()?);
+        issues.extend(self.fee_store().check_integrity()?);
+
+        // A helper function to find the transaction ID that contains the given transition ID.
+        let find_transaction_id = |transition_id: &N::TransitionID| -> Result<Option<N::TransactionID>> {
+            match self.execution_store().find_transaction_id_from_transition_id(transition_id)? {
+                Some(transaction_id) => Ok(Some(transaction_id)),
+                None => self.fee_store().find_transaction_id_from_transition_id(transition_id),
+            }
+        };
+        // Check that each transition belongs to a transaction.
+        for transition_id in self.transition_store().transition_ids() {
+            if find_transaction_id(&transition_id)?.is_none() {
+                issues.push((None, IntegrityIssue::dangling("transition", *transition_id)));
+            }
+        }
+        // Check the transitions.
+        for (transition_id, issue) in self.transition_store().check_integrity()? {
+            let transaction_id = match transition_id {
+                Some(transition_id) => find_transaction_id(&transition_id)?,
+                None => None,
+            };
+            issues.push((transaction_id, issue));
+        }
+        Ok(issues)
+    }
 }
 
 /// The transaction store.
@@ -262,6 +348,11 @@ impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
         self.storage.remove(transaction_id)
     }
 
+    /// Prunes the proofs and transition values of the transaction for the given `transaction ID`.
+    pub fn prune(&self, transaction_id: &N::TransactionID) -> Result<()> {
+        self.storage.prune(transaction_id)
+    }
+
     /// Returns the deployment store.
     pub fn deployment_store(&self) -> &DeploymentStore<N, T::DeploymentStorage> {
         self.storage.deployment_store()
@@ -311,6 +402,12 @@ impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Checks that the maps of the transaction store, and of the stores it contains, are mutually consistent.
+    /// Returns the inconsistent entries, along with the transaction ID they belong to, if it is known.
+    pub fn check_integrity(&self) -> Result<Vec<(Option<N::TransactionID>, IntegrityIssue)>> {
+        self.storage.check_integrity()
+    }
 }
 
 impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
@@ -14,7 +14,10 @@
 
 use crate::{
     atomic_batch_scope,
+    check_dangling_keys,
+    check_reverse_map,
     helpers::{Map, MapRead},
+    IntegrityIssue,
 };
 use console::{
     network::prelude::*,
@@ -217,6 +220,27 @@ pub trait InputStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the input values for the given `transition ID`, retaining the input IDs, serial numbers and tags.
+    fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        // Retrieve the input IDs.
+        let input_ids: Vec<_> = match self.id_map().get_confirmed(transition_id)? {
+            Some(Cow::Borrowed(ids)) => ids.to_vec(),
+            Some(Cow::Owned(ids)) => ids.into_iter().collect(),
+            None => return Ok(()),
+        };
+
+        atomic_batch_scope!(self, {
+            // Remove the input values.
+            for input_id in input_ids {
+                self.constant_map().remove(&input_id)?;
+                self.public_map().remove(&input_id)?;
+                self.private_map().remove(&input_id)?;
+            }
+
+            Ok(())
+        })
+    }
+
     /// Returns the transition ID that contains the given `input ID`.
     fn find_transition_id(&self, input_id: &Field<N>) -> Result<Option<N::TransitionID>> {
         match self.reverse_id_map().get_confirmed(input_id)? {
@@ -283,6 +307,34 @@ pub trait InputStorage<N: Network>: Clone + Send + Sync {
             None => Ok(vec![]),
         }
     }
+
+    /// Checks that the maps of the input storage are mutually consistent.
+    /// Returns the inconsistent entries, along with the transition ID they belong to, if it is known.
+    fn check_integrity(&self) -> Result<Vec<(Option<N::TransitionID>, IntegrityIssue)>> {
+        let mut issues = Vec::new();
+        // Check the input IDs against the reverse input IDs.
+        for (transition_id, issue) in
+            check_reverse_map(self.id_map(), ("input reverse ID", self.reverse_id_map()), |ids| ids.clone())?
+        {
+            issues.push((Some(transition_id), issue));
+        }
+        // Check the record serial numbers against the record tags.
+        for (serial_number, issue) in
+            check_reverse_map(self.record_map(), ("input record tag", self.record_tag_map()), |tag| vec![*tag])?
+        {
+            issues.push((self.find_transition_id(&serial_number)?, issue));
+        }
+        // Check that each input value belongs to an input ID.
+        let dangling = [
+            check_dangling_keys(("input constant", self.constant_map()), self.reverse_id_map())?,
+            check_dangling_keys(("input public", self.public_map()), self.reverse_id_map())?,
+            check_dangling_keys(("input private", self.private_map()), self.reverse_id_map())?,
+            check_dangling_keys(("input record", self.record_map()), self.reverse_id_map())?,
+            check_dangling_keys(("input external record", self.external_record_map()), self.reverse_id_map())?,
+        ];
+        issues.extend(dangling.into_iter().flatten().map(|(_, issue)| (None, issue)));
+        Ok(issues)
+    }
 }
 
 /// The transition input store.
@@ -344,6 +396,11 @@ impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
         self.storage.remove(transition_id)
     }
 
+    /// Prunes the input values for the given `transition ID`.
+    pub fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        self.storage.prune(transition_id)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -383,6 +440,12 @@ impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Checks that the maps of the input store are mutually consistent.
+    /// Returns the inconsistent entries, along with the transition ID they belong to, if it is known.
+    pub fn check_integrity(&self) -> Result<Vec<(Option<N::TransitionID>, IntegrityIssue)>> {
+        self.storage.check_integrity()
+    }
 }
 
 impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
@@ -422,6 +485,11 @@ impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
 }
 
 impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
+    /// Returns an iterator over the transition IDs, for all transitions in the input store.
+    pub fn transition_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, N::TransitionID>> {
+        self.storage.id_map().keys_confirmed()
+    }
+
     /// Returns an iterator over the input IDs, for all transition inputs.
     pub fn input_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, Field<N>>> {
         self.storage.reverse_id_map().keys_confirmed()
@@ -20,9 +20,12 @@ pub use output::*;
 
 use crate::{
     atomic_batch_scope,
+    check_dangling_keys,
+    check_reverse_map,
     cow_to_cloned,
     cow_to_copied,
     helpers::{Map, MapRead},
+    IntegrityIssue,
 };
 use console::{
     network::prelude::*,
@@ -225,6 +228,18 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the input and output values of the transition for the given `transition ID`.
+    fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        atomic_batch_scope!(self, {
+            // Prune the inputs.
+            self.input_store().prune(transition_id)?;
+            // Prune the outputs.
+            self.output_store().prune(transition_id)?;
+
+            Ok(())
+        })
+    }
+
     /// Returns the transition for the given `transition ID`.
     fn get(&self, transition_id: &N::TransitionID) -> Result<Option<Transition<N>>> {
         // Retrieve the program ID and function name.
@@ -264,6 +279,55 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
             _ => bail!("Transition '{transition_id}' is missing some data (possible corruption)"),
         }
     }
+
+    /// Checks that the maps of the transition storage are mutually consistent.
+    /// Returns the inconsistent entries, along with the transition ID they belong to, if it is known.
+    fn check_integrity(&self) -> Result<Vec<(Option<N::TransitionID>, IntegrityIssue)>> {
+        let mut issues = Vec::new();
+        // Check that each transition has its `tpk`, `tcm` and `scm`.
+        for transition_id in self.locator_map().keys_confirmed() {
+            if !self.tpk_map().contains_key_confirmed(&transition_id)? {
+                issues.push((Some(*transition_id), IntegrityIssue::missing("transition public key", *transition_id)));
+            }
+            if !self.tcm_map().contains_key_confirmed(&transition_id)? {
+                issues.push((Some(*transition_id), IntegrityIssue::missing("transition commitment", *transition_id)));
+            }
+            if !self.scm_map().contains_key_confirmed(&transition_id)? {
+                issues.push((Some(*transition_id), IntegrityIssue::missing("signer commitment", *transition_id)));
+            }
+        }
+        // Check the `tpk` and `tcm` maps against their reverse maps.
+        let reverse = [
+            check_reverse_map(self.tpk_map(), ("reverse transition public key", self.reverse_tpk_map()), |tpk| {
+                vec![*tpk]
+            })?,
+            check_reverse_map(self.tcm_map(), ("reverse transition commitment", self.reverse_tcm_map()), |tcm| {
+                vec![*tcm]
+            })?,
+        ];
+        issues.extend(reverse.into_iter().flatten().map(|(transition_id, issue)| (Some(transition_id), issue)));
+        // Check that each entry belongs to a transition.
+        let dangling = [
+            check_dangling_keys(("transition public key", self.tpk_map()), self.locator_map())?,
+            check_dangling_keys(("transition commitment", self.tcm_map()), self.locator_map())?,
+            check_dangling_keys(("signer commitment", self.scm_map()), self.locator_map())?,
+        ];
+        issues.extend(dangling.into_iter().flatten().map(|(transition_id, issue)| (Some(transition_id), issue)));
+        for transition_id in self.input_store().transition_ids() {
+            if !self.locator_map().contains_key_confirmed(&transition_id)? {
+                issues.push((Some(*transition_id), IntegrityIssue::dangling("input ID", *transition_id)));
+            }
+        }
+        for transition_id in self.output_store().transition_ids() {
+            if !self.locator_map().contains_key_confirmed(&transition_id)? {
+                issues.push((Some(*transition_id), IntegrityIssue::dangling("output ID", *transition_id)));
+            }
+        }
+        // Check the transition inputs and outputs.
+        issues.extend(self.input_store().check_integrity()?);
+        issues.extend(self.output_store().check_integrity()?);
+        Ok(issues)
+    }
 }
 
 /// The transition store.
@@ -333,6 +397,11 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
         self.storage.remove(transition_id)
     }
 
+    /// Prunes the input and output values of the transition for the given `transition ID`.
+    pub fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        self.storage.prune(transition_id)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -372,6 +441,12 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Checks that the maps of the transition store are mutually consistent.
+    /// Returns the inconsistent entries, along with the transition ID they belong to, if it is known.
+    pub fn check_integrity(&self) -> Result<Vec<(Option<N::TransitionID>, IntegrityIssue)>> {
+        self.storage.check_integrity()
+    }
 }
 
 impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
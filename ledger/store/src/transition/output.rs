@@ -14,7 +14,10 @@
 
 use crate::{
     atomic_batch_scope,
+    check_dangling_keys,
+    check_reverse_map,
     helpers::{Map, MapRead},
+    IntegrityIssue,
 };
 use console::{
     network::prelude::*,
@@ -234,6 +237,33 @@ pub trait OutputStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Prunes the output values for the given `transition ID`, retaining the output IDs, commitments and nonces.
+    fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        // Retrieve the output IDs.
+        let output_ids: Vec<_> = match self.id_map().get_confirmed(transition_id)? {
+            Some(Cow::Borrowed(ids)) => ids.to_vec(),
+            Some(Cow::Owned(ids)) => ids.into_iter().collect(),
+            None => return Ok(()),
+        };
+
+        atomic_batch_scope!(self, {
+            // Remove the output values.
+            for output_id in output_ids {
+                // If the output is a record, remove the record ciphertext.
+                if let Some(record) = self.record_map().get_confirmed(&output_id)? {
+                    self.record_map().insert(output_id, (record.0, None))?;
+                }
+
+                self.constant_map().remove(&output_id)?;
+                self.public_map().remove(&output_id)?;
+                self.private_map().remove(&output_id)?;
+                self.future_map().remove(&output_id)?;
+            }
+
+            Ok(())
+        })
+    }
+
     /// Returns the transition ID that contains the given `output ID`.
     fn find_transition_id(&self, output_id: &Field<N>) -> Result<Option<N::TransitionID>> {
         match self.reverse_id_map().get_confirmed(output_id)? {
@@ -302,6 +332,38 @@ pub trait OutputStorage<N: Network>: Clone + Send + Sync {
             None => Ok(vec![]),
         }
     }
+
+    /// Checks that the maps of the output storage are mutually consistent.
+    /// Returns the inconsistent entries, along with the transition ID they belong to, if it is known.
+    fn check_integrity(&self) -> Result<Vec<(Option<N::TransitionID>, IntegrityIssue)>> {
+        let mut issues = Vec::new();
+        // Check the output IDs against the reverse output IDs.
+        for (transition_id, issue) in
+            check_reverse_map(self.id_map(), ("output reverse ID", self.reverse_id_map()), |ids| ids.clone())?
+        {
+            issues.push((Some(transition_id), issue));
+        }
+        // Check that each record nonce belongs to a record.
+        for (nonce, commitment) in self.record_nonce_map().iter_confirmed() {
+            if !self.record_map().contains_key_confirmed(&commitment)? {
+                issues.push((
+                    self.find_transition_id(&commitment)?,
+                    IntegrityIssue::dangling("output record nonce", nonce),
+                ));
+            }
+        }
+        // Check that each output value belongs to an output ID.
+        let dangling = [
+            check_dangling_keys(("output constant", self.constant_map()), self.reverse_id_map())?,
+            check_dangling_keys(("output public", self.public_map()), self.reverse_id_map())?,
+            check_dangling_keys(("output private", self.private_map()), self.reverse_id_map())?,
+            check_dangling_keys(("output record", self.record_map()), self.reverse_id_map())?,
+            check_dangling_keys(("output external record", self.external_record_map()), self.reverse_id_map())?,
+            check_dangling_keys(("output future", self.future_map()), self.reverse_id_map())?,
+        ];
+        issues.extend(dangling.into_iter().flatten().map(|(_, issue)| (None, issue)));
+        Ok(issues)
+    }
 }
 
 /// The transition output store.
@@ -367,6 +429,11 @@ impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
         self.storage.remove(transition_id)
     }
 
+    /// Prunes the output values for the given `transition ID`.
+    pub fn prune(&self, transition_id: &N::TransitionID) -> Result<()> {
+        self.storage.prune(transition_id)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -406,6 +473,12 @@ impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Checks that the maps of the output store are mutually consistent.
+    /// Returns the inconsistent entries, along with the transition ID they belong to, if it is known.
+    pub fn check_integrity(&self) -> Result<Vec<(Option<N::TransitionID>, IntegrityIssue)>> {
+        self.storage.check_integrity()
+    }
 }
 
 impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
@@ -466,6 +539,11 @@ impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
 }
 
 impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
+    /// Returns an iterator over the transition IDs, for all transitions in the output store.
+    pub fn transition_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, N::TransitionID>> {
+        self.storage.id_map().keys_confirmed()
+    }
+
     /// Returns an iterator over the output IDs, for all transition outputs.
     pub fn output_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, Field<N>>> {
         self.storage.reverse_id_map().keys_confirmed()
@@ -12,14 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Process, Stack, StackProgramTypes};
+use crate::{Authorization, Process, Stack, StackProgramTypes};
 
 use console::{
     prelude::*,
     program::{FinalizeType, Identifier, LiteralType, PlaintextType},
 };
-use ledger_block::{Deployment, Execution};
-use synthesizer_program::{CastType, Command, Finalize, Instruction, Operand, StackProgram};
+use ledger_block::{Deployment, Execution, Input};
+use synthesizer_program::{CallOperator, CastType, Command, Finalize, Instruction, Operand, StackProgram};
+
+use indexmap::IndexSet;
 
 /// Returns the *minimum* cost in microcredits to publish the given deployment (total cost, (storage cost, synthesis cost, namespace cost)).
 pub fn deployment_cost<N: Network>(deployment: &Deployment<N>) -> Result<(u64, (u64, u64, u64))> {
@@ -76,6 +78,96 @@ pub fn execution_cost<N: Network>(process: &Process<N>, execution: &Execution<N>
     Ok((total_cost, (storage_cost, finalize_cost)))
 }
 
+/// Returns the *estimated* cost in microcredits to publish an execution for the given authorization (total cost, (storage cost, finalize cost)).
+///
+/// Note: As the authorization is not yet proven, the storage cost is computed from the estimated
+/// size of the execution (see `execution_size_in_bytes_from_authorization`).
+pub fn execution_cost_from_authorization<N: Network>(
+    process: &Process<N>,
+    authorization: &Authorization<N>,
+) -> Result<(u64, (u64, u64))> {
+    // Compute the storage cost in microcredits.
+    let storage_cost = execution_storage_cost::<N>(execution_size_in_bytes_from_authorization(authorization)?);
+
+    // Get the root transition.
+    let transitions = authorization.transitions();
+    let transition =
+        transitions.values().last().ok_or(anyhow!("The authorization does not contain any transitions"))?;
+
+    // Get the finalize cost for the root transition.
+    let finalize_cost = process.get_stack(transition.program_id())?.get_finalize_cost(transition.function_name())?;
+
+    // Compute the total cost in microcredits.
+    let total_cost = storage_cost
+        .checked_add(finalize_cost)
+        .ok_or(anyhow!("The total cost computation overflowed for an execution"))?;
+
+    Ok((total_cost, (storage_cost, finalize_cost)))
+}
+
+/// Returns the *estimated* size in bytes of the execution that would be produced from the given authorization.
+///
+/// The transitions in an authorization are identical to those of the resulting execution,
+/// so only the size of the batch proof is estimated. The proof has one circuit per distinct function
+/// (plus the inclusion circuit), and one instance per transition (plus one per input record).
+pub fn execution_size_in_bytes_from_authorization<N: Network>(authorization: &Authorization<N>) -> Result<u64> {
+    // Retrieve the transitions.
+    let transitions = authorization.transitions();
+    // Ensure the authorization contains transitions.
+    ensure!(!transitions.is_empty(), "The authorization does not contain any transitions");
+
+    // Compute the size of the transitions.
+    let size_of_transitions = transitions.values().try_fold(0u64, |acc, transition| {
+        acc.checked_add(transition.to_bytes_le()?.len() as u64)
+            .ok_or(anyhow!("Overflowed while computing the size of the transitions in an authorization"))
+    })?;
+
+    // Determine the number of distinct functions that are proven.
+    let num_functions = transitions
+        .values()
+        .map(|transition| (transition.program_id(), transition.function_name()))
+        .collect::<IndexSet<_>>()
+        .len();
+    // Determine the number of input records, each of which is proven with an inclusion instance.
+    let num_input_records = transitions
+        .values()
+        .flat_map(|transition| transition.inputs())
+        .filter(|input| matches!(input, Input::Record(..)))
+        .count();
+    // Determine the number of circuits and instances in the batch proof.
+    let num_circuits = num_functions as u64 + (num_input_records > 0) as u64;
+    let num_instances = transitions.len() as u64 + num_input_records as u64;
+
+    // Compute the size of the execution as:
+    //   version + number of transitions + transitions + global state root + proof variant + proof.
+    Ok(2u64
+        .saturating_add(size_of_transitions)
+        .saturating_add(N::StateRoot::default().to_bytes_le()?.len() as u64)
+        .saturating_add(1)
+        .saturating_add(proof_size_in_bytes(num_circuits, num_instances)))
+}
+
+/// Returns the size in bytes of a batch proof with the given number of circuits and instances.
+///
+/// A batch proof is comprised of (in compressed form):
+///   - a fixed component of `PROOF_BASE_SIZE` bytes, for the version, the masking and `h_i` commitments,
+///     the `g_1` evaluation, and the `KZG` opening proofs (one per query point, of which only one is hiding),
+///   - `PROOF_PER_CIRCUIT_SIZE` bytes per circuit, for the batch size, the `g_a`, `g_b`, `g_c` commitments
+///     and evaluations, and the circuit sums,
+///   - `PROOF_PER_INSTANCE_SIZE` bytes per instance, for the witness commitment and the instance sums.
+fn proof_size_in_bytes(num_circuits: u64, num_instances: u64) -> u64 {
+    PROOF_BASE_SIZE
+        .saturating_add(num_circuits.saturating_mul(PROOF_PER_CIRCUIT_SIZE))
+        .saturating_add(num_instances.saturating_mul(PROOF_PER_INSTANCE_SIZE))
+}
+
+/// The size of a batch proof is derived as:
+/// `BASE_SIZE + (PER_CIRCUIT_SIZE * NUM_CIRCUITS) + (PER_INSTANCE_SIZE * NUM_INSTANCES)`.
+
+const PROOF_BASE_SIZE: u64 = 469;
+const PROOF_PER_CIRCUIT_SIZE: u64 = 344;
+const PROOF_PER_INSTANCE_SIZE: u64 = 144;
+
 /// Returns the storage cost in microcredits for a program execution.
 fn execution_storage_cost<N: Network>(size_in_bytes: u64) -> u64 {
     if size_in_bytes > N::EXECUTION_STORAGE_PENALTY_THRESHOLD {
@@ -136,6 +228,22 @@ fn plaintext_size_in_bytes<N: Network>(stack: &Stack<N>, plaintext_type: &Plaint
     }
 }
 
+/// A helper function to determine the size in bytes of the key and value types of the given mapping.
+fn mapping_entry_size_in_bytes<N: Network>(stack: &Stack<N>, mapping: &CallOperator<N>) -> Result<(u64, u64)> {
+    // Retrieve the stack that defines the mapping, and the mapping name.
+    let (stack, mapping_name) = match mapping {
+        CallOperator::Locator(locator) => (&**stack.get_external_stack(locator.program_id())?, locator.resource()),
+        CallOperator::Resource(mapping_name) => (stack, mapping_name),
+    };
+    // Retrieve the mapping from the stack.
+    let mapping = stack.program().get_mapping(mapping_name)?;
+    // Return the size of the key and value types.
+    Ok((
+        plaintext_size_in_bytes(stack, mapping.key().plaintext_type())?,
+        plaintext_size_in_bytes(stack, mapping.value().plaintext_type())?,
+    ))
+}
+
 /// A helper function to compute the following: base_cost + (byte_multiplier * size_of_operands).
 fn cost_in_size<'a, N: Network>(
     stack: &Stack<N>,
@@ -362,6 +470,19 @@ pub fn cost_per_command<N: Network>(stack: &Stack<N>, finalize: &Finalize<N>, co
         }
         Command::BranchEq(_) | Command::BranchNeq(_) => Ok(500),
         Command::Position(_) => Ok(100),
+        // The length of a mapping is stored, so it is read once.
+        Command::MappingLen(_) => Ok(MAPPING_BASE_COST),
+        // The key at an index is read once from the positions of the mapping, and is charged by its size.
+        Command::MappingKeyAt(command) => {
+            let (key_size, _) = mapping_entry_size_in_bytes(stack, command.mapping())?;
+            Ok(MAPPING_BASE_COST.saturating_add(MAPPING_PER_BYTE_COST.saturating_mul(key_size)))
+        }
+        // The entry at an index reads the key from the positions of the mapping, and then its value.
+        Command::MappingEntryAt(command) => {
+            let (key_size, value_size) = mapping_entry_size_in_bytes(stack, command.mapping())?;
+            let entry_size = key_size.saturating_add(value_size);
+            Ok(MAPPING_BASE_COST.saturating_mul(2).saturating_add(MAPPING_PER_BYTE_COST.saturating_mul(entry_size)))
+        }
     }
 }
 
@@ -399,7 +520,12 @@ mod tests {
     use super::*;
     use crate::test_helpers::get_execution;
 
-    use console::network::{CanaryV0, MainnetV0, TestnetV0};
+    use circuit::network::AleoV0;
+    use console::{
+        account::PrivateKey,
+        network::{CanaryV0, MainnetV0, TestnetV0},
+    };
+    use ledger_store::{helpers::memory::BlockMemory, BlockStore};
     use synthesizer_program::Program;
 
     // Test program with two functions just below and above the size threshold.
@@ -481,4 +607,113 @@ function over_five_thousand:
         assert_eq!(storage_cost_under_5000, execution_storage_cost::<MainnetV0>(execution_size_under_5000));
         assert_eq!(storage_cost_over_5000, execution_storage_cost::<MainnetV0>(execution_size_over_5000));
     }
+
+    #[test]
+    fn test_proof_size_in_bytes() {
+        use circuit::{
+            environment::{Circuit, Environment, Inject, Mode},
+            types::Field,
+        };
+        use synthesizer_snark::{ProvingKey, UniversalSRS};
+
+        let rng = &mut TestRng::default();
+
+        // Returns the assignment of a circuit with the given number of public inputs and multiplications.
+        let sample_assignment = |num_public: usize, num_multiplications: usize| {
+            Circuit::reset();
+            let mut accumulator = Field::<Circuit>::new(Mode::Private, console::types::Field::one());
+            for _ in 0..num_public {
+                accumulator += Field::new(Mode::Public, console::types::Field::one());
+            }
+            for _ in 0..num_multiplications {
+                accumulator *= Field::new(Mode::Private, console::types::Field::one().double());
+            }
+            Circuit::eject_assignment_and_reset()
+        };
+
+        // Initialize circuits of different shapes.
+        let srs = UniversalSRS::<MainnetV0>::load().unwrap();
+        let circuits = [(1, 8), (4, 64), (2, 300)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (num_public, num_multiplications))| {
+                let assignment = sample_assignment(num_public, num_multiplications);
+                let (proving_key, _) = srs.to_circuit_key(&format!("circuit_{i}"), &assignment).unwrap();
+                (proving_key, assignment)
+            })
+            .collect::<Vec<_>>();
+
+        // Ensure the proof size matches the serialized size of a batch proof, for each number of instances per circuit.
+        for num_instances_per_circuit in [&[1][..], &[3], &[1, 1], &[2, 1], &[1, 2, 4]] {
+            let batch = circuits
+                .iter()
+                .zip(num_instances_per_circuit)
+                .map(|((proving_key, assignment), num_instances)| {
+                    (proving_key.clone(), vec![assignment.clone(); *num_instances])
+                })
+                .collect::<Vec<_>>();
+            let proof = ProvingKey::prove_batch("test", &batch, rng).unwrap();
+
+            let num_circuits = num_instances_per_circuit.len() as u64;
+            let num_instances = num_instances_per_circuit.iter().sum::<usize>() as u64;
+            assert_eq!(
+                proof_size_in_bytes(num_circuits, num_instances),
+                proof.to_bytes_le().unwrap().len() as u64,
+                "Incorrect proof size for {num_instances_per_circuit:?} instances per circuit"
+            );
+        }
+    }
+
+    #[test]
+    fn test_execution_cost_from_authorization() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the process.
+        let mut process = Process::load().unwrap();
+
+        // Add the program to the process.
+        let program = Program::from_str(
+            r"
+program estimate.aleo;
+
+function sum:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;
+
+function sum_public:
+    input r0 as u32.public;
+    input r1 as u32.public;
+    add r0 r1 into r2;
+    output r2 as u32.public;
+    output r1 as u32.public;",
+        )
+        .unwrap();
+        process.add_program(&program).unwrap();
+
+        // Initialize a private key.
+        let private_key = PrivateKey::new(rng).unwrap();
+
+        for function_name in ["sum", "sum_public"] {
+            // Compute the authorization.
+            let authorization = process
+                .authorize::<AleoV0, _>(&private_key, program.id(), function_name, ["1u32", "2u32"].into_iter(), rng)
+                .unwrap();
+
+            // Estimate the cost from the authorization.
+            let estimated_size = execution_size_in_bytes_from_authorization(&authorization).unwrap();
+            let estimated_cost = execution_cost_from_authorization(&process, &authorization).unwrap();
+
+            // Execute and prove the authorization.
+            let (_, mut trace) = process.execute::<AleoV0, _>(authorization, rng).unwrap();
+            let block_store = BlockStore::<MainnetV0, BlockMemory<_>>::open(None).unwrap();
+            trace.prepare(ledger_query::Query::from(block_store)).unwrap();
+            let execution = trace.prove_execution::<AleoV0, _>(function_name, rng).unwrap();
+
+            // Ensure the estimate matches the proven execution.
+            assert_eq!(estimated_size, execution.size_in_bytes().unwrap());
+            assert_eq!(estimated_cost, execution_cost(&process, &execution).unwrap());
+        }
+    }
 }
@@ -0,0 +1,408 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    network::prelude::*,
+    program::{Identifier, ProgramID, Value},
+};
+use synthesizer_program::{Command, Instruction};
+
+use indexmap::IndexMap;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// The debugger attached to a process (and shared by its stacks), if any.
+pub(crate) type DebuggerSlot<N> = Arc<RwLock<Option<Arc<dyn Debugger<N>>>>>;
+
+/// The scope of an instruction or command.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DebugScope {
+    /// An instruction in a closure.
+    Closure,
+    /// An instruction in a function.
+    Function,
+    /// A command in the finalize scope of a function.
+    Finalize,
+}
+
+/// The location of an instruction or command, as (program ID, closure or function name, scope, index).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DebugLocation<N: Network> {
+    /// The program ID.
+    program_id: ProgramID<N>,
+    /// The closure or function name.
+    name: Identifier<N>,
+    /// The scope.
+    scope: DebugScope,
+    /// The index of the instruction or command in the scope.
+    index: usize,
+}
+
+impl<N: Network> DebugLocation<N> {
+    /// Initializes a new debug location.
+    pub const fn new(program_id: ProgramID<N>, name: Identifier<N>, scope: DebugScope, index: usize) -> Self {
+        Self { program_id, name, scope, index }
+    }
+
+    /// Returns the program ID.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the closure or function name.
+    pub const fn name(&self) -> &Identifier<N> {
+        &self.name
+    }
+
+    /// Returns the scope.
+    pub const fn scope(&self) -> DebugScope {
+        self.scope
+    }
+
+    /// Returns the index of the instruction or command in the scope.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<N: Network> Display for DebugLocation<N> {
+    /// Prints the location as `program_id/name:index`, or `program_id/name/finalize:index` for a command.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.scope {
+            DebugScope::Closure | DebugScope::Function => write!(f, "{}/{}:{}", self.program_id, self.name, self.index),
+            DebugScope::Finalize => write!(f, "{}/{}/finalize:{}", self.program_id, self.name, self.index),
+        }
+    }
+}
+
+/// A read-only view of the assigned registers, as (locator, value).
+#[derive(Copy, Clone, Debug)]
+pub struct RegistersView<'a, N: Network> {
+    /// The mapping of assigned registers to their values.
+    registers: &'a IndexMap<u64, Value<N>>,
+}
+
+impl<'a, N: Network> RegistersView<'a, N> {
+    /// Initializes a new registers view.
+    pub(crate) const fn new(registers: &'a IndexMap<u64, Value<N>>) -> Self {
+        Self { registers }
+    }
+
+    /// Returns the value of the given register locator, if it is assigned.
+    pub fn get(&self, locator: u64) -> Option<&'a Value<N>> {
+        self.registers.get(&locator)
+    }
+
+    /// Returns an iterator over the assigned registers, as (locator, value), in the order they were assigned.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (u64, &'a Value<N>)> {
+        self.registers.iter().map(|(locator, value)| (*locator, value))
+    }
+
+    /// Returns the number of assigned registers.
+    pub fn len(&self) -> usize {
+        self.registers.len()
+    }
+
+    /// Returns `true` if no registers are assigned.
+    pub fn is_empty(&self) -> bool {
+        self.registers.is_empty()
+    }
+}
+
+/// A debugger that is notified as a process evaluates instructions and finalizes commands.
+///
+/// If a callback returns an error, evaluation (or finalize) halts with the error.
+pub trait Debugger<N: Network>: Send + Sync {
+    /// Returns `true` if there is a breakpoint at the given location.
+    fn is_breakpoint(&self, _location: &DebugLocation<N>) -> bool {
+        false
+    }
+
+    /// Invoked when a breakpoint is reached, before the instruction or command at the location is evaluated.
+    fn on_breakpoint(&self, _location: &DebugLocation<N>, _registers: RegistersView<N>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Invoked before the given instruction is evaluated.
+    fn before_instruction(
+        &self,
+        _location: &DebugLocation<N>,
+        _instruction: &Instruction<N>,
+        _registers: RegistersView<N>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Invoked after the given instruction is evaluated successfully.
+    fn after_instruction(
+        &self,
+        _location: &DebugLocation<N>,
+        _instruction: &Instruction<N>,
+        _registers: RegistersView<N>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Invoked before the given command is finalized.
+    fn before_command(
+        &self,
+        _location: &DebugLocation<N>,
+        _command: &Command<N>,
+        _registers: RegistersView<N>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Invoked after the given command is finalized successfully.
+    /// For an `await` command, this is invoked before the awaited finalize scope is finalized.
+    fn after_command(
+        &self,
+        _location: &DebugLocation<N>,
+        _command: &Command<N>,
+        _registers: RegistersView<N>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Notifies the debugger (if any) before the given instruction is evaluated.
+pub(crate) fn debug_before_instruction<N: Network>(
+    debugger: Option<&dyn Debugger<N>>,
+    location: impl FnOnce() -> DebugLocation<N>,
+    instruction: &Instruction<N>,
+    registers: RegistersView<N>,
+) -> Result<()> {
+    if let Some(debugger) = debugger {
+        let location = location();
+        if debugger.is_breakpoint(&location) {
+            debugger.on_breakpoint(&location, registers)?;
+        }
+        debugger.before_instruction(&location, instruction, registers)?;
+    }
+    Ok(())
+}
+
+/// Notifies the debugger (if any) after the given instruction is evaluated successfully.
+pub(crate) fn debug_after_instruction<N: Network>(
+    debugger: Option<&dyn Debugger<N>>,
+    location: impl FnOnce() -> DebugLocation<N>,
+    instruction: &Instruction<N>,
+    registers: RegistersView<N>,
+) -> Result<()> {
+    match debugger {
+        Some(debugger) => debugger.after_instruction(&location(), instruction, registers),
+        None => Ok(()),
+    }
+}
+
+/// Notifies the debugger (if any) before the given command is finalized.
+pub(crate) fn debug_before_command<N: Network>(
+    debugger: Option<&dyn Debugger<N>>,
+    location: impl FnOnce() -> DebugLocation<N>,
+    command: &Command<N>,
+    registers: RegistersView<N>,
+) -> Result<()> {
+    if let Some(debugger) = debugger {
+        let location = location();
+        if debugger.is_breakpoint(&location) {
+            debugger.on_breakpoint(&location, registers)?;
+        }
+        debugger.before_command(&location, command, registers)?;
+    }
+    Ok(())
+}
+
+/// Notifies the debugger (if any) after the given command is finalized successfully.
+pub(crate) fn debug_after_command<N: Network>(
+    debugger: Option<&dyn Debugger<N>>,
+    location: impl FnOnce() -> DebugLocation<N>,
+    command: &Command<N>,
+    registers: RegistersView<N>,
+) -> Result<()> {
+    match debugger {
+        Some(debugger) => debugger.after_command(&location(), command, registers),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Process;
+    use circuit::network::AleoV0;
+    use console::{account::PrivateKey, network::MainnetV0};
+    use ledger_block::Execution;
+    use ledger_store::{helpers::memory::FinalizeMemory, FinalizeStore};
+    use synthesizer_program::{FinalizeGlobalState, Program};
+
+    use parking_lot::Mutex;
+
+    type CurrentNetwork = MainnetV0;
+
+    /// A debugger that records each callback, and halts at the given location (if any).
+    #[derive(Default)]
+    struct SampleDebugger {
+        breakpoints: Vec<DebugLocation<CurrentNetwork>>,
+        halt_at: Option<DebugLocation<CurrentNetwork>>,
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Debugger<CurrentNetwork> for SampleDebugger {
+        fn is_breakpoint(&self, location: &DebugLocation<CurrentNetwork>) -> bool {
+            self.breakpoints.contains(location)
+        }
+
+        fn on_breakpoint(
+            &self,
+            location: &DebugLocation<CurrentNetwork>,
+            registers: RegistersView<CurrentNetwork>,
+        ) -> Result<()> {
+            let registers = registers.iter().map(|(locator, value)| format!("r{locator}={value}")).collect::<Vec<_>>();
+            self.events.lock().push(format!("break {location} [{}]", registers.join(", ")));
+            Ok(())
+        }
+
+        fn before_instruction(
+            &self,
+            location: &DebugLocation<CurrentNetwork>,
+            instruction: &Instruction<CurrentNetwork>,
+            _registers: RegistersView<CurrentNetwork>,
+        ) -> Result<()> {
+            ensure!(self.halt_at.as_ref() != Some(location), "Halted at {location}");
+            self.events.lock().push(format!("before {location} {instruction}"));
+            Ok(())
+        }
+
+        fn after_instruction(
+            &self,
+            location: &DebugLocation<CurrentNetwork>,
+            _instruction: &Instruction<CurrentNetwork>,
+            registers: RegistersView<CurrentNetwork>,
+        ) -> Result<()> {
+            self.events.lock().push(format!("after {location} ({} registers)", registers.len()));
+            Ok(())
+        }
+
+        fn before_command(
+            &self,
+            location: &DebugLocation<CurrentNetwork>,
+            command: &Command<CurrentNetwork>,
+            _registers: RegistersView<CurrentNetwork>,
+        ) -> Result<()> {
+            self.events.lock().push(format!("before {location} {command}"));
+            Ok(())
+        }
+
+        fn after_command(
+            &self,
+            location: &DebugLocation<CurrentNetwork>,
+            _command: &Command<CurrentNetwork>,
+            registers: RegistersView<CurrentNetwork>,
+        ) -> Result<()> {
+            self.events.lock().push(format!("after {location} ({} registers)", registers.len()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_debugger() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the process.
+        let mut process = Process::<CurrentNetwork>::load().unwrap();
+        // Add the program to the process.
+        let program = Program::from_str(
+            r"
+program debug.aleo;
+
+mapping counts:
+    key as u32.public;
+    value as u32.public;
+
+function sum:
+    input r0 as u32.public;
+    input r1 as u32.public;
+    add r0 r1 into r2;
+    mul r2 r2 into r3;
+    async sum r3 into r4;
+    output r3 as u32.public;
+    output r4 as debug.aleo/sum.future;
+
+finalize sum:
+    input r0 as u32.public;
+    get.or_use counts[0u32] 0u32 into r1;
+    add r0 r1 into r2;
+    set r2 into counts[0u32];",
+        )
+        .unwrap();
+        process.add_program(&program).unwrap();
+
+        // Prepare the locations.
+        let program_id = *program.id();
+        let function_name = Identifier::from_str("sum").unwrap();
+        let function = |index| DebugLocation::new(program_id, function_name, DebugScope::Function, index);
+        let finalize = |index| DebugLocation::new(program_id, function_name, DebugScope::Finalize, index);
+
+        // Authorize the function.
+        let private_key = PrivateKey::new(rng).unwrap();
+        let authorization = process
+            .authorize::<AleoV0, _>(&private_key, program_id, function_name, ["1u32", "2u32"].into_iter(), rng)
+            .unwrap();
+
+        // Attach the debugger, with a breakpoint in the function and in the finalize scope.
+        let debugger = Arc::new(SampleDebugger { breakpoints: vec![function(1), finalize(2)], ..Default::default() });
+        process.attach_debugger(debugger.clone());
+
+        // Evaluate the function.
+        let response = process.evaluate::<AleoV0>(authorization.replicate()).unwrap();
+        assert_eq!(response.outputs()[0], Value::from_str("9u32").unwrap());
+
+        // Finalize the (unproven) execution.
+        let store = FinalizeStore::<CurrentNetwork, FinalizeMemory<_>>::open(None).unwrap();
+        store.initialize_mapping(program_id, Identifier::from_str("counts").unwrap()).unwrap();
+        let execution = Execution::from(authorization.transitions().into_values(), Default::default(), None).unwrap();
+        let state = FinalizeGlobalState::from(1, 1, [0u8; 32]);
+        process.finalize_execution(state, &store, &execution, None).unwrap();
+
+        // Ensure the debugger was notified of each instruction and command, in order.
+        assert_eq!(*debugger.events.lock(), vec![
+            "before debug.aleo/sum:0 add r0 r1 into r2;",
+            "after debug.aleo/sum:0 (3 registers)",
+            "break debug.aleo/sum:1 [r0=1u32, r1=2u32, r2=3u32]",
+            "before debug.aleo/sum:1 mul r2 r2 into r3;",
+            "after debug.aleo/sum:1 (4 registers)",
+            "before debug.aleo/sum:2 async sum r3 into r4;",
+            "after debug.aleo/sum:2 (5 registers)",
+            "before debug.aleo/sum/finalize:0 get.or_use counts[0u32] 0u32 into r1;",
+            "after debug.aleo/sum/finalize:0 (2 registers)",
+            "before debug.aleo/sum/finalize:1 add r0 r1 into r2;",
+            "after debug.aleo/sum/finalize:1 (3 registers)",
+            "break debug.aleo/sum/finalize:2 [r0=9u32, r1=0u32, r2=9u32]",
+            "before debug.aleo/sum/finalize:2 set r2 into counts[0u32];",
+            "after debug.aleo/sum/finalize:2 (3 registers)",
+        ]);
+
+        // Ensure a debugger can halt evaluation.
+        let debugger = Arc::new(SampleDebugger { halt_at: Some(function(1)), ..Default::default() });
+        process.attach_debugger(debugger.clone());
+        let error = process.evaluate::<AleoV0>(authorization.replicate()).unwrap_err();
+        assert!(error.to_string().contains("Halted at debug.aleo/sum:1"));
+        assert_eq!(debugger.events.lock().len(), 2);
+
+        // Ensure a detached debugger is no longer notified.
+        assert!(process.detach_debugger().is_some());
+        process.evaluate::<AleoV0>(authorization).unwrap();
+        assert_eq!(debugger.events.lock().len(), 2);
+    }
+}
@@ -21,11 +21,33 @@ impl<N: Network> Process<N> {
         &self,
         program: &Program<N>,
         rng: &mut R,
+    ) -> Result<Deployment<N>> {
+        self.deploy_with_upgrade_policy::<A, R>(program, UpgradePolicy::Immutable, rng)
+    }
+
+    /// Deploys the given program with the given upgrade policy.
+    /// If the program already exists, the deployment is for the next edition of the program.
+    #[inline]
+    pub fn deploy_with_upgrade_policy<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        program: &Program<N>,
+        upgrade_policy: UpgradePolicy<N>,
+        rng: &mut R,
     ) -> Result<Deployment<N>> {
         let timer = timer!("Process::deploy");
 
+        // Determine the edition.
+        let edition = match self.contains_program(program.id()) {
+            true => self
+                .get_stack(program.id())?
+                .edition()
+                .checked_add(1)
+                .ok_or_else(|| anyhow!("Program '{}' exceeds the maximum edition", program.id()))?,
+            false => N::EDITION,
+        };
+
         // Compute the stack.
-        let stack = Stack::new(self, program)?;
+        let stack = Stack::new_edition(self, program, edition, upgrade_policy)?;
         lap!(timer, "Compute the stack");
 
         // Return the deployment.
@@ -44,7 +66,13 @@ impl<N: Network> Process<N> {
         let timer = timer!("Process::load_deployment");
 
         // Compute the program stack.
-        let stack = Stack::new(self, deployment.program())?;
+        // Note: As the deployment is assumed to be valid, a program that is not yet loaded may be at any edition.
+        let stack = match self.contains_program(deployment.program_id()) {
+            true => Stack::from_deployment(self, deployment)?,
+            false => {
+                Stack::new_edition(self, deployment.program(), deployment.edition(), *deployment.upgrade_policy())?
+            }
+        };
         lap!(timer, "Compute the stack");
 
         // Insert the verifying keys.
@@ -54,7 +82,10 @@ impl<N: Network> Process<N> {
         lap!(timer, "Insert the verifying keys");
 
         // Add the stack to the process.
-        self.add_stack(stack);
+        match self.contains_program(stack.program_id()) {
+            true => self.upgrade_stack(stack)?,
+            false => self.add_stack(stack),
+        }
 
         finish!(timer);
 
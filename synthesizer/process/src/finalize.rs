@@ -34,7 +34,7 @@ impl<N: Network> Process<N> {
         let timer = timer!("Process::finalize_deployment");
 
         // Compute the program stack.
-        let stack = Stack::new(self, deployment.program())?;
+        let stack = Stack::from_deployment(self, deployment)?;
         lap!(timer, "Compute the stack");
 
         // Insert the verifying keys.
@@ -62,6 +62,10 @@ impl<N: Network> Process<N> {
             let program_id = deployment.program_id();
             // Iterate over the mappings.
             for mapping in deployment.program().mappings().values() {
+                // If this is an upgrade, skip the existing mappings, so that their data is preserved.
+                if deployment.is_upgrade() && store.contains_mapping_speculative(program_id, mapping.name())? {
+                    continue;
+                }
                 // Initialize the mapping.
                 finalize_operations.push(store.initialize_mapping(*program_id, *mapping.name())?);
             }
@@ -204,6 +208,9 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
     // Initialize a list for finalize operations.
     let mut finalize_operations = Vec::new();
 
+    // Retrieve the debugger, if one is attached.
+    let debugger = stack.debugger();
+
     // Initialize a stack of active finalize states.
     let mut states = Vec::new();
 
@@ -224,6 +231,11 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
         while counter < finalize.commands().len() {
             // Retrieve the command.
             let command = &finalize.commands()[counter];
+            // Construct the location of the command, for the debugger.
+            let index = counter;
+            let location = || DebugLocation::new(*stack.program_id(), *finalize.name(), DebugScope::Finalize, index);
+            // Notify the debugger before finalizing the command.
+            debug_before_command(debugger.as_deref(), location, command, registers.view())?;
             // Finalize the command.
             match &command {
                 Command::BranchEq(branch_eq) => {
@@ -289,6 +301,8 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                     counter += 1;
                     // Add the awaited register to the tracked set.
                     awaited.insert(await_.register().clone());
+                    // Notify the debugger after finalizing the command.
+                    debug_after_command(debugger.as_deref(), location, command, registers.view())?;
 
                     // Aggregate the caller state.
                     let caller_state = FinalizeState { counter, finalize, registers, stack, call_counter, awaited };
@@ -315,6 +329,8 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                     counter += 1;
                 }
             };
+            // Notify the debugger after finalizing the command.
+            debug_after_command(debugger.as_deref(), location, command, registers.view())?;
         }
         // Check that all future registers have been awaited.
         let mut unawaited = Vec::new();
@@ -518,4 +534,87 @@ function compute:
         // Ensure the program exists.
         assert!(process.contains_program(program.id()));
     }
+
+    #[test]
+    fn test_upgrade_deployment() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the editions of the program.
+        let program_0 = Program::<CurrentNetwork>::from_str(
+            r"
+program upgradable.aleo;
+
+mapping counter:
+    key as u8.public;
+    value as u8.public;
+
+function compute:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    output r1 as u8.private;",
+        )
+        .unwrap();
+        let program_1 = Program::<CurrentNetwork>::from_str(
+            r"
+program upgradable.aleo;
+
+mapping counter:
+    key as u8.public;
+    value as u8.public;
+
+function compute:
+    input r0 as u8.private;
+    mul r0 r0 into r1;
+    output r1 as u8.private;",
+        )
+        .unwrap();
+        // Initialize a program that imports the upgradable program.
+        let dependent = Program::<CurrentNetwork>::from_str(
+            r"
+import upgradable.aleo;
+
+program dependent.aleo;
+
+function call_compute:
+    input r0 as u8.private;
+    call upgradable.aleo/compute r0 into r1;
+    output r1 as u8.private;",
+        )
+        .unwrap();
+
+        // Initialize a new process.
+        let mut process = Process::load().unwrap();
+
+        // Deploy the first edition, which may only be upgraded to the second edition.
+        let upgrade_policy = UpgradePolicy::Checksum(program_1.to_checksum().unwrap());
+        let deployment = process.deploy_with_upgrade_policy::<CurrentAleo, _>(&program_0, upgrade_policy, rng).unwrap();
+        assert!(!deployment.is_upgrade());
+        process.load_deployment(&deployment).unwrap();
+        // Deploy the dependent program.
+        let deployment = process.deploy::<CurrentAleo, _>(&dependent, rng).unwrap();
+        process.load_deployment(&deployment).unwrap();
+
+        // Ensure an upgrade that does not match the checksum is rejected.
+        let program_2 = Program::<CurrentNetwork>::from_str(&program_1.to_string().replace("mul", "sub")).unwrap();
+        let deployment = process.deploy::<CurrentAleo, _>(&program_2, rng).unwrap();
+        assert!(process.load_deployment(&deployment).is_err());
+
+        // Upgrade to the second edition.
+        let deployment = process.deploy::<CurrentAleo, _>(&program_1, rng).unwrap();
+        assert!(deployment.is_upgrade());
+        assert_eq!(deployment.edition(), CurrentNetwork::EDITION + 1);
+        process.load_deployment(&deployment).unwrap();
+
+        // Ensure the upgraded program is loaded, and the dependent program links to it.
+        let stack = process.get_stack(program_1.id()).unwrap();
+        assert_eq!(stack.program(), &program_1);
+        assert_eq!(stack.edition(), CurrentNetwork::EDITION + 1);
+        assert!(stack.upgrade_policy().is_immutable());
+        let dependent_stack = process.get_stack(dependent.id()).unwrap();
+        assert_eq!(dependent_stack.get_external_program(program_1.id()).unwrap(), &program_1);
+
+        // Ensure the second edition may not be upgraded.
+        let deployment = process.deploy::<CurrentAleo, _>(&program_2, rng).unwrap();
+        assert!(process.load_deployment(&deployment).is_err());
+    }
 }
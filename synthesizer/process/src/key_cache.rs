@@ -0,0 +1,302 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use circuit::environment::{Assignment, AssignmentLC, AssignmentVariable};
+use console::{network::prelude::*, program::Identifier, types::Field};
+use synthesizer_snark::{ProvingKey, VerifyingKey};
+
+use parking_lot::RwLock;
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The key cache attached to a process (and shared by its stacks), if any.
+pub(crate) type KeyCacheSlot = Arc<RwLock<Option<KeyCache>>>;
+
+/// The version of the key cache entries.
+const KEY_CACHE_VERSION: u8 = 2;
+
+/// Returns the hash of the constraints of the given circuit assignment, which is independent of its witness.
+pub(crate) fn to_circuit_hash<N: Network>(assignment: &Assignment<N::Field>) -> Result<[u8; 32]> {
+    /// Absorbs the given linear combination into the hasher.
+    fn absorb_lc<N: Network>(hasher: &mut Sha256, lc: &AssignmentLC<N::Field>) -> Result<()> {
+        hasher.update(lc.constant().to_bytes_le()?);
+        hasher.update((lc.terms().len() as u64).to_le_bytes());
+        for (variable, coefficient) in lc.terms() {
+            match variable {
+                AssignmentVariable::Constant(value) => {
+                    hasher.update([0u8]);
+                    hasher.update(value.to_bytes_le()?);
+                }
+                AssignmentVariable::Public(index) => {
+                    hasher.update([1u8]);
+                    hasher.update(index.to_le_bytes());
+                }
+                AssignmentVariable::Private(index) => {
+                    hasher.update([2u8]);
+                    hasher.update(index.to_le_bytes());
+                }
+            }
+            hasher.update(coefficient.to_bytes_le()?);
+        }
+        Ok(())
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(assignment.num_public().to_le_bytes());
+    hasher.update(assignment.num_private().to_le_bytes());
+    hasher.update(assignment.num_constraints().to_le_bytes());
+    for (a, b, c) in assignment.constraints().iter() {
+        absorb_lc::<N>(&mut hasher, a)?;
+        absorb_lc::<N>(&mut hasher, b)?;
+        absorb_lc::<N>(&mut hasher, c)?;
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// A persistent, on-disk cache of the synthesized proving and verifying keys of functions.
+///
+/// Each entry is keyed by `(network ID, program checksum, edition, function name)`, so an entry is never
+/// reused for a different network, a modified program, or another edition of the program.
+/// Each entry also records the hash of the circuit it was synthesized for, and is only loaded for that circuit.
+/// An entry that is corrupt, for another circuit, or whose keys are inconsistent with each other,
+/// is removed when it is loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyCache {
+    /// The directory of the cache entries.
+    directory: PathBuf,
+}
+
+impl KeyCache {
+    /// Opens the key cache in the given directory, creating the directory if it does not exist.
+    pub fn open<P: AsRef<Path>>(directory: P) -> Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)
+            .map_err(|error| anyhow!("Failed to create the key cache directory '{}': {error}", directory.display()))?;
+        Ok(Self { directory })
+    }
+
+    /// Returns the directory of the cache entries.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns `true` if the cache contains an entry for the given program checksum, edition, and function name.
+    pub fn contains<N: Network>(&self, checksum: &Field<N>, edition: u16, function_name: &Identifier<N>) -> bool {
+        self.to_path(checksum, edition, function_name).is_file()
+    }
+
+    /// Returns the proving and verifying key for the given program checksum, edition, function name,
+    /// and circuit hash, or `None` if there is no entry. If the entry is corrupt, inconsistent,
+    /// or for another circuit, it is removed and `None` is returned.
+    pub fn load<N: Network>(
+        &self,
+        checksum: &Field<N>,
+        edition: u16,
+        function_name: &Identifier<N>,
+        circuit_hash: &[u8; 32],
+    ) -> Result<Option<(ProvingKey<N>, VerifyingKey<N>)>> {
+        let path = self.to_path(checksum, edition, function_name);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        match Self::read_entry(&path, checksum, edition, function_name, circuit_hash) {
+            Ok(keys) => Ok(Some(keys)),
+            Err(_) => {
+                self.remove(checksum, edition, function_name)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Stores the given proving and verifying key for the given program checksum, edition, function name,
+    /// and circuit hash. If an entry already exists, it is replaced.
+    pub fn store<N: Network>(
+        &self,
+        checksum: &Field<N>,
+        edition: u16,
+        function_name: &Identifier<N>,
+        circuit_hash: &[u8; 32],
+        proving_key: &ProvingKey<N>,
+        verifying_key: &VerifyingKey<N>,
+    ) -> Result<()> {
+        // Ensure the proving key corresponds to the verifying key.
+        ensure!(
+            proving_key.circuit_verifying_key == **verifying_key,
+            "The proving key for '{function_name}' does not match its verifying key"
+        );
+
+        // Write the entry to a temporary file, and move it into place once it is complete,
+        // so that a partially-written entry is never loaded.
+        let path = self.to_path(checksum, edition, function_name);
+        let temporary_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&temporary_path)?);
+        KEY_CACHE_VERSION.write_le(&mut writer)?;
+        N::ID.write_le(&mut writer)?;
+        checksum.write_le(&mut writer)?;
+        edition.write_le(&mut writer)?;
+        function_name.write_le(&mut writer)?;
+        circuit_hash.write_le(&mut writer)?;
+        proving_key.write_le(&mut writer)?;
+        verifying_key.write_le(&mut writer)?;
+        writer.into_inner().map_err(|error| error.into_error())?.sync_all()?;
+        fs::rename(&temporary_path, &path)?;
+        Ok(())
+    }
+
+    /// Removes the entry for the given program checksum, edition, and function name, if it exists.
+    pub fn remove<N: Network>(&self, checksum: &Field<N>, edition: u16, function_name: &Identifier<N>) -> Result<()> {
+        match fs::remove_file(self.to_path(checksum, edition, function_name)) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Returns the path of the entry for the given program checksum, edition, and function name.
+    fn to_path<N: Network>(&self, checksum: &Field<N>, edition: u16, function_name: &Identifier<N>) -> PathBuf {
+        self.directory.join(format!("{}-{checksum}-{edition}-{function_name}.key", N::ID))
+    }
+
+    /// Reads the entry at the given path, and ensures it is for the given program checksum, edition,
+    /// function name, and circuit hash, and that its proving key corresponds to its verifying key.
+    fn read_entry<N: Network>(
+        path: &Path,
+        checksum: &Field<N>,
+        edition: u16,
+        function_name: &Identifier<N>,
+        circuit_hash: &[u8; 32],
+    ) -> Result<(ProvingKey<N>, VerifyingKey<N>)> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        // Ensure the header matches the entry.
+        ensure!(u8::read_le(&mut reader)? == KEY_CACHE_VERSION, "Invalid key cache version");
+        ensure!(u16::read_le(&mut reader)? == N::ID, "Invalid network ID in the key cache entry");
+        ensure!(Field::<N>::read_le(&mut reader)? == *checksum, "Invalid program checksum in the key cache entry");
+        ensure!(u16::read_le(&mut reader)? == edition, "Invalid edition in the key cache entry");
+        ensure!(Identifier::<N>::read_le(&mut reader)? == *function_name, "Invalid function in the key cache entry");
+        ensure!(<[u8; 32]>::read_le(&mut reader)? == *circuit_hash, "Invalid circuit in the key cache entry");
+
+        // Read the keys.
+        let proving_key = ProvingKey::<N>::read_le(&mut reader)?;
+        let verifying_key = VerifyingKey::<N>::read_le(&mut reader)?;
+        // Ensure the proving key corresponds to the verifying key.
+        ensure!(
+            proving_key.circuit_verifying_key == *verifying_key,
+            "The proving key for '{function_name}' does not match its verifying key"
+        );
+        Ok((proving_key, verifying_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Process;
+    use circuit::network::AleoV0;
+    use console::network::MainnetV0;
+    use synthesizer_program::Program;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_key_cache() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the program.
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program cache.aleo;
+
+function add:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;",
+        )
+        .unwrap();
+        let program_id = *program.id();
+        let function_name = Identifier::from_str("add").unwrap();
+        let checksum = program.to_checksum().unwrap();
+
+        // Open the key cache.
+        let directory = tempfile::tempdir().unwrap();
+        let key_cache = KeyCache::open(directory.path()).unwrap();
+        assert!(!key_cache.contains(&checksum, 1, &function_name));
+
+        // Synthesize the keys, with the key cache attached.
+        let mut process = Process::<CurrentNetwork>::load().unwrap();
+        process.attach_key_cache(key_cache.clone());
+        process.add_program(&program).unwrap();
+        process.synthesize_key::<AleoV0, _>(&program_id, &function_name, rng).unwrap();
+        let verifying_key = process.get_verifying_key(program_id, function_name).unwrap();
+
+        // Ensure the keys were stored in the key cache.
+        let edition = process.get_stack(program_id).unwrap().edition();
+        assert!(key_cache.contains(&checksum, edition, &function_name));
+
+        // Ensure a new process loads the keys from the key cache.
+        let mut process = Process::<CurrentNetwork>::load().unwrap();
+        process.attach_key_cache(key_cache.clone());
+        process.add_program(&program).unwrap();
+        process.synthesize_key::<AleoV0, _>(&program_id, &function_name, rng).unwrap();
+        assert_eq!(process.get_verifying_key(program_id, function_name).unwrap(), verifying_key);
+
+        // Ensure an entry is only loaded for the circuit it was stored for.
+        let credits_proving_key = process.get_proving_key("credits.aleo", "transfer_public").unwrap();
+        let credits_verifying_key = process.get_verifying_key("credits.aleo", "transfer_public").unwrap();
+        let (circuit_hash, other_circuit_hash) = ([0u8; 32], [1u8; 32]);
+        key_cache
+            .store(&checksum, edition, &function_name, &circuit_hash, &credits_proving_key, &credits_verifying_key)
+            .unwrap();
+        let (_, cached_verifying_key) =
+            key_cache.load(&checksum, edition, &function_name, &circuit_hash).unwrap().unwrap();
+        assert_eq!(cached_verifying_key, credits_verifying_key);
+        // Ensure the entry is not used for another edition.
+        assert!(key_cache.load(&checksum, edition + 1, &function_name, &circuit_hash).unwrap().is_none());
+        // Ensure the entry is not used, and is removed, for another circuit.
+        assert!(key_cache.load(&checksum, edition, &function_name, &other_circuit_hash).unwrap().is_none());
+        assert!(!key_cache.contains(&checksum, edition, &function_name));
+
+        // Ensure the synthesis does not use an entry that was stored for another circuit.
+        key_cache
+            .store(&checksum, edition, &function_name, &circuit_hash, &credits_proving_key, &credits_verifying_key)
+            .unwrap();
+        let mut process = Process::<CurrentNetwork>::load().unwrap();
+        process.attach_key_cache(key_cache.clone());
+        process.add_program(&program).unwrap();
+        process.synthesize_key::<AleoV0, _>(&program_id, &function_name, rng).unwrap();
+        assert_eq!(process.get_verifying_key(program_id, function_name).unwrap(), verifying_key);
+
+        // Ensure the cached keys are not used if they do not match the verifying key of the deployment.
+        let proving_key = process.get_proving_key(program_id, function_name).unwrap();
+        key_cache.store(&checksum, edition, &function_name, &circuit_hash, &proving_key, &verifying_key).unwrap();
+        let mut process = Process::<CurrentNetwork>::load().unwrap();
+        process.attach_key_cache(key_cache.clone());
+        process.add_program(&program).unwrap();
+        process.insert_verifying_key(&program_id, &function_name, credits_verifying_key).unwrap();
+        assert!(!process.get_stack(program_id).unwrap().load_cached_key(&function_name, &circuit_hash).unwrap());
+        assert!(!key_cache.contains(&checksum, edition, &function_name));
+
+        // Ensure a corrupt entry is removed.
+        let path = key_cache.to_path(&checksum, edition, &function_name);
+        fs::write(&path, [KEY_CACHE_VERSION, 0, 1, 2, 3]).unwrap();
+        assert!(key_cache.load(&checksum, edition, &function_name, &circuit_hash).unwrap().is_none());
+        assert!(!path.exists());
+    }
+}
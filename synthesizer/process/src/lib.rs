@@ -21,6 +21,12 @@
 mod cost;
 pub use cost::*;
 
+mod debugger;
+pub use debugger::*;
+
+mod key_cache;
+pub use key_cache::*;
+
 mod stack;
 pub use stack::*;
 
@@ -45,7 +51,18 @@ mod tests;
 use console::{
     account::PrivateKey,
     network::prelude::*,
-    program::{compute_function_id, Identifier, Literal, Locator, Plaintext, ProgramID, Record, Response, Value},
+    program::{
+        compute_function_id,
+        Identifier,
+        Literal,
+        Locator,
+        Plaintext,
+        ProgramID,
+        Record,
+        Response,
+        UpgradePolicy,
+        Value,
+    },
     types::{Field, U16, U64},
 };
 use ledger_block::{Deployment, Execution, Fee, Input, Transition};
@@ -63,10 +80,10 @@ use synthesizer_program::{
     RegistersStore,
     StackProgram,
 };
-use synthesizer_snark::{ProvingKey, UniversalSRS, VerifyingKey};
+use synthesizer_snark::{ProofAccumulator, ProvingKey, UniversalSRS, VerifyingKey};
 
 use aleo_std::prelude::{finish, lap, timer};
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use parking_lot::RwLock;
 use std::{collections::HashMap, sync::Arc};
 
@@ -79,6 +96,10 @@ pub struct Process<N: Network> {
     universal_srs: Arc<UniversalSRS<N>>,
     /// The mapping of program IDs to stacks.
     stacks: IndexMap<ProgramID<N>, Arc<Stack<N>>>,
+    /// The attached debugger, which is shared with the stacks.
+    debugger: DebuggerSlot<N>,
+    /// The attached key cache, which is shared with the stacks.
+    key_cache: KeyCacheSlot,
 }
 
 impl<N: Network> Process<N> {
@@ -88,7 +109,12 @@ impl<N: Network> Process<N> {
         let timer = timer!("Process:setup");
 
         // Initialize the process.
-        let mut process = Self { universal_srs: Arc::new(UniversalSRS::load()?), stacks: IndexMap::new() };
+        let mut process = Self {
+            universal_srs: Arc::new(UniversalSRS::load()?),
+            stacks: IndexMap::new(),
+            debugger: Default::default(),
+            key_cache: Default::default(),
+        };
         lap!(timer, "Initialize process");
 
         // Initialize the 'credits.aleo' program.
@@ -134,6 +160,51 @@ impl<N: Network> Process<N> {
         // Add the stack to the process.
         self.stacks.insert(*stack.program_id(), Arc::new(stack));
     }
+
+    /// Replaces the stack of an existing program with the stack of its new edition,
+    /// and relinks every program that (transitively) imports the upgraded program.
+    /// If you intend to `execute` the program, use `deploy` and `finalize_deployment` instead.
+    #[inline]
+    pub fn upgrade_stack(&mut self, stack: Stack<N>) -> Result<()> {
+        // Retrieve the program ID.
+        let program_id = *stack.program_id();
+        // Ensure the program already exists in the process.
+        ensure!(self.contains_program(&program_id), "Cannot upgrade program '{program_id}' as it does not exist");
+        // Replace the stack in the process.
+        self.stacks.insert(program_id, Arc::new(stack));
+
+        // Determine the programs that (transitively) import the upgraded program.
+        let mut dependents = IndexSet::new();
+        loop {
+            let num_dependents = dependents.len();
+            for (id, stack) in &self.stacks {
+                let imports = stack.program().imports().keys();
+                if imports.into_iter().any(|import| import == &program_id || dependents.contains(import)) {
+                    dependents.insert(*id);
+                }
+            }
+            if dependents.len() == num_dependents {
+                break;
+            }
+        }
+
+        // Relink the dependents, ensuring each program is relinked after the dependents it imports.
+        while !dependents.is_empty() {
+            // Find a dependent that does not import any dependent that is yet to be relinked.
+            let Some(index) = dependents.iter().position(|id| match self.stacks.get(id) {
+                Some(stack) => !stack.program().imports().keys().any(|import| dependents.contains(import)),
+                None => true,
+            }) else {
+                bail!("Failed to relink the dependents of program '{program_id}'")
+            };
+            // Relink the dependent.
+            if let Some(id) = dependents.shift_remove_index(index) {
+                let stack = self.get_stack(id)?.relink(self)?;
+                self.stacks.insert(id, Arc::new(stack));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<N: Network> Process<N> {
@@ -143,7 +214,12 @@ impl<N: Network> Process<N> {
         let timer = timer!("Process::load");
 
         // Initialize the process.
-        let mut process = Self { universal_srs: Arc::new(UniversalSRS::load()?), stacks: IndexMap::new() };
+        let mut process = Self {
+            universal_srs: Arc::new(UniversalSRS::load()?),
+            stacks: IndexMap::new(),
+            debugger: Default::default(),
+            key_cache: Default::default(),
+        };
         lap!(timer, "Initialize process");
 
         // Initialize the 'credits.aleo' program.
@@ -181,7 +257,12 @@ impl<N: Network> Process<N> {
     #[cfg(feature = "wasm")]
     pub fn load_web() -> Result<Self> {
         // Initialize the process.
-        let mut process = Self { universal_srs: Arc::new(UniversalSRS::load()?), stacks: IndexMap::new() };
+        let mut process = Self {
+            universal_srs: Arc::new(UniversalSRS::load()?),
+            stacks: IndexMap::new(),
+            debugger: Default::default(),
+            key_cache: Default::default(),
+        };
 
         // Initialize the 'credits.aleo' program.
         let program = Program::credits()?;
@@ -202,6 +283,32 @@ impl<N: Network> Process<N> {
         &self.universal_srs
     }
 
+    /// Attaches the given debugger, which is notified as the process evaluates instructions and finalizes commands.
+    /// If a debugger is already attached, it is replaced.
+    #[inline]
+    pub fn attach_debugger(&self, debugger: Arc<dyn Debugger<N>>) {
+        *self.debugger.write() = Some(debugger);
+    }
+
+    /// Detaches the debugger, returning it if one was attached.
+    #[inline]
+    pub fn detach_debugger(&self) -> Option<Arc<dyn Debugger<N>>> {
+        self.debugger.write().take()
+    }
+
+    /// Attaches the given key cache, which is consulted before synthesizing circuit keys, and written to after.
+    /// If a key cache is already attached, it is replaced.
+    #[inline]
+    pub fn attach_key_cache(&self, key_cache: KeyCache) {
+        *self.key_cache.write() = Some(key_cache);
+    }
+
+    /// Detaches the key cache, returning it if one was attached.
+    #[inline]
+    pub fn detach_key_cache(&self) -> Option<KeyCache> {
+        self.key_cache.write().take()
+    }
+
     /// Returns `true` if the process contains the program with the given ID.
     #[inline]
     pub fn contains_program(&self, program_id: &ProgramID<N>) -> bool {
@@ -276,6 +383,7 @@ impl<N: Network> Process<N> {
     }
 
     /// Synthesizes the proving and verifying key for the given program ID and function name.
+    /// If a key cache is attached, the keys are loaded from the cache when possible, and stored in it otherwise.
     #[inline]
     pub fn synthesize_key<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
         &self,
@@ -286,6 +394,18 @@ impl<N: Network> Process<N> {
         // Synthesize the proving and verifying key.
         self.get_stack(program_id)?.synthesize_key::<A, R>(function_name, rng)
     }
+
+    /// Synthesizes the circuit for the given program ID and function name on sampled inputs, and returns its assignment.
+    #[inline]
+    pub fn synthesize_assignment<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        rng: &mut R,
+    ) -> Result<circuit::Assignment<N::Field>> {
+        // Synthesize the assignment.
+        self.get_stack(program_id)?.synthesize_assignment::<A, R>(function_name, rng)
+    }
 }
 
 #[cfg(any(test, feature = "test"))]
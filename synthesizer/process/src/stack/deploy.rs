@@ -50,7 +50,86 @@ impl<N: Network> Stack<N> {
         finish!(timer);
 
         // Return the deployment.
-        Deployment::new(N::EDITION, self.program.clone(), verifying_keys)
+        Deployment::new_with_upgrade_policy(self.edition, self.program.clone(), verifying_keys, self.upgrade_policy)
+    }
+
+    /// Ensures the given deployment is a valid upgrade of the program in this stack.
+    ///
+    /// An upgrade must be the next edition of the program, must be permitted by the current upgrade policy,
+    /// and must preserve the existing mappings, structs, records, and function interfaces of the program.
+    ///
+    /// Note: The program owner is not part of the deployment, so the `Admin` upgrade policy
+    /// is checked against the owner of the deployment transaction in `VM::check_transaction`.
+    #[inline]
+    pub fn check_upgrade(&self, deployment: &Deployment<N>) -> Result<()> {
+        // Retrieve the program ID.
+        let program_id = self.program.id();
+        // Retrieve the new program.
+        let program = deployment.program();
+
+        // Ensure the program IDs match.
+        ensure!(program.id() == program_id, "Expected an upgrade for '{program_id}', found '{}'", program.id());
+        // Ensure the deployment is for the next edition.
+        let next_edition =
+            self.edition.checked_add(1).ok_or_else(|| anyhow!("Program '{program_id}' exceeds the maximum edition"))?;
+        ensure!(
+            deployment.edition() == next_edition,
+            "Expected edition {next_edition} for the upgrade of '{program_id}', found edition {}",
+            deployment.edition()
+        );
+
+        // Ensure the upgrade policy permits the upgrade.
+        match &self.upgrade_policy {
+            UpgradePolicy::Immutable => bail!("Program '{program_id}' is immutable and cannot be upgraded"),
+            // Note: The admin is checked against the program owner in `VM::check_transaction`.
+            UpgradePolicy::Admin(..) => (),
+            UpgradePolicy::Checksum(checksum) => {
+                let candidate = program.to_checksum()?;
+                ensure!(
+                    &candidate == checksum,
+                    "Upgrade of '{program_id}' has checksum '{candidate}', but the upgrade policy requires '{checksum}'"
+                )
+            }
+        }
+
+        // Ensure the existing mappings are unchanged, so that the mapping data is preserved.
+        for (name, mapping) in self.program.mappings() {
+            match program.mappings().get(name) {
+                Some(candidate) if candidate == mapping => (),
+                _ => bail!("Upgrade of '{program_id}' must preserve the layout of mapping '{name}'"),
+            }
+        }
+        // Ensure the existing structs are unchanged.
+        for (name, struct_) in self.program.structs() {
+            match program.structs().get(name) {
+                Some(candidate) if candidate == struct_ => (),
+                _ => bail!("Upgrade of '{program_id}' must preserve the layout of struct '{name}'"),
+            }
+        }
+        // Ensure the existing records are unchanged.
+        for (name, record) in self.program.records() {
+            match program.records().get(name) {
+                Some(candidate) if candidate == record => (),
+                _ => bail!("Upgrade of '{program_id}' must preserve the layout of record '{name}'"),
+            }
+        }
+        // Ensure the existing functions retain their interface, so that dependent programs remain valid.
+        for (name, function) in self.program.functions() {
+            let Some(candidate) = program.functions().get(name) else {
+                bail!("Upgrade of '{program_id}' must preserve function '{name}'")
+            };
+            ensure!(
+                candidate.input_types() == function.input_types()
+                    && candidate.output_types() == function.output_types(),
+                "Upgrade of '{program_id}' must preserve the inputs and outputs of function '{name}'"
+            );
+            ensure!(
+                candidate.finalize_logic().map(|finalize| finalize.input_types())
+                    == function.finalize_logic().map(|finalize| finalize.input_types()),
+                "Upgrade of '{program_id}' must preserve the finalize inputs of function '{name}'"
+            );
+        }
+        Ok(())
     }
 
     /// Checks each function in the program on the given verifying key and certificate.
@@ -53,12 +53,20 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
         })?;
         lap!(timer, "Store the inputs");
 
+        // Retrieve the debugger, if one is attached.
+        let debugger = self.debugger();
+        let location = |index| DebugLocation::new(*self.program_id(), *closure.name(), DebugScope::Closure, index);
+
         // Evaluate the instructions.
-        for instruction in closure.instructions() {
+        for (index, instruction) in closure.instructions().iter().enumerate() {
+            // Notify the debugger before evaluating the instruction.
+            debug_before_instruction(debugger.as_deref(), || location(index), instruction, registers.view())?;
             // If the evaluation fails, bail and return the error.
             if let Err(error) = instruction.evaluate(self, &mut registers) {
                 bail!("Failed to evaluate instruction ({instruction}): {error}");
             }
+            // Notify the debugger after evaluating the instruction.
+            debug_after_instruction(debugger.as_deref(), || location(index), instruction, registers.view())?;
         }
         lap!(timer, "Evaluate the instructions");
 
@@ -175,9 +183,15 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
         })?;
         lap!(timer, "Store the inputs");
 
+        // Retrieve the debugger, if one is attached.
+        let debugger = self.debugger();
+        let location = |index| DebugLocation::new(*self.program_id(), *function.name(), DebugScope::Function, index);
+
         // Evaluate the instructions.
         // Note: We handle the `call` instruction separately, as it requires special handling.
-        for instruction in function.instructions() {
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            // Notify the debugger before evaluating the instruction.
+            debug_before_instruction(debugger.as_deref(), || location(index), instruction, registers.view())?;
             // Evaluate the instruction.
             let result = match instruction {
                 // If the instruction is a `call` instruction, we need to handle it separately.
@@ -189,6 +203,8 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
             if let Err(error) = result {
                 bail!("Failed to evaluate instruction ({instruction}): {error}");
             }
+            // Notify the debugger after evaluating the instruction.
+            debug_after_instruction(debugger.as_deref(), || location(index), instruction, registers.view())?;
         }
         lap!(timer, "Evaluate the instructions");
 
@@ -281,8 +281,19 @@ impl<N: Network> StackExecute<N> for Stack<N> {
         // Initialize a tracker to determine if there are any function calls.
         let mut contains_function_call = false;
 
+        // Retrieve the number of calls with recorded metrics, as the calls nested in this function are recorded after.
+        let num_recorded_calls = match registers.call_stack() {
+            CallStack::CheckDeployment(_, _, assignments, _, _) | CallStack::PackageRun(_, _, assignments) => {
+                assignments.read().len()
+            }
+            CallStack::Execute(_, trace) => trace.read().call_metrics().len(),
+            _ => 0,
+        };
+        // Initialize the circuit size attributed to each instruction.
+        let mut instruction_metrics = Vec::with_capacity(function.instructions().len());
+
         // Execute the instructions.
-        for instruction in function.instructions() {
+        for (index, instruction) in function.instructions().iter().enumerate() {
             // If the circuit is in execute mode, then evaluate the instructions.
             if let CallStack::Execute(..) = registers.call_stack() {
                 // Evaluate the instruction.
@@ -298,6 +309,9 @@ impl<N: Network> StackExecute<N> for Stack<N> {
                 }
             }
 
+            // Retrieve the circuit size prior to executing the instruction.
+            let (num_constraints, num_variables, num_nonzeros) = Self::circuit_size::<A>();
+
             // Execute the instruction.
             let result = match instruction {
                 // If the instruction is a `call` instruction, we need to handle it separately.
@@ -310,6 +324,16 @@ impl<N: Network> StackExecute<N> for Stack<N> {
                 bail!("Failed to execute instruction ({instruction}): {error}");
             }
 
+            // Attribute the growth in the circuit size to the instruction.
+            let (next_num_constraints, next_num_variables, next_num_nonzeros) = Self::circuit_size::<A>();
+            instruction_metrics.push(InstructionMetrics {
+                index,
+                opcode: instruction.opcode(),
+                num_constraints: next_num_constraints.saturating_sub(num_constraints),
+                num_variables: next_num_variables.saturating_sub(num_variables),
+                num_nonzeros: next_num_nonzeros.saturating_sub(num_nonzeros),
+            });
+
             // If the instruction was a function call, then set the tracker to `true`.
             if let Instruction::Call(call) = instruction {
                 // Check if the call is a function call.
@@ -457,9 +481,12 @@ impl<N: Network> StackExecute<N> for Stack<N> {
                 num_request_constraints,
                 num_function_constraints,
                 num_response_constraints,
+                call_depth: 0,
             };
-            // Add the assignment to the assignments.
-            assignments.write().push((assignment, metrics));
+            // Add the assignment to the assignments, and increment the call depth of the nested calls.
+            let mut assignments = assignments.write();
+            assignments[num_recorded_calls..].iter_mut().for_each(|(_, metrics)| metrics.call_depth += 1);
+            assignments.push((assignment, metrics));
             lap!(timer, "Save the circuit assignment");
         }
         // If the circuit is in `Execute` mode, then execute the circuit into a transition.
@@ -479,15 +506,14 @@ impl<N: Network> StackExecute<N> for Stack<N> {
                 num_request_constraints,
                 num_function_constraints,
                 num_response_constraints,
+                call_depth: 0,
             };
 
-            // Add the transition to the trace.
-            trace.write().insert_transition(
-                console_request.input_ids(),
-                &transition,
-                (proving_key, assignment),
-                metrics,
-            )?;
+            // Add the transition to the trace, and increment the call depth of the nested calls.
+            let mut trace = trace.write();
+            trace.increment_call_depths(num_recorded_calls);
+            trace.insert_transition(console_request.input_ids(), &transition, (proving_key, assignment), metrics)?;
+            trace.insert_instruction_metrics(instruction_metrics);
         }
         // If the circuit is in `PackageRun` mode, then save the assignment.
         else if let CallStack::PackageRun(_, _, ref assignments) = registers.call_stack() {
@@ -499,9 +525,12 @@ impl<N: Network> StackExecute<N> for Stack<N> {
                 num_request_constraints,
                 num_function_constraints,
                 num_response_constraints,
+                call_depth: 0,
             };
-            // Add the assignment to the assignments.
-            assignments.write().push((assignment, metrics));
+            // Add the assignment to the assignments, and increment the call depth of the nested calls.
+            let mut assignments = assignments.write();
+            assignments[num_recorded_calls..].iter_mut().for_each(|(_, metrics)| metrics.call_depth += 1);
+            assignments.push((assignment, metrics));
             lap!(timer, "Save the circuit assignment");
         }
 
@@ -513,6 +542,12 @@ impl<N: Network> StackExecute<N> for Stack<N> {
 }
 
 impl<N: Network> Stack<N> {
+    /// Returns the current size of the circuit, as (number of constraints, number of variables, number of non-zeros).
+    fn circuit_size<A: circuit::Aleo<Network = N>>() -> (u64, u64, u64) {
+        let (num_nonzeros_a, num_nonzeros_b, num_nonzeros_c) = A::num_nonzeros();
+        (A::num_constraints(), A::num_variables(), num_nonzeros_a + num_nonzeros_b + num_nonzeros_c)
+    }
+
     /// Prints the current state of the circuit.
     #[cfg(debug_assertions)]
     pub(crate) fn log_circuit<A: circuit::Aleo<Network = N>, S: Into<String>>(scope: S) {
@@ -15,7 +15,7 @@
 mod load;
 mod store;
 
-use crate::FinalizeTypes;
+use crate::{FinalizeTypes, RegistersView};
 use console::{
     network::prelude::*,
     program::{Identifier, Literal, Plaintext, Register, Value},
@@ -60,6 +60,12 @@ impl<N: Network> FinalizeRegisters<N> {
     ) -> Self {
         Self { state, transition_id, finalize_types, function_name, registers: IndexMap::new(), last_register: None }
     }
+
+    /// Returns a view of the assigned registers.
+    #[inline]
+    pub fn view(&self) -> RegistersView<N> {
+        RegistersView::new(&self.registers)
+    }
 }
 
 impl<N: Network> FinalizeRegistersState<N> for FinalizeRegisters<N> {
@@ -178,6 +178,9 @@ impl<N: Network> FinalizeTypes<N> {
             Command::BranchNeq(branch_neq) => self.check_branch(stack, finalize, branch_neq)?,
             // Note that the `Position`s are checked for uniqueness when constructing `Finalize`.
             Command::Position(_) => (),
+            Command::MappingLen(mapping_len) => self.check_mapping_len(stack, mapping_len)?,
+            Command::MappingKeyAt(mapping_key_at) => self.check_mapping_key_at(stack, mapping_key_at)?,
+            Command::MappingEntryAt(mapping_entry_at) => self.check_mapping_entry_at(stack, mapping_entry_at)?,
         }
         Ok(())
     }
@@ -450,6 +453,128 @@ impl<N: Network> FinalizeTypes<N> {
         Ok(())
     }
 
+    /// Ensures the given `mapping.len` command is well-formed.
+    #[inline]
+    fn check_mapping_len(
+        &mut self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        mapping_len: &MappingLen<N>,
+    ) -> Result<()> {
+        // Ensure the mapping is defined.
+        Self::get_mapping_from_operator(stack, mapping_len.mapping())?;
+        // Get the destination register.
+        let destination = mapping_len.destination().clone();
+        // Ensure the destination register is a locator (and does not reference an access).
+        ensure!(matches!(destination, Register::Locator(..)), "Destination '{destination}' must be a locator.");
+        // Insert the destination register.
+        self.add_destination(destination, FinalizeType::Plaintext(PlaintextType::Literal(LiteralType::U32)))?;
+        Ok(())
+    }
+
+    /// Ensures the given `mapping.key_at` command is well-formed.
+    #[inline]
+    fn check_mapping_key_at(
+        &mut self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        mapping_key_at: &MappingKeyAt<N>,
+    ) -> Result<()> {
+        // Retrieve the mapping.
+        let mapping = Self::get_mapping_from_operator(stack, mapping_key_at.mapping())?;
+        // Ensure the index is a `u32`.
+        self.check_mapping_index(stack, mapping_key_at.index(), MappingKeyAt::<N>::opcode())?;
+        // Get the destination register.
+        let destination = mapping_key_at.destination().clone();
+        // Ensure the destination register is a locator (and does not reference an access).
+        ensure!(matches!(destination, Register::Locator(..)), "Destination '{destination}' must be a locator.");
+        // Insert the destination register.
+        self.add_destination(destination, FinalizeType::Plaintext(mapping.key().plaintext_type().clone()))?;
+        Ok(())
+    }
+
+    /// Ensures the given `mapping.entry_at` command is well-formed.
+    #[inline]
+    fn check_mapping_entry_at(
+        &mut self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        mapping_entry_at: &MappingEntryAt<N>,
+    ) -> Result<()> {
+        // Retrieve the mapping.
+        let mapping = Self::get_mapping_from_operator(stack, mapping_entry_at.mapping())?;
+        // Ensure the index is a `u32`.
+        self.check_mapping_index(stack, mapping_entry_at.index(), MappingEntryAt::<N>::opcode())?;
+        // Get the destination registers.
+        let key_destination = mapping_entry_at.key_destination().clone();
+        let value_destination = mapping_entry_at.value_destination().clone();
+        // Ensure the destination registers are locators (and do not reference an access).
+        ensure!(matches!(key_destination, Register::Locator(..)), "Destination '{key_destination}' must be a locator.");
+        ensure!(
+            matches!(value_destination, Register::Locator(..)),
+            "Destination '{value_destination}' must be a locator."
+        );
+        // Insert the destination registers.
+        self.add_destination(key_destination, FinalizeType::Plaintext(mapping.key().plaintext_type().clone()))?;
+        self.add_destination(value_destination, FinalizeType::Plaintext(mapping.value().plaintext_type().clone()))?;
+        Ok(())
+    }
+
+    /// Ensures the given index operand of a mapping iteration command is a `u32`.
+    #[inline]
+    fn check_mapping_index(
+        &self,
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        index: &Operand<N>,
+        opcode: Opcode,
+    ) -> Result<()> {
+        match self.get_type_from_operand(stack, index)? {
+            FinalizeType::Plaintext(PlaintextType::Literal(LiteralType::U32)) => Ok(()),
+            FinalizeType::Plaintext(plaintext_type) => {
+                bail!("Index in `{opcode}` must be a 'u32', found '{plaintext_type}'")
+            }
+            FinalizeType::Future(..) => bail!("A future cannot be used as an index in a `{opcode}` command"),
+        }
+    }
+
+    /// Returns the mapping referenced by the given call operator.
+    /// If the mapping is external, ensures the external program is imported by the current program.
+    fn get_mapping_from_operator(
+        stack: &(impl StackMatches<N> + StackProgram<N>),
+        mapping: &CallOperator<N>,
+    ) -> Result<Mapping<N>> {
+        match mapping {
+            CallOperator::Locator(locator) => {
+                // Retrieve the program ID.
+                let program_id = locator.program_id();
+                // Retrieve the mapping_name.
+                let mapping_name = locator.resource();
+
+                // Ensure the locator does not reference the current program.
+                if stack.program_id() == program_id {
+                    bail!("Locator '{locator}' does not reference an external mapping.");
+                }
+                // Ensure the current program contains an import for this external program.
+                if !stack.program().imports().keys().contains(program_id) {
+                    bail!("External program '{program_id}' is not imported by '{}'.", stack.program_id());
+                }
+                // Retrieve the program.
+                let external = stack.get_external_program(program_id)?;
+                // Ensure the mapping exists in the program.
+                if !external.contains_mapping(mapping_name) {
+                    bail!("Mapping '{mapping_name}' in '{program_id}' is not defined.")
+                }
+                // Retrieve the mapping from the program.
+                external.get_mapping(mapping_name)
+            }
+            CallOperator::Resource(mapping_name) => {
+                // Ensure the mapping is defined in the current program.
+                if !stack.program().contains_mapping(mapping_name) {
+                    bail!("Mapping '{mapping_name}' in '{}' is not defined.", stack.program_id())
+                }
+                // Retrieve the mapping from the program.
+                stack.program().get_mapping(mapping_name)
+            }
+        }
+    }
+
     /// Ensure the given `rand.chacha` command is well-formed.
     #[inline]
     fn check_rand_chacha(
@@ -44,6 +44,10 @@ use synthesizer_program::{
     GetOrUse,
     Instruction,
     InstructionTrait,
+    Mapping,
+    MappingEntryAt,
+    MappingKeyAt,
+    MappingLen,
     Opcode,
     Operand,
     Program,
@@ -25,11 +25,15 @@ impl<N: Network> Stack<N> {
             register_types: Default::default(),
             finalize_types: Default::default(),
             universal_srs: process.universal_srs().clone(),
+            debugger: process.debugger.clone(),
+            key_cache: process.key_cache.clone(),
             proving_keys: Default::default(),
             verifying_keys: Default::default(),
             number_of_calls: Default::default(),
             finalize_costs: Default::default(),
             program_depth: 0,
+            edition: N::EDITION,
+            upgrade_policy: UpgradePolicy::Immutable,
         };
 
         // Add all the imports into the stack.
@@ -99,6 +103,25 @@ impl<N: Network> Stack<N> {
         // Return the stack.
         Ok(stack)
     }
+
+    /// Returns a new stack for the same program, linked against the current external stacks in the process.
+    /// This is used to refresh the dependents of a program that has been upgraded.
+    ///
+    /// Note: The circuit keys, edition, and upgrade policy are carried over, as an upgrade
+    /// preserves the interface of every function that can be called by a dependent program.
+    #[inline]
+    pub(crate) fn relink(&self, process: &Process<N>) -> Result<Self> {
+        // Initialize the stack against the current external stacks.
+        let mut stack = Stack::initialize(process, &self.program)?;
+        // Carry over the circuit keys.
+        stack.proving_keys = self.proving_keys.clone();
+        stack.verifying_keys = self.verifying_keys.clone();
+        // Carry over the edition and upgrade policy.
+        stack.edition = self.edition;
+        stack.upgrade_policy = self.upgrade_policy;
+        // Return the stack.
+        Ok(stack)
+    }
 }
 
 impl<N: Network> Stack<N> {
@@ -27,6 +27,128 @@ impl<N: Network> Stack<N> {
             return Ok(());
         }
 
+        // Sample the request, with a burner private key.
+        let (burner_private_key, request) = self.sample_burner_request(function_name, rng)?;
+        // Initialize the authorization.
+        let authorization = Authorization::new(request.clone());
+        // Initialize the call stack.
+        let call_stack = CallStack::Synthesize(vec![request], burner_private_key, authorization);
+        // Synthesize the circuit, without a caller or a `root_tvk`, as the circuit is for an individual function.
+        let _response = self.execute_function::<A, R>(call_stack, None, None, rng)?;
+
+        // Ensure the proving key exists.
+        ensure!(self.contains_proving_key(function_name), "Function '{function_name}' is missing a proving key.");
+        // Ensure the verifying key exists.
+        ensure!(self.contains_verifying_key(function_name), "Function '{function_name}' is missing a verifying key.");
+        Ok(())
+    }
+
+    /// Synthesizes the circuit for the given function name on sampled inputs, and returns its assignment.
+    ///
+    /// The assignment can be exported with `Assignment::write_r1cs` and `Assignment::write_wtns`.
+    /// Note: The assignment only contains the circuit of the given function, and not of the functions it calls.
+    /// As the inputs are sampled, the assignment is not guaranteed to be satisfied.
+    #[inline]
+    pub fn synthesize_assignment<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        function_name: &Identifier<N>,
+        rng: &mut R,
+    ) -> Result<circuit::Assignment<N::Field>> {
+        // Sample the request, with a burner private key.
+        let (burner_private_key, request) = self.sample_burner_request(function_name, rng)?;
+        // Initialize the assignments.
+        let assignments = Assignments::<N>::default();
+        // Initialize the call stack, without a constraint limit or variable limit.
+        let call_stack = CallStack::CheckDeployment(vec![request], burner_private_key, assignments.clone(), None, None);
+        // Synthesize the circuit, without a caller or a `root_tvk`, as the circuit is for an individual function.
+        let _response = self.execute_function::<A, R>(call_stack, None, None, rng)?;
+
+        // Retrieve the assignment of the function, which is the last one to be recorded.
+        let assignment = assignments.write().pop().map(|(assignment, _)| assignment);
+        assignment.ok_or_else(|| anyhow!("The assignment for function '{function_name}' is missing"))
+    }
+
+    /// Synthesizes and stores the `(proving_key, verifying_key)` for the given function name and assignment.
+    #[inline]
+    pub fn synthesize_from_assignment(
+        &self,
+        function_name: &Identifier<N>,
+        assignment: &circuit::Assignment<N::Field>,
+    ) -> Result<()> {
+        // If the proving and verifying key already exist, skip the synthesis for this function.
+        if self.contains_proving_key(function_name) && self.contains_verifying_key(function_name) {
+            return Ok(());
+        }
+        // Compute the circuit hash, if a key cache is attached.
+        let circuit_hash = match self.key_cache() {
+            Some(_) => Some(to_circuit_hash::<N>(assignment)?),
+            None => None,
+        };
+        // If the proving and verifying key are in the key cache, skip the synthesis for this function.
+        if let Some(circuit_hash) = &circuit_hash {
+            if self.load_cached_key(function_name, circuit_hash)? {
+                return Ok(());
+            }
+        }
+
+        // Synthesize the proving and verifying key.
+        let (proving_key, verifying_key) = self.universal_srs.to_circuit_key(&function_name.to_string(), assignment)?;
+        // Store the proving and verifying key in the key cache, if one is attached.
+        // Note: The key cache is an optimization, so a failure to store the keys does not fail the synthesis.
+        if let (Some(key_cache), Some(circuit_hash)) = (self.key_cache(), &circuit_hash) {
+            if let Err(_error) = self.program.to_checksum().and_then(|checksum| {
+                key_cache.store(&checksum, self.edition, function_name, circuit_hash, &proving_key, &verifying_key)
+            }) {
+                #[cfg(debug_assertions)]
+                eprintln!(
+                    "Failed to store the keys of '{}/{function_name}' in the key cache: {_error}",
+                    self.program_id()
+                );
+            }
+        }
+        // Insert the proving key.
+        self.insert_proving_key(function_name, proving_key)?;
+        // Insert the verifying key.
+        self.insert_verifying_key(function_name, verifying_key)
+    }
+
+    /// Loads the proving and verifying key for the given function name and circuit hash from the key cache,
+    /// if one is attached. Returns `true` if the keys were loaded.
+    ///
+    /// If the stack already has a verifying key for the function (i.e. from the deployment of the program),
+    /// the cached keys are only used if their verifying key matches it. Otherwise, the entry is removed.
+    pub(crate) fn load_cached_key(&self, function_name: &Identifier<N>, circuit_hash: &[u8; 32]) -> Result<bool> {
+        // Retrieve the key cache.
+        let Some(key_cache) = self.key_cache() else {
+            return Ok(false);
+        };
+        // Load the proving and verifying key.
+        let checksum = self.program.to_checksum()?;
+        let Some((proving_key, verifying_key)) =
+            key_cache.load(&checksum, self.edition, function_name, circuit_hash)?
+        else {
+            return Ok(false);
+        };
+        // Ensure the verifying key matches the existing verifying key, if any.
+        if let Ok(existing_verifying_key) = self.get_verifying_key(function_name) {
+            if existing_verifying_key != verifying_key {
+                key_cache.remove(&checksum, self.edition, function_name)?;
+                return Ok(false);
+            }
+        }
+        // Insert the proving key.
+        self.insert_proving_key(function_name, proving_key)?;
+        // Insert the verifying key.
+        self.insert_verifying_key(function_name, verifying_key)?;
+        Ok(true)
+    }
+
+    /// Returns a burner private key, and a request that it signed for the given function name on sampled inputs.
+    fn sample_burner_request<R: Rng + CryptoRng>(
+        &self,
+        function_name: &Identifier<N>,
+        rng: &mut R,
+    ) -> Result<(PrivateKey<N>, Request<N>)> {
         // Retrieve the program ID.
         let program_id = self.program_id();
         // Retrieve the function input types.
@@ -55,9 +177,6 @@ impl<N: Network> Stack<N> {
         // The `root_tvk` is `None` when deploying an individual circuit.
         let root_tvk = None;
 
-        // The caller is `None` when deploying an individual circuit.
-        let caller = None;
-
         // Compute the request, with a burner private key.
         let request = Request::sign(
             &burner_private_key,
@@ -69,37 +188,6 @@ impl<N: Network> Stack<N> {
             is_root,
             rng,
         )?;
-        // Initialize the authorization.
-        let authorization = Authorization::new(request.clone());
-        // Initialize the call stack.
-        let call_stack = CallStack::Synthesize(vec![request], burner_private_key, authorization);
-        // Synthesize the circuit.
-        let _response = self.execute_function::<A, R>(call_stack, caller, root_tvk, rng)?;
-
-        // Ensure the proving key exists.
-        ensure!(self.contains_proving_key(function_name), "Function '{function_name}' is missing a proving key.");
-        // Ensure the verifying key exists.
-        ensure!(self.contains_verifying_key(function_name), "Function '{function_name}' is missing a verifying key.");
-        Ok(())
-    }
-
-    /// Synthesizes and stores the `(proving_key, verifying_key)` for the given function name and assignment.
-    #[inline]
-    pub fn synthesize_from_assignment(
-        &self,
-        function_name: &Identifier<N>,
-        assignment: &circuit::Assignment<N::Field>,
-    ) -> Result<()> {
-        // If the proving and verifying key already exist, skip the synthesis for this function.
-        if self.contains_proving_key(function_name) && self.contains_verifying_key(function_name) {
-            return Ok(());
-        }
-
-        // Synthesize the proving and verifying key.
-        let (proving_key, verifying_key) = self.universal_srs.to_circuit_key(&function_name.to_string(), assignment)?;
-        // Insert the proving key.
-        self.insert_proving_key(function_name, proving_key)?;
-        // Insert the verifying key.
-        self.insert_verifying_key(function_name, verifying_key)
+        Ok((burner_private_key, request))
     }
 }
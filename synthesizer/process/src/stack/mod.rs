@@ -36,7 +36,23 @@ mod evaluate;
 mod execute;
 mod helpers;
 
-use crate::{cost_in_microcredits, traits::*, CallMetrics, Process, Trace};
+use crate::{
+    cost_in_microcredits,
+    debug_after_instruction,
+    debug_before_instruction,
+    to_circuit_hash,
+    traits::*,
+    CallMetrics,
+    DebugLocation,
+    DebugScope,
+    Debugger,
+    DebuggerSlot,
+    InstructionMetrics,
+    KeyCache,
+    KeyCacheSlot,
+    Process,
+    Trace,
+};
 use console::{
     account::{Address, PrivateKey},
     network::prelude::*,
@@ -58,6 +74,7 @@ use console::{
         RegisterType,
         Request,
         Response,
+        UpgradePolicy,
         Value,
         ValueType,
     },
@@ -182,6 +199,10 @@ pub struct Stack<N: Network> {
     finalize_types: IndexMap<Identifier<N>, FinalizeTypes<N>>,
     /// The universal SRS.
     universal_srs: Arc<UniversalSRS<N>>,
+    /// The debugger attached to the process, which is shared with the process.
+    debugger: DebuggerSlot<N>,
+    /// The key cache attached to the process, which is shared with the process.
+    key_cache: KeyCacheSlot,
     /// The mapping of function name to proving key.
     proving_keys: Arc<RwLock<IndexMap<Identifier<N>, ProvingKey<N>>>>,
     /// The mapping of function name to verifying key.
@@ -192,6 +213,10 @@ pub struct Stack<N: Network> {
     finalize_costs: IndexMap<Identifier<N>, u64>,
     /// The program depth.
     program_depth: usize,
+    /// The edition of the program.
+    edition: u16,
+    /// The upgrade policy for the next edition of the program.
+    upgrade_policy: UpgradePolicy<N>,
 }
 
 impl<N: Network> Stack<N> {
@@ -202,6 +227,43 @@ impl<N: Network> Stack<N> {
         let program_id = program.id();
         // Ensure the program does not already exist in the process.
         ensure!(!process.contains_program(program_id), "Program '{program_id}' already exists");
+        // Return the stack.
+        Self::new_edition(process, program, N::EDITION, UpgradePolicy::Immutable)
+    }
+
+    /// Initializes a new stack, given the process and the deployment.
+    /// If the program already exists in the process, the deployment must be a valid upgrade of it.
+    #[inline]
+    pub fn from_deployment(process: &Process<N>, deployment: &Deployment<N>) -> Result<Self> {
+        // Retrieve the program ID.
+        let program_id = deployment.program_id();
+        match process.contains_program(program_id) {
+            // Ensure the deployment is a valid upgrade of the existing program.
+            true => process.get_stack(program_id)?.check_upgrade(deployment)?,
+            // Ensure the deployment is for the initial edition.
+            false => ensure!(
+                deployment.edition() == N::EDITION,
+                "Program '{program_id}' does not exist, and cannot be deployed with edition {}",
+                deployment.edition()
+            ),
+        }
+        // Return the stack.
+        Self::new_edition(process, deployment.program(), deployment.edition(), *deployment.upgrade_policy())
+    }
+
+    /// Initializes a new stack for the given edition and upgrade policy, given the process and the program.
+    ///
+    /// Note: This method does **not** check if the program already exists in the process,
+    /// nor if the edition is a valid upgrade; use `Stack::from_deployment` for this.
+    #[inline]
+    pub(crate) fn new_edition(
+        process: &Process<N>,
+        program: &Program<N>,
+        edition: u16,
+        upgrade_policy: UpgradePolicy<N>,
+    ) -> Result<Self> {
+        // Retrieve the program ID.
+        let program_id = program.id();
         // Ensure the program contains functions.
         ensure!(!program.functions().is_empty(), "No functions present in the deployment for program '{program_id}'");
 
@@ -215,8 +277,13 @@ impl<N: Network> Stack<N> {
         // Ensure the program deserializes from a string correctly.
         ensure!(program == &Program::from_str(&program_string)?, "Program string serialization failed");
 
+        // Compute the stack.
+        let mut stack = Stack::initialize(process, program)?;
+        // Set the edition and upgrade policy.
+        stack.edition = edition;
+        stack.upgrade_policy = upgrade_policy;
         // Return the stack.
-        Stack::initialize(process, program)
+        Ok(stack)
     }
 }
 
@@ -365,6 +432,30 @@ impl<N: Network> StackProgramTypes<N> for Stack<N> {
 }
 
 impl<N: Network> Stack<N> {
+    /// Returns the edition of the program.
+    #[inline]
+    pub const fn edition(&self) -> u16 {
+        self.edition
+    }
+
+    /// Returns the upgrade policy for the next edition of the program.
+    #[inline]
+    pub const fn upgrade_policy(&self) -> &UpgradePolicy<N> {
+        &self.upgrade_policy
+    }
+
+    /// Returns the debugger attached to the process, if any.
+    #[inline]
+    pub fn debugger(&self) -> Option<Arc<dyn Debugger<N>>> {
+        self.debugger.read().clone()
+    }
+
+    /// Returns the key cache attached to the process, if any.
+    #[inline]
+    pub fn key_cache(&self) -> Option<KeyCache> {
+        self.key_cache.read().clone()
+    }
+
     /// Returns `true` if the proving key for the given function name exists.
     #[inline]
     pub fn contains_proving_key(&self, function_name: &Identifier<N>) -> bool {
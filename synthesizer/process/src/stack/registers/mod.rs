@@ -17,7 +17,7 @@ mod caller;
 mod load;
 mod store;
 
-use crate::{CallStack, RegisterTypes, RegistersCall};
+use crate::{CallStack, RegisterTypes, RegistersCall, RegistersView};
 use console::{
     network::prelude::*,
     program::{Entry, Literal, Plaintext, Register, Value},
@@ -85,6 +85,12 @@ impl<N: Network, A: circuit::Aleo<Network = N>> Registers<N, A> {
         }
     }
 
+    /// Returns a view of the assigned console registers.
+    #[inline]
+    pub fn view(&self) -> RegistersView<N> {
+        RegistersView::new(&self.console_registers)
+    }
+
     /// Ensure the console and circuit registers match.
     #[inline]
     pub fn ensure_console_and_circuit_registers_match(&self) -> Result<()> {
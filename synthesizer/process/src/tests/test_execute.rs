@@ -2259,6 +2259,110 @@ finalize compute:
     assert_eq!(candidate, Value::from_str("{ count: 3u8, data: 6u8 }").unwrap());
 }
 
+#[test]
+fn test_process_execute_and_finalize_mapping_iteration() {
+    // Initialize a new program.
+    let (string, program) = Program::<CurrentNetwork>::parse(
+        r"
+program testing.aleo;
+
+mapping queue:
+    key as u32.public;
+    value as u64.public;
+
+mapping front:
+    key as u8.public;
+    value as u64.public;
+
+function push:
+    input r0 as u64.public;
+    async push r0 into r1;
+    output r1 as testing.aleo/push.future;
+
+finalize push:
+    input r0 as u64.public;
+    mapping.len queue into r1;
+    set r0 into queue[r1];
+
+function pop:
+    async pop into r0;
+    output r0 as testing.aleo/pop.future;
+
+finalize pop:
+    mapping.entry_at queue[0u32] into r0 r1;
+    mapping.key_at queue[0u32] into r2;
+    assert.eq r0 r2;
+    remove queue[r0];
+    set r1 into front[0u8];
+",
+    )
+    .unwrap();
+    assert!(string.is_empty(), "Parser did not consume all of the string: '{string}'");
+
+    // Declare the mappings.
+    let queue = Identifier::from_str("queue").unwrap();
+    let front = Identifier::from_str("front").unwrap();
+
+    // Initialize the RNG.
+    let rng = &mut TestRng::default();
+
+    // Initialize a new process.
+    let mut process = Process::load().unwrap();
+    // Initialize a new block store.
+    let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+    // Initialize a new finalize store.
+    let finalize_store = FinalizeStore::<_, FinalizeMemory<_>>::open(None).unwrap();
+
+    // Add the program to the process, and initialize its mappings.
+    let deployment = process.deploy::<CurrentAleo, _>(&program, rng).unwrap();
+    process.load_deployment(&deployment).unwrap();
+    finalize_store.initialize_mapping(*program.id(), queue).unwrap();
+    finalize_store.initialize_mapping(*program.id(), front).unwrap();
+
+    // Initialize a new caller account.
+    let caller_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+
+    // A helper to execute and finalize a function.
+    let mut execute_and_finalize = |function_name: &str, inputs: &[Value<CurrentNetwork>]| {
+        // Authorize the function call.
+        let authorization = process
+            .authorize::<CurrentAleo, _>(&caller_private_key, program.id(), function_name, inputs.iter(), rng)
+            .unwrap();
+        // Execute the request.
+        let (_, mut trace) = process.execute::<CurrentAleo, _>(authorization, rng).unwrap();
+        // Prepare the trace.
+        trace.prepare(Query::from(block_store.clone())).unwrap();
+        // Prove the execution.
+        let execution = trace.prove_execution::<CurrentAleo, _>("testing", rng).unwrap();
+        // Finalize the execution.
+        process.finalize_execution(sample_finalize_state(1), &finalize_store, &execution, None)
+    };
+
+    // Ensure popping from an empty queue fails.
+    assert!(execute_and_finalize("pop", &[]).is_err());
+
+    // Push two values onto the queue.
+    execute_and_finalize("push", &[Value::from_str("7u64").unwrap()]).unwrap();
+    execute_and_finalize("push", &[Value::from_str("9u64").unwrap()]).unwrap();
+
+    // Check that the values are stored at consecutive indices.
+    let candidate = finalize_store.get_value_speculative(*program.id(), queue, &Plaintext::from_str("1u32").unwrap());
+    assert_eq!(candidate.unwrap().unwrap(), Value::from_str("9u64").unwrap());
+    assert_eq!(finalize_store.len_mapping_speculative(*program.id(), queue).unwrap(), 2);
+
+    // Pop the first value from the queue.
+    execute_and_finalize("pop", &[]).unwrap();
+
+    // Check that the first value was removed, and stored in `front`.
+    let candidate = finalize_store.get_value_speculative(*program.id(), front, &Plaintext::from_str("0u8").unwrap());
+    assert_eq!(candidate.unwrap().unwrap(), Value::from_str("7u64").unwrap());
+    assert_eq!(
+        finalize_store.get_entry_at_speculative(*program.id(), queue, 0).unwrap(),
+        Some((Plaintext::from_str("1u32").unwrap(), Value::from_str("9u64").unwrap()))
+    );
+    assert_eq!(finalize_store.get_entry_at_speculative(*program.id(), queue, 1).unwrap(), None);
+}
+
 #[test]
 fn test_process_execute_and_verify_call_to_closure() {
     // Initialize a new program.
@@ -2363,8 +2467,12 @@ fn test_process_deploy_credits_program() {
     let rng = &mut TestRng::default();
 
     // Initialize an empty process without the `credits` program.
-    let empty_process =
-        Process { universal_srs: Arc::new(UniversalSRS::<CurrentNetwork>::load().unwrap()), stacks: IndexMap::new() };
+    let empty_process = Process {
+        universal_srs: Arc::new(UniversalSRS::<CurrentNetwork>::load().unwrap()),
+        stacks: IndexMap::new(),
+        debugger: Default::default(),
+        key_cache: Default::default(),
+    };
 
     // Construct the process.
     let process = Process::load().unwrap();
@@ -2645,3 +2753,133 @@ fn test_program_exceeding_transaction_spend_limit() {
     let result = Stack::initialize(&process, &program);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_process_execute_call_metrics() {
+    // Initialize a new program.
+    let child = Program::<CurrentNetwork>::from_str(
+        r"
+program child.aleo;
+
+function twice:
+    input r0 as u32.private;
+    add r0 r0 into r1;
+    output r1 as u32.private;",
+    )
+    .unwrap();
+    let parent = Program::<CurrentNetwork>::from_str(
+        r"
+import child.aleo;
+
+program parent.aleo;
+
+function main:
+    input r0 as u32.private;
+    call child.aleo/twice r0 into r1;
+    mul r1 r1 into r2;
+    output r2 as u32.private;",
+    )
+    .unwrap();
+
+    // Construct the process.
+    let mut process = Process::load().unwrap();
+    process.add_program(&child).unwrap();
+    process.add_program(&parent).unwrap();
+
+    // Initialize an RNG.
+    let rng = &mut TestRng::default();
+    // Initialize a private key.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+
+    // Authorize the function call.
+    let authorization = process
+        .authorize::<CurrentAleo, _>(
+            &private_key,
+            parent.id(),
+            Identifier::from_str("main").unwrap(),
+            ["3u32"].into_iter(),
+            rng,
+        )
+        .unwrap();
+
+    // Execute the function.
+    let (response, trace) = process.execute::<CurrentAleo, _>(authorization, rng).unwrap();
+    assert_eq!(response.outputs()[0], Value::from_str("36u32").unwrap());
+
+    // Retrieve the call metrics and instruction metrics, which are in the order the calls completed.
+    let metrics = trace.call_metrics();
+    let instructions = trace.instruction_metrics();
+    assert_eq!(metrics.len(), 2);
+    assert_eq!(instructions.len(), 2);
+
+    // Check the metrics of the child call.
+    assert_eq!(metrics[0].program_id, *child.id());
+    assert_eq!(metrics[0].call_depth, 1);
+    assert_eq!(instructions[0].len(), 1);
+    assert_eq!(instructions[0][0].opcode.to_string(), "add");
+
+    // Check the metrics of the parent call.
+    assert_eq!(metrics[1].program_id, *parent.id());
+    assert_eq!(metrics[1].call_depth, 0);
+    assert_eq!(instructions[1].len(), 2);
+    assert_eq!(instructions[1][0].opcode.to_string(), "call");
+    assert_eq!(instructions[1][1].opcode.to_string(), "mul");
+    assert!(instructions[1][1].num_constraints > 0);
+    assert!(instructions[1][1].num_variables > 0);
+    assert!(instructions[1][1].num_nonzeros > 0);
+
+    // Ensure the constraints of the instructions are attributed within the function constraints.
+    for (metrics, instructions) in metrics.iter().zip(instructions) {
+        let num_constraints = instructions.iter().map(|instruction| instruction.num_constraints).sum::<u64>();
+        assert!(num_constraints <= metrics.num_function_constraints);
+    }
+}
+
+#[test]
+fn test_synthesize_assignment_to_r1cs_and_wtns() {
+    // Initialize a new program.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program iden3.aleo;
+
+function main:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    mul.w r0 r1 into r2;
+    add.w r2 r0 into r3;
+    output r3 as u32.private;",
+    )
+    .unwrap();
+    let function_name = Identifier::from_str("main").unwrap();
+
+    // Construct the process.
+    let mut process = Process::load().unwrap();
+    process.add_program(&program).unwrap();
+
+    // Initialize an RNG.
+    let rng = &mut TestRng::default();
+
+    // Synthesize the assignment of the function.
+    let assignment = process.synthesize_assignment::<CurrentAleo, _>(program.id(), &function_name, rng).unwrap();
+    assert!(assignment.num_constraints() > 0);
+
+    // Ensure the assignment is the circuit of the function, by comparing it to its synthesized verifying key.
+    // Note: The verifying key has an additional constraint, which makes the Varuna zerocheck hiding.
+    process.synthesize_key::<CurrentAleo, _>(program.id(), &function_name, rng).unwrap();
+    let verifying_key = process.get_verifying_key(program.id(), function_name).unwrap();
+    assert_eq!(assignment.num_constraints() + 1, verifying_key.circuit_info.num_constraints as u64);
+
+    // Export the assignment as iden3 `.r1cs` and `.wtns` files.
+    let mut r1cs = Vec::new();
+    assignment.write_r1cs(&mut r1cs).unwrap();
+    let mut wtns = Vec::new();
+    assignment.write_wtns(&mut wtns).unwrap();
+
+    // Import the files, and ensure they describe the same satisfied circuit.
+    let candidate = circuit::Assignment::read_r1cs_and_wtns(&r1cs[..], &wtns[..]).unwrap();
+    assert_eq!(assignment.num_public(), candidate.num_public());
+    assert_eq!(assignment.num_private(), candidate.num_private());
+    assert_eq!(assignment.num_constraints(), candidate.num_constraints());
+    assert!(assignment.to_r1cs_circuit().unwrap().is_satisfied());
+    assert!(candidate.to_r1cs_circuit().unwrap().is_satisfied());
+}
@@ -16,6 +16,7 @@ use console::{
     network::Network,
     program::{Identifier, ProgramID},
 };
+use synthesizer_program::Opcode;
 
 #[derive(Copy, Clone, Debug)]
 pub struct CallMetrics<N: Network> {
@@ -25,4 +26,22 @@ pub struct CallMetrics<N: Network> {
     pub num_request_constraints: u64,
     pub num_function_constraints: u64,
     pub num_response_constraints: u64,
+    /// The number of calls between the top-level function and this function (i.e. `0` for the top-level function).
+    pub call_depth: usize,
+}
+
+/// The circuit size attributed to an instruction, as the growth of the circuit while executing the instruction.
+/// Note: For a `call` instruction, this excludes the circuit of the called function.
+#[derive(Copy, Clone, Debug)]
+pub struct InstructionMetrics {
+    /// The index of the instruction in the function.
+    pub index: usize,
+    /// The opcode of the instruction.
+    pub opcode: Opcode,
+    /// The number of constraints.
+    pub num_constraints: u64,
+    /// The number of (public and private) variables.
+    pub num_variables: u64,
+    /// The number of non-zero entries, summed over the A, B, and C matrices.
+    pub num_nonzeros: u64,
 }
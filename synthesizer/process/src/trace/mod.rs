@@ -25,7 +25,7 @@ use console::{
 };
 use ledger_block::{Execution, Fee, Transition};
 use ledger_query::QueryTrait;
-use synthesizer_snark::{Proof, ProvingKey, VerifyingKey};
+use synthesizer_snark::{Proof, ProofAccumulator, ProvingKey, VerifyingKey};
 
 use once_cell::sync::OnceCell;
 use std::collections::HashMap;
@@ -40,6 +40,8 @@ pub struct Trace<N: Network> {
     inclusion_tasks: Inclusion<N>,
     /// A list of call metrics.
     call_metrics: Vec<CallMetrics<N>>,
+    /// A list of the circuit sizes attributed to each instruction, for each call in `call_metrics`.
+    instruction_metrics: Vec<Vec<InstructionMetrics>>,
 
     /// A tracker for the inclusion assignments.
     inclusion_assignments: OnceCell<Vec<InclusionAssignment<N>>>,
@@ -57,6 +59,7 @@ impl<N: Network> Trace<N> {
             inclusion_assignments: OnceCell::new(),
             global_state_root: OnceCell::new(),
             call_metrics: Vec::new(),
+            instruction_metrics: Vec::new(),
         }
     }
 
@@ -69,6 +72,11 @@ impl<N: Network> Trace<N> {
     pub fn call_metrics(&self) -> &[CallMetrics<N>] {
         &self.call_metrics
     }
+
+    /// Returns the circuit sizes attributed to each instruction, for each call in the call metrics.
+    pub fn instruction_metrics(&self) -> &[Vec<InstructionMetrics>] {
+        &self.instruction_metrics
+    }
 }
 
 impl<N: Network> Trace<N> {
@@ -98,6 +106,16 @@ impl<N: Network> Trace<N> {
 
         Ok(())
     }
+
+    /// Inserts the circuit sizes attributed to the instructions of the last inserted call.
+    pub(crate) fn insert_instruction_metrics(&mut self, instruction_metrics: Vec<InstructionMetrics>) {
+        self.instruction_metrics.push(instruction_metrics);
+    }
+
+    /// Increments the call depth of the call metrics from the given index onwards.
+    pub(crate) fn increment_call_depths(&mut self, start: usize) {
+        self.call_metrics.iter_mut().skip(start).for_each(|metrics| metrics.call_depth += 1);
+    }
 }
 
 impl<N: Network> Trace<N> {
@@ -252,6 +270,57 @@ impl<N: Network> Trace<N> {
             Err(e) => bail!("Fee is invalid - {e}"),
         }
     }
+
+    /// Checks the proof for the execution, except for its final pairing check,
+    /// which is returned as an accumulator to be checked with `ProofAccumulator::verify_batch`.
+    /// Note: This does *not* check that the global state root exists in the ledger.
+    pub fn accumulate_execution_proof(
+        locator: &str,
+        verifier_inputs: Vec<(VerifyingKey<N>, Vec<Vec<N::Field>>)>,
+        execution: &Execution<N>,
+    ) -> Result<ProofAccumulator<N>> {
+        // Retrieve the global state root.
+        let global_state_root = execution.global_state_root();
+        // Ensure the global state root is not zero.
+        if global_state_root == N::StateRoot::default() {
+            bail!("Inclusion expected the global state root in the execution to *not* be zero")
+        }
+        // Retrieve the proof.
+        let Some(proof) = execution.proof() else { bail!("Expected the execution to contain a proof") };
+        // Accumulate the execution proof.
+        match Self::accumulate_batch(locator, verifier_inputs, global_state_root, execution.transitions(), proof) {
+            Ok(accumulator) => Ok(accumulator),
+            Err(e) => bail!("Execution is invalid - {e}"),
+        }
+    }
+
+    /// Checks the proof for the fee, except for its final pairing check,
+    /// which is returned as an accumulator to be checked with `ProofAccumulator::verify_batch`.
+    /// Note: This does *not* check that the global state root exists in the ledger.
+    pub fn accumulate_fee_proof(
+        verifier_inputs: (VerifyingKey<N>, Vec<Vec<N::Field>>),
+        fee: &Fee<N>,
+    ) -> Result<ProofAccumulator<N>> {
+        // Retrieve the global state root.
+        let global_state_root = fee.global_state_root();
+        // Ensure the global state root is not zero.
+        if global_state_root == N::StateRoot::default() {
+            bail!("Inclusion expected the global state root in the fee to *not* be zero")
+        }
+        // Retrieve the proof.
+        let Some(proof) = fee.proof() else { bail!("Expected the fee to contain a proof") };
+        // Accumulate the fee proof.
+        match Self::accumulate_batch(
+            "credits.aleo/fee (private or public)",
+            vec![verifier_inputs],
+            global_state_root,
+            [fee.transition()].into_iter(),
+            proof,
+        ) {
+            Ok(accumulator) => Ok(accumulator),
+            Err(e) => bail!("Fee is invalid - {e}"),
+        }
+    }
 }
 
 impl<N: Network> Trace<N> {
@@ -299,11 +368,39 @@ impl<N: Network> Trace<N> {
     /// Note: This does *not* check that the global state root exists in the ledger.
     fn verify_batch<'a>(
         locator: &str,
-        mut verifier_inputs: Vec<(VerifyingKey<N>, Vec<Vec<N::Field>>)>,
+        verifier_inputs: Vec<(VerifyingKey<N>, Vec<Vec<N::Field>>)>,
         global_state_root: N::StateRoot,
         transitions: impl ExactSizeIterator<Item = &'a Transition<N>>,
         proof: &Proof<N>,
     ) -> Result<()> {
+        // Construct the verifier inputs, including the inclusion verifier inputs.
+        let verifier_inputs = Self::to_batch_verifier_inputs(verifier_inputs, global_state_root, transitions)?;
+        // Verify the proof.
+        VerifyingKey::verify_batch(locator, verifier_inputs, proof).map_err(|e| anyhow!("Failed to verify proof - {e}"))
+    }
+
+    /// Checks the proof for the given inputs, except for its final pairing check, which is returned as an accumulator.
+    /// Note: This does *not* check that the global state root exists in the ledger.
+    fn accumulate_batch<'a>(
+        locator: &str,
+        verifier_inputs: Vec<(VerifyingKey<N>, Vec<Vec<N::Field>>)>,
+        global_state_root: N::StateRoot,
+        transitions: impl ExactSizeIterator<Item = &'a Transition<N>>,
+        proof: &Proof<N>,
+    ) -> Result<ProofAccumulator<N>> {
+        // Construct the verifier inputs, including the inclusion verifier inputs.
+        let verifier_inputs = Self::to_batch_verifier_inputs(verifier_inputs, global_state_root, transitions)?;
+        // Accumulate the proof.
+        VerifyingKey::accumulate_batch(locator, verifier_inputs, proof)
+            .map_err(|e| anyhow!("Failed to verify proof - {e}"))
+    }
+
+    /// Returns the given verifier inputs, along with the batch of inclusion verifier inputs for the given transitions.
+    fn to_batch_verifier_inputs<'a>(
+        mut verifier_inputs: Vec<(VerifyingKey<N>, Vec<Vec<N::Field>>)>,
+        global_state_root: N::StateRoot,
+        transitions: impl ExactSizeIterator<Item = &'a Transition<N>>,
+    ) -> Result<Vec<(VerifyingKey<N>, Vec<Vec<N::Field>>)>> {
         // Construct the batch of inclusion verifier inputs.
         let batch_inclusion_inputs = Inclusion::prepare_verifier_inputs(global_state_root, transitions)?;
         // Insert the batch of inclusion verifier inputs to the verifier inputs.
@@ -317,7 +414,6 @@ impl<N: Network> Trace<N> {
             // Insert the inclusion verifier inputs.
             verifier_inputs.push((VerifyingKey::<N>::new(verifying_key, num_variables), batch_inclusion_inputs));
         }
-        // Verify the proof.
-        VerifyingKey::verify_batch(locator, verifier_inputs, proof).map_err(|e| anyhow!("Failed to verify proof - {e}"))
+        Ok(verifier_inputs)
     }
 }
@@ -21,6 +21,47 @@ impl<N: Network> Process<N> {
     pub fn verify_execution(&self, execution: &Execution<N>) -> Result<()> {
         let timer = timer!("Process::verify_execution");
 
+        // Check the execution, and construct the verifier inputs.
+        let (locator, verifier_inputs) = self.to_execution_verifier_inputs(execution)?;
+        lap!(timer, "Construct the verifier inputs");
+
+        // Verify the execution proof.
+        Trace::verify_execution_proof(&locator, verifier_inputs, execution)?;
+        lap!(timer, "Verify the proof");
+
+        finish!(timer);
+        Ok(())
+    }
+
+    /// Verifies the given execution is valid, except for the final pairing check of its proof,
+    /// which is returned as an accumulator to be checked with `ProofAccumulator::verify_batch`.
+    /// Note: This does *not* check that the global state root exists in the ledger.
+    #[inline]
+    pub fn verify_execution_deferred(&self, execution: &Execution<N>) -> Result<ProofAccumulator<N>> {
+        let timer = timer!("Process::verify_execution_deferred");
+
+        // Check the execution, and construct the verifier inputs.
+        let (locator, verifier_inputs) = self.to_execution_verifier_inputs(execution)?;
+        lap!(timer, "Construct the verifier inputs");
+
+        // Accumulate the execution proof.
+        let accumulator = Trace::accumulate_execution_proof(&locator, verifier_inputs, execution)?;
+        lap!(timer, "Accumulate the proof");
+
+        finish!(timer);
+        Ok(accumulator)
+    }
+}
+
+impl<N: Network> Process<N> {
+    /// Checks the given execution, except for its proof, and returns the locator of its main function
+    /// along with the verifying keys and public inputs to verify its proof.
+    fn to_execution_verifier_inputs(
+        &self,
+        execution: &Execution<N>,
+    ) -> Result<(String, Vec<(VerifyingKey<N>, Vec<Vec<N::Field>>)>)> {
+        let timer = timer!("Process::to_execution_verifier_inputs");
+
         // Ensure the execution contains transitions.
         ensure!(!execution.is_empty(), "There are no transitions in the execution");
 
@@ -149,13 +190,9 @@ impl<N: Network> Process<N> {
 
         // Construct the list of verifier inputs.
         let verifier_inputs: Vec<_> = verifier_inputs.values().cloned().collect();
-        // Verify the execution proof.
-        Trace::verify_execution_proof(&locator, verifier_inputs, execution)?;
-
-        lap!(timer, "Verify the proof");
 
         finish!(timer);
-        Ok(())
+        Ok((locator, verifier_inputs))
     }
 }
 
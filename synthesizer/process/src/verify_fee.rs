@@ -21,6 +21,47 @@ impl<N: Network> Process<N> {
     pub fn verify_fee(&self, fee: &Fee<N>, deployment_or_execution_id: Field<N>) -> Result<()> {
         let timer = timer!("Process::verify_fee");
 
+        // Check the fee, and construct the verifier inputs.
+        let verifier_inputs = self.to_fee_verifier_inputs(fee, deployment_or_execution_id)?;
+        lap!(timer, "Construct the verifier inputs");
+
+        // Ensure the fee proof is valid.
+        Trace::verify_fee_proof(verifier_inputs, fee)?;
+        finish!(timer, "Verify the fee proof");
+        Ok(())
+    }
+
+    /// Verifies the given fee is valid, except for the final pairing check of its proof,
+    /// which is returned as an accumulator to be checked with `ProofAccumulator::verify_batch`.
+    /// Note: This does *not* check that the global state root exists in the ledger.
+    #[inline]
+    pub fn verify_fee_deferred(
+        &self,
+        fee: &Fee<N>,
+        deployment_or_execution_id: Field<N>,
+    ) -> Result<ProofAccumulator<N>> {
+        let timer = timer!("Process::verify_fee_deferred");
+
+        // Check the fee, and construct the verifier inputs.
+        let verifier_inputs = self.to_fee_verifier_inputs(fee, deployment_or_execution_id)?;
+        lap!(timer, "Construct the verifier inputs");
+
+        // Accumulate the fee proof.
+        let accumulator = Trace::accumulate_fee_proof(verifier_inputs, fee)?;
+        finish!(timer, "Accumulate the fee proof");
+        Ok(accumulator)
+    }
+}
+
+impl<N: Network> Process<N> {
+    /// Checks the given fee, except for its proof, and returns the verifying key and public inputs to verify its proof.
+    fn to_fee_verifier_inputs(
+        &self,
+        fee: &Fee<N>,
+        deployment_or_execution_id: Field<N>,
+    ) -> Result<(VerifyingKey<N>, Vec<Vec<N::Field>>)> {
+        let timer = timer!("Process::to_fee_verifier_inputs");
+
         #[cfg(debug_assertions)]
         {
             println!("Verifying fee from {}/{}...", fee.program_id(), fee.function_name());
@@ -62,18 +103,19 @@ impl<N: Network> Process<N> {
         lap!(timer, "Verify the deployment or execution ID");
 
         // Verify the fee transition is well-formed.
-        match is_fee_private {
+        let verifier_inputs = match is_fee_private {
             true => self.verify_fee_private(&fee)?,
             false => self.verify_fee_public(&fee)?,
-        }
+        };
         finish!(timer, "Verify the fee transition");
-        Ok(())
+        Ok(verifier_inputs)
     }
 }
 
 impl<N: Network> Process<N> {
-    /// Verifies the transition for `credits.aleo/fee_private` is well-formed.
-    fn verify_fee_private(&self, fee: &&Fee<N>) -> Result<()> {
+    /// Verifies the transition for `credits.aleo/fee_private` is well-formed,
+    /// and returns the verifying key and public inputs to verify its proof.
+    fn verify_fee_private(&self, fee: &&Fee<N>) -> Result<(VerifyingKey<N>, Vec<Vec<N::Field>>)> {
         let timer = timer!("Process::verify_fee_private");
 
         // Retrieve the network ID.
@@ -133,16 +175,15 @@ impl<N: Network> Process<N> {
 
         // Retrieve the verifying key.
         let verifying_key = self.get_verifying_key(fee.program_id(), fee.function_name())?;
+        finish!(timer, "Retrieve the verifying key");
 
-        // Ensure the fee proof is valid.
-        Trace::verify_fee_proof((verifying_key, vec![inputs]), fee)?;
-        finish!(timer, "Verify the fee proof");
-        Ok(())
+        Ok((verifying_key, vec![inputs]))
     }
 
-    /// Verifies the transition for `credits.aleo/fee_public` is well-formed.
+    /// Verifies the transition for `credits.aleo/fee_public` is well-formed,
+    /// and returns the verifying key and public inputs to verify its proof.
     /// Attention: This method does *not* verify the account balance is sufficient.
-    fn verify_fee_public(&self, fee: &&Fee<N>) -> Result<()> {
+    fn verify_fee_public(&self, fee: &&Fee<N>) -> Result<(VerifyingKey<N>, Vec<Vec<N::Field>>)> {
         let timer = timer!("Process::verify_fee_public");
 
         // Retrieve the network ID.
@@ -202,11 +243,9 @@ impl<N: Network> Process<N> {
 
         // Retrieve the verifying key.
         let verifying_key = self.get_verifying_key(fee.program_id(), fee.function_name())?;
+        finish!(timer, "Retrieve the verifying key");
 
-        // Ensure the fee proof is valid.
-        Trace::verify_fee_proof((verifying_key, vec![inputs]), fee)?;
-        finish!(timer, "Verify the fee proof");
-        Ok(())
+        Ok((verifying_key, vec![inputs]))
     }
 }
 
@@ -247,10 +286,15 @@ mod tests {
                     // Verify the fee.
                     process.verify_fee(&fee.unwrap(), execution_id).unwrap();
                 }
-                Transaction::Fee(_, fee) => match fee.is_fee_private() {
-                    true => process.verify_fee_private(&&fee).unwrap(),
-                    false => process.verify_fee_public(&&fee).unwrap(),
-                },
+                Transaction::Fee(_, fee) => {
+                    // Check the fee, and retrieve its verifier inputs.
+                    let (verifying_key, inputs) = match fee.is_fee_private() {
+                        true => process.verify_fee_private(&&fee).unwrap(),
+                        false => process.verify_fee_public(&&fee).unwrap(),
+                    };
+                    // Verify the fee proof.
+                    Trace::verify_fee_proof((verifying_key, inputs), &fee).unwrap();
+                }
             }
         }
     }
@@ -79,12 +79,14 @@ use console::{
         Sanitizer,
         Serialize,
         Serializer,
+        ToBits,
         ToBytes,
         ToBytesSerializer,
         TypeName,
         Write,
     },
     program::{Identifier, PlaintextType, ProgramID, RecordType, StructType},
+    types::Field,
 };
 
 use indexmap::IndexMap;
@@ -183,6 +185,11 @@ impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> Pro
         &self.functions
     }
 
+    /// Returns the checksum of the program, computed as the BHP-1024 hash of the program bytes.
+    pub fn to_checksum(&self) -> Result<Field<N>> {
+        N::hash_bhp1024(&self.to_bytes_le()?.to_bits_le())
+    }
+
     /// Returns `true` if the program contains an import with the given program ID.
     pub fn contains_import(&self, id: &ProgramID<N>) -> bool {
         self.imports.contains_key(id)
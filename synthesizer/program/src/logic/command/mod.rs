@@ -27,6 +27,15 @@ pub use get::*;
 mod get_or_use;
 pub use get_or_use::*;
 
+mod mapping_entry_at;
+pub use mapping_entry_at::*;
+
+mod mapping_key_at;
+pub use mapping_key_at::*;
+
+mod mapping_len;
+pub use mapping_len::*;
+
 mod rand_chacha;
 pub use crate::command::rand_chacha::*;
 
@@ -84,6 +93,12 @@ pub enum Command<N: Network> {
     BranchNeq(BranchNeq<N>),
     /// Indicates a position to which the program can branch to.
     Position(Position<N>),
+    /// Stores the number of entries in `mapping` into `destination`.
+    MappingLen(MappingLen<N>),
+    /// Gets the key of the entry at the `index` operand in `mapping` and stores the result into `destination`.
+    MappingKeyAt(MappingKeyAt<N>),
+    /// Gets the entry at the `index` operand in `mapping` and stores the key and value into the destinations.
+    MappingEntryAt(MappingEntryAt<N>),
 }
 
 impl<N: Network> CommandTrait<N> for Command<N> {
@@ -96,6 +111,11 @@ impl<N: Network> CommandTrait<N> for Command<N> {
             Command::Get(get) => vec![get.destination().clone()],
             Command::GetOrUse(get_or_use) => vec![get_or_use.destination().clone()],
             Command::RandChaCha(rand_chacha) => vec![rand_chacha.destination().clone()],
+            Command::MappingLen(mapping_len) => vec![mapping_len.destination().clone()],
+            Command::MappingKeyAt(mapping_key_at) => vec![mapping_key_at.destination().clone()],
+            Command::MappingEntryAt(mapping_entry_at) => {
+                vec![mapping_entry_at.key_destination().clone(), mapping_entry_at.value_destination().clone()]
+            }
             Command::Await(_)
             | Command::BranchEq(_)
             | Command::BranchNeq(_)
@@ -176,6 +196,14 @@ impl<N: Network> Command<N> {
             }
             // Finalize the `position` command, and return no finalize operation.
             Command::Position(position) => position.finalize().map(|_| None),
+            // Finalize the 'mapping.len' command, and return no finalize operation.
+            Command::MappingLen(mapping_len) => mapping_len.finalize(stack, store, registers).map(|_| None),
+            // Finalize the 'mapping.key_at' command, and return no finalize operation.
+            Command::MappingKeyAt(mapping_key_at) => mapping_key_at.finalize(stack, store, registers).map(|_| None),
+            // Finalize the 'mapping.entry_at' command, and return no finalize operation.
+            Command::MappingEntryAt(mapping_entry_at) => {
+                mapping_entry_at.finalize(stack, store, registers).map(|_| None)
+            }
         }
     }
 }
@@ -208,8 +236,14 @@ impl<N: Network> FromBytes for Command<N> {
             9 => Ok(Self::BranchNeq(BranchNeq::read_le(&mut reader)?)),
             // Read the `position` command.
             10 => Ok(Self::Position(Position::read_le(&mut reader)?)),
+            // Read the `mapping.len` command.
+            11 => Ok(Self::MappingLen(MappingLen::read_le(&mut reader)?)),
+            // Read the `mapping.key_at` command.
+            12 => Ok(Self::MappingKeyAt(MappingKeyAt::read_le(&mut reader)?)),
+            // Read the `mapping.entry_at` command.
+            13 => Ok(Self::MappingEntryAt(MappingEntryAt::read_le(&mut reader)?)),
             // Invalid variant.
-            11.. => Err(error(format!("Invalid command variant: {variant}"))),
+            14.. => Err(error(format!("Invalid command variant: {variant}"))),
         }
     }
 }
@@ -284,6 +318,24 @@ impl<N: Network> ToBytes for Command<N> {
                 // Write the position command.
                 position.write_le(&mut writer)
             }
+            Self::MappingLen(mapping_len) => {
+                // Write the variant.
+                11u8.write_le(&mut writer)?;
+                // Write the `mapping.len` command.
+                mapping_len.write_le(&mut writer)
+            }
+            Self::MappingKeyAt(mapping_key_at) => {
+                // Write the variant.
+                12u8.write_le(&mut writer)?;
+                // Write the `mapping.key_at` command.
+                mapping_key_at.write_le(&mut writer)
+            }
+            Self::MappingEntryAt(mapping_entry_at) => {
+                // Write the variant.
+                13u8.write_le(&mut writer)?;
+                // Write the `mapping.entry_at` command.
+                mapping_entry_at.write_le(&mut writer)
+            }
         }
     }
 }
@@ -305,6 +357,9 @@ impl<N: Network> Parser for Command<N> {
             map(BranchEq::parse, |branch_eq| Self::BranchEq(branch_eq)),
             map(BranchNeq::parse, |branch_neq| Self::BranchNeq(branch_neq)),
             map(Position::parse, |position| Self::Position(position)),
+            map(MappingLen::parse, |mapping_len| Self::MappingLen(mapping_len)),
+            map(MappingKeyAt::parse, |mapping_key_at| Self::MappingKeyAt(mapping_key_at)),
+            map(MappingEntryAt::parse, |mapping_entry_at| Self::MappingEntryAt(mapping_entry_at)),
             map(Instruction::parse, |instruction| Self::Instruction(instruction)),
         ))(string)
     }
@@ -350,6 +405,9 @@ impl<N: Network> Display for Command<N> {
             Self::BranchEq(branch_eq) => Display::fmt(branch_eq, f),
             Self::BranchNeq(branch_neq) => Display::fmt(branch_neq, f),
             Self::Position(position) => Display::fmt(position, f),
+            Self::MappingLen(mapping_len) => Display::fmt(mapping_len, f),
+            Self::MappingKeyAt(mapping_key_at) => Display::fmt(mapping_key_at, f),
+            Self::MappingEntryAt(mapping_entry_at) => Display::fmt(mapping_entry_at, f),
         }
     }
 }
@@ -442,6 +500,24 @@ mod tests {
         let command = Command::<CurrentNetwork>::parse(expected).unwrap().1;
         let bytes = command.to_bytes_le().unwrap();
         assert_eq!(command, Command::from_bytes_le(&bytes).unwrap());
+
+        // MappingLen
+        let expected = "mapping.len object into r0;";
+        let command = Command::<CurrentNetwork>::parse(expected).unwrap().1;
+        let bytes = command.to_bytes_le().unwrap();
+        assert_eq!(command, Command::from_bytes_le(&bytes).unwrap());
+
+        // MappingKeyAt
+        let expected = "mapping.key_at object[r0] into r1;";
+        let command = Command::<CurrentNetwork>::parse(expected).unwrap().1;
+        let bytes = command.to_bytes_le().unwrap();
+        assert_eq!(command, Command::from_bytes_le(&bytes).unwrap());
+
+        // MappingEntryAt
+        let expected = "mapping.entry_at object[r0] into r1 r2;";
+        let command = Command::<CurrentNetwork>::parse(expected).unwrap().1;
+        let bytes = command.to_bytes_le().unwrap();
+        assert_eq!(command, Command::from_bytes_le(&bytes).unwrap());
     }
 
     #[test]
@@ -519,5 +595,23 @@ mod tests {
         let command = Command::<CurrentNetwork>::parse(expected).unwrap().1;
         assert_eq!(Command::Position(Position::from_str(expected).unwrap()), command);
         assert_eq!(expected, command.to_string());
+
+        // MappingLen
+        let expected = "mapping.len object into r0;";
+        let command = Command::<CurrentNetwork>::parse(expected).unwrap().1;
+        assert_eq!(Command::MappingLen(MappingLen::from_str(expected).unwrap()), command);
+        assert_eq!(expected, command.to_string());
+
+        // MappingKeyAt
+        let expected = "mapping.key_at object[r0] into r1;";
+        let command = Command::<CurrentNetwork>::parse(expected).unwrap().1;
+        assert_eq!(Command::MappingKeyAt(MappingKeyAt::from_str(expected).unwrap()), command);
+        assert_eq!(expected, command.to_string());
+
+        // MappingEntryAt
+        let expected = "mapping.entry_at object[r0] into r1 r2;";
+        let command = Command::<CurrentNetwork>::parse(expected).unwrap().1;
+        assert_eq!(Command::MappingEntryAt(MappingEntryAt::from_str(expected).unwrap()), command);
+        assert_eq!(expected, command.to_string());
     }
 }
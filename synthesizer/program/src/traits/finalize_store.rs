@@ -39,6 +39,30 @@ pub trait FinalizeStoreTrait<N: Network> {
         key: &Plaintext<N>,
     ) -> Result<Option<Value<N>>>;
 
+    /// Returns the number of speculative entries for the given `program ID` and `mapping name`.
+    fn len_mapping_speculative(&self, program_id: ProgramID<N>, mapping_name: Identifier<N>) -> Result<usize>;
+
+    /// Returns the speculative key at the given `index` for the given `program ID` and `mapping name`.
+    /// A new key is appended at the end of the mapping, and a removed key is replaced by the last key of the mapping,
+    /// so the index of a key is the same during speculation and once the block is committed.
+    /// If the `index` is out of bounds, the method returns `None`.
+    fn get_key_at_speculative(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        index: usize,
+    ) -> Result<Option<Plaintext<N>>>;
+
+    /// Returns the speculative `(key, value)` entry at the given `index` for the given `program ID` and `mapping name`.
+    /// The entries are ordered as in `get_key_at_speculative`.
+    /// If the `index` is out of bounds, the method returns `None`.
+    fn get_entry_at_speculative(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        index: usize,
+    ) -> Result<Option<(Plaintext<N>, Value<N>)>>;
+
     /// Stores the given `(key, value)` pair at the given `program ID` and `mapping name` in storage.
     /// If the `mapping name` is not initialized, an error is returned.
     /// If the `key` already exists, the method returns an error.
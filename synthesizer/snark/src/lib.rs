@@ -34,6 +34,9 @@ pub use certificate::Certificate;
 mod proof;
 pub use proof::Proof;
 
+mod proof_accumulator;
+pub use proof_accumulator::ProofAccumulator;
+
 mod proving_key;
 pub use proving_key::ProvingKey;
 
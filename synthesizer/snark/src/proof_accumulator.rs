@@ -0,0 +1,71 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm_algorithms::polycommit::sonic_pc::PairingAccumulator;
+
+/// The deferred pairing check of a batch proof, which is produced by `VerifyingKey::accumulate_batch`.
+///
+/// The pairing checks of many proofs can be verified at once with `ProofAccumulator::verify_batch`,
+/// which combines them with random scalars into a single multi-pairing check.
+#[derive(Clone)]
+pub struct ProofAccumulator<N: Network> {
+    /// The accumulated opening checks of the proof.
+    accumulator: PairingAccumulator<N::PairingCurve>,
+}
+
+impl<N: Network> ProofAccumulator<N> {
+    /// Initializes a new proof accumulator.
+    pub(crate) const fn new(accumulator: PairingAccumulator<N::PairingCurve>) -> Self {
+        Self { accumulator }
+    }
+
+    /// Returns `true` if the proof of this accumulator is valid.
+    pub fn verify<R: Rng + CryptoRng>(&self, rng: &mut R) -> bool {
+        Self::verify_batch(core::slice::from_ref(self), rng)
+    }
+
+    /// Returns `true` if the proofs of all of the given accumulators are valid.
+    /// If this returns `false`, use `ProofAccumulator::verify` to determine which proof is invalid.
+    pub fn verify_batch<R: Rng + CryptoRng>(accumulators: &[Self], rng: &mut R) -> bool {
+        #[cfg(feature = "aleo-cli")]
+        let timer = std::time::Instant::now();
+
+        // Retrieve the verification parameters.
+        let universal_verifier = N::varuna_universal_verifier();
+
+        // Check the accumulators.
+        let accumulators = accumulators.iter().map(|accumulator| accumulator.accumulator.clone()).collect::<Vec<_>>();
+        match Varuna::<N>::check_accumulators(universal_verifier, &accumulators, rng) {
+            Ok(is_valid) => {
+                #[cfg(feature = "aleo-cli")]
+                println!(
+                    "{}",
+                    format!(
+                        " • Verified {} proofs: {is_valid} (in {} ms)",
+                        accumulators.len(),
+                        timer.elapsed().as_millis()
+                    )
+                    .dimmed()
+                );
+                is_valid
+            }
+            Err(error) => {
+                #[cfg(feature = "aleo-cli")]
+                println!("{}", format!(" • Verifier failed: {error}").dimmed());
+                false
+            }
+        }
+    }
+}
@@ -103,6 +103,31 @@ impl<N: Network> VerifyingKey<N> {
             }
         }
     }
+
+    /// Verifies the batch proof for the given public inputs, except for its final pairing check,
+    /// which is returned as an accumulator to be checked with `ProofAccumulator::verify_batch`.
+    #[allow(clippy::type_complexity)]
+    pub fn accumulate_batch(
+        locator: &str,
+        inputs: Vec<(VerifyingKey<N>, Vec<Vec<N::Field>>)>,
+        proof: &Proof<N>,
+    ) -> Result<ProofAccumulator<N>> {
+        // Convert the instances.
+        let num_expected_keys = inputs.len();
+        let keys_to_inputs: BTreeMap<_, _> =
+            inputs.iter().map(|(verifying_key, inputs)| (verifying_key.deref(), inputs.as_slice())).collect();
+        ensure!(keys_to_inputs.len() == num_expected_keys, "Incorrect number of verifying keys for batch proof");
+
+        // Retrieve the verification parameters.
+        let universal_verifier = N::varuna_universal_verifier();
+        let fiat_shamir = N::varuna_fs_parameters();
+
+        // Accumulate the batch proof.
+        match Varuna::<N>::accumulate_batch(universal_verifier, fiat_shamir, &keys_to_inputs, proof)? {
+            Some(accumulator) => Ok(ProofAccumulator::new(accumulator)),
+            None => bail!("'accumulate_batch' failed for '{locator}' - the proof has an incorrect hiding mode"),
+        }
+    }
 }
 
 impl<N: Network> Deref for VerifyingKey<N> {
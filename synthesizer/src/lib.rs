@@ -48,7 +48,7 @@ pub mod prelude {
     #[cfg(feature = "program")]
     pub use crate::program::{Closure, Finalize, Function, Instruction, Mapping, Program};
     #[cfg(feature = "snark")]
-    pub use crate::snark::{Certificate, Proof, ProvingKey, UniversalSRS, VerifyingKey};
+    pub use crate::snark::{Certificate, Proof, ProofAccumulator, ProvingKey, UniversalSRS, VerifyingKey};
     #[cfg(all(feature = "process", feature = "program", feature = "snark"))]
     pub use crate::vm::*;
 }
@@ -29,9 +29,36 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         priority_fee_in_microcredits: u64,
         query: Option<Query<N, C::BlockStorage>>,
         rng: &mut R,
+    ) -> Result<Transaction<N>> {
+        self.deploy_with_upgrade_policy(
+            private_key,
+            program,
+            UpgradePolicy::Immutable,
+            fee_record,
+            priority_fee_in_microcredits,
+            query,
+            rng,
+        )
+    }
+
+    /// Returns a new deploy transaction, with the given upgrade policy.
+    ///
+    /// If the program already exists, the deploy transaction is an upgrade to the next edition of the program,
+    /// and must be permitted by the upgrade policy of the existing program.
+    /// The given `upgrade_policy` governs the upgrades of the newly-deployed edition.
+    #[allow(clippy::too_many_arguments)]
+    pub fn deploy_with_upgrade_policy<R: Rng + CryptoRng>(
+        &self,
+        private_key: &PrivateKey<N>,
+        program: &Program<N>,
+        upgrade_policy: UpgradePolicy<N>,
+        fee_record: Option<Record<N, Plaintext<N>>>,
+        priority_fee_in_microcredits: u64,
+        query: Option<Query<N, C::BlockStorage>>,
+        rng: &mut R,
     ) -> Result<Transaction<N>> {
         // Compute the deployment.
-        let deployment = self.deploy_raw(program, rng)?;
+        let deployment = self.deploy_raw(program, upgrade_policy, rng)?;
         // Ensure the transaction is not empty.
         ensure!(!deployment.program().functions().is_empty(), "Attempted to create an empty transaction deployment");
         // Compute the deployment ID.
@@ -70,13 +97,20 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     /// Returns a deployment for the given program.
     #[inline]
-    pub(super) fn deploy_raw<R: Rng + CryptoRng>(&self, program: &Program<N>, rng: &mut R) -> Result<Deployment<N>> {
+    pub(super) fn deploy_raw<R: Rng + CryptoRng>(
+        &self,
+        program: &Program<N>,
+        upgrade_policy: UpgradePolicy<N>,
+        rng: &mut R,
+    ) -> Result<Deployment<N>> {
         macro_rules! logic {
             ($process:expr, $network:path, $aleo:path) => {{
                 // Prepare the program.
                 let program = cast_ref!(&program as Program<$network>);
+                // Prepare the upgrade policy.
+                let upgrade_policy = cast_ref!(upgrade_policy as UpgradePolicy<$network>);
                 // Compute the deployment.
-                let deployment = $process.deploy::<$aleo, _>(program, rng)?;
+                let deployment = $process.deploy_with_upgrade_policy::<$aleo, _>(program, *upgrade_policy, rng)?;
                 // Prepare the deployment.
                 Ok(cast_ref!(deployment as Deployment<N>).clone())
             }};
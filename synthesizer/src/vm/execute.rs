@@ -104,6 +104,62 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         debug_assert!(authorization.is_fee_private() || authorization.is_fee_public(), "Expected a fee authorization");
         self.execute_fee_authorization_raw(authorization, query, rng)
     }
+
+    /// Returns an estimate of the fees (in microcredits) to publish an execution for the given authorization,
+    /// without proving the execution.
+    ///
+    /// The storage cost is estimated from the authorized transitions and the expected size of the proof,
+    /// and the priority fee is estimated from the priority fees paid in the most recent blocks.
+    ///
+    /// If a finalize `state` is provided, then finalize is dry-run on the execution against
+    /// the current finalize store, and the estimate reports whether the execution would be rejected.
+    pub fn estimate_cost(
+        &self,
+        authorization: &Authorization<N>,
+        state: Option<FinalizeGlobalState>,
+    ) -> Result<CostEstimate> {
+        // Estimate the execution cost.
+        let (_, (storage_cost, finalize_cost)) =
+            execution_cost_from_authorization(&self.process().read(), authorization)?;
+        // Estimate the priority fee.
+        let priority_fee = self.estimate_priority_fee()?;
+        // If a finalize state is provided, dry-run finalize on the execution.
+        let rejection = match state {
+            Some(state) => self.atomic_dry_run_authorization(state, authorization)?,
+            None => None,
+        };
+        // Return the cost estimate.
+        Ok(CostEstimate::new(storage_cost, finalize_cost, priority_fee, rejection))
+    }
+
+    /// Returns an estimate of the priority fee (in microcredits), as the median priority fee of the transactions
+    /// in the last `PRIORITY_FEE_ESTIMATE_NUM_BLOCKS` blocks, or zero if there are no such transactions.
+    ///
+    /// Note: The genesis block is excluded, as its transactions do not pay a priority fee.
+    pub fn estimate_priority_fee(&self) -> Result<u64> {
+        // Determine the range of the most recent blocks, excluding the genesis block.
+        let latest_height = self.block_store().current_block_height();
+        let start_height = latest_height.saturating_sub(PRIORITY_FEE_ESTIMATE_NUM_BLOCKS - 1).max(1);
+
+        // Collect the priority fees of the transactions in the most recent blocks.
+        let mut priority_fees = Vec::new();
+        for height in start_height..=latest_height {
+            // Retrieve the transactions of the block, skipping the blocks that are not available.
+            let Some(block_hash) = self.block_store().get_block_hash(height)? else { continue };
+            let Some(transactions) = self.block_store().get_block_transactions(&block_hash)? else { continue };
+            for confirmed in transactions.iter() {
+                // Skip the transactions that do not pay a fee.
+                if let Transaction::Execute(_, _, None) = confirmed.transaction() {
+                    continue;
+                }
+                priority_fees.push(*confirmed.transaction().priority_fee_amount()?);
+            }
+        }
+
+        // Return the median priority fee.
+        priority_fees.sort_unstable();
+        Ok(priority_fees.get(priority_fees.len() / 2).copied().unwrap_or(0))
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
@@ -464,6 +520,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_estimate_cost() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a new caller.
+        let caller_private_key = crate::vm::test_helpers::sample_genesis_private_key(rng);
+        let address = Address::try_from(&caller_private_key).unwrap();
+
+        // Prepare the VM and records.
+        let (vm, _) = prepare_vm(rng).unwrap();
+
+        // Prepare the inputs.
+        let inputs = [
+            Value::<CurrentNetwork>::from_str(&address.to_string()).unwrap(),
+            Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+        ];
+
+        // Authorize the execution.
+        let authorization = vm.authorize(&caller_private_key, "credits.aleo", "transfer_public", inputs, rng).unwrap();
+        // Estimate the cost, with a dry run of finalize.
+        let state = crate::vm::test_helpers::sample_finalize_state(1);
+        let estimate = vm.estimate_cost(&authorization, Some(state)).unwrap();
+        assert!(!estimate.is_rejected());
+        // Ensure the priority fee is zero, as there are no blocks after the genesis block yet.
+        assert_eq!(estimate.priority_fee(), 0);
+        assert_eq!(estimate.total_fee(), estimate.base_fee());
+
+        // Ensure the estimate matches the cost of the proven execution.
+        let execution = vm.execute_authorization_raw(authorization, None, rng).unwrap();
+        let (total_cost, (storage_cost, finalize_cost)) = execution_cost(&vm.process().read(), &execution).unwrap();
+        assert_eq!(estimate.storage_cost(), storage_cost);
+        assert_eq!(estimate.finalize_cost(), finalize_cost);
+        assert_eq!(estimate.base_fee(), total_cost);
+
+        // Ensure a caller without a public balance would be rejected.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let inputs = [
+            Value::<CurrentNetwork>::from_str(&address.to_string()).unwrap(),
+            Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+        ];
+        let authorization = vm.authorize(&private_key, "credits.aleo", "transfer_public", inputs, rng).unwrap();
+        let estimate = vm.estimate_cost(&authorization, Some(state)).unwrap();
+        assert!(estimate.is_rejected());
+        // Ensure finalize is not dry-run if no state is provided.
+        let estimate = vm.estimate_cost(&authorization, None).unwrap();
+        assert!(!estimate.is_rejected());
+
+        // Add a block with transactions that pay different priority fees.
+        let transactions = [300, 100, 200]
+            .into_iter()
+            .map(|priority_fee| {
+                let inputs = [
+                    Value::<CurrentNetwork>::from_str(&address.to_string()).unwrap(),
+                    Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+                ];
+                vm.execute(
+                    &caller_private_key,
+                    ("credits.aleo", "transfer_public"),
+                    inputs.into_iter(),
+                    None,
+                    priority_fee,
+                    None,
+                    rng,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let next_block = crate::test_helpers::sample_next_block(&vm, &caller_private_key, &transactions, rng).unwrap();
+        vm.add_next_block(&next_block).unwrap();
+
+        // Ensure the priority fee is estimated as the median priority fee of the recent transactions.
+        let estimate = vm.estimate_cost(&authorization, None).unwrap();
+        assert_eq!(estimate.priority_fee(), 200);
+        assert_eq!(estimate.total_fee(), estimate.base_fee() + 200);
+    }
+
     #[test]
     fn test_transfer_public_as_signer_transaction_size() {
         let rng = &mut TestRng::default();
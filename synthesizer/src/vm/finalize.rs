@@ -15,8 +15,13 @@
 use super::*;
 
 use ledger_committee::{MAX_DELEGATORS, MIN_DELEGATOR_STAKE, MIN_VALIDATOR_SELF_STAKE};
+use ledger_store::{Journal, JournalEntry};
+use synthesizer_program::StackProgram;
 use utilities::cfg_sort_by_cached_key;
 
+/// The rejection reason (if any) and the changes to the key-value entries, for each confirmed transaction.
+type TransactionDiffs<N> = IndexMap<<N as Network>::TransactionID, (Option<String>, Vec<MappingDiff<N>>)>;
+
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     /// Speculates on the given list of transactions in the VM.
     /// This function aborts all transactions that are not are well-formed or unique.
@@ -163,11 +168,33 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         let timer = timer!("VM::finalize");
 
         // Performs a **real-run** of finalize over the list of ratifications, solutions, and transactions.
-        let ratified_finalize_operations = self.atomic_finalize(state, ratifications, solutions, transactions)?;
+        let (ratified_finalize_operations, _) =
+            self.atomic_finalize(state, ratifications, solutions, transactions, false)?;
 
         finish!(timer, "Finished real-run of finalize");
         Ok(ratified_finalize_operations)
     }
+
+    /// Finalizes the given transactions into the VM.
+    ///
+    /// Returns the finalize operations from pre-ratify and post-ratify,
+    /// and the changes to the key-value entries written to in the block.
+    #[inline]
+    pub(crate) fn finalize_with_diffs(
+        &self,
+        state: FinalizeGlobalState,
+        ratifications: &Ratifications<N>,
+        solutions: &Solutions<N>,
+        transactions: &Transactions<N>,
+    ) -> Result<(Vec<FinalizeOperation<N>>, Vec<MappingDiff<N>>)> {
+        let timer = timer!("VM::finalize_with_diffs");
+
+        // Performs a **real-run** of finalize over the list of ratifications, solutions, and transactions.
+        let outcome = self.atomic_finalize(state, ratifications, solutions, transactions, true)?;
+
+        finish!(timer, "Finished real-run of finalize");
+        Ok(outcome)
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
@@ -201,6 +228,30 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         Vec<ConfirmedTransaction<N>>,
         Vec<(Transaction<N>, String)>,
         Vec<FinalizeOperation<N>>,
+    )> {
+        let (speculation, _) =
+            self.atomic_speculate_with_diffs(state, coinbase_reward, ratifications, solutions, transactions, false)?;
+        Ok(speculation)
+    }
+
+    /// Performs atomic speculation over a list of transactions.
+    ///
+    /// Returns the outcome of `VM::atomic_speculate`, and if `with_diffs` is set, the rejection reason (if any)
+    /// and the changes to the key-value entries of each confirmed transaction, in the order they were first written to.
+    ///
+    /// Note: This method is used by `VM::atomic_speculate` and `VM::simulate_transactions`.
+    #[allow(clippy::type_complexity)]
+    fn atomic_speculate_with_diffs<'a>(
+        &self,
+        state: FinalizeGlobalState,
+        coinbase_reward: Option<u64>,
+        ratifications: Vec<Ratify<N>>,
+        solutions: &Solutions<N>,
+        transactions: impl ExactSizeIterator<Item = &'a Transaction<N>>,
+        with_diffs: bool,
+    ) -> Result<(
+        (Ratifications<N>, Vec<ConfirmedTransaction<N>>, Vec<(Transaction<N>, String)>, Vec<FinalizeOperation<N>>),
+        TransactionDiffs<N>,
     )> {
         // Acquire the atomic lock, which is needed to ensure this function is not called concurrently
         // with other `atomic_finalize!` macro calls, which will cause a `bail!` to be triggered erroneously.
@@ -215,7 +266,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         let num_transactions = transactions.len();
 
         // Perform the finalize operation on the preset finalize mode.
-        atomic_finalize!(self.finalize_store(), FinalizeMode::DryRun, {
+        let outcome = atomic_finalize!(self.finalize_store(), FinalizeMode::DryRun, {
             // Ensure the number of solutions does not exceed the maximum.
             if num_solutions > Solutions::<N>::MAX_ABORTED_SOLUTIONS {
                 // Note: This will abort the entire atomic batch.
@@ -285,6 +336,8 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             let mut tpks: IndexSet<Group<N>> = IndexSet::new();
             // Initialize the list of deployment payers.
             let mut deployment_payers: IndexSet<Address<N>> = IndexSet::new();
+            // Initialize the rejection reasons and changes to the key-value entries of the confirmed transactions.
+            let mut transaction_diffs = TransactionDiffs::<N>::new();
 
             // Finalize the transactions.
             'outer: for transaction in transactions {
@@ -312,6 +365,13 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     continue 'outer;
                 }
 
+                // If requested, journal the key-value entries written to by the transaction.
+                if with_diffs {
+                    store.start_journal();
+                }
+                // Initialize the reason the transaction is rejected, if any.
+                let mut rejection = None;
+
                 // Process the transaction in an isolated atomic batch.
                 // - If the transaction succeeds, the finalize operations are stored.
                 // - If the transaction fails, the atomic batch is aborted and no finalize operations are stored.
@@ -320,10 +380,16 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     // and adding the program to the finalize tree.
                     Transaction::Deploy(_, program_owner, deployment, fee) => {
                         // Define the closure for processing a rejected deployment.
-                        let process_rejected_deployment =
+                        let mut process_rejected_deployment =
                             |fee: &Fee<N>,
-                             deployment: Deployment<N>|
+                             deployment: Deployment<N>,
+                             reason: String|
                              -> Result<Result<ConfirmedTransaction<N>, String>> {
+                                // Store the rejection reason, and restart the journal, as only the fee is finalized.
+                                rejection = Some(reason);
+                                if with_diffs {
+                                    store.start_journal();
+                                }
                                 process
                                     .finalize_fee(state, store, fee)
                                     .and_then(|finalize| {
@@ -339,7 +405,11 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                         // Check if the program has already been deployed in this block.
                         match deployments.contains(deployment.program_id()) {
                             // If the program has already been deployed, construct the rejected deploy transaction.
-                            true => match process_rejected_deployment(fee, *deployment.clone()) {
+                            true => match process_rejected_deployment(
+                                fee,
+                                *deployment.clone(),
+                                format!("Program '{}' is already deployed in this block", deployment.program_id()),
+                            ) {
                                 Ok(result) => result,
                                 Err(error) => {
                                     // Note: On failure, skip this transaction, and continue speculation.
@@ -361,18 +431,20 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                                         .map_err(|e| e.to_string())
                                 }
                                 // Construct the rejected deploy transaction.
-                                Err(_error) => match process_rejected_deployment(fee, *deployment.clone()) {
-                                    Ok(result) => result,
-                                    Err(error) => {
-                                        // Note: On failure, skip this transaction, and continue speculation.
-                                        #[cfg(debug_assertions)]
-                                        eprintln!("Failed to finalize the fee in a rejected deploy - {error}");
-                                        // Store the aborted transaction.
-                                        aborted.push((transaction.clone(), error.to_string()));
-                                        // Continue to the next transaction.
-                                        continue 'outer;
+                                Err(error) => {
+                                    match process_rejected_deployment(fee, *deployment.clone(), error.to_string()) {
+                                        Ok(result) => result,
+                                        Err(error) => {
+                                            // Note: On failure, skip this transaction, and continue speculation.
+                                            #[cfg(debug_assertions)]
+                                            eprintln!("Failed to finalize the fee in a rejected deploy - {error}");
+                                            // Store the aborted transaction.
+                                            aborted.push((transaction.clone(), error.to_string()));
+                                            // Continue to the next transaction.
+                                            continue 'outer;
+                                        }
                                     }
-                                },
+                                }
                             },
                         }
                     }
@@ -389,9 +461,14 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                                     .map_err(|e| e.to_string())
                             }
                             // Construct the rejected execute transaction.
-                            Err(_error) => match fee {
+                            Err(error) => match fee {
                                 // Finalize the fee, to ensure it is valid.
                                 Some(fee) => {
+                                    rejection = Some(error.to_string());
+                                    // Restart the journal, as only the fee is finalized.
+                                    if with_diffs {
+                                        store.start_journal();
+                                    }
                                     match process.finalize_fee(state, store, fee).and_then(|finalize| {
                                         Transaction::from_fee(fee.clone()).map(|fee_tx| (fee_tx, finalize))
                                     }) {
@@ -425,9 +502,17 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                 };
                 lap!(timer, "Speculated on transaction '{}'", transaction.id());
 
+                // Stop journaling, and retrieve the original values of the key-value entries.
+                let journal = store.finish_journal();
+
                 match outcome {
                     // If the transaction succeeded, store it and continue to the next transaction.
                     Ok(confirmed_transaction) => {
+                        // If requested, store the rejection reason and the changes to the key-value entries.
+                        if with_diffs {
+                            let diffs = Self::journal_to_diffs(store, &journal)?;
+                            transaction_diffs.insert(transaction.id(), (rejection, diffs));
+                        }
                         // Add the transition IDs to the set of produced transition IDs.
                         transition_ids.extend(confirmed_transaction.transaction().transition_ids());
                         // Add the input IDs to the set of spent input IDs.
@@ -515,13 +600,19 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
 
             // On return, 'atomic_finalize!' will abort the batch, and return the ratifications,
             // confirmed & aborted transactions, and finalize operations from pre-ratify and post-ratify.
-            Ok((ratifications, confirmed, aborted, ratified_finalize_operations))
-        })
+            Ok(((ratifications, confirmed, aborted, ratified_finalize_operations), transaction_diffs))
+        });
+
+        // Stop the journal, in case the atomic batch was aborted while journaling.
+        self.finalize_store().finish_journal();
+
+        outcome
     }
 
     /// Performs atomic finalization over a list of transactions.
     ///
-    /// Returns the finalize operations from pre-ratify and post-ratify.
+    /// Returns the finalize operations from pre-ratify and post-ratify, and if `with_diffs` is set,
+    /// the changes to the key-value entries written to in the block, in the order they were first written to.
     #[inline]
     fn atomic_finalize(
         &self,
@@ -529,7 +620,8 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         ratifications: &Ratifications<N>,
         solutions: &Solutions<N>,
         transactions: &Transactions<N>,
-    ) -> Result<Vec<FinalizeOperation<N>>> {
+        with_diffs: bool,
+    ) -> Result<(Vec<FinalizeOperation<N>>, Vec<MappingDiff<N>>)> {
         // Acquire the atomic lock, which is needed to ensure this function is not called concurrently
         // with other `atomic_finalize!` macro calls, which will cause a `bail!` to be triggered erroneously.
         // Note: This lock must be held for the entire scope of the call to `atomic_finalize!`.
@@ -538,7 +630,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         let timer = timer!("VM::atomic_finalize");
 
         // Perform the finalize operation on the preset finalize mode.
-        atomic_finalize!(self.finalize_store(), FinalizeMode::RealRun, {
+        let outcome = atomic_finalize!(self.finalize_store(), FinalizeMode::RealRun, {
             // Initialize an iterator for ratifications before finalize.
             let pre_ratifications = ratifications.iter().filter(|r| match r {
                 Ratify::Genesis(_, _, _) => true,
@@ -556,6 +648,17 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             // Retrieve the finalize store.
             let store = self.finalize_store();
 
+            // If the finalize history is enabled, journal the original values of the entries written to in this block.
+            let is_history_enabled = match store.history_start_height() {
+                Ok(start_height) => start_height.is_some(),
+                // Note: This will abort the entire atomic batch.
+                Err(e) => return Err(format!("Failed to retrieve the finalize history - {e}")),
+            };
+            // Likewise, journal the original values to compute the changes to the entries, if requested.
+            if is_history_enabled || with_diffs {
+                store.start_journal();
+            }
+
             /* Perform the ratifications before finalize. */
 
             match Self::atomic_pre_ratify(store, state, pre_ratifications) {
@@ -746,17 +849,68 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                 Err(e) => return Err(format!("Failed to post-ratify - {e}")),
             }
 
+            // Stop journaling, and retrieve the original values of the entries written to in this block.
+            let journal = store.finish_journal();
+
+            // If requested, compute the changes to the key-value entries.
+            let diffs = match with_diffs {
+                true => Self::journal_to_diffs(store, &journal)?,
+                false => Vec::new(),
+            };
+
+            // If the finalize history is enabled, store the original values of the entries written to in this block.
+            if is_history_enabled {
+                if let Err(e) = store.insert_history(state.block_height(), journal) {
+                    // Note: This will abort the entire atomic batch.
+                    return Err(format!("Failed to store the finalize history - {e}"));
+                }
+            }
+
             /* Start the commit process. */
 
             // Commit all of the stacks to the process.
-            if !stacks.is_empty() {
-                stacks.into_iter().for_each(|stack| process.add_stack(stack))
+            for stack in stacks {
+                // If the program already exists, then upgrade it. Otherwise, add it.
+                match process.contains_program(stack.program_id()) {
+                    // Note: This will abort the entire atomic batch.
+                    true => process.upgrade_stack(stack).map_err(|e| format!("Failed to upgrade a program - {e}"))?,
+                    false => process.add_stack(stack),
+                }
             }
 
             finish!(timer); // <- Note: This timer does **not** include the time to write batch to DB.
 
-            Ok(ratified_finalize_operations)
-        })
+            Ok((ratified_finalize_operations, diffs))
+        });
+
+        // Stop the journal, in case the atomic batch was aborted while journaling.
+        self.finalize_store().finish_journal();
+
+        outcome
+    }
+
+    /// Returns the changes to the key-value entries in the given journal, skipping the unchanged entries.
+    fn journal_to_diffs(
+        store: &FinalizeStore<N, C::FinalizeStorage>,
+        journal: &Journal<N>,
+    ) -> Result<Vec<MappingDiff<N>>, String> {
+        let mut diffs = Vec::with_capacity(journal.len());
+        for entry in journal.values() {
+            // Skip the entries that are not key-value entries.
+            let JournalEntry::KeyValue(program_id, mapping_name, key, original) = entry else {
+                continue;
+            };
+            // Retrieve the original value, and the new value.
+            let old_value = original.as_ref().map(|(_, value)| value.clone());
+            let new_value = store
+                .get_value_speculative(*program_id, *mapping_name, key)
+                .map_err(|e| format!("Failed to retrieve a finalized value - {e}"))?;
+            // Skip the key-value entry, if it is unchanged.
+            if old_value != new_value {
+                diffs.push(MappingDiff::new(*program_id, *mapping_name, key.clone(), old_value, new_value));
+            }
+        }
+        Ok(diffs)
     }
 
     /// Returns `Some(reason)` if the transaction is aborted. Otherwise, returns `None`.
@@ -825,6 +979,106 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         None
     }
 
+    /// Performs a **dry-run** of finalize on the (unproven) execution for the given authorization.
+    ///
+    /// Returns the reason the execution would be rejected, or `None` if it would be accepted.
+    /// Note: The fee is not finalized, and the finalize store is left unchanged.
+    pub(crate) fn atomic_dry_run_authorization(
+        &self,
+        state: FinalizeGlobalState,
+        authorization: &Authorization<N>,
+    ) -> Result<Option<String>> {
+        // Construct the execution from the authorized transitions.
+        let execution =
+            Execution::from(authorization.transitions().into_values(), self.block_store().current_state_root(), None)?;
+
+        // Acquire the atomic lock, which is needed to ensure this function is not called concurrently
+        // with other `atomic_finalize!` macro calls, which will cause a `bail!` to be triggered erroneously.
+        // Note: This lock must be held for the entire scope of the call to `atomic_finalize!`.
+        let _atomic_lock = self.atomic_lock.lock();
+
+        // Perform the finalize operation as a dry run, which aborts the atomic batch on return.
+        atomic_finalize!(self.finalize_store(), FinalizeMode::DryRun, {
+            // Retrieve the finalize store.
+            let store = self.finalize_store();
+            // Acquire the read lock on the process.
+            let process = self.process.read();
+            // Finalize the execution, and return the rejection reason (if any).
+            match Self::prepare_for_execution(store, &execution)
+                .and_then(|_| process.finalize_execution(state, store, &execution, None))
+            {
+                Ok(_) => Ok(None),
+                Err(error) => Ok(Some(error.to_string())),
+            }
+        })
+    }
+
+    /// Performs a **dry-run** of finalize on the given transactions, in order, and returns the simulated result of each.
+    ///
+    /// The transactions are speculated on as in `VM::speculate`, without ratifications or solutions, so each transaction
+    /// is finalized on top of the state left by the transactions before it, and is aborted under the same rules.
+    /// The simulated result of each transaction records its status, the ordered finalize operations,
+    /// and the changes to the key-value entries it wrote to.
+    /// Note: The transactions are **not** verified, and the finalize store is left unchanged.
+    pub fn simulate_transactions<'a>(
+        &self,
+        state: FinalizeGlobalState,
+        transactions: impl ExactSizeIterator<Item = &'a Transaction<N>>,
+    ) -> Result<Vec<SimulatedTransaction<N>>> {
+        let timer = timer!("VM::simulate_transactions");
+
+        // Separate the fee transactions, which cannot be speculated on.
+        // Note: A fee transaction is only ever constructed from a rejected transaction.
+        let transactions = transactions.collect::<Vec<_>>();
+        let candidate_transactions = transactions.iter().copied().filter(|transaction| !transaction.is_fee());
+
+        // Perform a dry-run of finalize over the transactions.
+        let ((_, confirmed, aborted, _), mut transaction_diffs) = self.atomic_speculate_with_diffs(
+            state,
+            None,
+            vec![],
+            &None.into(),
+            candidate_transactions.collect::<Vec<_>>().into_iter(),
+            true,
+        )?;
+        lap!(timer, "Speculated on the transactions");
+
+        // Index the confirmed and aborted transactions by their ID.
+        let mut confirmed = confirmed
+            .into_iter()
+            .map(|confirmed| Ok((confirmed.to_unconfirmed_transaction_id()?, confirmed)))
+            .collect::<Result<IndexMap<_, _>>>()?;
+        let mut aborted: IndexMap<_, _> =
+            aborted.into_iter().map(|(transaction, reason)| (transaction.id(), reason)).collect();
+
+        // Construct the simulated result of each transaction, in order.
+        let simulations = transactions
+            .into_iter()
+            .map(|transaction| {
+                let transaction_id = transaction.id();
+                if let Some(confirmed) = confirmed.swap_remove(&transaction_id) {
+                    let (rejection, diffs) = transaction_diffs.swap_remove(&transaction_id).unwrap_or_default();
+                    let status = match rejection {
+                        Some(reason) => SimulationStatus::Rejected(reason),
+                        None => SimulationStatus::Accepted,
+                    };
+                    let finalize_operations = confirmed.finalize_operations().to_vec();
+                    SimulatedTransaction::new(transaction_id, status, diffs, finalize_operations)
+                } else {
+                    let reason = match aborted.swap_remove(&transaction_id) {
+                        Some(reason) => reason,
+                        None => "Cannot simulate a fee transaction".to_string(),
+                    };
+                    SimulatedTransaction::new(transaction_id, SimulationStatus::Aborted(reason), vec![], vec![])
+                }
+            })
+            .collect();
+
+        finish!(timer);
+
+        Ok(simulations)
+    }
+
     /// Performs precondition checks on the transactions prior to speculation.
     ///
     /// This method is used to check the following conditions:
@@ -2265,6 +2519,72 @@ finalize compute:
         assert_eq!(value, expected);
     }
 
+    #[test]
+    fn test_simulate_transactions() {
+        let rng = &mut TestRng::default();
+
+        // Sample a private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let address = Address::try_from(&private_key).unwrap();
+        // Sample a recipient.
+        let recipient = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+        // Initialize the vm.
+        let vm = test_helpers::sample_vm_with_genesis_block(rng);
+
+        // Prepare the mapping keys.
+        let program_id = ProgramID::from_str("credits.aleo").unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+        let caller_key = Plaintext::from(Literal::Address(address));
+        let recipient_key = Plaintext::from(Literal::Address(recipient));
+        // Retrieve the caller's public balance.
+        let balance = vm.finalize_store().get_value_speculative(program_id, mapping_name, &caller_key).unwrap();
+
+        // Create an execution transaction, that will be accepted.
+        let inputs = [Value::<CurrentNetwork>::from(Literal::Address(recipient)), Value::from_str("1u64").unwrap()];
+        let accepted =
+            vm.execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng).unwrap();
+        // Create an execution transaction that exceeds the caller's balance, that will be rejected.
+        let inputs =
+            [Value::<CurrentNetwork>::from(Literal::Address(recipient)), Value::from(Literal::U64(U64::new(u64::MAX)))];
+        let rejected =
+            vm.execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng).unwrap();
+
+        // Simulate the transactions.
+        let simulations =
+            vm.simulate_transactions(sample_finalize_state(1), [&accepted, &rejected].into_iter()).unwrap();
+        assert_eq!(simulations.len(), 2);
+
+        // Check the accepted transaction.
+        let simulation = &simulations[0];
+        assert_eq!(simulation.transaction_id(), &accepted.id());
+        assert!(simulation.is_accepted());
+        assert!(simulation.reason().is_none());
+        assert!(!simulation.finalize_operations().is_empty());
+        // Ensure the diff includes the caller and the recipient, in the order they were written to.
+        let diffs = simulation.diffs();
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].key(), &caller_key);
+        assert_eq!(diffs[0].old_value(), balance.as_ref());
+        assert_eq!(diffs[1].key(), &recipient_key);
+        assert_eq!(diffs[1].old_value(), None);
+        assert_eq!(diffs[1].new_value(), Some(&Value::from_str("1u64").unwrap()));
+
+        // Check the rejected transaction.
+        let simulation = &simulations[1];
+        assert_eq!(simulation.transaction_id(), &rejected.id());
+        assert!(matches!(simulation.status(), SimulationStatus::Rejected(_)));
+        // Ensure only the fee is reflected in the diff, on top of the accepted transaction.
+        let diffs = simulation.diffs();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].key(), &caller_key);
+        assert_eq!(diffs[0].old_value(), simulations[0].diffs()[0].new_value());
+
+        // Ensure the finalize store was not updated.
+        assert_eq!(vm.finalize_store().get_value_speculative(program_id, mapping_name, &caller_key).unwrap(), balance);
+        assert!(!vm.finalize_store().contains_key_speculative(program_id, mapping_name, &recipient_key).unwrap());
+    }
+
     #[test]
     fn test_excess_transactions_should_be_aborted() {
         let rng = &mut TestRng::default();
@@ -0,0 +1,71 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The number of most recent blocks from which the priority fee is estimated.
+pub const PRIORITY_FEE_ESTIMATE_NUM_BLOCKS: u32 = 10;
+
+/// An estimate of the fees (in microcredits) required to publish an execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CostEstimate {
+    /// The estimated storage cost in microcredits.
+    storage_cost: u64,
+    /// The finalize cost in microcredits.
+    finalize_cost: u64,
+    /// The estimated priority fee in microcredits.
+    priority_fee: u64,
+    /// The reason the execution would be rejected, if finalize was dry-run and failed.
+    rejection: Option<String>,
+}
+
+impl CostEstimate {
+    /// Initializes a new cost estimate.
+    pub const fn new(storage_cost: u64, finalize_cost: u64, priority_fee: u64, rejection: Option<String>) -> Self {
+        Self { storage_cost, finalize_cost, priority_fee, rejection }
+    }
+
+    /// Returns the estimated storage cost in microcredits.
+    pub const fn storage_cost(&self) -> u64 {
+        self.storage_cost
+    }
+
+    /// Returns the finalize cost in microcredits.
+    pub const fn finalize_cost(&self) -> u64 {
+        self.finalize_cost
+    }
+
+    /// Returns the estimated base fee in microcredits, as the sum of the storage and finalize costs.
+    pub const fn base_fee(&self) -> u64 {
+        self.storage_cost.saturating_add(self.finalize_cost)
+    }
+
+    /// Returns the estimated priority fee in microcredits.
+    pub const fn priority_fee(&self) -> u64 {
+        self.priority_fee
+    }
+
+    /// Returns the estimated total fee in microcredits, as the sum of the base fee and the priority fee.
+    pub const fn total_fee(&self) -> u64 {
+        self.base_fee().saturating_add(self.priority_fee)
+    }
+
+    /// Returns the reason the execution would be rejected, if finalize was dry-run and failed.
+    pub fn rejection(&self) -> Option<&str> {
+        self.rejection.as_deref()
+    }
+
+    /// Returns `true` if finalize was dry-run and the execution would be rejected.
+    pub const fn is_rejected(&self) -> bool {
+        self.rejection.is_some()
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::MappingDiff;
+use console::{
+    account::{Address, ViewKey},
+    network::prelude::*,
+    program::{Plaintext, Record},
+    types::Field,
+};
+use ledger_block::Block;
+use ledger_committee::Committee;
+
+use parking_lot::{Mutex, RwLock};
+use std::sync::{
+    mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+    Arc,
+};
+
+/// The maximum number of events that are buffered for a subscriber, before it is dropped.
+pub const MAX_BUFFERED_EVENTS: usize = 1 << 16;
+
+/// An event that is published when a block is added to the VM.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event<N: Network> {
+    /// A block was added, as (`height`, `block hash`).
+    BlockAdded(u32, N::BlockHash),
+    /// A transaction was accepted, as (`height`, `transaction ID`).
+    TransactionConfirmed(u32, N::TransactionID),
+    /// A transaction was rejected, as (`height`, `fee transaction ID`, `unconfirmed transaction ID`).
+    TransactionRejected(u32, N::TransactionID, N::TransactionID),
+    /// A transaction was aborted, as (`height`, `transaction ID`).
+    TransactionAborted(u32, N::TransactionID),
+    /// A key-value entry was written to in the block, as (`height`, `mapping diff`).
+    MappingUpdated(u32, MappingDiff<N>),
+    /// A record was created for a registered view key, as (`height`, `owner`, `transition ID`, `commitment`, `record`).
+    RecordCreated(u32, Address<N>, N::TransitionID, Field<N>, Record<N, Plaintext<N>>),
+    /// The members of the committee, or their stakes, changed, as (`height`, `committee`).
+    CommitteeChanged(u32, Committee<N>),
+}
+
+impl<N: Network> Event<N> {
+    /// Returns the height of the block that the event belongs to.
+    pub const fn height(&self) -> u32 {
+        match self {
+            Self::BlockAdded(height, ..)
+            | Self::TransactionConfirmed(height, ..)
+            | Self::TransactionRejected(height, ..)
+            | Self::TransactionAborted(height, ..)
+            | Self::MappingUpdated(height, ..)
+            | Self::RecordCreated(height, ..)
+            | Self::CommitteeChanged(height, ..) => *height,
+        }
+    }
+}
+
+/// The publisher of the events of the VM, to its subscribers.
+///
+/// The events of a block are published once the block is committed to storage, in the order:
+/// the block, its transactions, its mapping updates, its records, and the committee change (if any).
+///
+/// Each subscriber buffers up to `MAX_BUFFERED_EVENTS` events. A subscriber that falls behind by more
+/// than that is dropped, which ends its subscription, so that publishing never blocks the VM.
+#[derive(Clone)]
+pub struct EventPublisher<N: Network> {
+    /// The senders for the subscribers.
+    senders: Arc<Mutex<Vec<SyncSender<Event<N>>>>>,
+    /// The registered view keys, along with their addresses.
+    view_keys: Arc<RwLock<Vec<(ViewKey<N>, Address<N>)>>>,
+}
+
+impl<N: Network> Default for EventPublisher<N> {
+    /// Initializes a new event publisher, without subscribers.
+    fn default() -> Self {
+        Self { senders: Default::default(), view_keys: Default::default() }
+    }
+}
+
+impl<N: Network> EventPublisher<N> {
+    /// Returns a receiver for the events that are published from now on.
+    /// The subscription ends when the receiver is dropped, or when it falls behind by more than `MAX_BUFFERED_EVENTS`.
+    pub fn subscribe(&self) -> Receiver<Event<N>> {
+        let (sender, receiver) = sync_channel(MAX_BUFFERED_EVENTS);
+        self.senders.lock().push(sender);
+        receiver
+    }
+
+    /// Returns `true` if there is at least one subscriber.
+    pub fn has_subscribers(&self) -> bool {
+        !self.senders.lock().is_empty()
+    }
+
+    /// Registers the given view key, so that a `RecordCreated` event is published for each record it owns.
+    pub fn register_view_key(&self, view_key: ViewKey<N>) {
+        let address = view_key.to_address();
+        let mut view_keys = self.view_keys.write();
+        if !view_keys.iter().any(|(_, candidate)| *candidate == address) {
+            view_keys.push((view_key, address));
+        }
+    }
+
+    /// Unregisters the given view key. Returns `true` if the view key was registered.
+    pub fn unregister_view_key(&self, view_key: &ViewKey<N>) -> bool {
+        let address = view_key.to_address();
+        let mut view_keys = self.view_keys.write();
+        let num_view_keys = view_keys.len();
+        view_keys.retain(|(_, candidate)| *candidate != address);
+        view_keys.len() != num_view_keys
+    }
+
+    /// Sends the given events to each subscriber, and removes the subscribers whose receiver was dropped,
+    /// or whose buffer of events is full.
+    pub(crate) fn publish(&self, events: Vec<Event<N>>) {
+        self.senders.lock().retain(|sender| {
+            events.iter().all(|event| match sender.try_send(event.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    warn!("Dropped an event subscriber, as it has more than {MAX_BUFFERED_EVENTS} unread events");
+                    false
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            })
+        });
+    }
+
+    /// Returns the events for the given block, using the changes to the key-value entries written to in the block,
+    /// and the committee before and after the block.
+    pub(crate) fn block_events(
+        &self,
+        block: &Block<N>,
+        diffs: Vec<MappingDiff<N>>,
+        previous_committee: Option<Committee<N>>,
+        next_committee: Option<Committee<N>>,
+    ) -> Result<Vec<Event<N>>> {
+        let height = block.height();
+
+        // Add the block.
+        let mut events = vec![Event::BlockAdded(height, block.hash())];
+        // Add the transactions.
+        for transaction in block.transactions().iter() {
+            match transaction.is_accepted() {
+                true => events.push(Event::TransactionConfirmed(height, transaction.id())),
+                false => events.push(Event::TransactionRejected(
+                    height,
+                    transaction.id(),
+                    transaction.to_unconfirmed_transaction_id()?,
+                )),
+            }
+        }
+        for transaction_id in block.aborted_transaction_ids() {
+            events.push(Event::TransactionAborted(height, *transaction_id));
+        }
+        // Add the mapping updates.
+        events.extend(diffs.into_iter().map(|diff| Event::MappingUpdated(height, diff)));
+        // Add the records of the registered view keys.
+        for (view_key, address) in self.view_keys.read().iter() {
+            let address_x_coordinate = address.to_x_coordinate();
+            for transition in block.transitions() {
+                for (commitment, record) in transition.records() {
+                    if record.is_owner_with_address_x_coordinate(view_key, &address_x_coordinate) {
+                        let record = record.decrypt(view_key)?;
+                        events.push(Event::RecordCreated(height, *address, *transition.id(), *commitment, record));
+                    }
+                }
+            }
+        }
+        // Add the committee, if its members or their stakes changed.
+        if let Some(next_committee) = next_committee {
+            let is_changed = match &previous_committee {
+                Some(previous_committee) => previous_committee.members() != next_committee.members(),
+                None => true,
+            };
+            if is_changed {
+                events.push(Event::CommitteeChanged(height, next_committee));
+            }
+        }
+        Ok(events)
+    }
+}
@@ -15,6 +15,12 @@
 pub(crate) mod committee;
 pub use committee::*;
 
+mod cost_estimate;
+pub use cost_estimate::*;
+
+mod events;
+pub use events::*;
+
 #[cfg(feature = "history")]
 mod history;
 #[cfg(feature = "history")]
@@ -24,3 +30,6 @@ mod macros;
 
 mod rewards;
 pub use rewards::*;
+
+mod simulation;
+pub use simulation::*;
@@ -0,0 +1,141 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    network::Network,
+    program::{Identifier, Plaintext, ProgramID, Value},
+};
+use synthesizer_program::FinalizeOperation;
+
+/// A change to a key-value entry in a mapping, from simulating finalize.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MappingDiff<N: Network> {
+    /// The program ID.
+    program_id: ProgramID<N>,
+    /// The mapping name.
+    mapping_name: Identifier<N>,
+    /// The key.
+    key: Plaintext<N>,
+    /// The value before the transaction was finalized, if the key existed.
+    old_value: Option<Value<N>>,
+    /// The value after the transaction was finalized, if the key exists.
+    new_value: Option<Value<N>>,
+}
+
+impl<N: Network> MappingDiff<N> {
+    /// Initializes a new mapping diff.
+    pub const fn new(
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: Plaintext<N>,
+        old_value: Option<Value<N>>,
+        new_value: Option<Value<N>>,
+    ) -> Self {
+        Self { program_id, mapping_name, key, old_value, new_value }
+    }
+
+    /// Returns the program ID.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the mapping name.
+    pub const fn mapping_name(&self) -> &Identifier<N> {
+        &self.mapping_name
+    }
+
+    /// Returns the key.
+    pub const fn key(&self) -> &Plaintext<N> {
+        &self.key
+    }
+
+    /// Returns the value before the transaction was finalized, if the key existed.
+    pub const fn old_value(&self) -> Option<&Value<N>> {
+        self.old_value.as_ref()
+    }
+
+    /// Returns the value after the transaction was finalized, if the key exists.
+    pub const fn new_value(&self) -> Option<&Value<N>> {
+        self.new_value.as_ref()
+    }
+}
+
+/// The status of a transaction, from simulating finalize.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SimulationStatus {
+    /// The transaction would be accepted.
+    Accepted,
+    /// The transaction would be rejected for the given reason, and only its fee would be finalized.
+    Rejected(String),
+    /// The transaction would be aborted for the given reason.
+    Aborted(String),
+}
+
+/// The result of simulating finalize on a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulatedTransaction<N: Network> {
+    /// The transaction ID.
+    transaction_id: N::TransactionID,
+    /// The status of the transaction.
+    status: SimulationStatus,
+    /// The changes to the key-value entries, in the order they were first written to.
+    diffs: Vec<MappingDiff<N>>,
+    /// The finalize operations, in the order they were performed.
+    finalize_operations: Vec<FinalizeOperation<N>>,
+}
+
+impl<N: Network> SimulatedTransaction<N> {
+    /// Initializes a new simulated transaction.
+    pub const fn new(
+        transaction_id: N::TransactionID,
+        status: SimulationStatus,
+        diffs: Vec<MappingDiff<N>>,
+        finalize_operations: Vec<FinalizeOperation<N>>,
+    ) -> Self {
+        Self { transaction_id, status, diffs, finalize_operations }
+    }
+
+    /// Returns the transaction ID.
+    pub const fn transaction_id(&self) -> &N::TransactionID {
+        &self.transaction_id
+    }
+
+    /// Returns the status of the transaction.
+    pub const fn status(&self) -> &SimulationStatus {
+        &self.status
+    }
+
+    /// Returns `true` if the transaction would be accepted.
+    pub const fn is_accepted(&self) -> bool {
+        matches!(self.status, SimulationStatus::Accepted)
+    }
+
+    /// Returns the reason the transaction would be rejected or aborted, if any.
+    pub fn reason(&self) -> Option<&str> {
+        match &self.status {
+            SimulationStatus::Accepted => None,
+            SimulationStatus::Rejected(reason) | SimulationStatus::Aborted(reason) => Some(reason),
+        }
+    }
+
+    /// Returns the changes to the key-value entries, in the order they were first written to.
+    pub fn diffs(&self) -> &[MappingDiff<N>] {
+        &self.diffs
+    }
+
+    /// Returns the finalize operations, in the order they were performed.
+    pub fn finalize_operations(&self) -> &[FinalizeOperation<N>] {
+        &self.finalize_operations
+    }
+}
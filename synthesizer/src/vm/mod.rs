@@ -25,7 +25,7 @@ use crate::{cast_mut_ref, cast_ref, convert, process, Restrictions};
 use console::{
     account::{Address, PrivateKey},
     network::prelude::*,
-    program::{Argument, Identifier, Literal, Locator, Plaintext, ProgramID, ProgramOwner, Record, Value},
+    program::{Argument, Identifier, Literal, Locator, Plaintext, ProgramID, ProgramOwner, Record, UpgradePolicy, Value},
     types::{Field, Group, U64},
 };
 use ledger_block::{
@@ -58,8 +58,16 @@ use ledger_store::{
     TransactionStore,
     TransitionStore,
 };
-use synthesizer_process::{deployment_cost, execution_cost, Authorization, Process, Trace};
+use synthesizer_process::{
+    deployment_cost,
+    execution_cost,
+    execution_cost_from_authorization,
+    Authorization,
+    Process,
+    Trace,
+};
 use synthesizer_program::{FinalizeGlobalState, FinalizeOperation, FinalizeStoreTrait, Program};
+use synthesizer_snark::ProofAccumulator;
 use utilities::try_vm_runtime;
 
 use aleo_std::prelude::{finish, lap, timer};
@@ -89,6 +97,8 @@ pub struct VM<N: Network, C: ConsensusStorage<N>> {
     atomic_lock: Arc<Mutex<()>>,
     /// The lock for ensuring there is no concurrency when advancing blocks.
     block_lock: Arc<Mutex<()>>,
+    /// The publisher of the events from advancing blocks.
+    events: EventPublisher<N>,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
@@ -114,6 +124,25 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             transaction_store: &TransactionStore<N, T>,
             transaction_id: N::TransactionID,
         ) -> Result<Vec<(ProgramID<N>, Deployment<N>)>> {
+            // Retrieve the program ID from the transaction ID.
+            let program_id = match transaction_store.deployment_store().get_program_id(&transaction_id)? {
+                Some(program_id) => program_id,
+                None => bail!("Deployment transaction '{transaction_id}' is not found in storage."),
+            };
+
+            // Return early if the program is already loaded.
+            if process.contains_program(&program_id) {
+                return Ok(vec![]);
+            }
+
+            // Return early if the deployment is not for the latest edition of the program.
+            // Note: The verifying keys of superseded editions may have been pruned.
+            if transaction_store.deployment_store().find_transaction_id_from_program_id(&program_id)?
+                != Some(transaction_id)
+            {
+                return Ok(vec![]);
+            }
+
             // Retrieve the deployment from the transaction ID.
             let deployment = match transaction_store.get_deployment(&transaction_id)? {
                 Some(deployment) => deployment,
@@ -122,12 +151,6 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
 
             // Fetch the program from the deployment.
             let program = deployment.program();
-            let program_id = program.id();
-
-            // Return early if the program is already loaded.
-            if process.contains_program(program_id) {
-                return Ok(vec![]);
-            }
 
             // Prepare a vector for the deployments.
             let mut deployments = vec![];
@@ -153,7 +176,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             }
 
             // Once all the imports have been included, add the parent deployment.
-            deployments.push((*program_id, deployment));
+            deployments.push((program_id, deployment));
 
             Ok(deployments)
         }
@@ -196,6 +219,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             restrictions: Restrictions::load()?,
             atomic_lock: Arc::new(Mutex::new(())),
             block_lock: Arc::new(Mutex::new(())),
+            events: Default::default(),
         })
     }
 
@@ -228,6 +252,12 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     pub const fn restrictions(&self) -> &Restrictions<N> {
         &self.restrictions
     }
+
+    /// Returns the publisher of the events from advancing blocks.
+    #[inline]
+    pub const fn events(&self) -> &EventPublisher<N> {
+        &self.events
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
@@ -254,6 +284,36 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     pub fn transition_store(&self) -> &TransitionStore<N, C::TransitionStorage> {
         self.store.transition_store()
     }
+
+    /// Enables or disables the finalize history, which allows mapping values to be queried as of any block.
+    /// If enabled, the history starts from the next block. If disabled, the history is removed from storage.
+    pub fn set_finalize_history(&self, enabled: bool) -> Result<()> {
+        // Acquire the atomic lock, to ensure no block is being finalized.
+        let _atomic_lock = self.atomic_lock.lock();
+        match enabled {
+            true => {
+                // Determine the height of the next block.
+                let next_height = self.block_store().heights().max().map_or(0, |height| *height + 1);
+                self.finalize_store().enable_history(next_height)
+            }
+            false => self.finalize_store().disable_history(),
+        }
+    }
+
+    /// Enables or disables the transition index, which allows transitions to be found by function and by address.
+    /// If enabled, the index starts from the next block. If disabled, the index is removed from storage.
+    pub fn set_transition_index(&self, enabled: bool) -> Result<()> {
+        // Acquire the atomic lock, to ensure no block is being added.
+        let _atomic_lock = self.atomic_lock.lock();
+        match enabled {
+            true => {
+                // Determine the height of the next block.
+                let next_height = self.block_store().heights().max().map_or(0, |height| *height + 1);
+                self.block_store().enable_transition_index(next_height)
+            }
+            false => self.block_store().disable_transition_index(),
+        }
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
@@ -406,13 +466,20 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             block.previous_hash(),
         )?;
 
+        // Determine whether to publish the events of the block, and retrieve the current committee if so.
+        let with_events = self.events.has_subscribers();
+        let previous_committee = match with_events {
+            true => self.finalize_store().committee_store().current_committee().ok(),
+            false => None,
+        };
+
         // Pause the atomic writes, so that both the insertion and finalization belong to a single batch.
-        #[cfg(feature = "rocks")]
+        #[cfg(any(feature = "rocks", feature = "kv"))]
         self.block_store().pause_atomic_writes()?;
 
         // First, insert the block.
         if let Err(insert_error) = self.block_store().insert(block) {
-            if cfg!(feature = "rocks") {
+            if cfg!(any(feature = "rocks", feature = "kv")) {
                 // Clear all pending atomic operations so that unpausing the atomic writes
                 // doesn't execute any of the queued storage operations.
                 self.block_store().abort_atomic();
@@ -424,16 +491,35 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             return Err(insert_error);
         };
 
-        // Next, finalize the transactions.
-        match self.finalize(state, block.ratifications(), block.solutions(), block.transactions()) {
-            Ok(_ratified_finalize_operations) => {
+        // Next, finalize the transactions, and store the finalize operations of the ratifications.
+        // Note: The changes to the key-value entries are only computed if the events are published.
+        let outcome = match with_events {
+            true => self.finalize_with_diffs(state, block.ratifications(), block.solutions(), block.transactions()),
+            false => self
+                .finalize(state, block.ratifications(), block.solutions(), block.transactions())
+                .map(|ratified_finalize_operations| (ratified_finalize_operations, vec![])),
+        };
+        match outcome.and_then(|(ratified_finalize_operations, diffs)| {
+            self.block_store().insert_ratified_finalize_operations(&block.hash(), ratified_finalize_operations)?;
+            Ok(diffs)
+        }) {
+            Ok(diffs) => {
                 // Unpause the atomic writes, executing the ones queued from block insertion and finalization.
-                #[cfg(feature = "rocks")]
+                #[cfg(any(feature = "rocks", feature = "kv"))]
                 self.block_store().unpause_atomic_writes::<false>()?;
+
+                // Publish the events of the block.
+                if with_events {
+                    let next_committee = self.finalize_store().committee_store().current_committee().ok();
+                    match self.events.block_events(block, diffs, previous_committee, next_committee) {
+                        Ok(events) => self.events.publish(events),
+                        Err(error) => error!("Failed to publish the events of block {} - {error}", block.height()),
+                    }
+                }
                 Ok(())
             }
             Err(finalize_error) => {
-                if cfg!(feature = "rocks") {
+                if cfg!(any(feature = "rocks", feature = "kv")) {
                     // Clear all pending atomic operations so that unpausing the atomic writes
                     // doesn't execute any of the queued storage operations.
                     self.block_store().abort_atomic();
@@ -478,7 +564,7 @@ pub(crate) mod test_helpers {
     #[cfg(feature = "rocks")]
     use ledger_store::helpers::rocksdb::ConsensusDB;
     use ledger_test_helpers::{large_transaction_program, small_transaction_program};
-    use synthesizer_program::Program;
+    use synthesizer_program::{Program, StackProgram};
 
     use indexmap::IndexMap;
     use once_cell::sync::OnceCell;
@@ -1076,6 +1162,135 @@ function a:
         assert!(VM::from(vm.store.clone()).is_ok());
     }
 
+    #[test]
+    fn test_program_upgrade() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a new caller.
+        let caller_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let caller_view_key = ViewKey::try_from(&caller_private_key).unwrap();
+        let caller_address = Address::try_from(&caller_private_key).unwrap();
+
+        // Initialize the VM.
+        let vm = crate::vm::test_helpers::sample_vm();
+        // Initialize the genesis block.
+        let genesis = vm.genesis_beacon(&caller_private_key, rng).unwrap();
+        // Update the VM.
+        vm.add_next_block(&genesis).unwrap();
+
+        // Fetch the unspent records.
+        let records = genesis.transitions().cloned().flat_map(Transition::into_records).collect::<Vec<(_, _)>>();
+        let record_0 = records[0].1.decrypt(&caller_view_key).unwrap();
+        let record_1 = records[1].1.decrypt(&caller_view_key).unwrap();
+        let record_2 = records[2].1.decrypt(&caller_view_key).unwrap();
+
+        // Deploy the first edition of the program, which may be upgraded by the caller.
+        let program_0 = Program::from_str(
+            r"
+program upgradable.aleo;
+
+mapping counter:
+    key as u8.public;
+    value as u8.public;
+
+function compute:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    output r1 as u8.private;",
+        )
+        .unwrap();
+        let upgrade_policy = UpgradePolicy::Admin(caller_address);
+        let deployment_0 = vm
+            .deploy_with_upgrade_policy(&caller_private_key, &program_0, upgrade_policy, Some(record_0), 0, None, rng)
+            .unwrap();
+        let block = sample_next_block(&vm, &caller_private_key, &[deployment_0.clone()], rng).unwrap();
+        vm.add_next_block(&block).unwrap();
+        assert_eq!(vm.process().read().get_stack(program_0.id()).unwrap().edition(), CurrentNetwork::EDITION);
+
+        // Prepare the second edition of the program, which adds a function.
+        let program_1 = Program::from_str(
+            r"
+program upgradable.aleo;
+
+mapping counter:
+    key as u8.public;
+    value as u8.public;
+
+function compute:
+    input r0 as u8.private;
+    mul r0 r0 into r1;
+    output r1 as u8.private;
+
+function extra:
+    input r0 as u8.private;
+    output r0 as u8.private;",
+        )
+        .unwrap();
+
+        // Ensure an upgrade from another account is rejected.
+        let other_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let invalid_upgrade = vm
+            .deploy_with_upgrade_policy(
+                &other_private_key,
+                &program_1,
+                UpgradePolicy::Immutable,
+                Some(record_1.clone()),
+                0,
+                None,
+                rng,
+            )
+            .unwrap();
+        assert!(vm.check_transaction(&invalid_upgrade, None, rng).is_err());
+
+        // Upgrade the program, and make it immutable.
+        let deployment_1 = vm
+            .deploy_with_upgrade_policy(
+                &caller_private_key,
+                &program_1,
+                UpgradePolicy::Immutable,
+                Some(record_1),
+                0,
+                None,
+                rng,
+            )
+            .unwrap();
+        vm.check_transaction(&deployment_1, None, rng).unwrap();
+        let block = sample_next_block(&vm, &caller_private_key, &[deployment_1.clone()], rng).unwrap();
+        vm.add_next_block(&block).unwrap();
+
+        // Ensure the upgraded program is loaded, and its mappings are preserved.
+        let stack = vm.process().read().get_stack(program_1.id()).unwrap().clone();
+        assert_eq!(stack.edition(), CurrentNetwork::EDITION + 1);
+        assert!(stack.upgrade_policy().is_immutable());
+        assert!(stack.get_function(&Identifier::from_str("extra").unwrap()).is_ok());
+        assert!(
+            vm.finalize_store().contains_mapping_confirmed(program_1.id(), &Identifier::from_str("counter").unwrap()).unwrap()
+        );
+
+        // Ensure the deployments of both editions are retrievable from storage.
+        assert_eq!(vm.transaction_store().get_transaction(&deployment_0.id()).unwrap().unwrap(), deployment_0);
+        assert_eq!(vm.transaction_store().get_transaction(&deployment_1.id()).unwrap().unwrap(), deployment_1);
+
+        // Ensure the program may no longer be upgraded.
+        let program_2 = Program::from_str(&program_1.to_string().replace("mul r0 r0", "sub r0 r0")).unwrap();
+        assert!(
+            vm.deploy_with_upgrade_policy(
+                &caller_private_key,
+                &program_2,
+                UpgradePolicy::Immutable,
+                Some(record_2),
+                0,
+                None,
+                rng
+            )
+            .is_err()
+        );
+
+        // Ensure the VM loads the latest edition of the program from storage.
+        let vm = VM::from(vm.store.clone()).unwrap();
+        assert_eq!(vm.process().read().get_stack(program_1.id()).unwrap().program(), &program_1);
+    }
+
     #[test]
     fn test_multiple_external_calls() {
         let rng = &mut TestRng::default();
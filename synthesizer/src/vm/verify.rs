@@ -38,6 +38,9 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     pub(crate) const MAX_PARALLEL_EXECUTE_VERIFICATIONS: usize = 1000;
 
     /// Verifies the list of transactions in the VM. On failure, returns an error.
+    ///
+    /// The final pairing checks of the fee and execution proofs are combined into a single multi-pairing check.
+    /// If it fails, the proofs of each transaction are checked individually, to identify the invalid transaction.
     pub fn check_transactions<R: CryptoRng + Rng>(
         &self,
         transactions: &[(&Transaction<N>, Option<Field<N>>)],
@@ -49,14 +52,46 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         let deployments_for_verification = deployments.chunks(Self::MAX_PARALLEL_DEPLOY_VERIFICATIONS);
         let executions_for_verification = executions.chunks(Self::MAX_PARALLEL_EXECUTE_VERIFICATIONS);
 
+        // Initialize a list of the transaction IDs and their deferred proofs.
+        let mut deferred_proofs = Vec::with_capacity(transactions.len());
+
         // Verify the transactions in batches.
         for transactions in deployments_for_verification.chain(executions_for_verification) {
-            // Ensure each transaction is well-formed and unique.
+            // Ensure each transaction is well-formed and unique, deferring the final pairing checks of its proofs.
             let rngs = (0..transactions.len()).map(|_| StdRng::from_seed(rng.gen())).collect::<Vec<_>>();
-            cfg_iter!(transactions).zip(rngs).try_for_each(|((transaction, rejected_id), mut rng)| {
-                self.check_transaction(transaction, *rejected_id, &mut rng)
-                    .map_err(|e| anyhow!("Invalid transaction found in the transactions list: {e}"))
-            })?;
+            let proofs = cfg_iter!(transactions)
+                .zip(rngs)
+                .map(|((transaction, rejected_id), mut rng)| {
+                    let mut proofs = Vec::new();
+                    let checksum = self
+                        .check_transaction_inner(transaction, *rejected_id, Some(&mut proofs), &mut rng)
+                        .map_err(|e| anyhow!("Invalid transaction found in the transactions list: {e}"))?;
+                    Ok((transaction.id(), checksum, proofs))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            deferred_proofs.extend(proofs);
+        }
+
+        // Verify the deferred proofs in a single batch.
+        let accumulators = deferred_proofs.iter().flat_map(|(_, _, proofs)| proofs.iter().cloned()).collect::<Vec<_>>();
+        if !ProofAccumulator::verify_batch(&accumulators, rng) {
+            // Verify the deferred proofs of each transaction, to identify the invalid transaction.
+            for (transaction_id, _, proofs) in &deferred_proofs {
+                if !ProofAccumulator::verify_batch(proofs, rng) {
+                    bail!(
+                        "Invalid transaction found in the transactions list: Transaction '{transaction_id}' has an invalid proof"
+                    )
+                }
+            }
+            bail!("Invalid transaction found in the transactions list: the batch of proofs is invalid")
+        }
+
+        // Now that the proofs are verified, add the transaction IDs to the partially-verified transactions cache.
+        let mut partially_verified_transactions = self.partially_verified_transactions.write();
+        for (transaction_id, checksum, _) in deferred_proofs {
+            if let Some(checksum) = checksum {
+                partially_verified_transactions.push(transaction_id, checksum);
+            }
         }
 
         Ok(())
@@ -72,6 +107,21 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         rejected_id: Option<Field<N>>,
         rng: &mut R,
     ) -> Result<()> {
+        self.check_transaction_inner(transaction, rejected_id, None, rng).map(|_| ())
+    }
+
+    /// Verifies the transaction in the VM. On failure, returns an error.
+    ///
+    /// If `deferred_proofs` is given, the final pairing checks of the fee and execution proofs are
+    /// added to it instead of being performed, and the transaction is not added to the partially-verified cache.
+    /// Instead, the transaction checksum is returned, to add the transaction once the deferred proofs are verified.
+    fn check_transaction_inner<R: CryptoRng + Rng>(
+        &self,
+        transaction: &Transaction<N>,
+        rejected_id: Option<Field<N>>,
+        mut deferred_proofs: Option<&mut Vec<ProofAccumulator<N>>>,
+        rng: &mut R,
+    ) -> Result<Option<N::TransmissionChecksum>> {
         let timer = timer!("VM::check_transaction");
 
         /* Transaction */
@@ -132,7 +182,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         lap!(timer, "Check for duplicate elements");
 
         // First, verify the fee.
-        self.check_fee(transaction, rejected_id)?;
+        self.check_fee_inner(transaction, rejected_id, deferred_proofs.as_deref_mut())?;
 
         // Construct the transaction checksum.
         let checksum = Data::<Transaction<N>>::Buffer(transaction.to_bytes_le()?.into()).to_checksum::<N>()?;
@@ -150,17 +200,36 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                 };
                 // Verify the signature corresponds to the transaction ID.
                 ensure!(owner.verify(deployment_id), "Invalid owner signature for deployment transaction '{id}'");
-                // Ensure the edition is correct.
-                if deployment.edition() != N::EDITION {
-                    bail!("Invalid deployment transaction '{id}' - expected edition {}", N::EDITION)
-                }
-                // Ensure the program ID does not already exist in the store.
-                if self.transaction_store().contains_program_id(deployment.program_id())? {
-                    bail!("Program ID '{}' is already deployed", deployment.program_id())
-                }
-                // Ensure the program does not already exist in the process.
-                if self.contains_program(deployment.program_id()) {
-                    bail!("Program ID '{}' already exists", deployment.program_id());
+                // Check the deployment against the existing program, if it is an upgrade.
+                match deployment.is_upgrade() {
+                    false => {
+                        // Ensure the edition is correct.
+                        if deployment.edition() != N::EDITION {
+                            bail!("Invalid deployment transaction '{id}' - expected edition {}", N::EDITION)
+                        }
+                        // Ensure the program ID does not already exist in the store.
+                        if self.transaction_store().contains_program_id(deployment.program_id())? {
+                            bail!("Program ID '{}' is already deployed", deployment.program_id())
+                        }
+                        // Ensure the program does not already exist in the process.
+                        if self.contains_program(deployment.program_id()) {
+                            bail!("Program ID '{}' already exists", deployment.program_id());
+                        }
+                    }
+                    true => {
+                        // Retrieve the stack of the existing program.
+                        let Ok(stack) = self.process().read().get_stack(deployment.program_id()).cloned() else {
+                            bail!(
+                                "Invalid upgrade transaction '{id}' - program '{}' does not exist",
+                                deployment.program_id()
+                            )
+                        };
+                        // Ensure the upgrade policy of the existing program permits the upgrade.
+                        let checksum = deployment.program().to_checksum()?;
+                        if let Err(error) = stack.upgrade_policy().check_upgrade(&owner.address(), &checksum) {
+                            bail!("Invalid upgrade transaction '{id}' - {error}")
+                        }
+                    }
                 }
                 // Verify the deployment if it has not been verified before.
                 if !is_partially_verified {
@@ -181,7 +250,11 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     bail!("Transaction '{id}' contains a previously rejected execution")
                 }
                 // Verify the execution.
-                match try_vm_runtime!(|| self.check_execution_internal(execution, is_partially_verified)) {
+                match try_vm_runtime!(|| self.check_execution_internal(
+                    execution,
+                    is_partially_verified,
+                    deferred_proofs.as_deref_mut()
+                )) {
                     Ok(result) => result?,
                     Err(_) => bail!("VM safely halted transaction '{id}' during verification"),
                 }
@@ -189,19 +262,38 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             Transaction::Fee(..) => { /* no-op */ }
         }
 
-        // If the above checks have passed and this is not a fee transaction,
-        // then add the transaction ID to the partially-verified transactions cache.
+        // If the above checks have passed, and this is not a fee transaction, then add the transaction ID
+        // to the partially-verified transactions cache if the proofs were verified, or return the checksum
+        // if the proofs were deferred.
+        let mut deferred_checksum = None;
         if !matches!(transaction, Transaction::Fee(..)) && !is_partially_verified {
-            self.partially_verified_transactions.write().push(transaction.id(), checksum);
+            match deferred_proofs {
+                None => {
+                    self.partially_verified_transactions.write().push(transaction.id(), checksum);
+                }
+                Some(_) => deferred_checksum = Some(checksum),
+            }
         }
 
         finish!(timer, "Verify the transaction");
-        Ok(())
+        Ok(deferred_checksum)
     }
 
     /// Verifies the `fee` in the given transaction. On failure, returns an error.
     #[inline]
     pub fn check_fee(&self, transaction: &Transaction<N>, rejected_id: Option<Field<N>>) -> Result<()> {
+        self.check_fee_inner(transaction, rejected_id, None)
+    }
+
+    /// Verifies the `fee` in the given transaction. On failure, returns an error.
+    ///
+    /// If `deferred_proofs` is given, the final pairing check of the fee proof is added to it instead of being performed.
+    fn check_fee_inner(
+        &self,
+        transaction: &Transaction<N>,
+        rejected_id: Option<Field<N>>,
+        deferred_proofs: Option<&mut Vec<ProofAccumulator<N>>>,
+    ) -> Result<()> {
         match transaction {
             Transaction::Deploy(id, _, deployment, fee) => {
                 // Ensure the rejected ID is not present.
@@ -217,7 +309,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                     bail!("Transaction '{id}' has an insufficient base fee (deployment) - requires {cost} microcredits")
                 }
                 // Verify the fee.
-                self.check_fee_internal(fee, deployment_id)?;
+                self.check_fee_internal(fee, deployment_id, deferred_proofs)?;
             }
             Transaction::Execute(id, execution, fee) => {
                 // Ensure the rejected ID is not present.
@@ -245,7 +337,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
                         ensure!(*fee.base_amount()? == 0, "Transaction '{id}' has a non-zero base fee (execution)");
                     }
                     // Verify the fee.
-                    self.check_fee_internal(fee, execution_id)?;
+                    self.check_fee_internal(fee, execution_id, deferred_proofs)?;
                 } else {
                     // Ensure the fee can be safely skipped.
                     ensure!(!is_fee_required, "Transaction '{id}' is missing a fee (execution)");
@@ -257,7 +349,7 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             Transaction::Fee(id, fee) => {
                 // Verify the fee.
                 match rejected_id {
-                    Some(rejected_id) => self.check_fee_internal(fee, rejected_id)?,
+                    Some(rejected_id) => self.check_fee_internal(fee, rejected_id, deferred_proofs)?,
                     None => bail!("Transaction '{id}' is missing a rejected ID (fee)"),
                 }
             }
@@ -293,8 +385,16 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     ///
     /// Note: This is an internal check only. To ensure all components of the execution are checked,
     /// use `VM::check_transaction` instead.
+    ///
+    /// If `deferred_proofs` is given, the final pairing check of the execution proof is added to it
+    /// instead of being performed.
     #[inline]
-    fn check_execution_internal(&self, execution: &Execution<N>, is_partially_verified: bool) -> Result<()> {
+    fn check_execution_internal(
+        &self,
+        execution: &Execution<N>,
+        is_partially_verified: bool,
+        deferred_proofs: Option<&mut Vec<ProofAccumulator<N>>>,
+    ) -> Result<()> {
         let timer = timer!("VM::check_execution");
 
         // Retrieve the block height.
@@ -306,9 +406,12 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         }
 
         // Verify the execution proof, if it has not been partially-verified before.
-        let verification = match is_partially_verified {
-            true => Ok(()),
-            false => self.process.read().verify_execution(execution),
+        let verification = match (is_partially_verified, deferred_proofs) {
+            (true, _) => Ok(()),
+            (false, None) => self.process.read().verify_execution(execution),
+            (false, Some(deferred_proofs)) => {
+                self.process.read().verify_execution_deferred(execution).map(|proof| deferred_proofs.push(proof))
+            }
         };
         lap!(timer, "Verify the execution");
 
@@ -330,8 +433,15 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
     ///
     /// Note: This is an internal check only. To ensure all components of the fee are checked,
     /// use `VM::check_fee` instead.
+    ///
+    /// If `deferred_proofs` is given, the final pairing check of the fee proof is added to it instead of being performed.
     #[inline]
-    fn check_fee_internal(&self, fee: &Fee<N>, deployment_or_execution_id: Field<N>) -> Result<()> {
+    fn check_fee_internal(
+        &self,
+        fee: &Fee<N>,
+        deployment_or_execution_id: Field<N>,
+        deferred_proofs: Option<&mut Vec<ProofAccumulator<N>>>,
+    ) -> Result<()> {
         let timer = timer!("VM::check_fee");
 
         // Ensure the fee does not exceed the limit.
@@ -339,7 +449,14 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         ensure!(*fee_amount <= N::MAX_FEE, "Fee verification failed: fee exceeds the maximum limit");
 
         // Verify the fee.
-        let verification = self.process.read().verify_fee(fee, deployment_or_execution_id);
+        let verification = match deferred_proofs {
+            None => self.process.read().verify_fee(fee, deployment_or_execution_id),
+            Some(deferred_proofs) => self
+                .process
+                .read()
+                .verify_fee_deferred(fee, deployment_or_execution_id)
+                .map(|proof| deferred_proofs.push(proof)),
+        };
         lap!(timer, "Verify the fee");
 
         // TODO (howardwu): This check is technically insufficient. Consider moving this upstream
@@ -421,7 +538,7 @@ mod tests {
         let program = crate::vm::test_helpers::sample_program();
 
         // Deploy the program.
-        let deployment = vm.deploy_raw(&program, rng).unwrap();
+        let deployment = vm.deploy_raw(&program, UpgradePolicy::Immutable, rng).unwrap();
 
         // Ensure the deployment is valid.
         vm.check_deployment_internal(&deployment, rng).unwrap();
@@ -449,13 +566,13 @@ mod tests {
                     // Ensure the proof exists.
                     assert!(execution.proof().is_some());
                     // Verify the execution.
-                    vm.check_execution_internal(&execution, false).unwrap();
+                    vm.check_execution_internal(&execution, false, None).unwrap();
 
                     // Ensure that deserialization doesn't break the transaction verification.
                     let serialized_execution = execution.to_string();
                     let recovered_execution: Execution<CurrentNetwork> =
                         serde_json::from_str(&serialized_execution).unwrap();
-                    vm.check_execution_internal(&recovered_execution, false).unwrap();
+                    vm.check_execution_internal(&recovered_execution, false, None).unwrap();
                 }
                 _ => panic!("Expected an execution transaction"),
             }
@@ -481,12 +598,12 @@ mod tests {
                     // Ensure the proof exists.
                     assert!(fee.proof().is_some());
                     // Verify the fee.
-                    vm.check_fee_internal(&fee, execution_id).unwrap();
+                    vm.check_fee_internal(&fee, execution_id, None).unwrap();
 
                     // Ensure that deserialization doesn't break the transaction verification.
                     let serialized_fee = fee.to_string();
                     let recovered_fee: Fee<CurrentNetwork> = serde_json::from_str(&serialized_fee).unwrap();
-                    vm.check_fee_internal(&recovered_fee, execution_id).unwrap();
+                    vm.check_fee_internal(&recovered_fee, execution_id, None).unwrap();
                 }
                 _ => panic!("Expected an execution with a fee"),
             }
@@ -517,6 +634,28 @@ mod tests {
         vm.check_transaction(&valid_transaction, None, rng).unwrap();
     }
 
+    #[test]
+    fn test_check_transactions_partially_verified() {
+        let rng = &mut TestRng::default();
+        let vm = crate::vm::test_helpers::sample_vm_with_genesis_block(rng);
+
+        // Fetch execution transactions.
+        let transactions = [
+            crate::vm::test_helpers::sample_execution_transaction_with_private_fee(rng),
+            crate::vm::test_helpers::sample_execution_transaction_with_public_fee(rng),
+        ];
+        let transactions_and_rejected_ids =
+            transactions.iter().map(|transaction| (transaction, None)).collect::<Vec<_>>();
+
+        // Ensure the transactions verify in a batch.
+        vm.check_transactions(&transactions_and_rejected_ids, rng).unwrap();
+
+        // Ensure the transactions were added to the partially-verified transactions cache.
+        for transaction in &transactions {
+            assert!(vm.partially_verified_transactions().read().contains(&transaction.id()));
+        }
+    }
+
     #[test]
     fn test_verify_deploy_and_execute() {
         // Initialize the RNG.
@@ -624,7 +763,7 @@ mod tests {
         let program = Program::credits().unwrap();
 
         // Ensure that the program can't be deployed.
-        assert!(vm.deploy_raw(&program, rng).is_err());
+        assert!(vm.deploy_raw(&program, UpgradePolicy::Immutable, rng).is_err());
 
         // Create a new `credits.aleo` program.
         let program = Program::from_str(
@@ -643,7 +782,7 @@ function compute:
         .unwrap();
 
         // Ensure that the program can't be deployed.
-        assert!(vm.deploy_raw(&program, rng).is_err());
+        assert!(vm.deploy_raw(&program, UpgradePolicy::Immutable, rng).is_err());
     }
 
     #[test]
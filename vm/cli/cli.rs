@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cli::commands::{Build, Clean, Execute, New, Run, Update};
+#[cfg(any(feature = "rocks", feature = "kv"))]
+use crate::cli::commands::Check;
+use crate::cli::commands::{Build, Clean, Debug, Deploy, Devnet, Execute, New, Run, Update};
 
 use anstyle::{AnsiColor, Color, Style};
 use anyhow::Result;
@@ -40,8 +42,17 @@ pub struct CLI {
 pub enum Command {
     #[clap(name = "build")]
     Build(Build),
+    #[cfg(any(feature = "rocks", feature = "kv"))]
+    #[clap(name = "check")]
+    Check(Check),
     #[clap(name = "clean")]
     Clean(Clean),
+    #[clap(name = "debug")]
+    Debug(Debug),
+    #[clap(name = "deploy")]
+    Deploy(Deploy),
+    #[clap(name = "devnet")]
+    Devnet(Devnet),
     #[clap(name = "execute")]
     Execute(Execute),
     #[clap(name = "new")]
@@ -57,7 +68,12 @@ impl Command {
     pub fn parse(self) -> Result<String> {
         match self {
             Self::Build(command) => command.parse(),
+            #[cfg(any(feature = "rocks", feature = "kv"))]
+            Self::Check(command) => command.parse(),
             Self::Clean(command) => command.parse(),
+            Self::Debug(command) => command.parse(),
+            Self::Deploy(command) => command.parse(),
+            Self::Devnet(command) => command.parse(),
             Self::Execute(command) => command.parse(),
             Self::New(command) => command.parse(),
             Self::Run(command) => command.parse(),
@@ -14,12 +14,17 @@
 
 use super::*;
 
+use crate::package::DEFAULT_DEPENDENCY_ENDPOINT;
+
 /// Compiles an Aleo program.
 #[derive(Debug, Parser)]
 pub struct Build {
     /// Uses the specified endpoint.
     #[clap(long)]
     endpoint: Option<String>,
+    /// Fetches the ledger dependencies from the specified endpoint.
+    #[clap(long, default_value = DEFAULT_DEPENDENCY_ENDPOINT)]
+    dependency_endpoint: String,
     /// Toggles offline mode.
     #[clap(long)]
     offline: bool,
@@ -37,7 +42,7 @@ impl Build {
         println!("⚠️  Attention - This command is deprecated. Use the {} command.\n", "'run'".to_string().bold());
 
         // Build the package, if the package requires building.
-        package.build::<Aleo>(self.endpoint)?;
+        package.build::<Aleo>(self.endpoint, &self.dependency_endpoint)?;
 
         // package.build::<Aleo>(match self.offline {
         //     true => None,
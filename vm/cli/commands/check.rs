@@ -0,0 +1,74 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[cfg(all(feature = "kv", not(feature = "rocks")))]
+use crate::ledger::store::helpers::kv::{ConsensusKV, RedbEngine};
+#[cfg(feature = "rocks")]
+use crate::ledger::store::helpers::rocksdb::ConsensusDB;
+use crate::{
+    console::network::Network,
+    ledger::{block::Block, Ledger},
+    utilities::FromBytes,
+};
+
+use std::path::PathBuf;
+
+/// The ledger backed by the persistent storage of a node.
+#[cfg(feature = "rocks")]
+type StoredLedger = Ledger<CurrentNetwork, ConsensusDB<CurrentNetwork>>;
+/// The ledger backed by the persistent storage of a node.
+#[cfg(all(feature = "kv", not(feature = "rocks")))]
+type StoredLedger = Ledger<CurrentNetwork, ConsensusKV<CurrentNetwork, RedbEngine>>;
+
+/// Checks that the maps of the ledger storage are mutually consistent, and optionally repairs the ledger
+#[derive(Debug, Parser)]
+pub struct Check {
+    /// The path to the ledger storage, if it is not in the default location.
+    #[clap(long, conflicts_with = "dev")]
+    path: Option<PathBuf>,
+    /// The ID of the development node, if the ledger storage belongs to one.
+    #[clap(long)]
+    dev: Option<u16>,
+    /// Rolls the ledger back to the last consistent block, if inconsistencies are found.
+    #[clap(long)]
+    repair: bool,
+}
+
+impl Check {
+    /// Checks the integrity of the ledger storage, and rolls it back if `repair` is set.
+    pub fn parse(self) -> Result<String> {
+        // Load the genesis block.
+        let genesis_block = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes())?;
+
+        // Load the ledger, without performing the spot checks of `Ledger::load`.
+        let ledger = match self.path {
+            Some(path) => StoredLedger::load_unchecked(genesis_block, path.into())?,
+            None => StoredLedger::load_unchecked(genesis_block, self.dev.into())?,
+        };
+
+        // Check the ledger.
+        let report = ledger.check_integrity(self.repair)?;
+
+        match (report.is_consistent(), report.repaired_height()) {
+            (true, _) => Ok(format!("✅ {report}")),
+            (false, Some(_)) => Ok(format!("⚠️  {report}")),
+            (false, None) => Ok(format!(
+                "⚠️  {report}\n\n{}",
+                "Run with '--repair' to roll back to the last consistent block".dimmed()
+            )),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::synthesizer::{
+    process::{DebugLocation, DebugScope, Debugger, RegistersView},
+    program::{Command, Instruction},
+};
+
+use std::{
+    io::{BufRead, Write},
+    sync::Arc,
+};
+
+/// Debugs an Aleo program function, stepping through its instructions
+#[derive(Debug, Parser)]
+pub struct Debug {
+    /// The function name.
+    function: Identifier<CurrentNetwork>,
+    /// The function inputs.
+    inputs: Vec<Value<CurrentNetwork>>,
+    /// Pauses before the instruction at the given location, as `program_id/function:index`,
+    /// or before the finalize command at `program_id/function/finalize:index` (may be repeated).
+    #[clap(short = 'b', long = "break", value_parser = parse_breakpoint)]
+    breakpoints: Vec<(Locator<CurrentNetwork>, bool, usize)>,
+    /// Pauses before every instruction.
+    #[clap(long)]
+    step: bool,
+}
+
+impl Debug {
+    /// Debugs an Aleo program function with the specified name.
+    pub fn parse(self) -> Result<String> {
+        // Derive the program directory path.
+        let path = std::env::current_dir()?;
+
+        // Load the package.
+        let package = Package::open(&path)?;
+        // Load the private key.
+        let private_key = crate::cli::helpers::dotenv_private_key()?;
+
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Initialize the debugger.
+        let debugger = Arc::new(CliDebugger { breakpoints: self.breakpoints, step: self.step });
+        // Evaluate the function, with the debugger attached.
+        let response = package.debug::<Aleo, _>(&private_key, self.function, &self.inputs, debugger, rng)?;
+
+        // Log the outputs.
+        match response.outputs().len() {
+            0 => (),
+            1 => println!("\n➡️  Output\n"),
+            _ => println!("\n➡️  Outputs\n"),
+        };
+        for output in response.outputs() {
+            println!(" • {output}");
+        }
+        println!();
+
+        // Prepare the locator.
+        let locator = Locator::<CurrentNetwork>::from_str(&format!("{}/{}", package.program_id(), self.function))?;
+        // Prepare the path string.
+        let path_string = format!("(in \"{}\")", path.display());
+
+        Ok(format!("✅ Finished debugging '{}' {}", locator.to_string().bold(), path_string.dimmed()))
+    }
+}
+
+/// Parses a breakpoint of the form `program_id/function:index` or `program_id/function/finalize:index`,
+/// as (locator, is finalize, index).
+fn parse_breakpoint(breakpoint: &str) -> Result<(Locator<CurrentNetwork>, bool, usize)> {
+    let Some((locator, index)) = breakpoint.rsplit_once(':') else {
+        anyhow::bail!("Invalid breakpoint '{breakpoint}', expected 'program_id/function:index'")
+    };
+    match locator.strip_suffix("/finalize") {
+        Some(locator) => Ok((Locator::from_str(locator)?, true, index.parse()?)),
+        None => Ok((Locator::from_str(locator)?, false, index.parse()?)),
+    }
+}
+
+/// A debugger that logs each instruction and finalize command, and pauses for input at breakpoints.
+struct CliDebugger {
+    /// The breakpoints, as (locator, is finalize, instruction or command index).
+    breakpoints: Vec<(Locator<CurrentNetwork>, bool, usize)>,
+    /// If `true`, pauses before every instruction.
+    step: bool,
+}
+
+impl Debugger<CurrentNetwork> for CliDebugger {
+    fn is_breakpoint(&self, location: &DebugLocation<CurrentNetwork>) -> bool {
+        self.step
+            || self.breakpoints.iter().any(|(locator, is_finalize, index)| {
+                (location.scope() == DebugScope::Finalize) == *is_finalize
+                    && locator.program_id() == location.program_id()
+                    && locator.resource() == location.name()
+                    && *index == location.index()
+            })
+    }
+
+    fn on_breakpoint(
+        &self,
+        location: &DebugLocation<CurrentNetwork>,
+        registers: RegistersView<CurrentNetwork>,
+    ) -> Result<()> {
+        // Log the registers.
+        println!("\n⏸  Paused at '{}'\n", location.to_string().bold());
+        for (locator, value) in registers.iter() {
+            println!(" • r{locator} = {value}");
+        }
+
+        // Wait for input.
+        print!("\n{}", "Press enter to continue, or 'q' to quit: ".dimmed());
+        std::io::stdout().flush()?;
+        let mut input = String::new();
+        std::io::stdin().lock().read_line(&mut input)?;
+        match input.trim() {
+            "q" | "quit" => anyhow::bail!("Debugging was stopped at '{location}'"),
+            _ => Ok(()),
+        }
+    }
+
+    fn before_instruction(
+        &self,
+        location: &DebugLocation<CurrentNetwork>,
+        instruction: &Instruction<CurrentNetwork>,
+        _registers: RegistersView<CurrentNetwork>,
+    ) -> Result<()> {
+        println!("{}", format!(" • {location}  {instruction}").dimmed());
+        Ok(())
+    }
+
+    fn after_instruction(
+        &self,
+        _location: &DebugLocation<CurrentNetwork>,
+        _instruction: &Instruction<CurrentNetwork>,
+        registers: RegistersView<CurrentNetwork>,
+    ) -> Result<()> {
+        // Log the last assigned register.
+        if let Some((locator, value)) = registers.iter().last() {
+            println!("     r{locator} = {value}");
+        }
+        Ok(())
+    }
+
+    fn before_command(
+        &self,
+        location: &DebugLocation<CurrentNetwork>,
+        command: &Command<CurrentNetwork>,
+        _registers: RegistersView<CurrentNetwork>,
+    ) -> Result<()> {
+        println!("{}", format!(" • {location}  {command}").dimmed());
+        Ok(())
+    }
+
+    fn after_command(
+        &self,
+        _location: &DebugLocation<CurrentNetwork>,
+        _command: &Command<CurrentNetwork>,
+        registers: RegistersView<CurrentNetwork>,
+    ) -> Result<()> {
+        // Log the last assigned register.
+        if let Some((locator, value)) = registers.iter().last() {
+            println!("     r{locator} = {value}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Command, CLI};
+
+    #[test]
+    fn clap_snarkvm_debug() {
+        let arg_vec = vec!["snarkvm", "debug", "hello", "1u32", "2u32", "--break", "hello.aleo/hello:1", "--step"];
+        let cli = CLI::parse_from(&arg_vec);
+
+        if let Command::Debug(debug) = cli.command {
+            assert_eq!(debug.function, Identifier::try_from(arg_vec[2]).unwrap());
+            assert_eq!(debug.inputs, vec![Value::try_from(arg_vec[3]).unwrap(), Value::try_from(arg_vec[4]).unwrap()]);
+            assert_eq!(debug.breakpoints, vec![(Locator::from_str("hello.aleo/hello").unwrap(), false, 1)]);
+            assert!(debug.step);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+
+    #[test]
+    fn test_parse_breakpoint() {
+        let (locator, is_finalize, index) = parse_breakpoint("hello.aleo/main:12").unwrap();
+        assert_eq!(locator, Locator::from_str("hello.aleo/main").unwrap());
+        assert!(!is_finalize);
+        assert_eq!(index, 12);
+
+        let (locator, is_finalize, index) = parse_breakpoint("hello.aleo/main/finalize:3").unwrap();
+        assert_eq!(locator, Locator::from_str("hello.aleo/main").unwrap());
+        assert!(is_finalize);
+        assert_eq!(index, 3);
+
+        assert!(parse_breakpoint("hello.aleo/main").is_err());
+        assert!(parse_breakpoint("hello.aleo/main:x").is_err());
+        assert!(parse_breakpoint("main:1").is_err());
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::{
+    cli::helpers::{FeeOptions, TransactionOptions},
+    console::program::ProgramOwner,
+    synthesizer::process::deployment_cost,
+};
+
+/// Deploys an Aleo program
+#[derive(Debug, Parser)]
+pub struct Deploy {
+    /// Uses the specified endpoint.
+    #[clap(default_value = "https://api.explorer.aleo.org/v1", long)]
+    endpoint: String,
+    /// The options for paying the fee.
+    #[clap(flatten)]
+    fee: FeeOptions,
+    /// The options for emitting the transaction.
+    #[clap(flatten)]
+    transaction: TransactionOptions,
+}
+
+impl Deploy {
+    /// Deploys an Aleo program.
+    pub fn parse(self) -> Result<String> {
+        // Derive the program directory path.
+        let path = std::env::current_dir()?;
+
+        // Load the package.
+        let package = Package::open(&path)?;
+        // Load the private key.
+        let private_key = crate::cli::helpers::dotenv_private_key()?;
+
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Compute the deployment.
+        let deployment = package.deploy::<Aleo>(None)?;
+        let deployment_id = deployment.to_deployment_id()?;
+
+        // Compute the base fee.
+        let (base_fee_in_microcredits, _) = deployment_cost(&deployment)?;
+        // Prove the fee.
+        let vm = FeeOptions::load_vm(&package)?;
+        let fee =
+            self.fee.prove_fee(&vm, &private_key, base_fee_in_microcredits, deployment_id, &self.endpoint, rng)?;
+
+        // Construct the transaction.
+        let owner = ProgramOwner::new(&private_key, deployment_id, rng)?;
+        let transaction = Transaction::from_deployment(owner, deployment, fee)?;
+
+        // Write or broadcast the transaction.
+        self.transaction.emit(&transaction, &self.endpoint)?;
+
+        // Prepare the path string.
+        let path_string = format!("(in \"{}\")", path.display());
+
+        Ok(format!("✅ Deployed '{}' {}", package.program_id().to_string().bold(), path_string.dimmed()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Command, CLI};
+
+    #[test]
+    fn clap_snarkvm_deploy() {
+        let arg_vec = vec!["snarkvm", "deploy", "--priority-fee", "10", "--store", "deployment.json", "--broadcast"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Deploy(deploy) = cli.command {
+            assert_eq!(deploy.endpoint, "https://api.explorer.aleo.org/v1");
+            assert_eq!(deploy.fee.priority_fee, 10);
+            assert!(deploy.fee.fee_record.is_none());
+            assert_eq!(deploy.transaction.store, Some(std::path::PathBuf::from("deployment.json")));
+            assert!(deploy.transaction.broadcast);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
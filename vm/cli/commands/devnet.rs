@@ -0,0 +1,423 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::{
+    console::{account::PrivateKey, network::Network, program::Plaintext},
+    ledger::{block::Block, store::helpers::memory::ConsensusMemory, Ledger},
+    synthesizer::program::Program,
+};
+
+use anyhow::ensure;
+use rand::{CryptoRng, Rng};
+use std::{
+    io::{BufRead, Write},
+    path::PathBuf,
+};
+
+/// The in-memory ledger backing the devnet.
+type DevnetLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+/// Starts a local devnet, backed by an in-memory ledger, with the Aleo package deployed
+#[derive(Debug, Parser)]
+pub struct Devnet {
+    /// Executes the given commands, one per line, instead of reading them from stdin.
+    #[clap(long)]
+    script: Option<PathBuf>,
+}
+
+impl Devnet {
+    /// Starts a local devnet, deploys the Aleo package, and evaluates the devnet commands.
+    pub fn parse(self) -> Result<String> {
+        // Derive the program directory path.
+        let path = std::env::current_dir()?;
+
+        // Load the package.
+        let package = Package::open(&path)?;
+        // Load the private key.
+        let private_key = crate::cli::helpers::dotenv_private_key()?;
+
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Start the devnet.
+        let devnet = DevnetSession::new(private_key, *package.program_id(), rng)?;
+        // Deploy the package and its imports.
+        devnet.deploy_package(&package, rng)?;
+
+        // Evaluate the devnet commands.
+        match self.script {
+            Some(script) => {
+                for line in std::fs::read_to_string(script)?.lines() {
+                    if !devnet.evaluate(line, rng)? {
+                        break;
+                    }
+                }
+            }
+            None => {
+                println!("\n💡 Type 'help' for the list of devnet commands\n");
+                let stdin = std::io::stdin();
+                let mut lines = stdin.lock().lines();
+                loop {
+                    print!("{} ", "devnet>".bold());
+                    std::io::stdout().flush()?;
+                    match lines.next() {
+                        Some(line) => match devnet.evaluate(&line?, rng) {
+                            Ok(true) => continue,
+                            Ok(false) => break,
+                            Err(error) => println!("⚠️  {error}\n"),
+                        },
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        Ok(format!("✅ Stopped the devnet at block {}", devnet.ledger.latest_height()))
+    }
+}
+
+/// The commands that are available in a devnet session.
+#[derive(Debug, Parser)]
+#[clap(name = "devnet", no_binary_name = true, disable_help_flag = true)]
+enum DevnetCommand {
+    /// Executes a program function, and advances the devnet by one block
+    #[clap(name = "execute")]
+    Execute {
+        /// The function name in the package program, or the locator of the function (e.g. 'token.aleo/mint').
+        function: String,
+        /// The function inputs.
+        inputs: Vec<Value<CurrentNetwork>>,
+    },
+    /// Deploys the Aleo package at the given directory and its imports, advancing the devnet by one block for each
+    #[clap(name = "deploy")]
+    Deploy {
+        /// The package directory, by default the current directory.
+        path: Option<PathBuf>,
+    },
+    /// Prints the value for the given key in a mapping, or every entry in the mapping
+    #[clap(name = "mapping")]
+    Mapping {
+        /// The program ID.
+        program_id: ProgramID<CurrentNetwork>,
+        /// The mapping name.
+        mapping_name: Identifier<CurrentNetwork>,
+        /// The mapping key.
+        key: Option<Plaintext<CurrentNetwork>>,
+    },
+    /// Advances the devnet by one empty block
+    #[clap(name = "advance")]
+    Advance,
+    /// Prints the latest block height
+    #[clap(name = "height")]
+    Height,
+    /// Stops the devnet
+    #[clap(name = "exit", alias = "quit")]
+    Exit,
+}
+
+/// A devnet session, consisting of an in-memory ledger and the account that produces its blocks.
+struct DevnetSession {
+    /// The ledger.
+    ledger: DevnetLedger,
+    /// The private key of the account that produces the blocks, and pays the fees.
+    private_key: PrivateKey<CurrentNetwork>,
+    /// The program ID of the package, whose functions may be executed by name.
+    program_id: ProgramID<CurrentNetwork>,
+}
+
+impl DevnetSession {
+    /// Initializes a new devnet session, starting from a genesis block produced by the given account.
+    fn new<R: Rng + CryptoRng>(
+        private_key: PrivateKey<CurrentNetwork>,
+        program_id: ProgramID<CurrentNetwork>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        println!("⏳ Starting a devnet...\n");
+
+        // Initialize the VM, and produce the genesis block.
+        let store = crate::ledger::store::ConsensusStore::<_, ConsensusMemory<_>>::open(None)?;
+        let genesis = crate::synthesizer::VM::from(store)?.genesis_beacon(&private_key, rng)?;
+        // Initialize the ledger.
+        let ledger = DevnetLedger::load(genesis, None.into())?;
+
+        println!(" •  Genesis block {}", ledger.latest_hash().to_string().dimmed());
+
+        Ok(Self { ledger, private_key, program_id })
+    }
+
+    /// Evaluates the given devnet command, returning `false` if the devnet should stop.
+    fn evaluate<R: Rng + CryptoRng>(&self, line: &str, rng: &mut R) -> Result<bool> {
+        // Skip empty lines and comments.
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            return Ok(true);
+        }
+        // Print the help message.
+        if line == "help" {
+            use clap::CommandFactory;
+            println!("{}", DevnetCommand::command().render_help());
+            return Ok(true);
+        }
+
+        match DevnetCommand::try_parse_from(split_arguments(line)?)? {
+            DevnetCommand::Execute { function, inputs } => self.execute(&function, &inputs, rng)?,
+            DevnetCommand::Deploy { path } => {
+                let path = match path {
+                    Some(path) => path,
+                    None => std::env::current_dir()?,
+                };
+                self.deploy_package(&Package::open(&path)?, rng)?
+            }
+            DevnetCommand::Mapping { program_id, mapping_name, key } => {
+                self.print_mapping(program_id, mapping_name, key.as_ref())?
+            }
+            DevnetCommand::Advance => self.advance(vec![], rng)?,
+            DevnetCommand::Height => println!("{}\n", self.ledger.latest_height()),
+            DevnetCommand::Exit => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Deploys the imports of the given package (recursively), followed by the package program,
+    /// if they are not already deployed.
+    fn deploy_package<R: Rng + CryptoRng>(&self, package: &Package<CurrentNetwork>, rng: &mut R) -> Result<()> {
+        // Deploy all import programs, in the order they import one another.
+        for import_program in package.get_imports()? {
+            if !self.ledger.vm().contains_program(import_program.id()) {
+                self.deploy(&import_program, rng)?;
+            }
+        }
+
+        // Deploy the program.
+        match self.ledger.vm().contains_program(package.program_id()) {
+            true => println!("💡 '{}' is already deployed\n", package.program_id().to_string().bold()),
+            false => self.deploy(package.program(), rng)?,
+        }
+        Ok(())
+    }
+
+    /// Deploys the given program, and advances the devnet by one block.
+    fn deploy<R: Rng + CryptoRng>(&self, program: &Program<CurrentNetwork>, rng: &mut R) -> Result<()> {
+        println!("⏳ Deploying '{}'...\n", program.id().to_string().bold());
+
+        // Create the deployment transaction, paying the fee from the public balance.
+        let transaction = self.ledger.vm().deploy(&self.private_key, program, None, 0, None, rng)?;
+        // Advance the devnet.
+        self.advance(vec![transaction], rng)?;
+
+        // Ensure the program was deployed.
+        ensure!(self.ledger.vm().contains_program(program.id()), "Failed to deploy '{}'", program.id());
+        Ok(())
+    }
+
+    /// Executes the given function, and advances the devnet by one block.
+    fn execute<R: Rng + CryptoRng>(&self, function: &str, inputs: &[Value<CurrentNetwork>], rng: &mut R) -> Result<()> {
+        // Resolve the locator, defaulting to the main program of the package.
+        let locator = match function.contains('/') {
+            true => Locator::<CurrentNetwork>::from_str(function)?,
+            false => Locator::new(self.program_id, Identifier::from_str(function)?),
+        };
+
+        println!("⏳ Executing '{}'...\n", locator.to_string().bold());
+
+        // Create the execution transaction, paying the fee from the public balance.
+        let transaction = self.ledger.vm().execute(
+            &self.private_key,
+            (locator.program_id(), locator.resource()),
+            inputs.iter(),
+            None,
+            0,
+            None,
+            rng,
+        )?;
+        // Advance the devnet.
+        self.advance(vec![transaction], rng)
+    }
+
+    /// Prints the value for the given key in the given mapping, or every entry in the mapping.
+    fn print_mapping(
+        &self,
+        program_id: ProgramID<CurrentNetwork>,
+        mapping_name: Identifier<CurrentNetwork>,
+        key: Option<&Plaintext<CurrentNetwork>>,
+    ) -> Result<()> {
+        let finalize_store = self.ledger.vm().finalize_store();
+        // Ensure the mapping exists.
+        let mapping_names = finalize_store.get_mapping_names_confirmed(&program_id)?.unwrap_or_default();
+        ensure!(mapping_names.contains(&mapping_name), "Mapping '{program_id}/{mapping_name}' does not exist");
+
+        match key {
+            Some(key) => match finalize_store.get_value_confirmed(program_id, mapping_name, key)? {
+                Some(value) => println!("{value}\n"),
+                None => println!("{}\n", "null".dimmed()),
+            },
+            None => {
+                for (key, value) in finalize_store.get_mapping_confirmed(program_id, mapping_name)? {
+                    println!(" •  {key} => {value}");
+                }
+                println!();
+            }
+        }
+        Ok(())
+    }
+
+    /// Produces a block containing the given transactions, adds it to the ledger, and logs the outcome.
+    fn advance<R: Rng + CryptoRng>(&self, transactions: Vec<Transaction<CurrentNetwork>>, rng: &mut R) -> Result<()> {
+        // Collect the transaction IDs.
+        let transaction_ids = transactions.iter().map(|transaction| transaction.id()).collect::<Vec<_>>();
+
+        // Prepare the next block.
+        let block =
+            self.ledger.prepare_advance_to_next_beacon_block(&self.private_key, vec![], vec![], transactions, rng)?;
+        // Check and add the block to the ledger.
+        self.ledger.check_next_block(&block, rng)?;
+        self.ledger.advance_to_next_block(&block)?;
+
+        println!(" •  Block {} {}", block.height(), block.hash().to_string().dimmed());
+        for transaction_id in transaction_ids {
+            println!(
+                " •  Transaction {} {}",
+                transaction_id.to_string().dimmed(),
+                transaction_status(&block, &transaction_id)
+            );
+        }
+        println!();
+        Ok(())
+    }
+}
+
+/// Returns the status of the given transaction in the given block.
+fn transaction_status(
+    block: &Block<CurrentNetwork>,
+    transaction_id: &<CurrentNetwork as Network>::TransactionID,
+) -> String {
+    match block.transactions().find_confirmed_transaction_for_unconfirmed_transaction_id(transaction_id) {
+        Some(confirmed) if confirmed.is_accepted() => "accepted".green().to_string(),
+        Some(_) => "rejected".yellow().to_string(),
+        None => "aborted".red().to_string(),
+    }
+}
+
+/// Splits the given devnet command into its arguments, keeping whitespace within braces and quotes.
+fn split_arguments(line: &str) -> Result<Vec<String>> {
+    let mut arguments = Vec::new();
+    let mut argument = String::new();
+    let (mut depth, mut is_quoted) = (0usize, false);
+    for character in line.chars() {
+        match character {
+            '"' => {
+                is_quoted = !is_quoted;
+                continue;
+            }
+            '{' | '[' if !is_quoted => depth += 1,
+            '}' | ']' if !is_quoted => {
+                depth = depth.checked_sub(1).ok_or_else(|| anyhow::anyhow!("Unbalanced '{character}' in '{line}'"))?
+            }
+            _ if character.is_whitespace() && !is_quoted && depth == 0 => {
+                if !argument.is_empty() {
+                    arguments.push(core::mem::take(&mut argument));
+                }
+                continue;
+            }
+            _ => (),
+        }
+        argument.push(character);
+    }
+    ensure!(depth == 0 && !is_quoted, "Unterminated argument in '{line}'");
+    if !argument.is_empty() {
+        arguments.push(argument);
+    }
+    Ok(arguments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Command, CLI};
+
+    #[test]
+    fn clap_snarkvm_devnet() {
+        let arg_vec = vec!["snarkvm", "devnet", "--script", "commands.txt"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Devnet(devnet) = cli.command {
+            assert_eq!(devnet.script, Some(PathBuf::from("commands.txt")));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+
+    #[test]
+    fn test_devnet_command() {
+        let command = DevnetCommand::try_parse_from(split_arguments("execute token.aleo/mint 5u64 true").unwrap());
+        match command.unwrap() {
+            DevnetCommand::Execute { function, inputs } => {
+                assert_eq!(function, "token.aleo/mint");
+                assert_eq!(inputs, vec![Value::from_str("5u64").unwrap(), Value::from_str("true").unwrap()]);
+            }
+            command => panic!("Unexpected devnet command: {command:?}"),
+        }
+
+        let command = DevnetCommand::try_parse_from(split_arguments("mapping credits.aleo account").unwrap());
+        match command.unwrap() {
+            DevnetCommand::Mapping { program_id, mapping_name, key } => {
+                assert_eq!(program_id.to_string(), "credits.aleo");
+                assert_eq!(mapping_name.to_string(), "account");
+                assert!(key.is_none());
+            }
+            command => panic!("Unexpected devnet command: {command:?}"),
+        }
+
+        assert!(DevnetCommand::try_parse_from(split_arguments("mint 5u64").unwrap()).is_err());
+        assert!(DevnetCommand::try_parse_from(split_arguments("mapping credits.aleo").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_deploy_package_with_transitive_imports() {
+        let rng = &mut snarkvm_utilities::TestRng::default();
+
+        // Samples a new package, whose program imports `parent.aleo`, which imports `child.aleo`.
+        let (directory, package) = crate::package::test_helpers::sample_transitive_package();
+
+        // Start the devnet, and deploy the package.
+        let private_key = PrivateKey::new(rng).unwrap();
+        let devnet = DevnetSession::new(private_key, *package.program_id(), rng).unwrap();
+        devnet.deploy_package(&package, rng).unwrap();
+
+        // Ensure the program and its imports were deployed, one per block.
+        for program_id in ["child.aleo", "parent.aleo", "main_wrapper.aleo"] {
+            assert!(devnet.ledger.vm().contains_program(&ProgramID::from_str(program_id).unwrap()));
+        }
+        assert_eq!(devnet.ledger.latest_height(), 3);
+
+        // Proactively remove the temporary directory (to conserve space).
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_split_arguments() {
+        assert_eq!(split_arguments("  execute   mint 1u8 ").unwrap(), ["execute", "mint", "1u8"]);
+        assert_eq!(split_arguments("execute mint { a: 1u8, b: [2u8, 3u8] } 4u8").unwrap(), [
+            "execute",
+            "mint",
+            "{ a: 1u8, b: [2u8, 3u8] }",
+            "4u8"
+        ]);
+        assert_eq!(split_arguments("deploy \"my package\"").unwrap(), ["deploy", "my package"]);
+        assert!(split_arguments("execute mint { a: 1u8").is_err());
+        assert!(split_arguments("execute mint 1u8 }").is_err());
+    }
+}
@@ -14,6 +14,13 @@
 
 use super::*;
 
+use crate::{
+    cli::helpers::{FeeOptions, TransactionOptions},
+    synthesizer::process::execution_cost,
+};
+
+use anyhow::ensure;
+
 /// Executes an Aleo program function locally
 #[derive(Debug, Parser)]
 pub struct Execute {
@@ -27,6 +34,15 @@ pub struct Execute {
     /// Toggles offline mode.
     #[clap(long)]
     offline: bool,
+    /// Prints the constraints attributed to each instruction in each call.
+    #[clap(long)]
+    profile: bool,
+    /// The options for paying the fee.
+    #[clap(flatten)]
+    fee: FeeOptions,
+    /// The options for emitting the transaction.
+    #[clap(flatten)]
+    transaction: TransactionOptions,
 }
 
 impl Execute {
@@ -45,14 +61,13 @@ impl Execute {
         let rng = &mut rand::thread_rng();
 
         // Execute the request.
-        let (response, execution, metrics) =
-            package.execute::<Aleo, _>(self.endpoint, &private_key, self.function, &self.inputs, rng)?;
-
-        // TODO (howardwu): Include the option to execute a fee.
-        let fee = None;
-
-        // Construct the transaction.
-        let transaction = Transaction::from_execution(execution, fee)?;
+        let (response, execution, metrics, instruction_metrics) = package.execute_and_profile::<Aleo, _>(
+            self.endpoint.clone(),
+            &private_key,
+            self.function,
+            &self.inputs,
+            rng,
+        )?;
 
         // Count the number of times a function is called.
         let mut program_frequency = HashMap::<String, usize>::new();
@@ -89,6 +104,11 @@ impl Execute {
             println!(" •  {function_constraints} {counter_string}",)
         }
 
+        // Log the profile.
+        if self.profile {
+            print_profile(&metrics, &instruction_metrics);
+        }
+
         // Log the outputs.
         match response.outputs().len() {
             0 => (),
@@ -100,8 +120,24 @@ impl Execute {
         }
         println!();
 
-        // Print the transaction.
-        println!("{transaction}\n");
+        // Construct the transaction, if it is to be stored or broadcast.
+        if self.transaction.is_stored_or_broadcast() {
+            ensure!(!self.offline, "The transaction requires a fee, which cannot be proven in offline mode");
+
+            // Compute the base fee.
+            let vm = FeeOptions::load_vm(&package)?;
+            vm.process().write().add_program(package.program())?;
+            let (base_fee_in_microcredits, _) = execution_cost(&vm.process().read(), &execution)?;
+            // Prove the fee.
+            let execution_id = execution.to_execution_id()?;
+            let fee =
+                self.fee.prove_fee(&vm, &private_key, base_fee_in_microcredits, execution_id, &self.endpoint, rng)?;
+
+            // Construct the transaction.
+            let transaction = Transaction::from_execution(execution, Some(fee))?;
+            // Write or broadcast the transaction.
+            self.transaction.emit(&transaction, &self.endpoint)?;
+        }
 
         // Prepare the locator.
         let locator = Locator::<CurrentNetwork>::from_str(&format!("{}/{}", package.program_id(), self.function))?;
@@ -111,3 +147,136 @@ impl Execute {
         Ok(format!("✅ Executed '{}' {}", locator.to_string().bold(), path_string.dimmed()))
     }
 }
+
+/// The width of the widest bar in the profile.
+const PROFILE_BAR_WIDTH: usize = 32;
+
+/// Prints the constraints attributed to each instruction in each call, and the constraints attributed to each opcode.
+fn print_profile(metrics: &[CallMetrics<CurrentNetwork>], instruction_metrics: &[Vec<InstructionMetrics>]) {
+    use num_format::ToFormattedString;
+
+    // Determine the total number of constraints in the function bodies.
+    let total_constraints = metrics.iter().map(|metric| metric.num_function_constraints).sum::<u64>().max(1);
+    // Returns a bar, with a width proportional to the given number of constraints.
+    let bar = |num_constraints: u64| {
+        "█".repeat((num_constraints as usize * PROFILE_BAR_WIDTH).div_ceil(total_constraints as usize))
+    };
+
+    println!("\n🔥 Profile\n");
+    for index in to_call_order(metrics) {
+        let (metric, instructions) = (&metrics[index], &instruction_metrics[index]);
+        // Log the call.
+        let indent = "    ".repeat(metric.call_depth);
+        println!(
+            "{indent} •  {} - {} constraints",
+            format!("'{}/{}'", metric.program_id, metric.function_name).bold(),
+            metric.num_function_constraints.to_formatted_string(LOCALE)
+        );
+        // Log the instructions.
+        for instruction in instructions {
+            println!(
+                "{indent}    {:>4}  {:<16} {:<width$} {} constraints {}",
+                instruction.index.to_string().dimmed(),
+                instruction.opcode.to_string(),
+                bar(instruction.num_constraints).red(),
+                instruction.num_constraints.to_formatted_string(LOCALE),
+                format!(
+                    "({} variables, {} non-zeros)",
+                    instruction.num_variables.to_formatted_string(LOCALE),
+                    instruction.num_nonzeros.to_formatted_string(LOCALE)
+                )
+                .dimmed(),
+                width = PROFILE_BAR_WIDTH,
+            );
+        }
+    }
+
+    // Aggregate the constraints for each opcode.
+    let mut opcodes = HashMap::<String, u64>::new();
+    for instruction in instruction_metrics.iter().flatten() {
+        *opcodes.entry(instruction.opcode.to_string()).or_default() += instruction.num_constraints;
+    }
+    let mut opcodes = opcodes.into_iter().collect::<Vec<_>>();
+    opcodes.sort_by(|(a_opcode, a), (b_opcode, b)| b.cmp(a).then_with(|| a_opcode.cmp(b_opcode)));
+
+    println!("\n📊 Opcodes\n");
+    for (opcode, num_constraints) in opcodes {
+        println!(
+            " •  {:<16} {:<width$} {} constraints {}",
+            opcode,
+            bar(num_constraints).red(),
+            num_constraints.to_formatted_string(LOCALE),
+            format!("({:.1}%)", num_constraints as f64 * 100.0 / total_constraints as f64).dimmed(),
+            width = PROFILE_BAR_WIDTH,
+        );
+    }
+}
+
+/// Returns the indexes of the call metrics in the order the calls were made,
+/// given the call metrics in the order the calls completed.
+fn to_call_order(metrics: &[CallMetrics<CurrentNetwork>]) -> Vec<usize> {
+    // Reconstruct each call followed by its nested calls, as a call completes after its nested calls.
+    let mut completed: Vec<Vec<usize>> = Vec::new();
+    for (index, metric) in metrics.iter().enumerate() {
+        // The nested calls are the trailing completed calls that are deeper than this call.
+        let start =
+            completed.iter().rposition(|calls| metrics[calls[0]].call_depth <= metric.call_depth).map_or(0, |i| i + 1);
+        let mut calls = vec![index];
+        calls.extend(completed.drain(start..).flatten());
+        completed.push(calls);
+    }
+    completed.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Command, CLI};
+
+    #[test]
+    fn clap_snarkvm_execute() {
+        let arg_vec =
+            vec!["snarkvm", "execute", "hello", "1u32", "2u32", "--offline", "--profile", "--priority-fee", "5"];
+        let cli = CLI::parse_from(&arg_vec);
+
+        if let Command::Execute(execute) = cli.command {
+            assert_eq!(execute.function, Identifier::try_from(arg_vec[2]).unwrap());
+            assert_eq!(execute.inputs, vec![
+                Value::try_from(arg_vec[3]).unwrap(),
+                Value::try_from(arg_vec[4]).unwrap()
+            ]);
+            assert!(execute.offline);
+            assert!(execute.profile);
+            assert_eq!(execute.fee.priority_fee, 5);
+            assert!(execute.fee.fee_record.is_none());
+            assert!(execute.transaction.store.is_none());
+            assert!(!execute.transaction.broadcast);
+            assert!(!execute.transaction.is_stored_or_broadcast());
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+
+    #[test]
+    fn test_to_call_order() {
+        // Samples the call metrics for the given function name and call depth.
+        let sample = |function_name: &str, call_depth| CallMetrics::<CurrentNetwork> {
+            program_id: ProgramID::from_str("hello.aleo").unwrap(),
+            function_name: Identifier::from_str(function_name).unwrap(),
+            num_instructions: 0,
+            num_request_constraints: 0,
+            num_function_constraints: 0,
+            num_response_constraints: 0,
+            call_depth,
+        };
+
+        // Construct the call metrics in the order the calls completed, for the calls:
+        // a -> (b -> (c, d), e)
+        let metrics = [sample("c", 2), sample("d", 2), sample("b", 1), sample("e", 1), sample("a", 0)];
+        let order = to_call_order(&metrics)
+            .into_iter()
+            .map(|index| metrics[index].function_name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(order, ["a", "b", "c", "d", "e"]);
+    }
+}
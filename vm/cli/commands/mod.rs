@@ -15,9 +15,23 @@
 pub mod build;
 pub use build::*;
 
+#[cfg(any(feature = "rocks", feature = "kv"))]
+pub mod check;
+#[cfg(any(feature = "rocks", feature = "kv"))]
+pub use check::*;
+
 pub mod clean;
 pub use clean::*;
 
+pub mod debug;
+pub use debug::*;
+
+pub mod deploy;
+pub use deploy::*;
+
+pub mod devnet;
+pub use devnet::*;
+
 pub mod execute;
 pub use execute::*;
 
@@ -34,6 +48,7 @@ use crate::{
     console::program::{Identifier, Locator, ProgramID, Value},
     ledger::block::Transaction,
     package::Package,
+    synthesizer::process::{CallMetrics, InstructionMetrics},
 };
 
 use anyhow::Result;
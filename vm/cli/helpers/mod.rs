@@ -15,5 +15,8 @@
 pub mod env;
 pub use env::*;
 
+pub mod transaction;
+pub use transaction::*;
+
 pub mod updater;
 pub use updater::*;
@@ -0,0 +1,137 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    cli::CurrentNetwork,
+    console::{
+        account::PrivateKey,
+        program::{Plaintext, Record},
+        types::Field,
+    },
+    ledger::{
+        block::{Fee, Transaction},
+        query::Query,
+        store::{
+            helpers::memory::{BlockMemory, ConsensusMemory},
+            ConsensusStore,
+        },
+    },
+    package::Package,
+    synthesizer::VM,
+};
+
+use anyhow::{ensure, Result};
+use clap::Args;
+use colored::Colorize;
+use rand::{CryptoRng, Rng};
+use std::path::PathBuf;
+
+/// A VM without a ledger, which is used to authorize and prove fees.
+pub type FeeVM = VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+/// The options for paying the fee of a transaction.
+#[derive(Debug, Args)]
+pub struct FeeOptions {
+    /// The priority fee in microcredits, which is paid on top of the base fee.
+    #[clap(long, default_value = "0")]
+    pub priority_fee: u64,
+    /// The record to pay the fee with, privately. By default, the fee is paid from the public balance.
+    #[clap(long)]
+    pub fee_record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+}
+
+impl FeeOptions {
+    /// Returns a VM, without a ledger, loaded with the imports of the given package.
+    pub fn load_vm(package: &Package<CurrentNetwork>) -> Result<FeeVM> {
+        let vm = FeeVM::from(ConsensusStore::open(None)?)?;
+        for import_program in package.get_imports()? {
+            vm.process().write().add_program(&import_program)?;
+        }
+        Ok(vm)
+    }
+
+    /// Returns the fee for the deployment or execution with the given ID, proven against the state at the endpoint.
+    pub fn prove_fee<R: Rng + CryptoRng>(
+        &self,
+        vm: &FeeVM,
+        private_key: &PrivateKey<CurrentNetwork>,
+        base_fee_in_microcredits: u64,
+        deployment_or_execution_id: Field<CurrentNetwork>,
+        endpoint: &str,
+        rng: &mut R,
+    ) -> Result<Fee<CurrentNetwork>> {
+        // Authorize the fee.
+        let authorization = match &self.fee_record {
+            Some(fee_record) => vm.authorize_fee_private(
+                private_key,
+                fee_record.clone(),
+                base_fee_in_microcredits,
+                self.priority_fee,
+                deployment_or_execution_id,
+                rng,
+            )?,
+            None => vm.authorize_fee_public(
+                private_key,
+                base_fee_in_microcredits,
+                self.priority_fee,
+                deployment_or_execution_id,
+                rng,
+            )?,
+        };
+        // Prove the fee.
+        vm.execute_fee_authorization(authorization, Some(Query::from(endpoint)), rng)
+    }
+}
+
+/// The options for emitting a transaction.
+#[derive(Debug, Args)]
+pub struct TransactionOptions {
+    /// Writes the transaction to the given file.
+    #[clap(long)]
+    pub store: Option<PathBuf>,
+    /// Broadcasts the transaction to the endpoint.
+    #[clap(long)]
+    pub broadcast: bool,
+}
+
+impl TransactionOptions {
+    /// Returns `true` if the transaction is to be written to a file, or broadcast to the endpoint.
+    pub fn is_stored_or_broadcast(&self) -> bool {
+        self.store.is_some() || self.broadcast
+    }
+
+    /// Writes the transaction to a file, or broadcasts it to the endpoint, or otherwise prints it.
+    pub fn emit(&self, transaction: &Transaction<CurrentNetwork>, endpoint: &str) -> Result<()> {
+        // Write the transaction to the file.
+        if let Some(path) = &self.store {
+            std::fs::write(path, serde_json::to_string_pretty(transaction)?)?;
+            println!("💾 Stored the transaction at {}\n", path.display().to_string().bold());
+        }
+
+        // Broadcast the transaction to the endpoint.
+        if self.broadcast {
+            let url =
+                Query::<CurrentNetwork, BlockMemory<CurrentNetwork>>::rest_url(endpoint, "transaction/broadcast")?;
+            let response = ureq::post(&url).send_json(transaction)?;
+            ensure!(response.status() == 200, "Failed to broadcast the transaction to {url}");
+            println!("📡 Broadcast the transaction to {}\n", url.bold());
+        }
+
+        // Otherwise, print the transaction.
+        if self.store.is_none() && !self.broadcast {
+            println!("{transaction}\n");
+        }
+        Ok(())
+    }
+}
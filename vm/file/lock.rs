@@ -0,0 +1,163 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::types::Field,
+    prelude::{Network, ProgramID},
+};
+
+use anyhow::{anyhow, ensure, Result};
+use core::str::FromStr;
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+const LOCK_FILE_NAME: &str = "program.lock";
+
+/// A resolved dependency, as recorded in the lock file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockedDependency<N: Network> {
+    /// The program ID.
+    program_id: ProgramID<N>,
+    /// The edition of the program on the ledger, or `None` if the program is from a local package.
+    edition: Option<u16>,
+    /// The checksum of the program.
+    checksum: Field<N>,
+}
+
+impl<N: Network> LockedDependency<N> {
+    /// Initializes a new locked dependency.
+    pub const fn new(program_id: ProgramID<N>, edition: Option<u16>, checksum: Field<N>) -> Self {
+        Self { program_id, edition, checksum }
+    }
+
+    /// Returns the program ID.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the edition of the program on the ledger, or `None` if the program is from a local package.
+    pub const fn edition(&self) -> Option<u16> {
+        self.edition
+    }
+
+    /// Returns the checksum of the program.
+    pub const fn checksum(&self) -> &Field<N> {
+        &self.checksum
+    }
+}
+
+pub struct LockFile<N: Network> {
+    /// The file path.
+    path: PathBuf,
+    /// The resolved dependencies, in the order they are imported.
+    dependencies: Vec<LockedDependency<N>>,
+}
+
+impl<N: Network> LockFile<N> {
+    /// Creates a lock file with the given directory path and resolved dependencies, replacing any existing lock file.
+    pub fn create(directory: &Path, dependencies: Vec<LockedDependency<N>>) -> Result<Self> {
+        // Ensure the directory path exists.
+        ensure!(directory.exists(), "The program directory does not exist: '{}'", directory.display());
+
+        // Construct the lock file string.
+        let entries = dependencies
+            .iter()
+            .map(|dependency| {
+                let mut entry = serde_json::Map::new();
+                entry.insert("program".to_string(), dependency.program_id.to_string().into());
+                if let Some(edition) = dependency.edition {
+                    entry.insert("edition".to_string(), edition.into());
+                }
+                entry.insert("checksum".to_string(), dependency.checksum.to_string().into());
+                serde_json::Value::Object(entry)
+            })
+            .collect::<Vec<_>>();
+        let lock_string = serde_json::to_string_pretty(&serde_json::json!({ "dependencies": entries }))?;
+
+        // Construct the file path.
+        let path = directory.join(LOCK_FILE_NAME);
+        // Write the file.
+        File::create(&path)?.write_all(format!("{lock_string}\n").as_bytes())?;
+
+        // Return the lock file.
+        Ok(Self { path, dependencies })
+    }
+
+    /// Opens the lock file for reading.
+    pub fn open(directory: &Path) -> Result<Self> {
+        // Ensure the directory path exists.
+        ensure!(directory.exists(), "The program directory does not exist: '{}'", directory.display());
+
+        // Construct the file path.
+        let path = directory.join(LOCK_FILE_NAME);
+        // Ensure the file path exists.
+        ensure!(path.exists(), "Lock file is missing: '{}'", path.display());
+
+        // Read the file to a string.
+        let lock_string = fs::read_to_string(&path)?;
+        let json: serde_json::Value = serde_json::from_str(&lock_string)?;
+
+        // Retrieve the resolved dependencies.
+        let entries = json["dependencies"].as_array().ok_or_else(|| anyhow!("Dependencies not found."))?;
+        let dependencies = entries
+            .iter()
+            .map(|entry| {
+                // Retrieve the program ID.
+                let program_id = entry["program"].as_str().ok_or_else(|| anyhow!("Program ID not found."))?;
+                // Retrieve the edition, if the program is from the ledger.
+                let edition = match entry.get("edition") {
+                    Some(edition) => Some(u16::try_from(edition.as_u64().ok_or_else(|| anyhow!("Invalid edition."))?)?),
+                    None => None,
+                };
+                // Retrieve the checksum.
+                let checksum = entry["checksum"].as_str().ok_or_else(|| anyhow!("Checksum not found."))?;
+                Ok(LockedDependency::new(ProgramID::from_str(program_id)?, edition, Field::from_str(checksum)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Return the lock file.
+        Ok(Self { path, dependencies })
+    }
+
+    /// Returns `true` if the lock file exists at the given path.
+    pub fn exists_at(directory: &Path) -> bool {
+        // Construct the file path.
+        let path = directory.join(LOCK_FILE_NAME);
+        // Return the result.
+        path.is_file() && path.exists()
+    }
+
+    /// Returns the lock file name.
+    pub const fn file_name() -> &'static str {
+        LOCK_FILE_NAME
+    }
+
+    /// Returns the file path.
+    pub const fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns the resolved dependencies, in the order they are imported.
+    pub fn dependencies(&self) -> &[LockedDependency<N>] {
+        &self.dependencies
+    }
+
+    /// Returns the resolved dependency for the given program ID, if it exists.
+    pub fn get(&self, program_id: &ProgramID<N>) -> Option<&LockedDependency<N>> {
+        self.dependencies.iter().find(|dependency| dependency.program_id == *program_id)
+    }
+}
@@ -13,12 +13,14 @@
 // limitations under the License.
 
 use crate::{
+    console::types::Field,
     prelude::{Network, ProgramID},
     synthesizer::Program,
 };
 
-use anyhow::{anyhow, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use core::str::FromStr;
+use indexmap::IndexMap;
 use std::{
     fs::{self, File},
     io::Write,
@@ -27,11 +29,50 @@ use std::{
 
 const MANIFEST_FILE_NAME: &str = "program.json";
 
+/// A dependency of the program, as declared in the `dependencies` section of the manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Dependency<N: Network> {
+    /// The main program of the package at the given directory, relative to the manifest.
+    Local { path: PathBuf },
+    /// A program fetched from the ledger, optionally pinned to the given edition and checksum.
+    Network { edition: Option<u16>, checksum: Option<Field<N>> },
+}
+
+impl<N: Network> Dependency<N> {
+    /// Parses the dependency from its manifest entry.
+    fn from_json(json: &serde_json::Value) -> Result<Self> {
+        // Local dependencies are declared with their path.
+        if let Some(path) = json.get("path") {
+            let path = path.as_str().ok_or_else(|| anyhow!("Dependency path must be a string."))?;
+            return Ok(Self::Local { path: PathBuf::from(path) });
+        }
+        // Otherwise, the dependency is fetched from the ledger.
+        let edition = match json.get("edition") {
+            Some(edition) => Some(
+                edition
+                    .as_u64()
+                    .and_then(|edition| u16::try_from(edition).ok())
+                    .ok_or_else(|| anyhow!("Dependency edition must be a u16."))?,
+            ),
+            None => None,
+        };
+        let checksum = match json.get("checksum") {
+            Some(checksum) => Some(Field::from_str(
+                checksum.as_str().ok_or_else(|| anyhow!("Dependency checksum must be a string."))?,
+            )?),
+            None => None,
+        };
+        Ok(Self::Network { edition, checksum })
+    }
+}
+
 pub struct Manifest<N: Network> {
     /// The file path.
     path: PathBuf,
     /// The program ID.
     program_id: ProgramID<N>,
+    /// The dependencies, keyed by program ID.
+    dependencies: IndexMap<ProgramID<N>, Dependency<N>>,
 }
 
 impl<N: Network> Manifest<N> {
@@ -62,7 +103,7 @@ impl<N: Network> Manifest<N> {
         File::create(&path)?.write_all(manifest_string.as_bytes())?;
 
         // Return the manifest file.
-        Ok(Self { path, program_id: *id })
+        Ok(Self { path, program_id: *id, dependencies: IndexMap::new() })
     }
 
     /// Opens the manifest file for reading.
@@ -85,8 +126,24 @@ impl<N: Network> Manifest<N> {
         // Ensure the program name is valid.
         ensure!(!Program::is_reserved_keyword(id.name()), "Program name is invalid (reserved): {id}");
 
+        // Retrieve the dependencies.
+        let mut dependencies = IndexMap::new();
+        if let Some(entries) = json.get("dependencies") {
+            let entries = entries.as_object().ok_or_else(|| anyhow!("Dependencies must be an object."))?;
+            for (dependency_id, dependency) in entries {
+                let dependency_id = ProgramID::from_str(dependency_id)?;
+                // Ensure the program does not depend on itself.
+                ensure!(dependency_id != id, "Program '{id}' cannot depend on itself");
+                // Parse the dependency.
+                match Dependency::from_json(dependency) {
+                    Ok(dependency) => dependencies.insert(dependency_id, dependency),
+                    Err(error) => bail!("Invalid dependency '{dependency_id}' in '{}': {error}", path.display()),
+                };
+            }
+        }
+
         // Return the manifest file.
-        Ok(Self { path, program_id: id })
+        Ok(Self { path, program_id: id, dependencies })
     }
 
     /// Returns `true` if the manifest file exists at the given path.
@@ -111,4 +168,9 @@ impl<N: Network> Manifest<N> {
     pub const fn program_id(&self) -> &ProgramID<N> {
         &self.program_id
     }
+
+    /// Returns the dependencies, keyed by program ID.
+    pub const fn dependencies(&self) -> &IndexMap<ProgramID<N>, Dependency<N>> {
+        &self.dependencies
+    }
 }
@@ -18,8 +18,11 @@ pub use aleo::AleoFile;
 mod avm;
 pub use avm::AVMFile;
 
+mod lock;
+pub use lock::{LockFile, LockedDependency};
+
 mod manifest;
-pub use manifest::Manifest;
+pub use manifest::{Dependency, Manifest};
 
 mod prover;
 pub use prover::ProverFile;
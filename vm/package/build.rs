@@ -14,8 +14,12 @@
 
 use super::*;
 
+use crate::file::{Dependency, LockFile, LockedDependency};
 use snarkvm_utilities::DeserializeExt;
 
+/// The default endpoint from which ledger dependencies are fetched.
+pub const DEFAULT_DEPENDENCY_ENDPOINT: &str = "https://api.explorer.aleo.org/v1";
+
 pub struct BuildRequest<N: Network> {
     program: Program<N>,
     imports: Vec<Program<N>>,
@@ -148,11 +152,17 @@ impl<'de, N: Network> Deserialize<'de> for BuildResponse<N> {
 }
 
 impl<N: Network> Package<N> {
-    /// Builds the package.
+    /// Builds the package, fetching any unresolved ledger dependencies from the given dependency endpoint.
     pub fn build<A: crate::circuit::Aleo<Network = N, BaseField = N::Field>>(
         &self,
         endpoint: Option<String>,
+        dependency_endpoint: &str,
     ) -> Result<()> {
+        // Resolve the dependencies declared in the manifest, if the lock file is missing or stale.
+        if self.is_resolution_required()? {
+            self.resolve_dependencies(dependency_endpoint)?;
+        }
+
         // Skip the 'build' if the program is already built.
         if !self.is_build_required::<A>() {
             return Ok(());
@@ -283,10 +293,238 @@ impl<N: Network> Package<N> {
     }
 }
 
+impl<N: Network> Package<N> {
+    /// Resolves the dependencies declared in the manifest, fetching ledger dependencies from the given endpoint.
+    /// The resolved programs are written to the imports directory, and their checksums to the lock file,
+    /// in the order they must be imported. Returns the resolved programs in that order.
+    ///
+    /// If the manifest does not declare any dependencies, the imports directory is used as is.
+    pub fn resolve_dependencies(&self, endpoint: &str) -> Result<Vec<Program<N>>> {
+        // If there are no declared dependencies, the imports are expected to exist in the imports directory.
+        if self.manifest_file.dependencies().is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Open the lock file, if it exists.
+        let lock_file = match LockFile::<N>::exists_at(&self.directory) {
+            true => Some(LockFile::open(&self.directory)?),
+            false => None,
+        };
+
+        // Resolve the imports of the main program, followed by any remaining declared dependencies.
+        let mut resolver = DependencyResolver {
+            query: Query::<_, BlockMemory<_>>::from(endpoint),
+            lock_file,
+            visiting: IndexSet::from([*self.program_id()]),
+            resolved: IndexMap::new(),
+        };
+        resolver.resolve_imports(self.program(), &self.directory, self.manifest_file.dependencies())?;
+        for (program_id, dependency) in self.manifest_file.dependencies() {
+            resolver.resolve(program_id, dependency, &self.directory)?;
+        }
+
+        // Write the resolved programs to the imports directory.
+        let imports_directory = self.imports_directory();
+        if !imports_directory.exists() {
+            std::fs::create_dir_all(&imports_directory)?;
+        }
+        for (program_id, (program, _)) in &resolver.resolved {
+            std::fs::write(imports_directory.join(program_id.to_string()), program.to_string())?;
+        }
+
+        // Write the lock file.
+        let (programs, locked): (Vec<_>, Vec<_>) = resolver.resolved.into_values().unzip();
+        LockFile::create(&self.directory, locked)?;
+
+        Ok(programs)
+    }
+
+    /// Returns `true` if the manifest declares dependencies, and the lock file is missing or stale.
+    /// The lock file is stale if it does not cover the imports and declared dependencies of the program,
+    /// if a local dependency changed, or if an import in the imports directory does not match its checksum.
+    pub fn is_resolution_required(&self) -> Result<bool> {
+        // If there are no declared dependencies, the imports directory is used as is.
+        if self.manifest_file.dependencies().is_empty() {
+            return Ok(false);
+        }
+        // If there is no lock file, the dependencies must be resolved.
+        if !LockFile::<N>::exists_at(&self.directory) {
+            return Ok(true);
+        }
+        let lock_file = LockFile::<N>::open(&self.directory)?;
+
+        // Ensure the lock file covers the imports of the program, and the declared dependencies.
+        let credits_program_id = ProgramID::<N>::from_str("credits.aleo")?;
+        let is_locked =
+            |program_id: &ProgramID<N>| *program_id == credits_program_id || lock_file.get(program_id).is_some();
+        if !self.program().imports().keys().chain(self.manifest_file.dependencies().keys()).all(is_locked) {
+            return Ok(true);
+        }
+
+        // Ensure the declared dependencies match the lock file.
+        for (program_id, dependency) in self.manifest_file.dependencies() {
+            let Some(locked) = lock_file.get(program_id) else { continue };
+            let is_stale = match dependency {
+                Dependency::Local { path } => {
+                    let package = Package::<N>::open(&self.directory.join(path))?;
+                    locked.edition().is_some() || *locked.checksum() != package.program().to_checksum()?
+                }
+                Dependency::Network { edition, checksum } => {
+                    edition.is_some_and(|edition| locked.edition() != Some(edition))
+                        || checksum.is_some_and(|checksum| *locked.checksum() != checksum)
+                }
+            };
+            if is_stale {
+                return Ok(true);
+            }
+        }
+
+        // Ensure the imports directory contains each locked program.
+        let imports_directory = self.imports_directory();
+        for locked in lock_file.dependencies() {
+            match AleoFile::<N>::open(&imports_directory, locked.program_id(), false) {
+                Ok(import) if import.program().to_checksum()? == *locked.checksum() => continue,
+                _ => return Ok(true),
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Resolves the dependency graph of a package, in the order the programs must be imported.
+struct DependencyResolver<N: Network> {
+    /// The query used to fetch ledger dependencies.
+    query: Query<N, BlockMemory<N>>,
+    /// The lock file from a previous resolution, if it exists.
+    lock_file: Option<LockFile<N>>,
+    /// The programs whose imports are being resolved, used to detect cycles.
+    visiting: IndexSet<ProgramID<N>>,
+    /// The resolved programs, in the order they must be imported.
+    resolved: IndexMap<ProgramID<N>, (Program<N>, LockedDependency<N>)>,
+}
+
+impl<N: Network> DependencyResolver<N> {
+    /// Resolves the given dependency, declared in the manifest at the given directory, after its own imports.
+    fn resolve(&mut self, program_id: &ProgramID<N>, dependency: &Dependency<N>, directory: &Path) -> Result<()> {
+        // Skip 'credits.aleo', as the process is already loaded with it, and any resolved programs.
+        if program_id == &ProgramID::from_str("credits.aleo")? || self.resolved.contains_key(program_id) {
+            return Ok(());
+        }
+        // Ensure the dependency graph is acyclic.
+        if !self.visiting.insert(*program_id) {
+            let cycle = self.visiting.iter().map(ToString::to_string).collect::<Vec<_>>().join(" -> ");
+            bail!("Found a cyclic dependency: {cycle} -> {program_id}");
+        }
+
+        // Retrieve the program, from the previous resolution if it was locked.
+        let locked = self.lock_file.as_ref().and_then(|lock_file| lock_file.get(program_id));
+        let (program, edition) = match dependency {
+            Dependency::Local { path } => {
+                // Open the package.
+                let package = Package::<N>::open(&directory.join(path))?;
+                ensure!(
+                    package.program_id() == program_id,
+                    "Expected '{program_id}' in '{}', found '{}'",
+                    package.directory().display(),
+                    package.program_id()
+                );
+                // Ensure the program matches the lock file, if it was previously resolved.
+                if let Some(locked) = locked {
+                    ensure!(
+                        locked.edition().is_none() && *locked.checksum() == package.program().to_checksum()?,
+                        "'{program_id}' changed since it was locked in '{}' (remove the lock file to update it)",
+                        LockFile::<N>::file_name()
+                    );
+                }
+                // Resolve the imports of the package, using the dependencies declared in its manifest.
+                self.resolve_imports(package.program(), package.directory(), package.manifest_file().dependencies())?;
+                (package.program().clone(), None)
+            }
+            Dependency::Network { edition, checksum } => {
+                // Fetch the pinned edition, or else the locked edition, or else the latest edition of the program.
+                let (program, edition) = match edition.or_else(|| locked.and_then(|locked| locked.edition())) {
+                    Some(edition) => (self.query.get_program_for_edition(program_id, edition)?, edition),
+                    None => (self.query.get_program(program_id)?, self.query.get_program_edition(program_id)?),
+                };
+                ensure!(program.id() == program_id, "Fetched '{}' instead of '{program_id}'", program.id());
+                // Ensure the program matches the pinned checksum.
+                if let Some(checksum) = checksum {
+                    ensure!(
+                        program.to_checksum()? == *checksum,
+                        "'{program_id}' does not match the pinned checksum '{checksum}'"
+                    );
+                }
+                // Ensure the program matches the lock file, if this edition was previously resolved.
+                if let Some(locked) = locked.filter(|locked| locked.edition() == Some(edition)) {
+                    ensure!(
+                        *locked.checksum() == program.to_checksum()?,
+                        "'{program_id}' changed since it was locked in '{}' (remove the lock file to update it)",
+                        LockFile::<N>::file_name()
+                    );
+                }
+                // Resolve the imports of the program from the ledger.
+                self.resolve_imports(&program, directory, &IndexMap::new())?;
+                (program, Some(edition))
+            }
+        };
+
+        // Record the program, now that its imports are resolved.
+        self.visiting.swap_remove(program_id);
+        let locked = LockedDependency::new(*program_id, edition, program.to_checksum()?);
+        self.resolved.insert(*program_id, (program, locked));
+        Ok(())
+    }
+
+    /// Resolves the imports of the given program, using the given declared dependencies, or the ledger otherwise.
+    fn resolve_imports(
+        &mut self,
+        program: &Program<N>,
+        directory: &Path,
+        dependencies: &IndexMap<ProgramID<N>, Dependency<N>>,
+    ) -> Result<()> {
+        for import_id in program.imports().keys() {
+            match dependencies.get(import_id) {
+                Some(dependency) => self.resolve(import_id, dependency, directory)?,
+                None => self.resolve(import_id, &Dependency::Network { edition: None, checksum: None }, directory)?,
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::prelude::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
     type CurrentAleo = snarkvm_circuit::network::AleoV0;
 
+    /// Writes a package with the given main program and manifest dependencies to a new temporary directory.
+    fn sample_package_with_dependencies(
+        program: &str,
+        dependencies: serde_json::Value,
+    ) -> (PathBuf, Package<CurrentNetwork>) {
+        let directory = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+        (directory.clone(), write_package_with_dependencies(&directory, program, dependencies))
+    }
+
+    /// Writes a package with the given main program and manifest dependencies to the given directory.
+    fn write_package_with_dependencies(
+        directory: &Path,
+        program: &str,
+        dependencies: serde_json::Value,
+    ) -> Package<CurrentNetwork> {
+        let program = Program::<CurrentNetwork>::from_str(program).unwrap();
+
+        // Write the main program and the manifest.
+        std::fs::write(directory.join("main.aleo"), program.to_string()).unwrap();
+        let manifest = serde_json::json!({ "program": program.id().to_string(), "dependencies": dependencies });
+        std::fs::write(directory.join("program.json"), manifest.to_string()).unwrap();
+
+        Package::open(directory).unwrap()
+    }
+
     #[test]
     fn test_build() {
         // Samples a new package at a temporary directory.
@@ -295,7 +533,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -311,7 +549,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -328,11 +566,109 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
         // Proactively remove the temporary directory (to conserve space).
         std::fs::remove_dir_all(directory).unwrap();
     }
+
+    #[test]
+    fn test_resolve_local_dependencies() {
+        // Samples a `token.aleo` package, and a `wallet.aleo` package which depends on it.
+        let (token_directory, token_package) = crate::package::test_helpers::sample_token_package();
+        let (directory, package) = sample_package_with_dependencies(
+            "
+import token.aleo;
+
+program wallet.aleo;
+
+function transfer:
+    input r0 as token.aleo/token.record;
+    input r1 as address.private;
+    input r2 as u64.private;
+    call token.aleo/transfer r0 r1 r2 into r3 r4;
+    output r3 as token.aleo/token.record;
+    output r4 as token.aleo/token.record;",
+            serde_json::json!({ "token.aleo": { "path": token_directory } }),
+        );
+        assert_eq!(package.manifest_file().dependencies().len(), 1);
+
+        // Resolve the dependencies.
+        let programs = package.resolve_dependencies(DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
+        assert_eq!(programs, vec![token_package.program().clone()]);
+
+        // Ensure the import and the lock file were written.
+        assert!(package.imports_directory().join("token.aleo").exists());
+        let lock_file = LockFile::<CurrentNetwork>::open(&directory).unwrap();
+        assert_eq!(lock_file.dependencies(), [LockedDependency::new(
+            *token_package.program_id(),
+            None,
+            token_package.program().to_checksum().unwrap()
+        )]);
+
+        // Ensure the process loads the resolved import.
+        assert!(package.get_process().unwrap().contains_program(token_package.program_id()));
+        // Ensure the dependencies are not resolved again, as the lock file is up to date.
+        assert!(!package.is_resolution_required().unwrap());
+
+        // Change the local dependency.
+        let changed_program = format!(
+            "{}\n\nfunction noop:\n    input r0 as u8.private;\n    output r0 as u8.private;\n",
+            token_package.program()
+        );
+        std::fs::write(token_directory.join("main.aleo"), changed_program).unwrap();
+        // Ensure the lock file is stale, and the change is not locked silently.
+        assert!(package.is_resolution_required().unwrap());
+        let error = package.resolve_dependencies(DEFAULT_DEPENDENCY_ENDPOINT).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "'token.aleo' changed since it was locked in 'program.lock' (remove the lock file to update it)"
+        );
+
+        // Proactively remove the temporary directories (to conserve space).
+        std::fs::remove_dir_all(directory).unwrap();
+        std::fs::remove_dir_all(token_directory).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_cyclic_dependencies() {
+        let first_directory = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+        let second_directory = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+
+        // Samples a `first.aleo` package and a `second.aleo` package, which depend on each other.
+        let package = write_package_with_dependencies(
+            &first_directory,
+            "
+import second.aleo;
+
+program first.aleo;
+
+function main:
+    input r0 as u8.private;
+    output r0 as u8.private;",
+            serde_json::json!({ "second.aleo": { "path": second_directory } }),
+        );
+        write_package_with_dependencies(
+            &second_directory,
+            "
+import first.aleo;
+
+program second.aleo;
+
+function main:
+    input r0 as u8.private;
+    output r0 as u8.private;",
+            serde_json::json!({ "first.aleo": { "path": first_directory } }),
+        );
+
+        // Ensure the cycle is detected.
+        let error = package.resolve_dependencies(DEFAULT_DEPENDENCY_ENDPOINT).unwrap_err();
+        assert_eq!(error.to_string(), "Found a cyclic dependency: first.aleo -> second.aleo -> first.aleo");
+
+        // Proactively remove the temporary directories (to conserve space).
+        std::fs::remove_dir_all(first_directory).unwrap();
+        std::fs::remove_dir_all(second_directory).unwrap();
+    }
 }
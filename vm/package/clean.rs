@@ -65,7 +65,7 @@ mod tests {
         assert!(!package.build_directory().exists());
 
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
 
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
@@ -91,7 +91,7 @@ mod tests {
         assert!(!package.build_directory().exists());
 
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
 
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
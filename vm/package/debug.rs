@@ -0,0 +1,197 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> Package<N> {
+    /// Evaluates a program function with the given inputs, with the given debugger attached.
+    ///
+    /// The function is evaluated without synthesizing circuits. If the function has a finalize scope,
+    /// it is then finalized on top of empty mappings, so the debugger is also notified of each finalize command.
+    pub fn debug<A: crate::circuit::Aleo<Network = N, BaseField = N::Field>, R: Rng + CryptoRng>(
+        &self,
+        private_key: &PrivateKey<N>,
+        function_name: Identifier<N>,
+        inputs: &[Value<N>],
+        debugger: Arc<dyn Debugger<N>>,
+        rng: &mut R,
+    ) -> Result<Response<N>> {
+        // Retrieve the main program.
+        let program = self.program();
+        // Retrieve the program ID.
+        let program_id = program.id();
+        // Ensure that the function exists.
+        if !program.contains_function(&function_name) {
+            bail!("Function '{function_name}' does not exist.")
+        }
+
+        #[cfg(feature = "aleo-cli")]
+        println!("🐞 Debugging '{}'...\n", Locator::new(*program_id, function_name).to_string().bold());
+
+        // Construct the process.
+        let process = self.get_process()?;
+        // Authorize the function call.
+        let authorization = process.authorize::<A, R>(private_key, program_id, function_name, inputs.iter(), rng)?;
+
+        // Attach the debugger.
+        process.attach_debugger(debugger);
+        // Evaluate the function, and then finalize it.
+        let response = process
+            .evaluate::<A>(authorization.replicate())
+            .and_then(|response| self.debug_finalize(&process, &authorization).map(|_| response));
+        // Detach the debugger.
+        process.detach_debugger();
+
+        response
+    }
+
+    /// Finalizes the (unproven) execution for the given authorization, on top of empty mappings.
+    fn debug_finalize(&self, process: &Process<N>, authorization: &Authorization<N>) -> Result<()> {
+        // Initialize an in-memory finalize store.
+        let store = FinalizeStore::<N, FinalizeMemory<N>>::open(None)?;
+        // Initialize the mappings of each program in the process.
+        let mut programs = vec![process.get_program("credits.aleo")?.clone()];
+        programs.extend(self.get_imports()?);
+        programs.push(self.program().clone());
+        for program in programs {
+            if store.contains_program_confirmed(program.id())? {
+                continue;
+            }
+            for mapping_name in program.mappings().keys() {
+                store.initialize_mapping(*program.id(), *mapping_name)?;
+            }
+        }
+
+        // Construct the execution from the authorized transitions.
+        let execution = Execution::from(authorization.transitions().into_values(), Default::default(), None)?;
+        // Finalize the execution, at the genesis state.
+        process.finalize_execution(FinalizeGlobalState::new_genesis::<N>()?, &store, &execution, None)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthesizer::{
+        process::{DebugLocation, RegistersView},
+        program::{Command, Instruction},
+    };
+    use snarkvm_utilities::TestRng;
+
+    use std::sync::Mutex;
+
+    type CurrentAleo = snarkvm_circuit::network::AleoV0;
+    type CurrentNetwork = snarkvm_console::network::MainnetV0;
+
+    /// A debugger that records the location of each instruction evaluated and each command finalized.
+    #[derive(Default)]
+    struct SampleDebugger(Mutex<Vec<String>>);
+
+    impl Debugger<CurrentNetwork> for SampleDebugger {
+        fn before_instruction(
+            &self,
+            location: &DebugLocation<CurrentNetwork>,
+            _instruction: &Instruction<CurrentNetwork>,
+            _registers: RegistersView<CurrentNetwork>,
+        ) -> Result<()> {
+            self.0.lock().unwrap().push(location.to_string());
+            Ok(())
+        }
+
+        fn before_command(
+            &self,
+            location: &DebugLocation<CurrentNetwork>,
+            _command: &Command<CurrentNetwork>,
+            _registers: RegistersView<CurrentNetwork>,
+        ) -> Result<()> {
+            self.0.lock().unwrap().push(location.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        // Samples a new package at a temporary directory.
+        let (directory, package) = crate::package::test_helpers::sample_token_package();
+
+        // Initialize an RNG.
+        let rng = &mut TestRng::default();
+        // Sample the function inputs.
+        let (private_key, function_name, inputs) =
+            crate::package::test_helpers::sample_package_run(package.program_id());
+        // Debug the program function.
+        let debugger = Arc::new(SampleDebugger::default());
+        let response =
+            package.debug::<CurrentAleo, _>(&private_key, function_name, &inputs, debugger.clone(), rng).unwrap();
+
+        // Ensure the function was evaluated.
+        let function = package.program().get_function(&function_name).unwrap();
+        assert_eq!(response.outputs().len(), function.outputs().len());
+        // Ensure the debugger was notified of each instruction in the function.
+        let expected = (0..function.instructions().len())
+            .map(|index| format!("{}/{function_name}:{index}", package.program_id()))
+            .collect::<Vec<_>>();
+        assert_eq!(*debugger.0.lock().unwrap(), expected);
+
+        // Proactively remove the temporary directory (to conserve space).
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_debug_finalize() {
+        // Samples a new package with a function that has a finalize scope.
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program counter.aleo;
+
+mapping counts:
+    key as u32.public;
+    value as u32.public;
+
+function increment:
+    input r0 as u32.public;
+    async increment r0 into r1;
+    output r1 as counter.aleo/increment.future;
+
+finalize increment:
+    input r0 as u32.public;
+    get.or_use counts[0u32] 0u32 into r1;
+    add r0 r1 into r2;
+    set r2 into counts[0u32];",
+        )
+        .unwrap();
+        let (directory, package) = crate::package::test_helpers::sample_package_with_program_and_imports(&program, &[]);
+
+        // Initialize an RNG.
+        let rng = &mut TestRng::default();
+        // Debug the program function.
+        let private_key = PrivateKey::new(rng).unwrap();
+        let function_name = Identifier::from_str("increment").unwrap();
+        let inputs = [Value::from_str("1u32").unwrap()];
+        let debugger = Arc::new(SampleDebugger::default());
+        package.debug::<CurrentAleo, _>(&private_key, function_name, &inputs, debugger.clone(), rng).unwrap();
+
+        // Ensure the debugger was notified of each instruction in the function, and each command in its finalize scope.
+        assert_eq!(*debugger.0.lock().unwrap(), vec![
+            "counter.aleo/increment:0",
+            "counter.aleo/increment/finalize:0",
+            "counter.aleo/increment/finalize:1",
+            "counter.aleo/increment/finalize:2",
+        ]);
+
+        // Proactively remove the temporary directory (to conserve space).
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
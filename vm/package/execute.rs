@@ -25,6 +25,23 @@ impl<N: Network> Package<N> {
         inputs: &[Value<N>],
         rng: &mut R,
     ) -> Result<(Response<N>, Execution<N>, Vec<CallMetrics<N>>)> {
+        // Execute the function, and discard the instruction metrics.
+        let (response, execution, call_metrics, _) =
+            self.execute_and_profile::<A, R>(endpoint, private_key, function_name, inputs, rng)?;
+        Ok((response, execution, call_metrics))
+    }
+
+    /// Executes a program function with the given inputs, and returns the circuit sizes attributed to
+    /// each instruction, for each call in the call metrics.
+    #[allow(clippy::type_complexity)]
+    pub fn execute_and_profile<A: crate::circuit::Aleo<Network = N, BaseField = N::Field>, R: Rng + CryptoRng>(
+        &self,
+        endpoint: String,
+        private_key: &PrivateKey<N>,
+        function_name: Identifier<N>,
+        inputs: &[Value<N>],
+        rng: &mut R,
+    ) -> Result<(Response<N>, Execution<N>, Vec<CallMetrics<N>>, Vec<Vec<InstructionMetrics>>)> {
         // Retrieve the main program.
         let program = self.program();
         // Retrieve the program ID.
@@ -37,7 +54,7 @@ impl<N: Network> Package<N> {
         // Build the package, if the package requires building.
         // TODO (howardwu): We currently choose only to support local synthesis of keys due to performance.
         // self.build::<A>(Some(endpoint.clone()))?;
-        self.build::<A>(None)?;
+        self.build::<A>(None, &endpoint)?;
 
         // Prepare the locator (even if logging is disabled, to sanity check the locator is well-formed).
         let locator = Locator::<N>::from_str(&format!("{program_id}/{function_name}"))?;
@@ -98,15 +115,16 @@ impl<N: Network> Package<N> {
         // Execute the circuit.
         let (response, mut trace) = process.execute::<A, R>(authorization, rng)?;
 
-        // Retrieve the call metrics.
+        // Retrieve the call metrics and instruction metrics.
         let call_metrics = trace.call_metrics().to_vec();
+        let instruction_metrics = trace.instruction_metrics().to_vec();
 
         // Prepare the trace.
         trace.prepare(Query::<_, BlockMemory<_>>::from(endpoint))?;
         // Prove the execution.
         let execution = trace.prove_execution::<A, R>(&locator.to_string(), rng)?;
-        // Return the response, execution, and call metrics.
-        Ok((response, execution, call_metrics))
+        // Return the response, execution, call metrics, and instruction metrics.
+        Ok((response, execution, call_metrics, instruction_metrics))
     }
 }
 
@@ -127,7 +145,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -156,7 +174,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -185,7 +203,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -14,12 +14,13 @@
 
 mod build;
 mod clean;
+mod debug;
 mod deploy;
 mod execute;
 mod is_build_required;
 mod run;
 
-pub use build::{BuildRequest, BuildResponse};
+pub use build::{BuildRequest, BuildResponse, DEFAULT_DEPENDENCY_ENDPOINT};
 pub use deploy::{DeployRequest, DeployResponse};
 
 use crate::{
@@ -28,20 +29,40 @@ use crate::{
         network::Network,
         program::{Identifier, Locator, ProgramID, Response, Value},
     },
-    file::{AVMFile, AleoFile, Manifest, ProverFile, VerifierFile, README},
-    ledger::{block::Execution, query::Query, store::helpers::memory::BlockMemory},
+    file::{AVMFile, AleoFile, LockFile, Manifest, ProverFile, VerifierFile, README},
+    ledger::{
+        block::Execution,
+        query::Query,
+        store::{
+            helpers::memory::{BlockMemory, FinalizeMemory},
+            FinalizeStore,
+        },
+    },
     prelude::{Deserialize, Deserializer, Serialize, SerializeStruct, Serializer},
     synthesizer::{
-        process::{Assignments, CallMetrics, CallStack, Process, StackExecute},
-        program::{CallOperator, Instruction, Program},
+        process::{
+            Assignments,
+            Authorization,
+            CallMetrics,
+            CallStack,
+            Debugger,
+            InstructionMetrics,
+            Process,
+            StackExecute,
+        },
+        program::{CallOperator, FinalizeGlobalState, Instruction, Program},
         snark::{ProvingKey, VerifyingKey},
     },
 };
 
 use anyhow::{bail, ensure, Error, Result};
 use core::str::FromStr;
+use indexmap::{IndexMap, IndexSet};
 use rand::{CryptoRng, Rng};
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[cfg(feature = "aleo-cli")]
 use colored::Colorize;
@@ -147,28 +168,75 @@ impl<N: Network> Package<N> {
         self.directory.join("imports")
     }
 
-    /// Returns a new process for the package.
-    pub fn get_process(&self) -> Result<Process<N>> {
-        // Create the process.
-        let mut process = Process::load()?;
-
+    /// Returns the imported programs (excluding `credits.aleo`), in the order they must be added to a process.
+    /// This includes the imports of the imported programs (recursively), each of which precedes the programs importing it.
+    pub fn get_imports(&self) -> Result<Vec<Program<N>>> {
         // Prepare the imports directory.
         let imports_directory = self.imports_directory();
 
         // Initialize the 'credits.aleo' program ID.
         let credits_program_id = ProgramID::<N>::from_str("credits.aleo")?;
 
+        // If there is a lock file, open the import programs in the order they were resolved.
+        if LockFile::<N>::exists_at(&self.directory) {
+            return LockFile::<N>::open(&self.directory)?
+                .dependencies()
+                .iter()
+                .map(|dependency| dependency.program_id())
+                // Skip `credits.aleo` as a process is already loaded with it.
+                .filter(|program_id| *program_id != &credits_program_id)
+                .map(|program_id| Ok(AleoFile::open(&imports_directory, program_id, false)?.program().clone()))
+                .collect();
+        }
+
+        // Otherwise, open the import programs (recursively), ordering each program after its own imports.
+        let mut visiting = IndexSet::from([*self.program_id()]);
+        let mut imports = IndexMap::new();
+        for program_id in self.program().imports().keys() {
+            self.add_import(program_id, &credits_program_id, &mut visiting, &mut imports)?;
+        }
+        Ok(imports.into_values().collect())
+    }
+
+    /// Adds the given import program to the given imports, after its own imports (recursively).
+    fn add_import(
+        &self,
+        program_id: &ProgramID<N>,
+        credits_program_id: &ProgramID<N>,
+        visiting: &mut IndexSet<ProgramID<N>>,
+        imports: &mut IndexMap<ProgramID<N>, Program<N>>,
+    ) -> Result<()> {
+        // Skip `credits.aleo` as a process is already loaded with it, and any added programs.
+        if program_id == credits_program_id || imports.contains_key(program_id) {
+            return Ok(());
+        }
+        // Ensure the imports are acyclic.
+        if !visiting.insert(*program_id) {
+            let cycle = visiting.iter().map(ToString::to_string).collect::<Vec<_>>().join(" -> ");
+            bail!("Found a cyclic import: {cycle} -> {program_id}");
+        }
+
+        // Open the import program, and add its own imports.
+        let program = AleoFile::open(&self.imports_directory(), program_id, false)?.program().clone();
+        for import_id in program.imports().keys() {
+            self.add_import(import_id, credits_program_id, visiting, imports)?;
+        }
+
+        // Add the import program.
+        visiting.swap_remove(program_id);
+        imports.insert(*program_id, program);
+        Ok(())
+    }
+
+    /// Returns a new process for the package.
+    pub fn get_process(&self) -> Result<Process<N>> {
+        // Create the process.
+        let mut process = Process::load()?;
+
         // Add all import programs (in order) to the process.
-        self.program().imports().keys().try_for_each(|program_id| {
-            // Don't add `credits.aleo` as the process is already loaded with it.
-            if program_id != &credits_program_id {
-                // Open the Aleo program file.
-                let import_program_file = AleoFile::open(&imports_directory, program_id, false)?;
-                // Add the import program.
-                process.add_program(import_program_file.program())?;
-            }
-            Ok::<_, Error>(())
-        })?;
+        for import_program in self.get_imports()? {
+            process.add_program(&import_program)?;
+        }
 
         // Add the program to the process.
         process.add_program(self.program())?;
@@ -327,6 +395,32 @@ function double_wrapper_mint:
         sample_package_with_program_and_imports(&grandparent_program, &[child_program, parent_program])
     }
 
+    /// Samples a (temporary) package containing a `main_wrapper.aleo` program which imports `parent.aleo` only,
+    /// where `parent.aleo` imports `child.aleo`.
+    pub(crate) fn sample_transitive_package() -> (PathBuf, Package<CurrentNetwork>) {
+        // Retrieve the child and parent programs from the nested package.
+        let (directory, package) = sample_nested_package();
+        let imports = package.get_imports().unwrap();
+        std::fs::remove_dir_all(directory).unwrap();
+
+        // Initialize the main program.
+        let main_program = Program::<CurrentNetwork>::from_str(
+            "
+import parent.aleo;
+
+program main_wrapper.aleo;
+
+function main:
+    input r0 as address.private;
+    input r1 as u32.private;
+    call parent.aleo/wrapper_mint r0 r1 into r2;",
+        )
+        .unwrap();
+
+        // Sample the package using the main program and imported programs.
+        sample_package_with_program_and_imports(&main_program, &imports)
+    }
+
     /// Samples a (temporary) package containing a `transfer.aleo` program which imports `credits.aleo`.
     pub(crate) fn sample_transfer_package() -> (PathBuf, Package<CurrentNetwork>) {
         // Initialize the imported program.
@@ -525,6 +619,22 @@ mod tests {
         std::fs::remove_dir_all(directory).unwrap();
     }
 
+    #[test]
+    fn test_get_imports_transitive() {
+        // Samples a new package, whose program imports `parent.aleo`, which imports `child.aleo`.
+        let (directory, package) = crate::package::test_helpers::sample_transitive_package();
+
+        // Ensure the imports include the transitive import, preceding the program importing it.
+        let imports = package.get_imports().unwrap();
+        let import_ids = imports.iter().map(|program| program.id().to_string()).collect::<Vec<_>>();
+        assert_eq!(import_ids, ["child.aleo", "parent.aleo"]);
+        // Ensure the process can be loaded with the imports.
+        assert!(package.get_process().is_ok());
+
+        // Proactively remove the temporary directory (to conserve space).
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
     #[test]
     fn test_package_run_and_execute_match() {
         // Initialize the program.
@@ -544,7 +654,7 @@ function bar:
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -76,7 +76,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -100,7 +100,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -124,7 +124,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 
@@ -150,7 +150,7 @@ mod tests {
         // Ensure the build directory does *not* exist.
         assert!(!package.build_directory().exists());
         // Build the package.
-        package.build::<CurrentAleo>(None).unwrap();
+        package.build::<CurrentAleo>(None, DEFAULT_DEPENDENCY_ENDPOINT).unwrap();
         // Ensure the build directory exists.
         assert!(package.build_directory().exists());
 